@@ -12,11 +12,22 @@ from ._ultrafast_client import (  # Configuration classes; Protocol configuratio
     AuthConfig,
     AuthType,
     Benchmark,
+    CacheMiddleware,
+    CircuitBreakerConfig,
+    CircuitOpenError,
     CompressionConfig,
+    ConnectionError,
+    Cookie,
+    DedupMiddleware,
+    DNSError,
     HeadersMiddleware,
+    HmacSigningMiddleware,
     Http2Settings,
     Http3Settings,
     HttpClient,
+    HTTPClientError,
+    HTTPServerError,
+    HTTPStatusError,
     HttpVersion,
     InterceptorMiddleware,
     LoggingMiddleware,
@@ -30,16 +41,26 @@ from ._ultrafast_client import (  # Configuration classes; Protocol configuratio
     ProxyConfig,
     RateLimitAlgorithm,
     RateLimitConfig,
+    RateLimitError,
     RateLimitMiddleware,
     Response,
+    ResponseContentIterator,
+    ResponseTooLargeError,
     RetryConfig,
     RetryMiddleware,
+    SchemaValidationError,
     Session,
     SSEClient,
     SSEEvent,
     SSEEventIterator,
     SSLConfig,
     TimeoutConfig,
+    TimeoutError,
+    TLSError,
+    TooManyRedirects,
+    TooManyRedirectsError,
+    TransformMiddleware,
+    UltrafastError,
     WebSocketClient,
     WebSocketMessage,
 )
@@ -105,8 +126,11 @@ __all__ = [
     "SSEEvent",
     "SSEEventIterator",
     "Response",
+    "ResponseContentIterator",
+    "Cookie",
     # Configuration classes
     "RetryConfig",
+    "CircuitBreakerConfig",
     "SSLConfig",
     "PoolConfig",
     "AuthConfig",
@@ -128,10 +152,29 @@ __all__ = [
     "RetryMiddleware",
     "MetricsMiddleware",
     "InterceptorMiddleware",
+    "CacheMiddleware",
+    "HmacSigningMiddleware",
+    "TransformMiddleware",
+    "DedupMiddleware",
     # Rate limiting
     "RateLimitConfig",
     "RateLimitAlgorithm",
     "RateLimitMiddleware",
+    # Exceptions
+    "UltrafastError",
+    "TimeoutError",
+    "ConnectionError",
+    "TLSError",
+    "DNSError",
+    "TooManyRedirectsError",
+    "SchemaValidationError",
+    "RateLimitError",
+    "HTTPStatusError",
+    "HTTPClientError",
+    "HTTPServerError",
+    "TooManyRedirects",
+    "CircuitOpenError",
+    "ResponseTooLargeError",
     # Benchmarking
     "Benchmark",
     "MemoryProfiler",
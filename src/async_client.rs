@@ -1,19 +1,26 @@
 use crate::auth_common;
+use crate::circuit_breaker::{circuit_host, is_failure_status, CircuitBreaker};
+use crate::client::ClientSettings;
 use crate::config::{
-    AuthConfig, CompressionConfig, PoolConfig, ProtocolConfig, ProxyConfig, RateLimitConfig,
-    RetryConfig, SSLConfig, TimeoutConfig,
+    AuthConfig, CircuitBreakerConfig, ClientConfigSnapshot, CompressionConfig, HttpVersion,
+    PoolConfig, ProtocolConfig, ProxyConfig, RateLimitConfig, RetryConfig, SSLConfig,
+    TimeoutConfig,
 };
 use crate::connection_pool::{ConnectionMultiplexer, FastConnectionPool};
+use crate::cookies::CookieJar;
+use crate::dns_cache::{DnsCache, IpVersion};
 use crate::error::map_reqwest_error;
 use crate::http3::{AsyncHttp3Client, AsyncHttp3ConnectionPool};
 use crate::middleware::{
-    HeadersMiddleware, LoggingMiddleware, MiddlewareManager, RateLimitMiddleware,
+    CacheMiddleware, DedupMiddleware, HeadersMiddleware, HmacSigningMiddleware, Hook,
+    InterceptorMiddleware, LoggingMiddleware, MiddlewareManager, RateLimitMiddleware,
+    TransformMiddleware,
 };
-use crate::performance_common::HeaderCache;
 use crate::protocol_enhanced::EnhancedProtocolNegotiator;
 use crate::protocol_stats_common::AsyncProtocolStatsManager;
 use crate::rate_limit_common::AsyncRateLimitManager;
 use crate::response::Response;
+use crate::timing::ConnectionPhases;
 use pyo3::prelude::*;
 use pyo3::types::PyAny;
 use pyo3::{PyRef, PyRefMut};
@@ -30,6 +37,10 @@ pub struct AsyncHttpClient {
     client: Client,
     base_url: Option<String>,
     headers: HashMap<String, String>,
+    // Query parameters added to every request
+    default_params: HashMap<String, String>,
+    // Overall request timeout in seconds
+    timeout: f64,
     auth_config: Option<AuthConfig>,
     retry_config: Option<RetryConfig>,
     timeout_config: TimeoutConfig,
@@ -39,10 +50,35 @@ pub struct AsyncHttpClient {
     compression_config: CompressionConfig,
     protocol_config: ProtocolConfig,
     rate_limit_config: Option<RateLimitConfig>,
+    // Defer reading response bodies until first access
+    lazy_body: bool,
+    allow_redirects: bool,
+    max_redirects: usize,
+    // Largest response body read before raising ResponseTooLargeError
+    max_response_size: Option<usize>,
+    // Cached hostname lookups, when `dns_cache_ttl` is set
+    dns_cache: Option<Arc<DnsCache>>,
+    // Socket path every request is sent over instead of TCP
+    unix_socket: Option<String>,
+    // Address family resolved hosts are connected over
+    ip_version: IpVersion,
+    // Fails requests fast to hosts that keep erroring, when configured
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    // Called before each retry sleep as on_retry(attempt, reason, next_delay)
+    on_retry: Option<PyObject>,
+    // Per-host client certificates (cert PEM, key PEM) and the clients presenting them
+    client_certs: HashMap<String, (String, String)>,
+    host_clients: HashMap<String, Client>,
+    // Clients for per-request proxies, each with its own connection pool
+    proxy_clients: Arc<parking_lot::Mutex<HashMap<ProxyConfig, Client>>>,
     protocol_negotiator: Arc<EnhancedProtocolNegotiator>,
     middleware_manager: Arc<tokio::sync::Mutex<MiddlewareManager>>,
     // OAuth2 token storage
     oauth2_token: Arc<TokioMutex<Option<crate::config::OAuth2Token>>>,
+    // Digest challenges and nonce counts per origin
+    digest_auth: Arc<auth_common::DigestAuthState>,
+    // Cookies shared across requests, set by sessions
+    cookie_jar: Option<Arc<CookieJar>>,
     // Shared rate limiting manager (async-compatible)
     rate_limit_manager: Arc<AsyncRateLimitManager>,
     // Shared protocol statistics manager (async-compatible)
@@ -52,13 +88,14 @@ pub struct AsyncHttpClient {
     // HTTP/3 connection pool
     http3_pool: Arc<AsyncHttp3ConnectionPool>,
     // Performance optimizations
-    header_cache: Arc<HeaderCache>,
     connection_pool: Arc<FastConnectionPool>,
     connection_multiplexer: Arc<ConnectionMultiplexer>,
     // Request statistics
     request_count: Arc<TokioMutex<u64>>,
     total_request_time: Arc<TokioMutex<f64>>,
     last_request_time: Arc<TokioMutex<Option<std::time::Instant>>>,
+    // Set by aclose(); requests fail once it is
+    closed: Arc<std::sync::atomic::AtomicBool>,
 }
 
 #[pymethods]
@@ -77,7 +114,16 @@ impl AsyncHttpClient {
         proxy_config = None,
         compression_config = None,
         protocol_config = None,
-        rate_limit_config = None
+        rate_limit_config = None,
+        body_mode = "eager",
+        allow_redirects = true,
+        max_redirects = 10,
+        dns_cache_ttl = None,
+        dns_cache_max_entries = crate::dns_cache::DEFAULT_MAX_ENTRIES,
+        unix_socket = None,
+        circuit_breaker_config = None,
+        ip_version = "auto",
+        max_response_size = None
     ))]
     pub fn new(
         base_url: Option<String>,
@@ -92,131 +138,54 @@ impl AsyncHttpClient {
         compression_config: Option<CompressionConfig>,
         protocol_config: Option<ProtocolConfig>,
         rate_limit_config: Option<RateLimitConfig>,
+        body_mode: &str,
+        allow_redirects: bool,
+        max_redirects: usize,
+        dns_cache_ttl: Option<f64>,
+        dns_cache_max_entries: usize,
+        unix_socket: Option<String>,
+        circuit_breaker_config: Option<CircuitBreakerConfig>,
+        ip_version: &str,
+        max_response_size: Option<usize>,
     ) -> PyResult<Self> {
+        let lazy_body = crate::response::parse_body_mode(body_mode)?;
+        let dns_cache = DnsCache::from_options(dns_cache_ttl, dns_cache_max_entries)?;
+        let ip_version = IpVersion::parse(ip_version)?;
         let timeout_cfg = timeout_config.unwrap_or_else(|| {
-            TimeoutConfig::new(Some(10.0), Some(timeout), Some(timeout), Some(30.0))
+            TimeoutConfig::new(Some(10.0), Some(timeout), Some(timeout), Some(30.0), None)
         });
 
-        let pool_cfg = pool_config.unwrap_or_else(|| PoolConfig::new(100, 10, 90.0, 30.0));
-        let ssl_cfg = ssl_config.unwrap_or_else(|| SSLConfig::new(true, None, None, None, None));
-        let compression_cfg = compression_config
-            .unwrap_or_else(|| CompressionConfig::new(false, true, None, None, 1024));
+        let pool_cfg = match pool_config {
+            Some(cfg) => cfg,
+            None => PoolConfig::new(100, 10, 90.0, 30.0, None)?,
+        };
+        let ssl_cfg = ssl_config.unwrap_or_else(|| {
+            SSLConfig::new(true, None, None, None, None, None, None, true, None)
+        });
+        let compression_cfg = match compression_config {
+            Some(cfg) => cfg,
+            None => CompressionConfig::new(false, true, None, None, 1024, false)?,
+        };
         let protocol_cfg = protocol_config
             .clone()
             .unwrap_or_else(|| ProtocolConfig::default());
+        protocol_cfg.validate()?;
         // Note: HTTP/3 pool settings would be used for HTTP/3 client initialization if implemented
         let http3_pool_settings = protocol_cfg.http3_settings.clone();
 
-        // --- User-Agent fix: set at client builder level if present in headers ---
-        let mut client_builder = Client::builder()
-            .timeout(Duration::from_secs_f64(timeout))
-            .pool_max_idle_per_host(pool_cfg.max_idle_per_host)
-            .pool_idle_timeout(Duration::from_secs_f64(pool_cfg.idle_timeout))
-            .user_agent(""); // Suppress reqwest default User-Agent
-
-        // Configure timeouts
-        if let Some(connect_timeout) = timeout_cfg.connect_timeout {
-            client_builder =
-                client_builder.connect_timeout(Duration::from_secs_f64(connect_timeout));
-        }
-
-        // Configure SSL/TLS
-        if !ssl_cfg.verify {
-            client_builder = client_builder.danger_accept_invalid_certs(true);
-        }
-
-        // Apply additional SSL configuration
-        if let Some(cert_path) = &ssl_cfg.cert_file {
-            if let Some(key_path) = &ssl_cfg.key_file {
-                // Load client certificate and key
-                match std::fs::read(cert_path) {
-                    Ok(cert_data) => {
-                        match std::fs::read(key_path) {
-                            Ok(key_data) => {
-                                // Use from_pkcs8_pem which is the correct method
-                                if let Ok(identity) =
-                                    reqwest::Identity::from_pkcs8_pem(&cert_data, &key_data)
-                                {
-                                    client_builder = client_builder.identity(identity);
-                                }
-                            }
-                            Err(_) => {} // Ignore key loading errors
-                        }
-                    }
-                    Err(_) => {} // Ignore cert loading errors
-                }
-            }
-        }
-
-        // Configure CA bundle if specified
-        if let Some(ca_bundle) = &ssl_cfg.ca_bundle {
-            match std::fs::read(ca_bundle) {
-                Ok(ca_data) => {
-                    if let Ok(cert) = reqwest::Certificate::from_pem(&ca_data) {
-                        client_builder = client_builder.add_root_certificate(cert);
-                    }
-                }
-                Err(_) => {} // Ignore CA bundle errors
-            }
-        }
-
-        // Configure proxy if specified
-        if let Some(proxy_cfg) = &proxy_config {
-            if let Ok(proxy) = reqwest::Proxy::all(&proxy_cfg.url) {
-                let mut proxy = proxy;
-
-                // Add authentication if specified
-                if let (Some(username), Some(password)) = (&proxy_cfg.username, &proxy_cfg.password)
-                {
-                    proxy = proxy.basic_auth(username, password);
-                }
-
-                client_builder = client_builder.proxy(proxy);
-            }
-        }
-
-        // Configure compression
-        if compression_cfg.enable_response_compression {
-            // reqwest enables gzip/deflate by default, we just need to ensure it's not disabled
-            client_builder = client_builder.gzip(true);
-
-            // Enable brotli if supported
-            if compression_cfg.supports_algorithm("brotli") {
-                client_builder = client_builder.brotli(true);
-            }
-        }
-
-        // Configure HTTP/2 settings
-        if protocol_cfg.is_http2_enabled() {
-            // reqwest enables HTTP/2 by default, but we can configure specific settings
-            if protocol_cfg.enable_http2_prior_knowledge {
-                client_builder = client_builder.http2_prior_knowledge();
-            }
-
-            // Configure HTTP/2 keep-alive if specified
-            if let Some(interval) = protocol_cfg.http2_settings.keep_alive_interval {
-                client_builder =
-                    client_builder.http2_keep_alive_interval(Some(Duration::from_secs(interval)));
-            }
-
-            if let Some(timeout) = protocol_cfg.http2_settings.keep_alive_timeout {
-                client_builder =
-                    client_builder.http2_keep_alive_timeout(Duration::from_secs(timeout));
-            }
-        }
-
-        // Configure HTTP/3 via Quiche
-        if protocol_cfg.is_http3_enabled() {
-            // HTTP/3 support requires unstable features
-            // This will only work if compiled with RUSTFLAGS="--cfg reqwest_unstable"
-            // client_builder = client_builder.http3_prior_knowledge();
-        }
-
-        // Set User-Agent at client level if present in headers
-        // let mut headers = headers.unwrap_or_default();
-        // if let Some(user_agent) = headers.remove("User-Agent").or_else(|| headers.remove("user-agent")) {
-        //     client_builder = client_builder.user_agent(user_agent);
-        // }
+        let client_builder = async_client_builder(&ClientSettings {
+            timeout,
+            timeout_config: &timeout_cfg,
+            pool_config: &pool_cfg,
+            ssl_config: &ssl_cfg,
+            proxy_config: proxy_config.as_ref(),
+            compression_config: &compression_cfg,
+            protocol_config: &protocol_cfg,
+            dns_cache: dns_cache.as_ref(),
+            unix_socket: unix_socket.as_deref(),
+            ip_version,
+            client_cert: None,
+        })?;
         let headers = headers.unwrap_or_default();
 
         let client = client_builder.build().map_err(|e| {
@@ -246,11 +215,18 @@ impl AsyncHttpClient {
         // Initialize shared managers
         let rate_limit_manager = Arc::new(AsyncRateLimitManager::new(rate_limit_config.clone()));
         let protocol_stats_manager = Arc::new(AsyncProtocolStatsManager::new());
+        let connection_pool = Arc::new(FastConnectionPool::new(
+            pool_cfg.max_idle_connections,
+            Duration::from_secs_f64(pool_cfg.idle_timeout),
+        ));
+        connection_pool.set_max_connections_per_host(pool_cfg.max_connections_per_host);
 
         Ok(AsyncHttpClient {
             client,
             base_url,
             headers,
+            default_params: HashMap::new(),
+            timeout,
             auth_config,
             retry_config,
             timeout_config: timeout_cfg,
@@ -260,11 +236,26 @@ impl AsyncHttpClient {
             compression_config: compression_cfg,
             protocol_config: protocol_cfg.clone(),
             rate_limit_config,
+            lazy_body,
+            allow_redirects,
+            max_redirects,
+            max_response_size,
+            dns_cache,
+            unix_socket,
+            ip_version,
+            circuit_breaker: circuit_breaker_config
+                .map(|config| Arc::new(CircuitBreaker::new(config))),
+            on_retry: None,
+            client_certs: HashMap::new(),
+            host_clients: HashMap::new(),
+            proxy_clients: Arc::new(parking_lot::Mutex::new(HashMap::new())),
             protocol_negotiator: Arc::new(EnhancedProtocolNegotiator::new(
                 protocol_cfg.fallback_strategy.clone(),
             )),
             middleware_manager: Arc::new(tokio::sync::Mutex::new(middleware_manager)),
             oauth2_token: Arc::new(TokioMutex::new(None)),
+            digest_auth: Arc::new(auth_common::DigestAuthState::new()),
+            cookie_jar: None,
             rate_limit_manager,
             protocol_stats_manager,
             http3_client: Arc::new(tokio::sync::Mutex::new(None)),
@@ -273,11 +264,7 @@ impl AsyncHttpClient {
                 http3_pool_settings.pool_timeout_seconds.unwrap_or(300),
             )),
             // Initialize performance optimizations
-            header_cache: Arc::new(HeaderCache::new()),
-            connection_pool: Arc::new(FastConnectionPool::new(
-                pool_cfg.max_idle_connections,
-                Duration::from_secs_f64(pool_cfg.idle_timeout),
-            )),
+            connection_pool,
             connection_multiplexer: Arc::new(ConnectionMultiplexer::new(
                 pool_cfg.max_idle_connections,
                 Duration::from_secs_f64(pool_cfg.idle_timeout),
@@ -285,6 +272,7 @@ impl AsyncHttpClient {
             request_count: Arc::new(TokioMutex::new(0)),
             total_request_time: Arc::new(TokioMutex::new(0.0)),
             last_request_time: Arc::new(TokioMutex::new(None)),
+            closed: Arc::new(std::sync::atomic::AtomicBool::new(false)),
         })
     }
 
@@ -295,19 +283,32 @@ impl AsyncHttpClient {
         url: &str,
         params: Option<HashMap<String, String>>,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
     ) -> PyResult<&'py PyAny> {
+        let options = crate::client::RequestOptions::new(deadline, timeout, allow_redirects)?
+            .with_proxy(proxy);
         let this = slf.borrow(py).clone();
         let url = url.to_string();
         let params = params.clone();
         let headers = headers.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            this.execute_request_with_retry(Method::GET, &url, params, None, headers)
+            this.execute_request_with_retry(Method::GET, &url, params, None, headers, options)
                 .await
         })
     }
 
     /// Async POST request with enhanced retry logic
-    #[pyo3(signature = (url, json = None, data = None, files = None, headers = None))]
+    ///
+    /// `expect_continue` holds the body back for `TimeoutConfig.expect_continue_timeout`
+    /// (0.1s by default), a wait paid even when the server accepts the body
+    #[pyo3(signature = (
+        url, json = None, data = None, files = None, headers = None, deadline = None,
+        timeout = None, allow_redirects = None, proxy = None,
+        expect_continue = false, progress_callback = None
+    ))]
     pub fn post<'py>(
         slf: Py<Self>,
         py: Python<'py>,
@@ -316,7 +317,17 @@ impl AsyncHttpClient {
         data: Option<HashMap<String, String>>,
         files: Option<HashMap<String, Vec<u8>>>,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
+        expect_continue: bool,
+        progress_callback: Option<PyObject>,
     ) -> PyResult<&'py PyAny> {
+        let options = crate::client::RequestOptions::new(deadline, timeout, allow_redirects)?
+            .with_proxy(proxy)
+            .with_expect_continue(expect_continue)
+            .with_upload_progress(progress_callback);
         let this = slf.borrow(py).clone();
         let url = url.to_string();
         let data = data.clone();
@@ -356,13 +367,20 @@ impl AsyncHttpClient {
                 }
                 prepared_body
             };
-            this.execute_request_with_retry(Method::POST, &url, None, body, Some(headers))
+            this.execute_request_with_retry(Method::POST, &url, None, body, Some(headers), options)
                 .await
         })
     }
 
     /// Async PUT request with enhanced retry logic
-    #[pyo3(signature = (url, json = None, data = None, files = None, headers = None))]
+    ///
+    /// `expect_continue` holds the body back for `TimeoutConfig.expect_continue_timeout`
+    /// (0.1s by default), a wait paid even when the server accepts the body
+    #[pyo3(signature = (
+        url, json = None, data = None, files = None, headers = None, deadline = None,
+        timeout = None, allow_redirects = None, proxy = None,
+        expect_continue = false, progress_callback = None
+    ))]
     pub fn put<'py>(
         slf: Py<Self>,
         py: Python<'py>,
@@ -371,7 +389,17 @@ impl AsyncHttpClient {
         data: Option<HashMap<String, String>>,
         files: Option<HashMap<String, Vec<u8>>>,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
+        expect_continue: bool,
+        progress_callback: Option<PyObject>,
     ) -> PyResult<&'py PyAny> {
+        let options = crate::client::RequestOptions::new(deadline, timeout, allow_redirects)?
+            .with_proxy(proxy)
+            .with_expect_continue(expect_continue)
+            .with_upload_progress(progress_callback);
         let this = slf.borrow(py).clone();
         let url = url.to_string();
         let data = data.clone();
@@ -411,7 +439,7 @@ impl AsyncHttpClient {
                 }
                 prepared_body
             };
-            this.execute_request_with_retry(Method::PUT, &url, None, body, Some(headers))
+            this.execute_request_with_retry(Method::PUT, &url, None, body, Some(headers), options)
                 .await
         })
     }
@@ -422,18 +450,31 @@ impl AsyncHttpClient {
         py: Python<'py>,
         url: &str,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
     ) -> PyResult<&'py PyAny> {
+        let options = crate::client::RequestOptions::new(deadline, timeout, allow_redirects)?
+            .with_proxy(proxy);
         let this = slf.borrow(py).clone();
         let url = url.to_string();
         let headers = headers.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            this.execute_request_with_retry(Method::DELETE, &url, None, None, headers)
+            this.execute_request_with_retry(Method::DELETE, &url, None, None, headers, options)
                 .await
         })
     }
 
     /// Async PATCH request with enhanced retry logic
-    #[pyo3(signature = (url, json = None, data = None, files = None, headers = None))]
+    ///
+    /// `expect_continue` holds the body back for `TimeoutConfig.expect_continue_timeout`
+    /// (0.1s by default), a wait paid even when the server accepts the body
+    #[pyo3(signature = (
+        url, json = None, data = None, files = None, headers = None, deadline = None,
+        timeout = None, allow_redirects = None, proxy = None,
+        expect_continue = false
+    ))]
     pub fn patch<'py>(
         slf: Py<Self>,
         py: Python<'py>,
@@ -442,7 +483,15 @@ impl AsyncHttpClient {
         data: Option<HashMap<String, String>>,
         files: Option<HashMap<String, Vec<u8>>>,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
+        expect_continue: bool,
     ) -> PyResult<&'py PyAny> {
+        let options = crate::client::RequestOptions::new(deadline, timeout, allow_redirects)?
+            .with_proxy(proxy)
+            .with_expect_continue(expect_continue);
         let this = slf.borrow(py).clone();
         let url = url.to_string();
         let data = data.clone();
@@ -482,7 +531,7 @@ impl AsyncHttpClient {
                 }
                 prepared_body
             };
-            this.execute_request_with_retry(Method::PATCH, &url, None, body, Some(headers))
+            this.execute_request_with_retry(Method::PATCH, &url, None, body, Some(headers), options)
                 .await
         })
     }
@@ -493,12 +542,18 @@ impl AsyncHttpClient {
         py: Python<'py>,
         url: &str,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
     ) -> PyResult<&'py PyAny> {
+        let options = crate::client::RequestOptions::new(deadline, timeout, allow_redirects)?
+            .with_proxy(proxy);
         let this = slf.borrow(py).clone();
         let url = url.to_string();
         let headers = headers.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            this.execute_request_with_retry(Method::HEAD, &url, None, None, headers)
+            this.execute_request_with_retry(Method::HEAD, &url, None, None, headers, options)
                 .await
         })
     }
@@ -509,16 +564,277 @@ impl AsyncHttpClient {
         py: Python<'py>,
         url: &str,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
     ) -> PyResult<&'py PyAny> {
+        let options = crate::client::RequestOptions::new(deadline, timeout, allow_redirects)?
+            .with_proxy(proxy);
         let this = slf.borrow(py).clone();
         let url = url.to_string();
         let headers = headers.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            this.execute_request_with_retry(Method::OPTIONS, &url, None, None, headers)
+            this.execute_request_with_retry(Method::OPTIONS, &url, None, None, headers, options)
                 .await
         })
     }
 
+    /// Async request with an arbitrary HTTP method, e.g. "PROPFIND" or "REPORT"
+    ///
+    /// `expect_continue` holds the body back for `TimeoutConfig.expect_continue_timeout`
+    /// (0.1s by default), a wait paid even when the server accepts the body
+    #[pyo3(signature = (
+        method, url, params = None, json = None, data = None, files = None, headers = None,
+        deadline = None, timeout = None, allow_redirects = None, proxy = None,
+        expect_continue = false
+    ))]
+    pub fn request<'py>(
+        slf: Py<Self>,
+        py: Python<'py>,
+        method: &str,
+        url: &str,
+        params: Option<HashMap<String, String>>,
+        json: Option<&PyAny>,
+        data: Option<HashMap<String, String>>,
+        files: Option<HashMap<String, Vec<u8>>>,
+        headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
+        expect_continue: bool,
+    ) -> PyResult<&'py PyAny> {
+        let options = crate::client::RequestOptions::new(deadline, timeout, allow_redirects)?
+            .with_proxy(proxy)
+            .with_expect_continue(expect_continue);
+        let method = crate::client::parse_method(method)?;
+        let this = slf.borrow(py).clone();
+        let url = url.to_string();
+        let mut headers = headers.unwrap_or_default();
+
+        // Convert JSON to bytes directly here in the synchronous context
+        let json_body = if let Some(json_obj) = json {
+            headers.insert("Content-Type".to_string(), "application/json".to_string());
+            let json_value: serde_json::Value = pythonize::depythonize(json_obj)?;
+            Some(serde_json::to_vec(&json_value).map_err(|e| {
+                pyo3::exceptions::PyValueError::new_err(format!("JSON serialization error: {}", e))
+            })?)
+        } else {
+            None
+        };
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let body = if let Some(json_bytes) = json_body {
+                Some(json_bytes)
+            } else {
+                let has_files = files.is_some();
+                let has_data = data.is_some();
+                let prepared_body = this.prepare_body(None, data, files)?;
+                if prepared_body.is_some() && !has_files && has_data {
+                    headers.insert(
+                        "Content-Type".to_string(),
+                        "application/x-www-form-urlencoded".to_string(),
+                    );
+                }
+                prepared_body
+            };
+            this.execute_request_with_retry(method, &url, params, body, Some(headers), options)
+                .await
+        })
+    }
+
+    /// GET every URL concurrently, at most `concurrency` at a time.
+    ///
+    /// Results come back in input order; a failed request yields its exception
+    /// in place of a Response instead of aborting the batch.
+    #[pyo3(signature = (urls, concurrency = 10, headers = None, timeout = None))]
+    pub fn get_many<'py>(
+        slf: Py<Self>,
+        py: Python<'py>,
+        urls: Vec<String>,
+        concurrency: usize,
+        headers: Option<HashMap<String, String>>,
+        timeout: Option<f64>,
+    ) -> PyResult<&'py PyAny> {
+        let options = crate::client::RequestOptions::new(None, timeout, None)?;
+        let this = slf.borrow(py).clone();
+        let requests = urls
+            .into_iter()
+            .map(|url| BatchRequest {
+                method: Method::GET,
+                url,
+                params: None,
+                headers: headers.clone(),
+                body: None,
+            })
+            .collect();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            this.execute_many(requests, concurrency, options).await
+        })
+    }
+
+    /// Send many requests concurrently, at most `concurrency` at a time.
+    ///
+    /// Each item is a dict with "url" and optional "method" (default "GET"),
+    /// "params", "headers" and "json". Results come back in input order, with
+    /// exceptions in place of the requests that failed.
+    #[pyo3(signature = (requests, concurrency = 10, timeout = None))]
+    pub fn request_many<'py>(
+        slf: Py<Self>,
+        py: Python<'py>,
+        requests: Vec<&PyAny>,
+        concurrency: usize,
+        timeout: Option<f64>,
+    ) -> PyResult<&'py PyAny> {
+        let options = crate::client::RequestOptions::new(None, timeout, None)?;
+        let this = slf.borrow(py).clone();
+        let requests = requests
+            .into_iter()
+            .map(BatchRequest::from_py)
+            .collect::<PyResult<Vec<_>>>()?;
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            this.execute_many(requests, concurrency, options).await
+        })
+    }
+
+    /// Async multipart upload that streams each file from disk instead of buffering it.
+    ///
+    /// `files` maps form field names to file paths. A streamed body can't be
+    /// replayed, so the request is sent once: no retries, redirects or Digest auth.
+    #[pyo3(signature = (url, files, data = None, headers = None, method = "POST", timeout = None))]
+    pub fn upload_files<'py>(
+        slf: Py<Self>,
+        py: Python<'py>,
+        url: &str,
+        files: HashMap<String, String>,
+        data: Option<HashMap<String, String>>,
+        headers: Option<HashMap<String, String>>,
+        method: &str,
+        timeout: Option<f64>,
+    ) -> PyResult<&'py PyAny> {
+        let method = crate::client::parse_method(method)?;
+        let this = slf.borrow(py).clone();
+        this.ensure_open()?;
+        let full_url = this.build_url(url)?;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let host = reqwest::Url::parse(&full_url)
+                .ok()
+                .and_then(|parsed| parsed.host_str().map(|h| h.to_string()))
+                .unwrap_or_default();
+            let client = this.host_clients.get(&host).unwrap_or(&this.client);
+            let mut request = this
+                .apply_oauth2_auth(client.request(method, &full_url))
+                .await?;
+
+            // The multipart form sets its own Content-Type with the boundary
+            let mut all_headers = this.headers.clone();
+            all_headers.extend(headers.unwrap_or_default());
+            for (key, value) in &all_headers {
+                if !key.eq_ignore_ascii_case("content-type") {
+                    request = request.header(key, value);
+                }
+            }
+            if let Some(timeout) = timeout {
+                request = request.timeout(Duration::from_secs_f64(timeout));
+            }
+
+            let form = crate::multipart::file_form(files, data).await?;
+            let (client, built) = auth_common::build_request(
+                request.multipart(form),
+                this.auth_config.as_ref(),
+                &this.digest_auth,
+            );
+            let mut built = built.map_err(|e| map_reqwest_error(&e))?;
+            if let Some(jar) = &this.cookie_jar {
+                jar.apply(&mut built);
+            }
+            let signers = this.middleware_manager.lock().await.signers();
+            for signer in &signers {
+                signer.sign(&mut built);
+            }
+            let response = client
+                .execute(built)
+                .await
+                .map_err(|e| map_reqwest_error(&e))?;
+            if let Some(jar) = &this.cookie_jar {
+                jar.store_response(&response);
+            }
+            Response::from_reqwest_response_async(response, this.max_response_size).await
+        })
+    }
+
+    /// Async upload of a body produced incrementally by `data_stream`, an iterator
+    /// or async iterator of `bytes`, using chunked transfer encoding.
+    ///
+    /// As with `upload_files` the request is sent once. If the iterator raises,
+    /// the upload is aborted and its exception is re-raised.
+    #[pyo3(signature = (
+        url, data_stream, headers = None, method = "POST", timeout = None,
+        progress_callback = None
+    ))]
+    pub fn upload_stream<'py>(
+        slf: Py<Self>,
+        py: Python<'py>,
+        url: &str,
+        data_stream: &PyAny,
+        headers: Option<HashMap<String, String>>,
+        method: &str,
+        timeout: Option<f64>,
+        progress_callback: Option<PyObject>,
+    ) -> PyResult<&'py PyAny> {
+        let method = crate::client::parse_method(method)?;
+        let this = slf.borrow(py).clone();
+        this.ensure_open()?;
+        let full_url = this.build_url(url)?;
+        let locals = pyo3_asyncio::tokio::get_current_locals(py)?;
+        let (body, stream_error) =
+            crate::body_stream::stream_body(data_stream, Some(locals), progress_callback)?;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let host = reqwest::Url::parse(&full_url)
+                .ok()
+                .and_then(|parsed| parsed.host_str().map(|h| h.to_string()))
+                .unwrap_or_default();
+            let client = this.host_clients.get(&host).unwrap_or(&this.client);
+            let mut request = this
+                .apply_oauth2_auth(client.request(method, &full_url))
+                .await?;
+
+            let mut all_headers = this.headers.clone();
+            all_headers.extend(headers.unwrap_or_default());
+            for (key, value) in &all_headers {
+                request = request.header(key, value);
+            }
+            if let Some(timeout) = timeout {
+                request = request.timeout(Duration::from_secs_f64(timeout));
+            }
+
+            let (client, built) = auth_common::build_request(
+                request.body(body),
+                this.auth_config.as_ref(),
+                &this.digest_auth,
+            );
+            let mut built = built.map_err(|e| map_reqwest_error(&e))?;
+            if let Some(jar) = &this.cookie_jar {
+                jar.apply(&mut built);
+            }
+            let signers = this.middleware_manager.lock().await.signers();
+            for signer in &signers {
+                signer.sign(&mut built);
+            }
+            let response = client
+                .execute(built)
+                .await
+                .map_err(|e| stream_error.or(map_reqwest_error(&e)))?;
+            if let Some(jar) = &this.cookie_jar {
+                jar.store_response(&response);
+            }
+            Response::from_reqwest_response_async(response, this.max_response_size).await
+        })
+    }
+
     /// Set a header for the client
     pub fn set_header(&mut self, key: String, value: String) {
         self.headers.insert(key, value);
@@ -546,6 +862,7 @@ impl AsyncHttpClient {
             // Token will be fetched on first request
             self.oauth2_token = Arc::new(TokioMutex::new(None));
         }
+        self.digest_auth.clear();
         self.auth_config = Some(auth_config);
         Ok(())
     }
@@ -553,6 +870,7 @@ impl AsyncHttpClient {
     /// Clear authentication
     pub fn clear_auth(&mut self) {
         self.auth_config = None;
+        self.digest_auth.clear();
         // Clear OAuth2 token synchronously by creating a new empty Arc
         self.oauth2_token = Arc::new(TokioMutex::new(None));
     }
@@ -567,6 +885,39 @@ impl AsyncHttpClient {
         self.auth_config.is_some()
     }
 
+    /// Current OAuth2 token, or None before the first fetch (awaitable)
+    pub fn get_oauth2_token<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let oauth2_token = Arc::clone(&self.oauth2_token);
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let token = oauth2_token.lock().await.clone();
+            Ok(token)
+        })
+    }
+
+    /// Fetch a new OAuth2 token now, even if the current one is still valid (awaitable)
+    pub fn refresh_oauth2_token<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let auth_config = match self.auth_config {
+            Some(ref auth_config) if auth_config.is_oauth2() => auth_config.clone(),
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "OAuth2 authentication is not configured",
+                ))
+            }
+        };
+        let oauth2_token = Arc::clone(&self.oauth2_token);
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let mut token_guard = oauth2_token.lock().await;
+            let token = auth_config
+                .fetch_oauth2_token(token_guard.as_ref())
+                .await
+                .map_err(crate::error::connection_error)?;
+            *token_guard = Some(token.clone());
+            drop(token_guard);
+            auth_config.notify_token_refresh(&token)?;
+            Ok(token)
+        })
+    }
+
     /// Set retry configuration
     pub fn set_retry_config(&mut self, retry_config: RetryConfig) {
         self.retry_config = Some(retry_config);
@@ -581,6 +932,8 @@ impl AsyncHttpClient {
 
     /// Set pool configuration (requires client rebuild)
     pub fn set_pool_config(&mut self, pool_config: PoolConfig) -> PyResult<()> {
+        self.connection_pool
+            .set_max_connections_per_host(pool_config.max_connections_per_host);
         self.pool_config = pool_config;
         self.rebuild_client()?;
         Ok(())
@@ -588,11 +941,38 @@ impl AsyncHttpClient {
 
     /// Set SSL configuration (requires client rebuild)
     pub fn set_ssl_config(&mut self, ssl_config: SSLConfig) -> PyResult<()> {
+        ssl_config.tls_version()?;
+        // Check the certificates load before replacing the working configuration
+        ssl_config.identity()?;
+        ssl_config.root_certificates()?;
+        crate::pinning::PinSet::parse(&ssl_config.pinned_public_keys)?;
         self.ssl_config = ssl_config;
         self.rebuild_client()?;
         Ok(())
     }
 
+    /// Present a specific client certificate (PEM, with a PKCS#8 key) for requests to `host`
+    pub fn set_client_cert(&mut self, host: &str, cert_pem: &str, key_pem: &str) -> PyResult<()> {
+        let client = self.build_host_client(cert_pem, key_pem)?;
+        self.client_certs.insert(
+            host.to_string(),
+            (cert_pem.to_string(), key_pem.to_string()),
+        );
+        self.host_clients.insert(host.to_string(), client);
+        Ok(())
+    }
+
+    /// Stop using a per-host client certificate
+    pub fn remove_client_cert(&mut self, host: &str) -> bool {
+        self.host_clients.remove(host);
+        self.client_certs.remove(host).is_some()
+    }
+
+    /// Hosts with a dedicated client certificate
+    pub fn get_client_cert_hosts(&self) -> Vec<String> {
+        self.client_certs.keys().cloned().collect()
+    }
+
     /// Set proxy configuration (requires client rebuild)
     pub fn set_proxy_config(&mut self, proxy_config: Option<ProxyConfig>) -> PyResult<()> {
         self.proxy_config = proxy_config;
@@ -618,6 +998,31 @@ impl AsyncHttpClient {
         Ok(())
     }
 
+    /// Set whether redirects are followed and how many before raising TooManyRedirects
+    #[pyo3(signature = (allow_redirects = true, max_redirects = 10))]
+    pub fn set_redirect_policy(&mut self, allow_redirects: bool, max_redirects: usize) {
+        self.allow_redirects = allow_redirects;
+        self.max_redirects = max_redirects;
+    }
+
+    /// Set the response body mode ("eager" or "lazy")
+    pub fn set_body_mode(&mut self, body_mode: &str) -> PyResult<()> {
+        self.lazy_body = crate::response::parse_body_mode(body_mode)?;
+        Ok(())
+    }
+
+    /// Set a callback invoked before each retry as on_retry(attempt, reason, next_delay)
+    #[pyo3(signature = (callback = None))]
+    pub fn set_on_retry(&mut self, py: Python, callback: Option<PyObject>) -> PyResult<()> {
+        self.on_retry = crate::client::validate_on_retry(py, callback)?;
+        Ok(())
+    }
+
+    /// Get the response body mode
+    pub fn get_body_mode(&self) -> String {
+        if self.lazy_body { "lazy" } else { "eager" }.to_string()
+    }
+
     /// Get protocol configuration
     pub fn get_protocol_config(&self) -> ProtocolConfig {
         self.protocol_config.clone()
@@ -669,6 +1074,19 @@ impl AsyncHttpClient {
                 })?;
                 Ok(Python::with_gil(|py| py.None()))
             })
+        } else if let Ok(interceptor) = middleware.extract::<PyRef<InterceptorMiddleware>>() {
+            let interceptor_val = (*interceptor).clone();
+            pyo3_asyncio::tokio::future_into_py(py, async move {
+                let mgr = manager.lock().await;
+                mgr.add_interceptor_middleware(interceptor_val)
+                    .map_err(|e| {
+                        pyo3::exceptions::PyRuntimeError::new_err(format!(
+                            "Failed to add interceptor middleware: {}",
+                            e
+                        ))
+                    })?;
+                Ok(Python::with_gil(|py| py.None()))
+            })
         } else if let Ok(rate_limit) = middleware.extract::<PyRef<RateLimitMiddleware>>() {
             let rate_limit_val = (*rate_limit).clone();
             pyo3_asyncio::tokio::future_into_py(py, async move {
@@ -676,6 +1094,54 @@ impl AsyncHttpClient {
                 mgr.add_rate_limit_middleware(rate_limit_val);
                 Ok(Python::with_gil(|py| py.None()))
             })
+        } else if let Ok(cache) = middleware.extract::<PyRef<CacheMiddleware>>() {
+            let cache_val = (*cache).clone();
+            pyo3_asyncio::tokio::future_into_py(py, async move {
+                let mgr = manager.lock().await;
+                mgr.add_cache_middleware(cache_val).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to add cache middleware: {}",
+                        e
+                    ))
+                })?;
+                Ok(Python::with_gil(|py| py.None()))
+            })
+        } else if let Ok(signing) = middleware.extract::<PyRef<HmacSigningMiddleware>>() {
+            let signing_val = (*signing).clone();
+            pyo3_asyncio::tokio::future_into_py(py, async move {
+                let mgr = manager.lock().await;
+                mgr.add_signing_middleware(signing_val).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to add signing middleware: {}",
+                        e
+                    ))
+                })?;
+                Ok(Python::with_gil(|py| py.None()))
+            })
+        } else if let Ok(transform) = middleware.extract::<PyRef<TransformMiddleware>>() {
+            let transform_val = (*transform).clone();
+            pyo3_asyncio::tokio::future_into_py(py, async move {
+                let mgr = manager.lock().await;
+                mgr.add_transform_middleware(transform_val).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to add transform middleware: {}",
+                        e
+                    ))
+                })?;
+                Ok(Python::with_gil(|py| py.None()))
+            })
+        } else if let Ok(dedup) = middleware.extract::<PyRef<DedupMiddleware>>() {
+            let dedup_val = (*dedup).clone();
+            pyo3_asyncio::tokio::future_into_py(py, async move {
+                let mgr = manager.lock().await;
+                mgr.add_dedup_middleware(dedup_val).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to add dedup middleware: {}",
+                        e
+                    ))
+                })?;
+                Ok(Python::with_gil(|py| py.None()))
+            })
         } else {
             Err(pyo3::exceptions::PyValueError::new_err(
                 "Unsupported middleware type",
@@ -698,25 +1164,7 @@ impl AsyncHttpClient {
                     e
                 ))
             })?;
-            let mut removed = false;
-            // Logging
-            let before = stack.logging_middleware.len();
-            stack.logging_middleware.retain(|m| m.name != name);
-            if stack.logging_middleware.len() < before {
-                removed = true;
-            }
-            // Headers
-            let before = stack.headers_middleware.len();
-            stack.headers_middleware.retain(|m| m.name != name);
-            if stack.headers_middleware.len() < before {
-                removed = true;
-            }
-            // RateLimit
-            let before = stack.rate_limit_middleware.len();
-            stack.rate_limit_middleware.retain(|m| m.name != name);
-            if stack.rate_limit_middleware.len() < before {
-                removed = true;
-            }
+            let removed = stack.remove_named(&name);
             Ok(Python::with_gil(|py| removed.into_py(py)))
         })
     }
@@ -749,30 +1197,7 @@ impl AsyncHttpClient {
                     )))?
                     .as_millis()
             );
-            let body = String::new();
             let mut body_bytes = Vec::new();
-            // Helper function to get MIME type from filename
-            fn get_mime_type(filename: &str) -> &'static str {
-                let ext = filename.split('.').last().unwrap_or("").to_lowercase();
-                match ext.as_str() {
-                    "pdf" => "application/pdf",
-                    "jpg" | "jpeg" => "image/jpeg",
-                    "png" => "image/png",
-                    "gif" => "image/gif",
-                    "webp" => "image/webp",
-                    "svg" => "image/svg+xml",
-                    "json" => "application/json",
-                    "xml" => "application/xml",
-                    "txt" => "text/plain",
-                    "html" | "htm" => "text/html",
-                    "css" => "text/css",
-                    "js" => "application/javascript",
-                    "zip" => "application/zip",
-                    "tar" => "application/x-tar",
-                    "gz" => "application/gzip",
-                    _ => "application/octet-stream",
-                }
-            }
             // Add form fields
             if let Some(data) = &data {
                 for (key, value) in data.iter() {
@@ -786,7 +1211,7 @@ impl AsyncHttpClient {
             }
             // Add files
             for (filename, file_bytes) in files.iter() {
-                let mime_type = get_mime_type(filename);
+                let mime_type = crate::multipart::mime_type_for(filename);
                 let content_length = file_bytes.len();
                 let part_header = format!(
                     "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n",
@@ -841,7 +1266,8 @@ impl AsyncHttpClient {
                     use flate2::Compression;
                     use std::io::Write;
 
-                    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                    let level = self.compression_config.level_for("gzip");
+                    let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level));
                     if encoder.write_all(&body_data).is_ok() {
                         if let Ok(compressed_data) = encoder.finish() {
                             return Ok(Some(compressed_data));
@@ -959,6 +1385,69 @@ impl AsyncHttpClient {
         })
     }
 
+    /// Async download of a GET's body to `path`, resolving to the number of bytes
+    /// written.
+    ///
+    /// `progress_callback(bytes_downloaded, total_bytes)` is called from a runtime
+    /// thread after every `chunk_size` bytes and at the end; `total_bytes` is None
+    /// without a Content-Length. Error statuses raise instead of writing the file.
+    #[pyo3(signature = (
+        url, path, progress_callback = None, headers = None, chunk_size = 65536,
+        deadline = None, timeout = None
+    ))]
+    pub fn download<'py>(
+        slf: Py<Self>,
+        py: Python<'py>,
+        url: &str,
+        path: &str,
+        progress_callback: Option<PyObject>,
+        headers: Option<HashMap<String, String>>,
+        chunk_size: usize,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+    ) -> PyResult<&'py PyAny> {
+        let options = crate::client::RequestOptions::new(deadline, timeout, None)?.with_stream();
+        let this = slf.borrow(py).clone();
+        let url = url.to_string();
+        let path = path.to_string();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let response = this
+                .execute_request_with_retry(Method::GET, &url, None, None, headers, options)
+                .await?;
+            Python::with_gil(|py| response.raise_for_status(py))?;
+            response
+                .save_with_progress_async(path, chunk_size, progress_callback)
+                .await
+        })
+    }
+
+    /// Open connections to the hosts of `urls` ahead of time, completing the TLS
+    /// handshake, so the first real request to each reuses a pooled connection.
+    ///
+    /// Resolves to `{"succeeded": n, "failed": n}`.
+    #[pyo3(signature = (urls, timeout = None))]
+    pub fn warmup<'py>(
+        &self,
+        py: Python<'py>,
+        urls: Vec<String>,
+        timeout: Option<f64>,
+    ) -> PyResult<&'py PyAny> {
+        self.ensure_open()?;
+        let mut targets = Vec::with_capacity(urls.len());
+        for url in &urls {
+            let full_url = self.build_url(url)?;
+            let host = reqwest::Url::parse(&full_url)
+                .ok()
+                .and_then(|parsed| parsed.host_str().map(|h| h.to_string()))
+                .unwrap_or_default();
+            targets.push((self.select_client(&host, None)?, full_url));
+        }
+        let timeout = timeout.map(Duration::from_secs_f64);
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            Ok(crate::client::warm_connections(targets, timeout).await)
+        })
+    }
+
     /// Get performance statistics (async)
     pub fn get_stats<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
         let protocol_stats_manager = self.protocol_stats_manager.clone();
@@ -1081,6 +1570,102 @@ impl AsyncHttpClient {
         Ok(self.headers.clone())
     }
 
+    /// Set query parameters sent with every request (per-request params take precedence)
+    pub fn set_default_params(&mut self, params: HashMap<String, String>) {
+        self.default_params = params;
+    }
+
+    /// Clear the default query parameters
+    pub fn clear_default_params(&mut self) {
+        self.default_params.clear();
+    }
+
+    /// Get the default query parameters
+    pub fn get_default_params(&self) -> HashMap<String, String> {
+        self.default_params.clone()
+    }
+
+    /// Export the client configuration as a JSON-compatible dict.
+    /// Auth credentials and proxy passwords are redacted unless `include_secrets` is set.
+    #[pyo3(signature = (include_secrets = false))]
+    pub fn config_to_dict(&self, py: Python, include_secrets: bool) -> PyResult<PyObject> {
+        let mut snapshot = ClientConfigSnapshot {
+            base_url: self.base_url.clone(),
+            headers: self.headers.clone(),
+            default_params: self.default_params.clone(),
+            timeout: self.timeout,
+            body_mode: self.get_body_mode(),
+            auth: self.auth_config.clone(),
+            retry: self.retry_config.clone(),
+            timeouts: self.timeout_config.clone(),
+            pool: self.pool_config.clone(),
+            ssl: self.ssl_config.clone(),
+            proxy: self.proxy_config.clone(),
+            compression: self.compression_config.clone(),
+            protocol: self.protocol_config.clone(),
+            rate_limit: self.rate_limit_config.clone(),
+            allow_redirects: self.allow_redirects,
+            max_redirects: self.max_redirects,
+            dns_cache_ttl: self
+                .dns_cache
+                .as_ref()
+                .map(|cache| cache.ttl().as_secs_f64()),
+            dns_cache_max_entries: self
+                .dns_cache
+                .as_ref()
+                .map_or(crate::dns_cache::DEFAULT_MAX_ENTRIES, |cache| {
+                    cache.max_entries()
+                }),
+            unix_socket: self.unix_socket.clone(),
+            ip_version: self.ip_version.as_str().to_string(),
+            max_response_size: self.max_response_size,
+            // The async client runs no cleanup task; keep the sync client's default
+            cleanup_interval: Some(crate::config::DEFAULT_CLEANUP_INTERVAL),
+            circuit_breaker: self
+                .circuit_breaker
+                .as_ref()
+                .map(|breaker| breaker.config().clone()),
+        };
+        if !include_secrets {
+            snapshot.redact_secrets();
+        }
+        snapshot.to_py(py)
+    }
+
+    /// Build a client from a dict produced by `config_to_dict`.
+    /// Redacted auth is dropped rather than sent.
+    #[staticmethod]
+    pub fn from_config_dict(config: &PyAny) -> PyResult<Self> {
+        let mut snapshot = ClientConfigSnapshot::from_py(config)?;
+        snapshot.strip_redacted();
+
+        let mut client = Self::new(
+            snapshot.base_url,
+            Some(snapshot.headers),
+            snapshot.timeout,
+            snapshot.auth,
+            snapshot.retry,
+            Some(snapshot.timeouts),
+            Some(snapshot.pool),
+            Some(snapshot.ssl),
+            snapshot.proxy,
+            Some(snapshot.compression),
+            Some(snapshot.protocol),
+            snapshot.rate_limit,
+            &snapshot.body_mode,
+            snapshot.allow_redirects,
+            snapshot.max_redirects,
+            snapshot.dns_cache_ttl,
+            snapshot.dns_cache_max_entries,
+            snapshot.unix_socket,
+            snapshot.circuit_breaker,
+            &snapshot.ip_version,
+            snapshot.max_response_size,
+        )?;
+        client.default_params = snapshot.default_params;
+        Ok(client)
+    }
+
     /// Get rate limit configuration - direct return (matches HttpClient)
     pub fn get_rate_limit_config_sync(&self) -> Option<RateLimitConfig> {
         self.rate_limit_config.clone()
@@ -1113,6 +1698,63 @@ impl AsyncHttpClient {
         Ok(())
     }
 
+    /// Connection pool utilisation: active/idle connections, total acquired and per-host counts
+    pub fn get_pool_stats(&self, py: Python) -> PyResult<PyObject> {
+        crate::connection_pool::pool_stats_dict(
+            py,
+            &self.connection_pool,
+            &self.connection_multiplexer,
+        )
+    }
+
+    /// Drop cached DNS lookups so the next request to each host resolves again
+    pub fn clear_dns_cache(&self) {
+        if let Some(cache) = &self.dns_cache {
+            cache.clear();
+        }
+    }
+
+    /// Circuit state for `host`: "closed", "open" or "half_open"
+    pub fn circuit_state(&self, host: &str) -> String {
+        self.circuit_breaker
+            .as_ref()
+            .map_or("closed", |breaker| breaker.state(host))
+            .to_string()
+    }
+
+    /// Release the client's resources; later requests raise "Client is closed"
+    pub fn aclose<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let this = self.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            this.close_resources().await;
+            Ok(())
+        })
+    }
+
+    /// Whether aclose() has been called
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    fn __aenter__<'py>(slf: Py<Self>, py: Python<'py>) -> PyResult<&'py PyAny> {
+        slf.borrow(py).ensure_open()?;
+        pyo3_asyncio::tokio::future_into_py(py, async move { Ok(slf) })
+    }
+
+    fn __aexit__<'py>(
+        &self,
+        py: Python<'py>,
+        _exc_type: Option<&PyAny>,
+        _exc_value: Option<&PyAny>,
+        _traceback: Option<&PyAny>,
+    ) -> PyResult<&'py PyAny> {
+        let this = self.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            this.close_resources().await;
+            Ok(false)
+        })
+    }
+
     /// Get performance statistics - direct return (matches HttpClient)
     pub fn get_stats_sync(&self) -> PyResult<HashMap<String, f64>> {
         // This is a simplified version that returns basic stats
@@ -1134,7 +1776,13 @@ impl AsyncHttpClient {
 }
 
 impl AsyncHttpClient {
-    /// Enhanced async retry logic with exponential backoff and circuit breaker
+    /// Share a cookie jar across this client's requests, or stop sending cookies with `None`
+    pub(crate) fn set_cookie_jar(&mut self, jar: Option<Arc<CookieJar>>) {
+        self.cookie_jar = jar;
+    }
+
+    /// Execute a request with retries, sharing the response of an identical request
+    /// already in flight when a `DedupMiddleware` is installed
     pub(crate) async fn execute_request_with_retry(
         &self,
         method: Method,
@@ -1142,7 +1790,36 @@ impl AsyncHttpClient {
         params: Option<HashMap<String, String>>,
         body: Option<Vec<u8>>,
         headers: Option<HashMap<String, String>>,
+        options: crate::client::RequestOptions,
     ) -> PyResult<Response> {
+        let deduplicator = self.middleware_manager.lock().await.deduplicator();
+        let dedup = deduplicator
+            .filter(|_| body.is_none() && !options.stream)
+            .and_then(|dedup| {
+                let full_url = self.build_url(url).ok()?;
+                let full_url = crate::client::cache_url(&full_url, params.as_ref());
+                let key = dedup.key(method.as_str(), &full_url, &self.headers, headers.as_ref())?;
+                Some((dedup, key))
+            });
+
+        let request = self.execute_request_attempts(method, url, params, body, headers, options);
+        match dedup {
+            Some((dedup, key)) => dedup.run(key, request).await,
+            None => request.await,
+        }
+    }
+
+    /// Enhanced async retry logic with exponential backoff and circuit breaker
+    async fn execute_request_attempts(
+        &self,
+        method: Method,
+        url: &str,
+        params: Option<HashMap<String, String>>,
+        body: Option<Vec<u8>>,
+        headers: Option<HashMap<String, String>>,
+        options: crate::client::RequestOptions,
+    ) -> PyResult<Response> {
+        self.ensure_open()?;
         let start_time = Instant::now();
         let max_retries = self
             .retry_config
@@ -1150,19 +1827,56 @@ impl AsyncHttpClient {
             .map(|c| c.max_retries)
             .unwrap_or(3);
         let mut last_error = None;
+        // URLs that don't parse are left for the request itself to reject
+        let circuit = self.circuit_breaker.as_ref().and_then(|breaker| {
+            self.build_url(url)
+                .ok()
+                .and_then(|full_url| circuit_host(&full_url))
+                .map(|host| (breaker, host))
+        });
 
         for attempt in 0..=max_retries {
-            match self
+            // Dropped unrecorded if this future is cancelled, freeing any half-open slot
+            let trial = match &circuit {
+                Some((breaker, host)) => Some(breaker.admit(host)?),
+                None => None,
+            };
+
+            let outcome = self
                 .execute_single_request(
                     &method,
                     url,
                     params.as_ref(),
                     body.as_ref(),
                     headers.as_ref(),
+                    &options,
                 )
-                .await
-            {
+                .await;
+            if let Some(trial) = trial {
+                let success = outcome
+                    .as_ref()
+                    .is_ok_and(|response| !is_failure_status(response.status_code));
+                trial.record(success);
+            }
+
+            match outcome {
                 Ok(response) => {
+                    // Retry retryable statuses, waiting as long as the server asks
+                    if let Some(retry_config) = &self.retry_config {
+                        if attempt < max_retries
+                            && retry_config.should_retry_method(method.as_str())
+                            && retry_config.should_retry_status(response.status_code)
+                        {
+                            let delay = retry_config
+                                .retry_after_delay(&response.headers)
+                                .unwrap_or_else(|| self.retry_backoff(attempt));
+                            let reason = crate::client::RetryReason::Status(response.status_code);
+                            self.wait_before_retry(attempt, reason, delay, &options)
+                                .await?;
+                            continue;
+                        }
+                    }
+
                     let elapsed = start_time.elapsed().as_secs_f64();
 
                     // Update request statistics
@@ -1182,23 +1896,31 @@ impl AsyncHttpClient {
                     return Ok(response);
                 }
                 Err(e) => {
+                    // Only idempotent methods are retried unless the config allows more
+                    let retryable_method = match &self.retry_config {
+                        Some(retry_config) => retry_config.should_retry_method(method.as_str()),
+                        None => crate::config::default_retry_methods()
+                            .iter()
+                            .any(|allowed| allowed == method.as_str()),
+                    };
+                    if !retryable_method {
+                        return Err(e);
+                    }
+                    // retry_on (or retry_on_connection_errors) decides which errors are retried
+                    if let Some(retry_config) = &self.retry_config {
+                        if !Python::with_gil(|py| retry_config.should_retry_error(py, &e)) {
+                            return Err(e);
+                        }
+                    }
+
+                    let reason = crate::client::RetryReason::Error(e.to_string());
                     last_error = Some(e);
 
                     // If this is not the last attempt, wait before retrying
                     if attempt < max_retries {
-                        let delay = if let Some(retry_config) = &self.retry_config {
-                            // Exponential backoff
-                            let base_delay = retry_config.initial_delay;
-                            let backoff_factor = retry_config.exponential_base;
-                            let max_delay = retry_config.max_delay;
-
-                            let calculated_delay = base_delay * backoff_factor.powi(attempt as i32);
-                            calculated_delay.min(max_delay)
-                        } else {
-                            2.0_f64.powi(attempt as i32).min(10.0) // Default exponential backoff
-                        };
-
-                        tokio::time::sleep(Duration::from_secs_f64(delay)).await;
+                        let delay = self.retry_backoff(attempt);
+                        self.wait_before_retry(attempt, reason, delay, &options)
+                            .await?;
                     }
                 }
             }
@@ -1210,6 +1932,63 @@ impl AsyncHttpClient {
         }))
     }
 
+    /// Fail with `client_closed_error` once aclose() has been called
+    fn ensure_open(&self) -> PyResult<()> {
+        if self.is_closed() {
+            return Err(crate::client::client_closed_error());
+        }
+        Ok(())
+    }
+
+    /// Drain pooled connections and shut down HTTP/3, once
+    async fn close_resources(&self) {
+        if self.closed.swap(true, std::sync::atomic::Ordering::SeqCst) {
+            return;
+        }
+
+        self.connection_pool.drain_idle();
+        self.connection_multiplexer.cleanup_all();
+        let http3_client = self.http3_client.lock().await.take();
+        if let Some(client) = http3_client {
+            client.close().await;
+        }
+        self.http3_pool.close().await;
+    }
+
+    /// Exponential backoff before the retry following `attempt`
+    fn retry_backoff(&self, attempt: u32) -> f64 {
+        if let Some(retry_config) = &self.retry_config {
+            let calculated_delay =
+                retry_config.initial_delay * retry_config.exponential_base.powi(attempt as i32);
+            calculated_delay.min(retry_config.max_delay)
+        } else {
+            2.0_f64.powi(attempt as i32).min(10.0) // Default exponential backoff
+        }
+    }
+
+    /// Report a retry to `on_retry` and sleep for `delay` seconds, unless that overruns the deadline
+    async fn wait_before_retry(
+        &self,
+        attempt: u32,
+        reason: crate::client::RetryReason,
+        delay: f64,
+        options: &crate::client::RequestOptions,
+    ) -> PyResult<()> {
+        // Sleeping past the deadline cannot succeed, so fail now
+        if let Some(remaining) = crate::client::deadline_remaining(options.deadline)? {
+            if remaining.as_secs_f64() <= delay {
+                return Err(crate::client::deadline_exceeded_error());
+            }
+        }
+
+        if let Some(callback) = &self.on_retry {
+            crate::client::invoke_on_retry(callback, attempt + 1, reason, delay)?;
+        }
+
+        tokio::time::sleep(Duration::from_secs_f64(delay)).await;
+        Ok(())
+    }
+
     /// Execute a single HTTP request
     async fn execute_single_request(
         &self,
@@ -1218,77 +1997,270 @@ impl AsyncHttpClient {
         params: Option<&HashMap<String, String>>,
         body: Option<&Vec<u8>>,
         headers: Option<&HashMap<String, String>>,
+        options: &crate::client::RequestOptions,
     ) -> PyResult<Response> {
+        // Fail immediately when the deadline has already passed
+        crate::client::deadline_remaining(options.deadline)?;
+
+        // Requests go over TCP, so required HTTP/3 can never be honoured
+        if self.protocol_config.preferred_version == HttpVersion::Http3
+            && self.protocol_config.is_strict()
+        {
+            return Err(crate::error::connection_error(
+                "HTTP/3 is required by ProtocolFallback.None but AsyncHttpClient only supports HTTP/1.1 and HTTP/2"
+                    .to_string(),
+            ));
+        }
+
         let request_start = Instant::now();
 
         // Build the full URL
         let full_url = self.build_url(url)?;
+        let parsed_url = reqwest::Url::parse(&full_url).ok();
+        let host = parsed_url
+            .as_ref()
+            .and_then(|parsed| parsed.host_str().map(|h| h.to_string()))
+            .unwrap_or_default();
+        let path = parsed_url
+            .as_ref()
+            .map_or_else(|| "/".to_string(), |parsed| parsed.path().to_string());
 
-        // Create the request builder
-        let mut request_builder = self.client.request(method.clone(), &full_url);
+        // Await rate limit capacity, queueing when the config allows it
+        let rate_limiters = self.middleware_manager.lock().await.rate_limiters();
+        for limiter in &rate_limiters {
+            limiter.check_rate_limit_async(&host, &path).await?;
+        }
 
-        // Add query parameters
-        if let Some(params) = params {
-            for (key, value) in params {
-                request_builder = request_builder.query(&[(key, value)]);
-            }
+        let client = self.select_client(&host, options.proxy.as_ref())?;
+
+        // Wait while the host already has max_connections_per_host requests in flight
+        let _host_slot = self
+            .connection_pool
+            .acquire_host_slot(&host, self.pool_config.slot_wait())
+            .await?;
+
+        // Track pool utilisation, reported by get_pool_stats
+        let connection_permit = self.connection_pool.try_acquire_connection().await;
+        if let Some(permit) = &connection_permit {
+            permit.mark_active(host.clone());
         }
 
-        // Add default headers
-        for (key, value) in &self.headers {
-            request_builder = request_builder.header(key, value);
+        // Create the request builder
+        let mut request_builder = client.request(method.clone(), &full_url);
+
+        // Add query parameters merged with the client defaults
+        let params = crate::client::merge_query_params(
+            &self.default_params,
+            params.cloned(),
+            self.auth_config.as_ref(),
+        );
+        let cache_key = crate::client::cache_url(&full_url, params.as_ref());
+        if let Some(params) = params {
+            request_builder = request_builder.query(&params);
         }
 
-        // Add request-specific headers
+        // Default headers, then request-specific ones; kept for redirect hops
+        let mut request_headers: Vec<(String, String)> = self
+            .headers
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
         if let Some(headers) = headers {
-            for (key, value) in headers {
-                request_builder = request_builder.header(key, value);
-            }
+            request_headers.extend(headers.iter().map(|(k, v)| (k.clone(), v.clone())));
         }
 
-        // Add body if present
+        // Auto-detect Content-Type if not already set
         if let Some(body_data) = body {
-            // Auto-detect Content-Type if not already set
-            let mut has_content_type = false;
-            if let Some(headers) = headers {
-                has_content_type = headers.keys().any(|k| k.to_lowercase() == "content-type");
-            }
-            if !has_content_type {
-                has_content_type = self
-                    .headers
-                    .keys()
-                    .any(|k| k.to_lowercase() == "content-type");
-            }
+            let has_content_type = request_headers
+                .iter()
+                .any(|(k, _)| k.to_lowercase() == "content-type");
 
             if !has_content_type {
                 // Try to detect content type from body
-                if let Ok(json_test) = std::str::from_utf8(body_data) {
+                let content_type = if let Ok(json_test) = std::str::from_utf8(body_data) {
                     if json_test.trim_start().starts_with('{')
                         || json_test.trim_start().starts_with('[')
                     {
-                        request_builder =
-                            request_builder.header("Content-Type", "application/json");
+                        "application/json"
                     } else {
-                        request_builder = request_builder
-                            .header("Content-Type", "application/x-www-form-urlencoded");
+                        "application/x-www-form-urlencoded"
                     }
                 } else {
-                    request_builder =
-                        request_builder.header("Content-Type", "application/octet-stream");
+                    "application/octet-stream"
+                };
+                request_headers.push(("Content-Type".to_string(), content_type.to_string()));
+            }
+        }
+
+        // Run header, interceptor and logging middleware over the final headers in priority order
+        let (hooks, interceptors) = {
+            let manager = self.middleware_manager.lock().await;
+            (manager.hooks(), manager.interceptors())
+        };
+        if !hooks.is_empty() {
+            let mut header_map: HashMap<String, String> = request_headers.drain(..).collect();
+            Python::with_gil(|py| {
+                hooks.iter().try_for_each(|hook| {
+                    hook.on_request(py, method.as_str(), &full_url, &cache_key, &mut header_map)
+                })
+            })?;
+            request_headers = header_map.into_iter().collect();
+        }
+
+        // Serve a fresh cached GET response, or ask the server to revalidate a stale one
+        let caches = if *method == Method::GET {
+            self.middleware_manager.lock().await.caches()
+        } else {
+            Vec::new()
+        };
+        let cache_headers: HashMap<String, String> = request_headers.iter().cloned().collect();
+        for cache in &caches {
+            if let Some(response) = cache.fresh_response(&cache_key, &cache_headers) {
+                return Ok(response);
+            }
+            for (key, value) in cache.validators(&cache_key, &cache_headers) {
+                if !request_headers
+                    .iter()
+                    .any(|(k, _)| k.eq_ignore_ascii_case(&key))
+                {
+                    request_headers.push((key, value));
                 }
             }
+        }
 
+        for (key, value) in &request_headers {
+            request_builder = request_builder.header(key, value);
+        }
+
+        // Add body if present
+        if let Some(body_data) = body {
             request_builder = request_builder.body(body_data.clone());
         }
 
         // Apply authentication
-        request_builder = self.apply_oauth2_auth(request_builder).await;
+        request_builder = self.apply_oauth2_auth(request_builder).await?;
 
-        // Execute the request
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| map_reqwest_error(&e))?;
+        // A per-call timeout or the remaining deadline budget replaces the client timeout
+        if let Some(timeout) = options.attempt_timeout()? {
+            request_builder = request_builder.timeout(timeout);
+        }
+
+        // Execute the request, following redirects unless disabled for this call
+        let follow_redirects = options.allow_redirects.unwrap_or(self.allow_redirects);
+        let mut current_method = method.clone();
+        let mut history = Vec::new();
+        let mut digest_attempts = 0;
+        let signers = self.middleware_manager.lock().await.signers();
+        let phases = Arc::new(ConnectionPhases::default());
+        let exchange_start = Instant::now();
+        // If this future is dropped mid-exchange (a cancelled task), hyper closes the
+        // connection and the permit must not park it for reuse
+        let exchange = connection_permit
+            .as_ref()
+            .map(|permit| permit.begin_exchange());
+        let response = loop {
+            let (client, built) = auth_common::build_request(
+                request_builder,
+                self.auth_config.as_ref(),
+                &self.digest_auth,
+            );
+            let sent = match built {
+                Ok(mut built) => {
+                    if let Some(jar) = &self.cookie_jar {
+                        jar.apply(&mut built);
+                    }
+                    // Sign last so the signature covers the final URL, headers and body
+                    for signer in &signers {
+                        signer.sign(&mut built);
+                    }
+                    phases
+                        .scope(crate::client::execute_with_write_timeout(
+                            &client,
+                            built,
+                            &self.timeout_config,
+                            options,
+                        ))
+                        .await?
+                }
+                Err(e) => Err(e),
+            };
+            let response = match sent {
+                Ok(response) => response,
+                Err(e) => {
+                    if let Some(permit) = &connection_permit {
+                        permit.mark_failed(&host);
+                    }
+                    let error = crate::client::map_deadline_error(&e, options.deadline);
+                    return Err(intercept_error(&interceptors, method, &full_url, error));
+                }
+            };
+            if let Some(jar) = &self.cookie_jar {
+                jar.store_response(&response);
+            }
+            if let Some(alt_svc) = response
+                .headers()
+                .get(reqwest::header::ALT_SVC)
+                .and_then(|value| value.to_str().ok())
+            {
+                self.protocol_negotiator
+                    .record_alt_svc(response.url().as_str(), alt_svc);
+            }
+
+            // Answer a Digest or NTLM challenge by re-sending the same request
+            let hop = if self.digest_auth.should_retry(
+                &response,
+                self.auth_config.as_ref(),
+                &mut digest_attempts,
+            ) {
+                let hop = crate::client::RedirectHop::repeat(response.url(), &current_method);
+                // Read the challenge body so the connection is kept for the next
+                // message, as NTLM authenticates the connection rather than requests
+                let _ = response.bytes().await;
+                hop
+            } else {
+                let hop = if follow_redirects {
+                    crate::client::RedirectHop::from_response(&response, &current_method)
+                } else {
+                    None
+                };
+                let hop = match hop {
+                    Some(hop) => hop,
+                    None => break response,
+                };
+                if history.len() >= self.max_redirects {
+                    return Err(crate::client::too_many_redirects_error(
+                        self.max_redirects,
+                        &full_url,
+                    ));
+                }
+                history.push(Response::from_redirect(&response));
+                hop
+            };
+
+            let hop_host = hop.url.host_str().unwrap_or_default();
+            let hop_client = self.select_client(hop_host, options.proxy.as_ref())?;
+            request_builder = hop_client.request(hop.method.clone(), hop.url.clone());
+            for (key, value) in &request_headers {
+                if hop.keeps_header(key) {
+                    request_builder = request_builder.header(key, value);
+                }
+            }
+            if hop.same_origin {
+                request_builder = self.apply_oauth2_auth(request_builder).await?;
+            }
+            if let (true, Some(body_data)) = (hop.keep_body, body) {
+                request_builder = request_builder.body(body_data.clone());
+            }
+            if let Some(timeout) = options.attempt_timeout()? {
+                request_builder = request_builder.timeout(timeout);
+            }
+            current_method = hop.method;
+        };
+        let ttfb = exchange_start.elapsed();
+
+        if let Some(permit) = &connection_permit {
+            permit.mark_used(&host, request_start.elapsed());
+        }
 
         let status_code = response.status().as_u16();
         let headers: HashMap<String, String> = response
@@ -1296,6 +2268,9 @@ impl AsyncHttpClient {
             .iter()
             .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
             .collect();
+        for limiter in &rate_limiters {
+            limiter.record_response(&host, &path, status_code, &headers);
+        }
 
         let protocol = response.version();
         let protocol_str = match protocol {
@@ -1317,36 +2292,88 @@ impl AsyncHttpClient {
             _ => 1.1,
         };
 
-        // Read response body
-        let content = response
-            .bytes()
-            .await
-            .map_err(|e| map_reqwest_error(&e))?
-            .to_vec();
+        // Caches and transform hooks read the body on this runtime, where a lazy body
+        // cannot be loaded, so it is awaited here instead
+        let mut response_obj = if (self.lazy_body || options.stream)
+            && caches.is_empty()
+            && !hooks.iter().any(Hook::reads_body)
+        {
+            let mut lazy_response = Response::from_reqwest_lazy(
+                response,
+                pyo3_asyncio::tokio::get_runtime().handle().clone(),
+                None,
+                self.max_response_size,
+            );
+            lazy_response.history = history;
+            lazy_response
+        } else {
+            let set_cookies = crate::cookies::set_cookie_headers(response.headers());
+            let final_url = response.url().to_string();
+
+            // Read response body
+            let content =
+                crate::response::read_body_mapped(response, self.max_response_size, |e| {
+                    crate::client::map_deadline_error(e, options.deadline)
+                })
+                .await;
+            let content = match content {
+                Ok(content) => content,
+                Err(e) => return Err(intercept_error(&interceptors, method, &full_url, e)),
+            };
 
-        let elapsed = request_start.elapsed().as_secs_f64();
+            Response {
+                status_code,
+                reason: crate::response::reason_phrase(status_code),
+                headers,
+                content,
+                url: final_url,
+                elapsed: 0.0,
+                protocol: Some(protocol_str),
+                protocol_version: Some(protocol_version),
+                protocol_stats: None,
+                request_time: 0.0,
+                response_time: 0.0,
+                total_time: 0.0,
+                start_time: 0.0,
+                end_time: 0.0,
+                timing: None,
+                lazy_body: None,
+                set_cookies,
+                history,
+                encoding_override: None,
+            }
+        };
+        response_obj.record_timing(phases.finish(exchange_start, ttfb));
+        if let Some(exchange) = exchange {
+            exchange.complete();
+        }
 
-        Ok(Response {
-            status_code,
-            headers,
-            content,
-            url: full_url,
-            elapsed,
-            protocol: Some(protocol_str),
-            protocol_version: Some(protocol_version),
-            protocol_stats: None,
-            request_time: elapsed,
-            response_time: elapsed,
-            total_time: elapsed,
-            start_time: elapsed,
-            end_time: elapsed,
-            timing: Some(elapsed),
+        // Store cacheable responses and expand 304s into the cached response
+        for cache in &caches {
+            response_obj = cache.handle_response(&cache_key, &cache_headers, response_obj)?;
+        }
+
+        // Hand the response to interceptor and logging middleware
+        if hooks.is_empty() {
+            return Ok(response_obj);
+        }
+        let response_time_ms = exchange_start.elapsed().as_secs_f64() * 1000.0;
+        Python::with_gil(|py| {
+            hooks.iter().try_fold(response_obj, |response, hook| {
+                hook.on_response(py, method.as_str(), &cache_key, response, response_time_ms)
+            })
         })
     }
 
-    /// Apply OAuth2 authentication asynchronously if needed
-    async fn apply_oauth2_auth(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    /// Apply OAuth2 or provider-supplied bearer authentication asynchronously if needed
+    async fn apply_oauth2_auth(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> PyResult<reqwest::RequestBuilder> {
         if let Some(ref auth_config) = self.auth_config {
+            if let Some(ref provider) = auth_config.token_provider {
+                return Ok(request.bearer_auth(provider.token_async().await?));
+            }
             if auth_config.is_oauth2() {
                 // Check if we have a valid token
                 let mut token_guard = self.oauth2_token.lock().await;
@@ -1359,127 +2386,242 @@ impl AsyncHttpClient {
                 };
 
                 if needs_refresh {
-                    // Fetch new token
-                    if let Ok(new_token) = auth_config.fetch_oauth2_token().await {
-                        *token_guard = Some(new_token);
+                    // Refresh with the current refresh token, or fetch a new token
+                    if let Ok(new_token) =
+                        auth_config.fetch_oauth2_token(token_guard.as_ref()).await
+                    {
+                        *token_guard = Some(new_token.clone());
+                        drop(token_guard);
+                        auth_config.notify_token_refresh(&new_token)?;
+                        return Ok(request.bearer_auth(&new_token.access_token));
                     }
                 }
 
                 // Apply token to request
                 if let Some(ref token) = *token_guard {
-                    return request.bearer_auth(&token.access_token);
+                    return Ok(request.bearer_auth(&token.access_token));
                 }
             }
         }
 
         // For non-OAuth2 auth, use the synchronous method
-        auth_common::apply_authentication(request, self.auth_config.as_ref())
+        Ok(auth_common::apply_authentication(
+            request,
+            self.auth_config.as_ref(),
+        ))
     }
 
     /// Rebuild the HTTP client with current configuration (internal method)
     fn rebuild_client(&mut self) -> PyResult<()> {
-        let mut client_builder = Client::builder()
-            .timeout(Duration::from_secs_f64(
-                self.timeout_config.read_timeout.unwrap_or(30.0),
-            ))
-            .pool_max_idle_per_host(self.pool_config.max_idle_per_host)
-            .pool_idle_timeout(Duration::from_secs_f64(self.pool_config.idle_timeout));
+        let client_builder = self.client_builder()?;
+        self.client = client_builder.build().map_err(|e| map_reqwest_error(&e))?;
+        self.rebuild_host_clients()?;
+        // A fresh map so clones still holding the old config keep their own
+        self.proxy_clients = Arc::new(parking_lot::Mutex::new(HashMap::new()));
 
-        // Configure timeouts
-        if let Some(connect_timeout) = self.timeout_config.connect_timeout {
-            client_builder =
-                client_builder.connect_timeout(Duration::from_secs_f64(connect_timeout));
-        }
+        Ok(())
+    }
 
-        // Configure SSL/TLS
-        if !self.ssl_config.verify {
-            client_builder = client_builder.danger_accept_invalid_certs(true);
-        }
+    /// Client builder reflecting the current configuration
+    fn client_builder(&self) -> PyResult<reqwest::ClientBuilder> {
+        self.client_builder_with_proxy(self.proxy_config.as_ref())
+    }
 
-        // Apply additional SSL configuration
-        if let Some(cert_path) = &self.ssl_config.cert_file {
-            if let Some(key_path) = &self.ssl_config.key_file {
-                // Load client certificate and key
-                match std::fs::read(cert_path) {
-                    Ok(cert_data) => {
-                        match std::fs::read(key_path) {
-                            Ok(key_data) => {
-                                // Use from_pkcs8_pem which is the correct method
-                                if let Ok(identity) =
-                                    reqwest::Identity::from_pkcs8_pem(&cert_data, &key_data)
-                                {
-                                    client_builder = client_builder.identity(identity);
-                                }
-                            }
-                            Err(_) => {} // Ignore key loading errors
-                        }
-                    }
-                    Err(_) => {} // Ignore cert loading errors
-                }
-            }
-        }
+    /// Client builder reflecting the current configuration, with `proxy` in place of the client's
+    fn client_builder_with_proxy(
+        &self,
+        proxy: Option<&ProxyConfig>,
+    ) -> PyResult<reqwest::ClientBuilder> {
+        async_client_builder(&self.client_settings(proxy))
+    }
 
-        // Configure CA bundle if specified
-        if let Some(ca_bundle) = &self.ssl_config.ca_bundle {
-            match std::fs::read(ca_bundle) {
-                Ok(ca_data) => {
-                    if let Ok(cert) = reqwest::Certificate::from_pem(&ca_data) {
-                        client_builder = client_builder.add_root_certificate(cert);
-                    }
-                }
-                Err(_) => {} // Ignore CA bundle errors
-            }
+    /// Settings the current configuration builds clients from, with `proxy` in place of the client's
+    fn client_settings<'a>(&'a self, proxy: Option<&'a ProxyConfig>) -> ClientSettings<'a> {
+        ClientSettings {
+            timeout: self.timeout,
+            timeout_config: &self.timeout_config,
+            pool_config: &self.pool_config,
+            ssl_config: &self.ssl_config,
+            proxy_config: proxy,
+            compression_config: &self.compression_config,
+            protocol_config: &self.protocol_config,
+            dns_cache: self.dns_cache.as_ref(),
+            unix_socket: self.unix_socket.as_deref(),
+            ip_version: self.ip_version,
+            client_cert: None,
         }
+    }
 
-        // Configure proxy if specified
-        if let Some(ref proxy_cfg) = self.proxy_config {
-            if let Ok(proxy) = reqwest::Proxy::all(&proxy_cfg.url) {
-                let mut proxy = proxy;
+    /// Build a client that presents the given certificate, otherwise configured like the main client
+    fn build_host_client(&self, cert_pem: &str, key_pem: &str) -> PyResult<Client> {
+        let client_builder = async_client_builder(&ClientSettings {
+            client_cert: Some((cert_pem, key_pem)),
+            ..self.client_settings(self.proxy_config.as_ref())
+        })?;
+        client_builder.build().map_err(|e| map_reqwest_error(&e))
+    }
 
-                // Add authentication if specified
-                if let (Some(username), Some(password)) = (&proxy_cfg.username, &proxy_cfg.password)
-                {
-                    proxy = proxy.basic_auth(username, password);
-                }
+    /// Recreate per-host clients after a configuration change
+    fn rebuild_host_clients(&mut self) -> PyResult<()> {
+        let mut host_clients = HashMap::new();
+        for (host, (cert_pem, key_pem)) in &self.client_certs {
+            host_clients.insert(host.clone(), self.build_host_client(cert_pem, key_pem)?);
+        }
+        self.host_clients = host_clients;
+        Ok(())
+    }
 
-                client_builder = client_builder.proxy(proxy);
-            }
+    /// Client for a request to `host`: a per-request proxy's client, else the
+    /// host's client-certificate client, else the shared one.
+    ///
+    /// Proxied clients are built on first use and kept until the configuration
+    /// changes; they don't present per-host client certificates.
+    fn select_client(&self, host: &str, proxy: Option<&ProxyConfig>) -> PyResult<Client> {
+        let Some(proxy) = proxy else {
+            return Ok(self.host_clients.get(host).unwrap_or(&self.client).clone());
+        };
+        let mut proxy_clients = self.proxy_clients.lock();
+        if let Some(client) = proxy_clients.get(proxy) {
+            return Ok(client.clone());
         }
 
-        // Configure compression
-        if self.compression_config.enable_response_compression {
-            client_builder = client_builder.gzip(true);
+        let client = self
+            .client_builder_with_proxy(Some(proxy))?
+            .build()
+            .map_err(|e| map_reqwest_error(&e))?;
+        proxy_clients.insert(proxy.clone(), client.clone());
+        Ok(client)
+    }
 
-            if self.compression_config.supports_algorithm("brotli") {
-                client_builder = client_builder.brotli(true);
-            }
+    /// Run `requests` with at most `concurrency` in flight, as a list of
+    /// Responses and exceptions in input order
+    async fn execute_many(
+        &self,
+        requests: Vec<BatchRequest>,
+        concurrency: usize,
+        options: crate::client::RequestOptions,
+    ) -> PyResult<PyObject> {
+        if concurrency == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "concurrency must be at least 1",
+            ));
         }
 
-        // Configure HTTP/2 settings
-        if self.protocol_config.is_http2_enabled() {
-            if self.protocol_config.enable_http2_prior_knowledge {
-                client_builder = client_builder.http2_prior_knowledge();
+        let semaphore = tokio::sync::Semaphore::new(concurrency);
+        let results = futures_util::future::join_all(requests.into_iter().map(|request| {
+            let semaphore = &semaphore;
+            let options = options.clone();
+            async move {
+                let _permit = semaphore.acquire().await.map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Batch semaphore closed: {}",
+                        e
+                    ))
+                })?;
+                self.execute_request_with_retry(
+                    request.method,
+                    &request.url,
+                    request.params,
+                    request.body,
+                    request.headers,
+                    options,
+                )
+                .await
             }
+        }))
+        .await;
+
+        Python::with_gil(|py| {
+            let items: Vec<PyObject> = results
+                .into_iter()
+                .map(|result| match result {
+                    Ok(response) => response.into_py(py),
+                    Err(e) => e.into_value(py).into_py(py),
+                })
+                .collect();
+            Ok(pyo3::types::PyList::new(py, items).into_py(py))
+        })
+    }
+}
 
-            if let Some(interval) = self.protocol_config.http2_settings.keep_alive_interval {
-                client_builder =
-                    client_builder.http2_keep_alive_interval(Some(Duration::from_secs(interval)));
-            }
+/// One request of a `request_many` batch
+struct BatchRequest {
+    method: Method,
+    url: String,
+    params: Option<HashMap<String, String>>,
+    headers: Option<HashMap<String, String>>,
+    body: Option<Vec<u8>>,
+}
 
-            if let Some(timeout) = self.protocol_config.http2_settings.keep_alive_timeout {
-                client_builder =
-                    client_builder.http2_keep_alive_timeout(Duration::from_secs(timeout));
+impl BatchRequest {
+    fn from_py(item: &PyAny) -> PyResult<Self> {
+        let mut fields: HashMap<String, &PyAny> = item.extract().map_err(|_| {
+            pyo3::exceptions::PyTypeError::new_err("request_many items must be dicts")
+        })?;
+        let url: String = fields
+            .remove("url")
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err("request is missing \"url\""))?
+            .extract()?;
+        let method = match fields.remove("method") {
+            Some(method) => crate::client::parse_method(method.extract()?)?,
+            None => Method::GET,
+        };
+        let params = fields.remove("params").map(|p| p.extract()).transpose()?;
+        let mut headers: Option<HashMap<String, String>> =
+            fields.remove("headers").map(|h| h.extract()).transpose()?;
+
+        let body = match fields.remove("json") {
+            Some(json) => {
+                let value: serde_json::Value = pythonize::depythonize(json)?;
+                headers
+                    .get_or_insert_with(HashMap::new)
+                    .insert("Content-Type".to_string(), "application/json".to_string());
+                Some(serde_json::to_vec(&value).map_err(|e| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "JSON serialization error: {}",
+                        e
+                    ))
+                })?)
             }
-        }
+            None => None,
+        };
 
-        // Configure HTTP/3 via Quiche
-        if self.protocol_config.is_http3_enabled() {
-            // HTTP/3 support requires unstable features
-            // This will only work if compiled with RUSTFLAGS="--cfg reqwest_unstable"
-            // client_builder = client_builder.http3_prior_knowledge();
+        if let Some(unknown) = fields.keys().next() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Unknown request field: {}",
+                unknown
+            )));
         }
 
-        self.client = client_builder.build().map_err(|e| map_reqwest_error(&e))?;
-        Ok(())
+        Ok(Self {
+            method,
+            url,
+            params,
+            headers,
+            body,
+        })
     }
 }
+
+/// Report a failed request to error interceptors, returning the error to raise
+fn intercept_error(
+    interceptors: &[InterceptorMiddleware],
+    method: &Method,
+    url: &str,
+    error: PyErr,
+) -> PyErr {
+    let reported = Python::with_gil(|py| {
+        interceptors.iter().try_for_each(|interceptor| {
+            interceptor.intercept_error(py, method.as_str(), url, &error)
+        })
+    });
+    match reported {
+        Ok(()) => error,
+        Err(interceptor_error) => interceptor_error,
+    }
+}
+
+/// Client builder for the async client, which suppresses reqwest's default User-Agent
+fn async_client_builder(settings: &ClientSettings) -> PyResult<reqwest::ClientBuilder> {
+    Ok(settings.builder()?.user_agent(""))
+}
@@ -1,20 +1,25 @@
 use crate::async_client::AsyncHttpClient;
 use crate::config::{AuthConfig, RetryConfig, TimeoutConfig};
+use crate::cookies::CookieJar;
+use crate::session::merge_headers;
 use pyo3::prelude::*;
 use pyo3::types::PyAny;
-use reqwest::cookie::Jar;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
-/// Async Session for managing HTTP requests with shared state
+/// Async Session for managing HTTP requests with shared state.
+///
+/// Session `headers` and `params` are merged into every request before dispatch,
+/// with the same precedence as `Session`: per-request values win.
 #[pyclass]
 pub struct AsyncSession {
     client: Arc<Mutex<AsyncHttpClient>>,
     session_headers: HashMap<String, String>,
-    cookies: Arc<Jar>,
+    cookies: Arc<CookieJar>,
     base_url: Option<String>,
     auth_config: Option<AuthConfig>,
     retry_config: Option<RetryConfig>,
+    #[allow(dead_code)]
     timeout_config: Option<TimeoutConfig>,
     session_data: HashMap<String, String>,
     persist_cookies: bool,
@@ -31,7 +36,8 @@ impl AsyncSession {
         timeout_config = None,
         pool_config = None,
         ssl_config = None,
-        persist_cookies = true
+        persist_cookies = true,
+        params = None
     ))]
     pub fn new(
         base_url: Option<String>,
@@ -42,10 +48,12 @@ impl AsyncSession {
         pool_config: Option<crate::config::PoolConfig>,
         ssl_config: Option<crate::config::SSLConfig>,
         persist_cookies: bool,
+        params: Option<HashMap<String, String>>,
     ) -> PyResult<Self> {
-        let client = AsyncHttpClient::new(
+        // Session headers are merged per request so later changes apply to the next call
+        let mut client = AsyncHttpClient::new(
             base_url.clone(),
-            headers.clone(),
+            None,
             30.0,
             auth_config.clone(),
             retry_config.clone(),
@@ -56,11 +64,28 @@ impl AsyncSession {
             None, // compression_config
             None, // protocol_config
             None, // rate_limit_config
+            "eager",
+            true, // allow_redirects
+            crate::config::DEFAULT_MAX_REDIRECTS,
+            None, // dns_cache_ttl
+            crate::dns_cache::DEFAULT_MAX_ENTRIES,
+            None,   // unix_socket
+            None,   // circuit_breaker_config
+            "auto", // ip_version
+            None,   // max_response_size
         )?;
+
+        // Responses fill the jar and later requests send back the cookies that match
+        let cookies = Arc::new(CookieJar::new());
+        if persist_cookies {
+            client.set_cookie_jar(Some(Arc::clone(&cookies)));
+        }
+        client.set_default_params(params.unwrap_or_default());
+
         Ok(AsyncSession {
             client: Arc::new(Mutex::new(client)),
-            session_headers: headers.clone().unwrap_or_default(),
-            cookies: Arc::new(Jar::default()),
+            session_headers: headers.unwrap_or_default(),
+            cookies,
             base_url,
             auth_config,
             retry_config,
@@ -82,11 +107,18 @@ impl AsyncSession {
         let client_guard = self.client.lock().unwrap();
         let client_py = Py::new(py, client_guard.clone())?;
         drop(client_guard);
-        let mut merged_headers = self.session_headers.clone();
-        if let Some(request_headers) = headers {
-            merged_headers.extend(request_headers);
-        }
-        AsyncHttpClient::get(client_py, py, url, params, Some(merged_headers))
+        let merged_headers = merge_headers(&self.session_headers, headers);
+        AsyncHttpClient::get(
+            client_py,
+            py,
+            url,
+            params,
+            Some(merged_headers),
+            None,
+            None,
+            None,
+            None,
+        )
     }
 
     /// POST request with session state
@@ -103,11 +135,22 @@ impl AsyncSession {
         let client_guard = self.client.lock().unwrap();
         let client_py = Py::new(py, client_guard.clone())?;
         drop(client_guard);
-        let mut merged_headers = self.session_headers.clone();
-        if let Some(request_headers) = headers {
-            merged_headers.extend(request_headers);
-        }
-        AsyncHttpClient::post(client_py, py, url, json, data, files, Some(merged_headers))
+        let merged_headers = merge_headers(&self.session_headers, headers);
+        AsyncHttpClient::post(
+            client_py,
+            py,
+            url,
+            json,
+            data,
+            files,
+            Some(merged_headers),
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+        )
     }
 
     /// PUT request with session state
@@ -124,11 +167,22 @@ impl AsyncSession {
         let client_guard = self.client.lock().unwrap();
         let client_py = Py::new(py, client_guard.clone())?;
         drop(client_guard);
-        let mut merged_headers = self.session_headers.clone();
-        if let Some(request_headers) = headers {
-            merged_headers.extend(request_headers);
-        }
-        AsyncHttpClient::put(client_py, py, url, json, data, files, Some(merged_headers))
+        let merged_headers = merge_headers(&self.session_headers, headers);
+        AsyncHttpClient::put(
+            client_py,
+            py,
+            url,
+            json,
+            data,
+            files,
+            Some(merged_headers),
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+        )
     }
 
     /// DELETE request with session state
@@ -142,11 +196,17 @@ impl AsyncSession {
         let client_guard = self.client.lock().unwrap();
         let client_py = Py::new(py, client_guard.clone())?;
         drop(client_guard);
-        let mut merged_headers = self.session_headers.clone();
-        if let Some(request_headers) = headers {
-            merged_headers.extend(request_headers);
-        }
-        AsyncHttpClient::delete(client_py, py, url, Some(merged_headers))
+        let merged_headers = merge_headers(&self.session_headers, headers);
+        AsyncHttpClient::delete(
+            client_py,
+            py,
+            url,
+            Some(merged_headers),
+            None,
+            None,
+            None,
+            None,
+        )
     }
 
     /// PATCH request with session state
@@ -163,11 +223,21 @@ impl AsyncSession {
         let client_guard = self.client.lock().unwrap();
         let client_py = Py::new(py, client_guard.clone())?;
         drop(client_guard);
-        let mut merged_headers = self.session_headers.clone();
-        if let Some(request_headers) = headers {
-            merged_headers.extend(request_headers);
-        }
-        AsyncHttpClient::patch(client_py, py, url, json, data, files, Some(merged_headers))
+        let merged_headers = merge_headers(&self.session_headers, headers);
+        AsyncHttpClient::patch(
+            client_py,
+            py,
+            url,
+            json,
+            data,
+            files,
+            Some(merged_headers),
+            None,
+            None,
+            None,
+            None,
+            false,
+        )
     }
 
     /// HEAD request with session state
@@ -181,11 +251,17 @@ impl AsyncSession {
         let client_guard = self.client.lock().unwrap();
         let client_py = Py::new(py, client_guard.clone())?;
         drop(client_guard);
-        let mut merged_headers = self.session_headers.clone();
-        if let Some(request_headers) = headers {
-            merged_headers.extend(request_headers);
-        }
-        AsyncHttpClient::head(client_py, py, url, Some(merged_headers))
+        let merged_headers = merge_headers(&self.session_headers, headers);
+        AsyncHttpClient::head(
+            client_py,
+            py,
+            url,
+            Some(merged_headers),
+            None,
+            None,
+            None,
+            None,
+        )
     }
 
     /// OPTIONS request with session state
@@ -199,11 +275,17 @@ impl AsyncSession {
         let client_guard = self.client.lock().unwrap();
         let client_py = Py::new(py, client_guard.clone())?;
         drop(client_guard);
-        let mut merged_headers = self.session_headers.clone();
-        if let Some(request_headers) = headers {
-            merged_headers.extend(request_headers);
-        }
-        AsyncHttpClient::options(client_py, py, url, Some(merged_headers))
+        let merged_headers = merge_headers(&self.session_headers, headers);
+        AsyncHttpClient::options(
+            client_py,
+            py,
+            url,
+            Some(merged_headers),
+            None,
+            None,
+            None,
+            None,
+        )
     }
 
     /// Get the session's base URL
@@ -291,12 +373,45 @@ impl AsyncSession {
     pub fn headers(&self) -> HashMap<String, String> {
         self.session_headers.clone()
     }
+    /// Replace all session headers
+    #[setter]
+    pub fn set_headers(&mut self, headers: HashMap<String, String>) {
+        self.session_headers = headers;
+    }
+    /// Query parameters sent with every request (per-request params take precedence)
+    #[getter]
+    pub fn params(&self) -> HashMap<String, String> {
+        self.client
+            .lock()
+            .map(|client| client.get_default_params())
+            .unwrap_or_default()
+    }
+    /// Replace all session query parameters
+    #[setter]
+    pub fn set_params(&mut self, params: HashMap<String, String>) {
+        if let Ok(mut client) = self.client.lock() {
+            client.set_default_params(params);
+        }
+    }
 
     /// Clear all cookies
     pub fn clear_cookies(&mut self) {
-        // Cookie clearing would require access to the internal cookie store
-        // This is a simplified implementation since reqwest::Jar doesn't have a public clear method
-        self.cookies = Arc::new(Jar::default());
+        self.cookies.clear();
+    }
+    /// Session cookies as a name -> value dict
+    #[getter]
+    pub fn cookies(&self) -> HashMap<String, String> {
+        self.cookies.to_dict()
+    }
+
+    /// Save the session's cookies to a JSON file
+    pub fn save_cookies(&self, path: &str) -> PyResult<()> {
+        self.cookies.save(path)
+    }
+
+    /// Load cookies from a file written by `save_cookies`, skipping expired ones
+    pub fn load_cookies(&self, path: &str) -> PyResult<usize> {
+        self.cookies.load(path)
     }
     /// Check if cookies are persisted
     #[getter]
@@ -314,23 +429,16 @@ impl AsyncSession {
         self.session_headers.remove(key)
     }
 
-    /// Set cookie (using a dummy URL for cookie store)
-    pub fn set_cookie(&mut self, name: &str, value: &str) {
-        // Create a simple cookie string for the cookie jar
-        let cookie_str = format!("{}={}", name, value);
-        let url = self.base_url.as_deref().unwrap_or("http://localhost");
-        if let Ok(parsed_url) = url.parse() {
-            self.cookies.add_cookie_str(&cookie_str, &parsed_url);
-        }
+    /// Set a cookie for `domain` (default: the base URL's host) and its subdomains
+    #[pyo3(signature = (name, value, domain = None, path = "/"))]
+    pub fn set_cookie(&mut self, name: &str, value: &str, domain: Option<String>, path: &str) {
+        let domain = crate::session::cookie_domain(domain, self.base_url.as_deref());
+        self.cookies.set(name, value, &domain, path);
     }
 
-    /// Get cookie (simplified implementation)
+    /// Get a stored cookie's value by name
     pub fn get_cookie(&self, name: &str) -> Option<String> {
-        // This is a simplified implementation since reqwest::Jar doesn't provide
-        // easy access to individual cookies. In practice, cookies are handled
-        // automatically by the HTTP client.
-        // For now, return None as cookies are managed internally by reqwest
-        None
+        self.cookies.get(name)
     }
 
     /// Close session (cleanup resources)
@@ -375,15 +483,3 @@ impl AsyncSession {
         Ok(())
     }
 }
-
-/// Helper function to merge session headers with request headers
-fn merge_headers(
-    session_headers: &HashMap<String, String>,
-    request_headers: Option<HashMap<String, String>>,
-) -> HashMap<String, String> {
-    let mut merged = session_headers.clone();
-    if let Some(req_headers) = request_headers {
-        merged.extend(req_headers);
-    }
-    merged
-}
@@ -1,5 +1,12 @@
 use crate::config::{AuthConfig, AuthType};
+use base64::{engine::general_purpose, Engine as _};
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use pyo3::prelude::*;
 use reqwest::RequestBuilder;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Shared authentication logic for both sync and async clients
 /// This eliminates code duplication between HttpClient and AsyncHttpClient
@@ -43,6 +50,9 @@ pub fn apply_authentication(
                     request = request.header("Authorization", format!("{} {}", token_type, token));
                 }
             }
+            AuthType::Digest | AuthType::AwsSigV4 | AuthType::Ntlm => {
+                // Applied to the built request by `build_request`
+            }
             AuthType::Custom => {
                 if let Some(custom_type) = auth.get_credential("custom_type") {
                     match custom_type.as_str() {
@@ -72,10 +82,626 @@ pub fn apply_authentication(
     request
 }
 
+/// Build a request and apply the auth schemes that need its final URL or body:
+/// Digest answers the cached challenge for the origin, NTLM sends the pending
+/// handshake message for the origin, SigV4 signs the request
+pub fn build_request(
+    request: RequestBuilder,
+    auth_config: Option<&AuthConfig>,
+    digest_auth: &DigestAuthState,
+) -> (reqwest::Client, reqwest::Result<reqwest::Request>) {
+    let (client, request) = request.build_split();
+    let (mut request, auth) = match (request, auth_config) {
+        (Ok(request), Some(auth)) => (request, auth),
+        (request, _) => return (client, request),
+    };
+
+    let authorization = match auth.auth_type {
+        AuthType::Digest => digest_auth.authorization(Some(auth), request.method(), request.url()),
+        AuthType::Ntlm => digest_auth.ntlm_authorization(request.url()),
+        AuthType::AwsSigV4 => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            sign_aws_sigv4(&mut request, auth, now)
+        }
+        _ => None,
+    };
+    if let Some(value) = authorization {
+        if let Ok(value) = reqwest::header::HeaderValue::from_str(&value) {
+            request
+                .headers_mut()
+                .insert(reqwest::header::AUTHORIZATION, value);
+        }
+    }
+    (client, Ok(request))
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Derive the SigV4 signing key for a date (YYYYMMDD), region and service
+fn aws_signing_key(secret_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("AWS4{}", secret_key).as_bytes(), date);
+    let k_region = hmac_sha256(&k_date, region);
+    let k_service = hmac_sha256(&k_region, service);
+    hmac_sha256(&k_service, "aws4_request")
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters
+fn aws_uri_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Format a unix timestamp as the SigV4 `x-amz-date` (YYYYMMDD'T'HHMMSS'Z')
+fn amz_date(unix_secs: u64) -> String {
+    let days = (unix_secs / 86400) as i64;
+    let seconds = unix_secs % 86400;
+
+    // Civil date from days since 1970-01-01
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// Sign a request with AWS Signature Version 4, adding the `x-amz-*` headers
+/// and returning the `Authorization` value
+fn sign_aws_sigv4(request: &mut reqwest::Request, auth: &AuthConfig, now: u64) -> Option<String> {
+    let access_key = auth.get_credential("access_key")?;
+    let secret_key = auth.get_credential("secret_key")?;
+    let region = auth.get_credential("region")?;
+    let service = auth.get_credential("service")?;
+
+    let timestamp = amz_date(now);
+    let date = &timestamp[..8];
+    let payload_hash = match request.body() {
+        Some(body) => match body.as_bytes() {
+            Some(bytes) => format!("{:x}", Sha256::digest(bytes)),
+            None => "UNSIGNED-PAYLOAD".to_string(),
+        },
+        None => format!("{:x}", Sha256::digest(b"")),
+    };
+
+    let url = request.url().clone();
+    let host = match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => return None,
+    };
+
+    // S3 expects the path as sent; other services encode each segment again
+    let path = if url.path().is_empty() {
+        "/"
+    } else {
+        url.path()
+    };
+    let canonical_uri = if service == "s3" {
+        path.to_string()
+    } else {
+        path.split('/')
+            .map(aws_uri_encode)
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+
+    let mut query: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| (aws_uri_encode(&k), aws_uri_encode(&v)))
+        .collect();
+    query.sort();
+    let canonical_query = query
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let mut signed: Vec<(String, String)> = vec![
+        ("host".to_string(), host),
+        ("x-amz-date".to_string(), timestamp.clone()),
+    ];
+    if service == "s3" {
+        signed.push(("x-amz-content-sha256".to_string(), payload_hash.clone()));
+    }
+    if let Some(token) = auth.get_credential("session_token") {
+        signed.push(("x-amz-security-token".to_string(), token));
+    }
+    signed.sort();
+
+    for (name, value) in &signed {
+        if name == "host" {
+            continue;
+        }
+        if let (Ok(name), Ok(value)) = (
+            reqwest::header::HeaderName::from_bytes(name.as_bytes()),
+            reqwest::header::HeaderValue::from_str(value),
+        ) {
+            request.headers_mut().insert(name, value);
+        }
+    }
+
+    let canonical_headers: String = signed
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = signed
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        request.method().as_str(),
+        canonical_uri,
+        canonical_query,
+        canonical_headers,
+        signed_headers,
+        payload_hash
+    );
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{:x}",
+        timestamp,
+        scope,
+        Sha256::digest(canonical_request.as_bytes())
+    );
+
+    let signing_key = aws_signing_key(&secret_key, date, &region, &service);
+    let signature: String = hmac_sha256(&signing_key, &string_to_sign)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    Some(format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        access_key, scope, signed_headers, signature
+    ))
+}
+
+/// A `WWW-Authenticate: Digest` challenge (RFC 7616)
+#[derive(Clone, Debug, PartialEq)]
+pub struct DigestChallenge {
+    pub realm: String,
+    pub nonce: String,
+    pub opaque: Option<String>,
+    pub algorithm: String,
+    /// Set when `qop` offers "auth"; "auth-int" alone is not supported
+    pub qop_auth: bool,
+    pub stale: bool,
+}
+
+impl DigestChallenge {
+    /// Parse a single `WWW-Authenticate` header value
+    pub fn parse(header: &str) -> Option<Self> {
+        let (scheme, params) = header.trim().split_once(' ')?;
+        if !scheme.eq_ignore_ascii_case("digest") {
+            return None;
+        }
+        let params = parse_auth_params(params);
+
+        let algorithm = params
+            .get("algorithm")
+            .map(|a| a.to_ascii_uppercase())
+            .unwrap_or_else(|| "MD5".to_string());
+        if !matches!(
+            algorithm.as_str(),
+            "MD5" | "MD5-SESS" | "SHA-256" | "SHA-256-SESS"
+        ) {
+            return None;
+        }
+        let qop_auth = match params.get("qop") {
+            Some(qop) => {
+                if !qop
+                    .split(',')
+                    .any(|q| q.trim().eq_ignore_ascii_case("auth"))
+                {
+                    return None;
+                }
+                true
+            }
+            None => false,
+        };
+
+        Some(DigestChallenge {
+            realm: params.get("realm").cloned().unwrap_or_default(),
+            nonce: params.get("nonce").cloned()?,
+            opaque: params.get("opaque").cloned(),
+            algorithm,
+            qop_auth,
+            stale: params
+                .get("stale")
+                .map_or(false, |s| s.eq_ignore_ascii_case("true")),
+        })
+    }
+
+    /// Find the first usable Digest challenge among the response headers
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Option<Self> {
+        headers
+            .get_all(reqwest::header::WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .find_map(Self::parse)
+    }
+
+    fn hash(&self, data: &str) -> String {
+        if self.algorithm.starts_with("SHA-256") {
+            format!("{:x}", Sha256::digest(data.as_bytes()))
+        } else {
+            format!("{:x}", Md5::digest(data.as_bytes()))
+        }
+    }
+
+    /// Build the `Authorization` header value for one request
+    pub fn authorization(
+        &self,
+        username: &str,
+        password: &str,
+        method: &str,
+        uri: &str,
+        nonce_count: u32,
+        cnonce: &str,
+    ) -> String {
+        let mut ha1 = self.hash(&format!("{}:{}:{}", username, self.realm, password));
+        if self.algorithm.ends_with("-SESS") {
+            ha1 = self.hash(&format!("{}:{}:{}", ha1, self.nonce, cnonce));
+        }
+        let ha2 = self.hash(&format!("{}:{}", method, uri));
+        let nc = format!("{:08x}", nonce_count);
+
+        let response = if self.qop_auth {
+            self.hash(&format!(
+                "{}:{}:{}:{}:auth:{}",
+                ha1, self.nonce, nc, cnonce, ha2
+            ))
+        } else {
+            self.hash(&format!("{}:{}:{}", ha1, self.nonce, ha2))
+        };
+
+        let mut header = format!(
+            "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\", algorithm={}, response=\"{}\"",
+            username, self.realm, self.nonce, uri, self.algorithm, response
+        );
+        if let Some(opaque) = &self.opaque {
+            header.push_str(&format!(", opaque=\"{}\"", opaque));
+        }
+        if self.qop_auth {
+            header.push_str(&format!(", qop=auth, nc={}, cnonce=\"{}\"", nc, cnonce));
+        }
+        header
+    }
+}
+
+/// Split `key=value, key="quoted, value"` auth parameters
+fn parse_auth_params(input: &str) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    let mut rest = input.trim();
+    while !rest.is_empty() {
+        let (key, after_key) = match rest.split_once('=') {
+            Some(parts) => parts,
+            None => break,
+        };
+        let key = key
+            .trim()
+            .trim_start_matches(',')
+            .trim()
+            .to_ascii_lowercase();
+        let after_key = after_key.trim_start();
+
+        let (value, remainder) = if let Some(quoted) = after_key.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = quoted.char_indices();
+            let mut end = quoted.len();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        if let Some((_, escaped)) = chars.next() {
+                            value.push(escaped);
+                        }
+                    }
+                    '"' => {
+                        end = i + 1;
+                        break;
+                    }
+                    _ => value.push(c),
+                }
+            }
+            (value, &quoted[end..])
+        } else {
+            match after_key.split_once(',') {
+                Some((value, remainder)) => (value.trim().to_string(), remainder),
+                None => (after_key.trim().to_string(), ""),
+            }
+        };
+
+        params.insert(key, value);
+        rest = remainder.trim_start().trim_start_matches(',').trim_start();
+    }
+    params
+}
+
+/// Digest challenge and nonce count for one origin
+struct DigestSession {
+    challenge: DigestChallenge,
+    nonce_count: u32,
+}
+
+/// Challenge-response (Digest and NTLM) auth state shared by a client's requests,
+/// keyed by origin
+#[derive(Default)]
+pub struct DigestAuthState {
+    sessions: parking_lot::Mutex<HashMap<String, DigestSession>>,
+    /// NTLM message to send with the next request to the origin
+    ntlm_messages: parking_lot::Mutex<HashMap<String, String>>,
+}
+
+impl DigestAuthState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget all cached challenges
+    pub fn clear(&self) {
+        self.sessions.lock().clear();
+        self.ntlm_messages.lock().clear();
+    }
+
+    /// Next `Authorization` value for a request, bumping the origin's nonce count
+    pub fn authorization(
+        &self,
+        auth_config: Option<&AuthConfig>,
+        method: &reqwest::Method,
+        url: &reqwest::Url,
+    ) -> Option<String> {
+        let auth = auth_config?;
+        let username = auth.get_credential("username")?;
+        let password = auth.get_credential("password")?;
+
+        let mut sessions = self.sessions.lock();
+        let session = sessions.get_mut(&url.origin().ascii_serialization())?;
+        session.nonce_count += 1;
+
+        let uri = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        let cnonce = format!("{:016x}", rand::random::<u64>());
+        Some(session.challenge.authorization(
+            &username,
+            &password,
+            method.as_str(),
+            &uri,
+            session.nonce_count,
+            &cnonce,
+        ))
+    }
+
+    /// `Authorization` value for the NTLM handshake message pending for the origin
+    pub fn ntlm_authorization(&self, url: &reqwest::Url) -> Option<String> {
+        self.ntlm_messages
+            .lock()
+            .remove(&url.origin().ascii_serialization())
+    }
+
+    /// Record the challenge of a 401 response and report whether to re-send the request.
+    /// A Digest request is answered once, plus once more when the server marks the
+    /// nonce stale; NTLM takes two rounds, negotiate then authenticate.
+    pub fn should_retry(
+        &self,
+        response: &reqwest::Response,
+        auth_config: Option<&AuthConfig>,
+        attempts: &mut u32,
+    ) -> bool {
+        let auth = match auth_config {
+            Some(auth) if response.status() == reqwest::StatusCode::UNAUTHORIZED => auth,
+            _ => return false,
+        };
+        match auth.auth_type {
+            AuthType::Digest => self.retry_digest(response, attempts),
+            AuthType::Ntlm => self.retry_ntlm(response, auth, attempts),
+            _ => false,
+        }
+    }
+
+    fn retry_digest(&self, response: &reqwest::Response, attempts: &mut u32) -> bool {
+        let challenge = match DigestChallenge::from_headers(response.headers()) {
+            Some(challenge) => challenge,
+            None => return false,
+        };
+        if *attempts >= 2 || (*attempts == 1 && !challenge.stale) {
+            return false;
+        }
+
+        *attempts += 1;
+        self.sessions.lock().insert(
+            response.url().origin().ascii_serialization(),
+            DigestSession {
+                challenge,
+                nonce_count: 0,
+            },
+        );
+        true
+    }
+
+    /// A bare `NTLM` or `Negotiate` challenge is answered with the negotiate message,
+    /// the server's challenge message that follows with the authenticate message
+    fn retry_ntlm(
+        &self,
+        response: &reqwest::Response,
+        auth: &AuthConfig,
+        attempts: &mut u32,
+    ) -> bool {
+        let (scheme, token) = match ntlm_challenge(response.headers()) {
+            Some(challenge) => challenge,
+            None => return false,
+        };
+        let message = match (*attempts, token) {
+            (0, None) => crate::ntlm::negotiate_message(),
+            (1, Some(token)) => {
+                let challenge = match general_purpose::STANDARD
+                    .decode(token)
+                    .ok()
+                    .and_then(|token| crate::ntlm::Challenge::parse(&token))
+                {
+                    Some(challenge) => challenge,
+                    None => return false,
+                };
+                crate::ntlm::authenticate_message(
+                    &challenge,
+                    &auth.get_credential("username").unwrap_or_default(),
+                    &auth.get_credential("password").unwrap_or_default(),
+                    &auth.get_credential("domain").unwrap_or_default(),
+                    rand::random(),
+                    crate::ntlm::filetime_now(),
+                )
+            }
+            _ => return false,
+        };
+
+        *attempts += 1;
+        self.ntlm_messages.lock().insert(
+            response.url().origin().ascii_serialization(),
+            format!("{} {}", scheme, general_purpose::STANDARD.encode(message)),
+        );
+        true
+    }
+}
+
+/// Scheme and token of the response's NTLM challenge, preferring `NTLM` over
+/// `Negotiate` since only NTLM tokens are sent under either
+fn ntlm_challenge(headers: &reqwest::header::HeaderMap) -> Option<(&'static str, Option<&str>)> {
+    let challenges: Vec<&str> = headers
+        .get_all(reqwest::header::WWW_AUTHENTICATE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .collect();
+    ["NTLM", "Negotiate"].iter().find_map(|scheme| {
+        challenges.iter().find_map(|challenge| {
+            let (name, token) = match challenge.split_once(' ') {
+                Some((name, token)) => (name, Some(token.trim())),
+                None => (*challenge, None),
+            };
+            name.eq_ignore_ascii_case(scheme)
+                .then_some((*scheme, token.filter(|t| !t.is_empty())))
+        })
+    })
+}
+
+/// Python callable supplying the bearer token, sync or returning an awaitable.
+/// The last token is reused until `ttl` has passed since it was fetched.
+#[derive(Debug)]
+pub struct BearerTokenProvider {
+    callback: PyObject,
+    ttl: Duration,
+    cached: parking_lot::Mutex<Option<(String, Instant)>>,
+}
+
+impl BearerTokenProvider {
+    pub fn new(callback: PyObject, ttl: Duration) -> Self {
+        BearerTokenProvider {
+            callback,
+            ttl,
+            cached: parking_lot::Mutex::new(None),
+        }
+    }
+
+    /// Cached token if it is still within the TTL
+    fn cached_token(&self) -> Option<String> {
+        match &*self.cached.lock() {
+            Some((token, fetched_at)) if fetched_at.elapsed() < self.ttl => Some(token.clone()),
+            _ => None,
+        }
+    }
+
+    fn store(&self, token: String) -> String {
+        *self.cached.lock() = Some((token.clone(), Instant::now()));
+        token
+    }
+
+    /// Current token for a blocking caller; an awaitable result is run with `asyncio.run`
+    pub fn token(&self) -> PyResult<String> {
+        if let Some(token) = self.cached_token() {
+            return Ok(token);
+        }
+        let token = Python::with_gil(|py| -> PyResult<String> {
+            let result = self.callback.as_ref(py).call0()?;
+            let result = if is_awaitable(py, result)? {
+                py.import("asyncio")?.call_method1("run", (result,))?
+            } else {
+                result
+            };
+            result.extract()
+        })?;
+        Ok(self.store(token))
+    }
+
+    /// Current token for the async client; an awaitable result is awaited on the
+    /// caller's event loop
+    pub async fn token_async(&self) -> PyResult<String> {
+        if let Some(token) = self.cached_token() {
+            return Ok(token);
+        }
+        let (result, awaitable) = Python::with_gil(|py| -> PyResult<_> {
+            let result = self.callback.as_ref(py).call0()?;
+            let awaitable = if is_awaitable(py, result)? {
+                Some(pyo3_asyncio::tokio::into_future(result)?)
+            } else {
+                None
+            };
+            Ok((result.to_object(py), awaitable))
+        })?;
+        let result = match awaitable {
+            Some(future) => future.await?,
+            None => result,
+        };
+        let token = Python::with_gil(|py| result.extract::<String>(py))?;
+        Ok(self.store(token))
+    }
+}
+
+fn is_awaitable(py: Python<'_>, value: &PyAny) -> PyResult<bool> {
+    py.import("inspect")?
+        .call_method1("isawaitable", (value,))?
+        .extract()
+}
+
 #[cfg(test)]
 mod tests {
 
-    use crate::config::{AuthConfig, AuthType};
+    use super::*;
 
     #[test]
     fn test_bearer_auth() {
@@ -96,7 +722,7 @@ mod tests {
 
     #[test]
     fn test_api_key_header() {
-        let auth = AuthConfig::api_key_header("X-API-Key".to_string(), "secret-key".to_string());
+        let auth = AuthConfig::api_key_header("secret-key".to_string(), "X-API-Key".to_string());
         assert_eq!(auth.auth_type, AuthType::ApiKeyHeader);
         assert_eq!(auth.get_credential("key"), Some("secret-key".to_string()));
         assert_eq!(
@@ -107,7 +733,7 @@ mod tests {
 
     #[test]
     fn test_api_key_query() {
-        let auth = AuthConfig::api_key_query("api_key".to_string(), "secret-key".to_string());
+        let auth = AuthConfig::api_key_query("secret-key".to_string(), "api_key".to_string());
         assert_eq!(auth.auth_type, AuthType::ApiKeyQuery);
         assert_eq!(auth.get_credential("key"), Some("secret-key".to_string()));
         assert_eq!(
@@ -115,4 +741,99 @@ mod tests {
             Some("api_key".to_string())
         );
     }
+
+    #[test]
+    fn test_digest_challenge_parse() {
+        let challenge = DigestChallenge::parse(
+            r#"Digest realm="api@example.org", qop="auth, auth-int", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#,
+        )
+        .unwrap();
+        assert_eq!(challenge.realm, "api@example.org");
+        assert_eq!(challenge.algorithm, "SHA-256");
+        assert!(challenge.qop_auth);
+        assert!(!challenge.stale);
+        assert!(DigestChallenge::parse(r#"Basic realm="x""#).is_none());
+        assert!(DigestChallenge::parse(r#"Digest realm="x", qop="auth-int", nonce="n""#).is_none());
+    }
+
+    #[test]
+    fn test_digest_response_rfc2617() {
+        let challenge = DigestChallenge::parse(
+            r#"Digest realm="testrealm@host.com", qop="auth,auth-int", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", opaque="5ccc069c403ebaf9f0171e9517f40e41""#,
+        )
+        .unwrap();
+        let header = challenge.authorization(
+            "Mufasa",
+            "Circle Of Life",
+            "GET",
+            "/dir/index.html",
+            1,
+            "0a4f113b",
+        );
+        assert!(header.contains(r#"response="6629fae49393a05397450978507c4ef1""#));
+        assert!(header.contains("nc=00000001"));
+    }
+
+    #[test]
+    fn test_digest_response_rfc7616_sha256() {
+        let challenge = DigestChallenge::parse(
+            r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#,
+        )
+        .unwrap();
+        let header = challenge.authorization(
+            "Mufasa",
+            "Circle of Life",
+            "GET",
+            "/dir/index.html",
+            1,
+            "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ",
+        );
+        assert!(header.contains(
+            r#"response="753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1""#
+        ));
+    }
+
+    #[test]
+    fn test_aws_signing_key() {
+        let key = aws_signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        let hex: String = key.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(
+            hex,
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_amz_date() {
+        assert_eq!(amz_date(1440938160), "20150830T123600Z");
+        assert_eq!(amz_date(951782400), "20000229T000000Z");
+    }
+
+    #[test]
+    fn test_sigv4_get_vanilla_query() {
+        // "get-vanilla-query-order-key-case" from the AWS SigV4 test suite
+        let auth = AuthConfig::aws_sigv4(
+            "AKIDEXAMPLE".to_string(),
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            "us-east-1".to_string(),
+            "service".to_string(),
+            None,
+        );
+        let url = reqwest::Url::parse("https://example.amazonaws.com/?Param2=value2&Param1=value1")
+            .unwrap();
+        let mut request = reqwest::Request::new(reqwest::Method::GET, url);
+        let authorization = sign_aws_sigv4(&mut request, &auth, 1440938160).unwrap();
+        assert_eq!(
+            authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500"
+        );
+        assert_eq!(request.headers()["x-amz-date"], "20150830T123600Z");
+    }
 }
@@ -1,35 +1,362 @@
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rand::Rng;
+use serde::Serialize;
 use std::collections::HashMap;
-use std::time::Instant;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
 
+/// Samples kept per benchmark for percentiles; aggregates cover every run
+const DEFAULT_MAX_SAMPLES: usize = 100_000;
+
+/// Bumped whenever the exported report layout changes
+const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Column order of `Benchmark.to_csv`
+const CSV_COLUMNS: [&str; 17] = [
+    "name",
+    "target",
+    "concurrency",
+    "iterations",
+    "duration_seconds",
+    "requests_per_second",
+    "errors",
+    "bytes_transferred",
+    "count",
+    "mean",
+    "min",
+    "max",
+    "std_dev",
+    "p50",
+    "p90",
+    "p95",
+    "p99",
+];
+
+/// How a benchmark was run, kept for exports
+#[derive(Clone, Debug)]
+struct RunInfo {
+    target: String,
+    concurrency: usize,
+    iterations: u64,
+    duration: f64,
+    errors: u64,
+    bytes_transferred: u64,
+}
+
+#[derive(Serialize)]
+struct LatencyReport {
+    count: u64,
+    mean: f64,
+    min: f64,
+    max: f64,
+    std_dev: f64,
+    p50: f64,
+    p90: f64,
+    p95: f64,
+    p99: f64,
+}
+
+#[derive(Serialize)]
+struct BenchmarkReport {
+    name: String,
+    target: String,
+    concurrency: usize,
+    iterations: u64,
+    duration_seconds: f64,
+    requests_per_second: f64,
+    errors: u64,
+    bytes_transferred: u64,
+    latency: LatencyReport,
+}
+
+impl BenchmarkReport {
+    fn csv_row(&self) -> String {
+        let latency = &self.latency;
+        [
+            csv_field(&self.name),
+            csv_field(&self.target),
+            self.concurrency.to_string(),
+            self.iterations.to_string(),
+            self.duration_seconds.to_string(),
+            self.requests_per_second.to_string(),
+            self.errors.to_string(),
+            self.bytes_transferred.to_string(),
+            latency.count.to_string(),
+            latency.mean.to_string(),
+            latency.min.to_string(),
+            latency.max.to_string(),
+            latency.std_dev.to_string(),
+            latency.p50.to_string(),
+            latency.p90.to_string(),
+            latency.p95.to_string(),
+            latency.p99.to_string(),
+        ]
+        .join(",")
+    }
+}
+
+#[derive(Serialize)]
+struct Report {
+    schema_version: u32,
+    benchmarks: Vec<BenchmarkReport>,
+}
+
+/// Quote a CSV field when it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Streaming timing statistics plus a bounded reservoir of samples for percentiles
+#[derive(Clone, Debug)]
+struct Samples {
+    count: u64,
+    sum: f64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+    reservoir: Vec<f64>,
+    capacity: usize,
+}
+
+impl Samples {
+    fn new(capacity: usize) -> Self {
+        Samples {
+            count: 0,
+            sum: 0.0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            reservoir: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Record a value, replacing a random reservoir slot once it is full (Algorithm R)
+    fn push(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+
+        if self.reservoir.len() < self.capacity {
+            self.reservoir.push(value);
+        } else {
+            let slot = rand::thread_rng().gen_range(0..self.count) as usize;
+            if slot < self.capacity {
+                self.reservoir[slot] = value;
+            }
+        }
+    }
+
+    /// Combine two sample sets (Chan et al. parallel variance)
+    fn merge(&mut self, other: &Samples) {
+        if other.count == 0 {
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.m2 +=
+            other.m2 + delta * delta * (self.count as f64 * other.count as f64) / count as f64;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.count = count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.reservoir.extend_from_slice(&other.reservoir);
+    }
+
+    fn std_dev(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            (self.m2 / self.count as f64).sqrt()
+        }
+    }
+
+    fn sorted(&self) -> Vec<f64> {
+        let mut sorted = self.reservoir.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        sorted
+    }
+
+    fn latency_report(&self) -> LatencyReport {
+        if self.count == 0 {
+            return LatencyReport {
+                count: 0,
+                mean: 0.0,
+                min: 0.0,
+                max: 0.0,
+                std_dev: 0.0,
+                p50: 0.0,
+                p90: 0.0,
+                p95: 0.0,
+                p99: 0.0,
+            };
+        }
+        let sorted = self.sorted();
+        LatencyReport {
+            count: self.count,
+            mean: self.mean,
+            min: self.min,
+            max: self.max,
+            std_dev: self.std_dev(),
+            p50: percentile(&sorted, 50.0),
+            p90: percentile(&sorted, 90.0),
+            p95: percentile(&sorted, 95.0),
+            p99: percentile(&sorted, 99.0),
+        }
+    }
+
+    fn summary(&self) -> HashMap<String, f64> {
+        let sorted = self.sorted();
+        let mut stats = HashMap::new();
+        stats.insert("count".to_string(), self.count as f64);
+        stats.insert("mean".to_string(), self.mean);
+        stats.insert("min".to_string(), self.min);
+        stats.insert("max".to_string(), self.max);
+        stats.insert("total".to_string(), self.sum);
+        stats.insert("std_dev".to_string(), self.std_dev());
+        stats.insert("median".to_string(), percentile(&sorted, 50.0));
+        for p in [50.0, 90.0, 95.0, 99.0] {
+            stats.insert(format!("p{}", p), percentile(&sorted, p));
+        }
+        stats
+    }
+}
+
+/// Percentile of sorted values, linearly interpolating between closest ranks
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        1 => sorted[0],
+        len => {
+            let rank = p / 100.0 * (len - 1) as f64;
+            let lower = rank.floor() as usize;
+            let upper = rank.ceil() as usize;
+            sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+        }
+    }
+}
+
 /// Benchmarking utility for performance tests
 #[pyclass]
 pub struct Benchmark {
-    results: HashMap<String, Vec<f64>>,
+    results: HashMap<String, Samples>,
+    runs: HashMap<String, RunInfo>,
+    max_samples: usize,
     runtime: Runtime,
 }
 
+impl Benchmark {
+    /// Samples for one benchmark, or every benchmark combined
+    fn samples(&self, name: Option<&str>) -> PyResult<Samples> {
+        let samples = match name {
+            Some(name) => self.results.get(name).cloned().ok_or_else(|| {
+                pyo3::exceptions::PyKeyError::new_err(format!("No results for '{}'", name))
+            })?,
+            None => {
+                self.results
+                    .values()
+                    .fold(Samples::new(self.max_samples), |mut all, samples| {
+                        all.merge(samples);
+                        all
+                    })
+            }
+        };
+        if samples.count == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "No benchmark data available",
+            ));
+        }
+        Ok(samples)
+    }
+
+    /// One report per benchmark, sorted by name so exports diff cleanly
+    fn report(&self) -> Report {
+        let mut names: Vec<&String> = self.results.keys().collect();
+        names.sort();
+        let benchmarks = names
+            .into_iter()
+            .map(|name| {
+                let samples = &self.results[name];
+                // Results added by hand count as sequential runs of their own durations
+                let run = self.runs.get(name).cloned().unwrap_or_else(|| RunInfo {
+                    target: name.clone(),
+                    concurrency: 1,
+                    iterations: samples.count,
+                    duration: samples.sum,
+                    errors: 0,
+                    bytes_transferred: 0,
+                });
+                BenchmarkReport {
+                    name: name.clone(),
+                    target: run.target,
+                    concurrency: run.concurrency,
+                    iterations: run.iterations,
+                    duration_seconds: run.duration,
+                    requests_per_second: if run.duration > 0.0 {
+                        samples.count as f64 / run.duration
+                    } else {
+                        0.0
+                    },
+                    errors: run.errors,
+                    bytes_transferred: run.bytes_transferred,
+                    latency: samples.latency_report(),
+                }
+            })
+            .collect();
+        Report {
+            schema_version: REPORT_SCHEMA_VERSION,
+            benchmarks,
+        }
+    }
+
+    fn record(&mut self, name: String, value: f64) {
+        let max_samples = self.max_samples;
+        self.results
+            .entry(name)
+            .or_insert_with(|| Samples::new(max_samples))
+            .push(value);
+    }
+}
+
 #[pymethods]
 impl Benchmark {
     #[new]
-    pub fn new() -> PyResult<Self> {
+    #[pyo3(signature = (max_samples = DEFAULT_MAX_SAMPLES))]
+    pub fn new(max_samples: usize) -> PyResult<Self> {
+        if max_samples == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "max_samples must be at least 1",
+            ));
+        }
         let runtime = Runtime::new().map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to create runtime: {}", e))
         })?;
 
         Ok(Benchmark {
             results: HashMap::new(),
+            runs: HashMap::new(),
+            max_samples,
             runtime,
         })
     }
 
     /// Add a new benchmark result
     pub fn add_result(&mut self, name: String, value: f64) {
-        self.results
-            .entry(name)
-            .or_insert_with(Vec::new)
-            .push(value);
+        self.record(name, value);
     }
 
     /// Run a benchmark test
@@ -39,69 +366,74 @@ impl Benchmark {
         iterations: u32,
         test_fn: &PyAny,
     ) -> PyResult<HashMap<String, f64>> {
-        let mut times = Vec::new();
+        let mut times = Samples::new(self.max_samples);
+        let started = Instant::now();
 
         for _ in 0..iterations {
             let start = Instant::now();
             test_fn.call0()?;
-            let elapsed = start.elapsed().as_secs_f64();
-            times.push(elapsed);
+            times.push(start.elapsed().as_secs_f64());
+        }
+        if times.count == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "iterations must be at least 1",
+            ));
         }
 
-        self.results.insert(name.to_string(), times.clone());
-
-        // Calculate statistics
-        let mut stats = HashMap::new();
-        let sum: f64 = times.iter().sum();
-        let mean = sum / times.len() as f64;
-
-        stats.insert("mean".to_string(), mean);
-        stats.insert(
-            "min".to_string(),
-            *times
-                .iter()
-                .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-                .unwrap_or(&0.0),
-        );
-        stats.insert(
-            "max".to_string(),
-            *times
-                .iter()
-                .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-                .unwrap_or(&0.0),
+        let stats = times.summary();
+        self.results.insert(name.to_string(), times);
+        self.runs.insert(
+            name.to_string(),
+            RunInfo {
+                target: name.to_string(),
+                concurrency: 1,
+                iterations: iterations as u64,
+                duration: started.elapsed().as_secs_f64(),
+                errors: 0,
+                bytes_transferred: 0,
+            },
         );
-        stats.insert("total".to_string(), sum);
+        Ok(stats)
+    }
 
-        // Calculate median
-        let mut sorted_times = times.clone();
-        sorted_times.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-        let median = if sorted_times.len() % 2 == 0 {
-            (sorted_times[sorted_times.len() / 2 - 1] + sorted_times[sorted_times.len() / 2]) / 2.0
-        } else {
-            sorted_times[sorted_times.len() / 2]
-        };
-        stats.insert("median".to_string(), median);
+    /// Latency at each requested percentile (0-100) as a dict of percentile -> seconds
+    #[pyo3(signature = (percentiles, name = None))]
+    pub fn percentiles(
+        &self,
+        py: Python,
+        percentiles: Vec<f64>,
+        name: Option<&str>,
+    ) -> PyResult<PyObject> {
+        if let Some(p) = percentiles.iter().find(|p| !(0.0..=100.0).contains(*p)) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Percentile must be between 0 and 100, got {}",
+                p
+            )));
+        }
+        let sorted = self.samples(name)?.sorted();
+        let dict = PyDict::new(py);
+        for p in percentiles {
+            dict.set_item(p, percentile(&sorted, p))?;
+        }
+        Ok(dict.into())
+    }
 
-        // Calculate percentiles
-        let p95_idx = ((0.95 * sorted_times.len() as f64) as usize).min(sorted_times.len() - 1);
-        let p99_idx = ((0.99 * sorted_times.len() as f64) as usize).min(sorted_times.len() - 1);
-        stats.insert("p95".to_string(), sorted_times[p95_idx]);
-        stats.insert("p99".to_string(), sorted_times[p99_idx]);
+    /// 95th percentile latency in seconds
+    #[pyo3(signature = (name = None))]
+    pub fn p95(&self, name: Option<&str>) -> PyResult<f64> {
+        Ok(percentile(&self.samples(name)?.sorted(), 95.0))
+    }
 
-        Ok(stats)
+    /// 99th percentile latency in seconds
+    #[pyo3(signature = (name = None))]
+    pub fn p99(&self, name: Option<&str>) -> PyResult<f64> {
+        Ok(percentile(&self.samples(name)?.sorted(), 99.0))
     }
 
     /// Compare two benchmark results
     pub fn compare(&self, name1: &str, name2: &str) -> PyResult<HashMap<String, f64>> {
-        let times1 = self.results.get(name1).ok_or_else(|| {
-            pyo3::exceptions::PyKeyError::new_err(format!("No results for '{}'", name1))
-        })?;
-        let times2 = self.results.get(name2).ok_or_else(|| {
-            pyo3::exceptions::PyKeyError::new_err(format!("No results for '{}'", name2))
-        })?;
-
-        let mean1: f64 = times1.iter().sum::<f64>() / times1.len() as f64;
-        let mean2: f64 = times2.iter().sum::<f64>() / times2.len() as f64;
+        let mean1 = self.samples(Some(name1))?.mean;
+        let mean2 = self.samples(Some(name2))?.mean;
 
         let mut comparison = HashMap::new();
         comparison.insert("mean1".to_string(), mean1);
@@ -116,100 +448,162 @@ impl Benchmark {
         Ok(comparison)
     }
 
-    /// Get all results
+    /// Get all results (the sampled values once a benchmark exceeds `max_samples`)
     pub fn get_results(&self) -> HashMap<String, Vec<f64>> {
-        self.results.clone()
+        self.results
+            .iter()
+            .map(|(name, samples)| (name.clone(), samples.reservoir.clone()))
+            .collect()
     }
 
     /// Clear all results
     pub fn clear(&mut self) {
         self.results.clear();
+        self.runs.clear();
     }
 
-    /// Benchmark HTTP requests per second
-    pub fn benchmark_rps(&mut self, url: &str, duration: f64) -> PyResult<HashMap<String, f64>> {
-        let start = Instant::now();
-        let mut request_count = 0;
-        let mut errors = 0;
-
-        let client = reqwest::blocking::Client::new();
+    /// Export every benchmark's configuration, throughput and latency percentiles as JSON
+    #[pyo3(signature = (pretty = true))]
+    pub fn to_json(&self, pretty: bool) -> PyResult<String> {
+        let report = self.report();
+        let json = if pretty {
+            serde_json::to_string_pretty(&report)
+        } else {
+            serde_json::to_string(&report)
+        };
+        json.map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("JSON serialization error: {}", e))
+        })
+    }
 
-        while start.elapsed().as_secs_f64() < duration {
-            match client.get(url).send() {
-                Ok(_) => request_count += 1,
-                Err(_) => errors += 1,
-            }
+    /// Write one CSV row per benchmark (same fields as `to_json`, latency flattened)
+    pub fn to_csv(&self, path: &str) -> PyResult<()> {
+        let mut csv = CSV_COLUMNS.join(",");
+        csv.push('\n');
+        for benchmark in self.report().benchmarks {
+            csv.push_str(&benchmark.csv_row());
+            csv.push('\n');
         }
-
-        let total_time = start.elapsed().as_secs_f64();
-        let rps = request_count as f64 / total_time;
-
-        let mut stats = HashMap::new();
-        stats.insert("requests_per_second".to_string(), rps);
-        stats.insert("total_requests".to_string(), request_count as f64);
-        stats.insert("errors".to_string(), errors as f64);
-        stats.insert(
-            "error_rate".to_string(),
-            errors as f64 / (request_count + errors) as f64,
-        );
-        stats.insert("duration".to_string(), total_time);
-
-        Ok(stats)
+        std::fs::write(path, csv).map_err(|e| {
+            pyo3::exceptions::PyIOError::new_err(format!("Failed to write {}: {}", path, e))
+        })
     }
 
-    pub fn calculate_statistics(&self) -> PyResult<HashMap<String, f64>> {
-        if self.results.is_empty() {
+    /// Benchmark HTTP requests per second with `concurrency` requests in flight.
+    ///
+    /// Latencies are recorded under the URL's name, so `percentiles(..., name=url)`
+    /// works afterwards. Failed requests are counted as errors and don't stop the run.
+    #[pyo3(signature = (url, duration, concurrency = 1))]
+    pub fn benchmark_rps(
+        &mut self,
+        py: Python,
+        url: &str,
+        duration: f64,
+        concurrency: usize,
+    ) -> PyResult<HashMap<String, f64>> {
+        if concurrency == 0 {
             return Err(pyo3::exceptions::PyValueError::new_err(
-                "No benchmark data available",
+                "concurrency must be at least 1",
             ));
         }
+        let duration = Duration::try_from_secs_f64(duration).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Invalid duration: {}", e))
+        })?;
 
-        let times = &self
-            .results
-            .values()
-            .flatten()
-            .cloned()
-            .collect::<Vec<f64>>();
-        let mut stats = HashMap::new();
-
-        let sum: f64 = times.iter().sum();
-        let mean = sum / times.len() as f64;
-
-        stats.insert("count".to_string(), times.len() as f64);
-        stats.insert("mean".to_string(), mean);
+        let client = reqwest::Client::new();
+        let latencies = Arc::new(Mutex::new(Samples::new(self.max_samples)));
+        let errors = Arc::new(AtomicU64::new(0));
+        let bytes_transferred = Arc::new(AtomicU64::new(0));
+        let runtime = &self.runtime;
+
+        let total_time = py.allow_threads(|| {
+            runtime.block_on(async {
+                let start = Instant::now();
+                let deadline = start + duration;
+                let workers: Vec<_> = (0..concurrency)
+                    .map(|_| {
+                        let client = client.clone();
+                        let url = url.to_string();
+                        let latencies = latencies.clone();
+                        let errors = errors.clone();
+                        let bytes_transferred = bytes_transferred.clone();
+                        tokio::spawn(async move {
+                            while Instant::now() < deadline {
+                                let sent = Instant::now();
+                                let result = match client.get(&url).send().await {
+                                    Ok(response) => response.bytes().await.map(|body| body.len()),
+                                    Err(e) => Err(e),
+                                };
+                                match result {
+                                    Ok(size) => {
+                                        let elapsed = sent.elapsed().as_secs_f64();
+                                        bytes_transferred.fetch_add(size as u64, Ordering::Relaxed);
+                                        if let Ok(mut latencies) = latencies.lock() {
+                                            latencies.push(elapsed);
+                                        }
+                                    }
+                                    Err(_) => {
+                                        errors.fetch_add(1, Ordering::Relaxed);
+                                    }
+                                }
+                            }
+                        })
+                    })
+                    .collect();
+                for worker in workers {
+                    let _ = worker.await;
+                }
+                start.elapsed().as_secs_f64()
+            })
+        });
+
+        let latencies = latencies
+            .lock()
+            .map(|latencies| latencies.clone())
+            .map_err(|_| pyo3::exceptions::PyRuntimeError::new_err("Latency samples poisoned"))?;
+        let request_count = latencies.count;
+        let errors = errors.load(Ordering::Relaxed);
+
+        let mut stats = if request_count > 0 {
+            latencies.summary()
+        } else {
+            HashMap::new()
+        };
         stats.insert(
-            "min".to_string(),
-            *times
-                .iter()
-                .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-                .unwrap_or(&0.0),
+            "requests_per_second".to_string(),
+            request_count as f64 / total_time,
         );
+        stats.insert("total_requests".to_string(), request_count as f64);
+        stats.insert("errors".to_string(), errors as f64);
         stats.insert(
-            "max".to_string(),
-            *times
-                .iter()
-                .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-                .unwrap_or(&0.0),
+            "error_rate".to_string(),
+            errors as f64 / (request_count + errors).max(1) as f64,
+        );
+        stats.insert("duration".to_string(), total_time);
+        stats.insert("concurrency".to_string(), concurrency as f64);
+        let bytes_transferred = bytes_transferred.load(Ordering::Relaxed);
+        stats.insert("bytes_transferred".to_string(), bytes_transferred as f64);
+        self.results.insert(url.to_string(), latencies);
+        self.runs.insert(
+            url.to_string(),
+            RunInfo {
+                target: url.to_string(),
+                concurrency,
+                iterations: request_count + errors,
+                duration: total_time,
+                errors,
+                bytes_transferred,
+            },
         );
-        stats.insert("total".to_string(), sum);
-
-        // Calculate median
-        let mut sorted_times = times.clone();
-        sorted_times.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-        let median = if sorted_times.len() % 2 == 0 {
-            (sorted_times[sorted_times.len() / 2 - 1] + sorted_times[sorted_times.len() / 2]) / 2.0
-        } else {
-            sorted_times[sorted_times.len() / 2]
-        };
-        stats.insert("median".to_string(), median);
-
-        // Calculate standard deviation
-        let variance = times.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / times.len() as f64;
-        let std_dev = variance.sqrt();
-        stats.insert("std_dev".to_string(), std_dev);
 
         Ok(stats)
     }
+
+    /// Count, mean, min, max, std_dev and percentiles over one or all benchmarks
+    #[pyo3(signature = (name = None))]
+    pub fn calculate_statistics(&self, name: Option<&str>) -> PyResult<HashMap<String, f64>> {
+        Ok(self.samples(name)?.summary())
+    }
 }
 
 /// Memory profiler for tracking allocations
@@ -271,3 +665,49 @@ impl MemoryProfiler {
         0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_interpolates_between_ranks() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 50.0), 2.5);
+        assert_eq!(percentile(&sorted, 100.0), 4.0);
+        assert!((percentile(&sorted, 90.0) - 3.7).abs() < 1e-9);
+        assert_eq!(percentile(&[], 95.0), 0.0);
+    }
+
+    #[test]
+    fn test_samples_stay_bounded_with_exact_aggregates() {
+        let mut samples = Samples::new(100);
+        for i in 1..=10_000 {
+            samples.push(i as f64);
+        }
+        assert_eq!(samples.reservoir.len(), 100);
+        assert_eq!(samples.count, 10_000);
+        assert_eq!(samples.min, 1.0);
+        assert_eq!(samples.max, 10_000.0);
+        assert!((samples.mean - 5000.5).abs() < 1e-6);
+
+        let mut merged = Samples::new(100);
+        let mut other = Samples::new(100);
+        for value in [2.0, 4.0] {
+            merged.push(value);
+        }
+        for value in [4.0, 5.0, 5.0, 7.0, 9.0] {
+            other.push(value);
+        }
+        merged.merge(&other);
+        assert_eq!(merged.count, 7);
+        assert!((merged.mean - 36.0 / 7.0).abs() < 1e-9);
+        let expected = [2.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+            .iter()
+            .map(|x| (x - 36.0 / 7.0_f64).powi(2))
+            .sum::<f64>()
+            / 7.0;
+        assert!((merged.std_dev() - expected.sqrt()).abs() < 1e-9);
+    }
+}
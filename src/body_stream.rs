@@ -0,0 +1,167 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes};
+use pyo3_asyncio::TaskLocals;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Exception raised by a `data_stream`, re-raised in place of the transport
+/// error it caused when the upload aborts
+#[derive(Clone, Default)]
+pub(crate) struct StreamError(Arc<parking_lot::Mutex<Option<PyErr>>>);
+
+impl StreamError {
+    pub(crate) fn record(&self, err: PyErr) -> std::io::Error {
+        let message = err.to_string();
+        *self.0.lock() = Some(err);
+        std::io::Error::other(format!("data_stream raised: {}", message))
+    }
+
+    /// The iterator's exception if it aborted the upload, otherwise `fallback`
+    pub(crate) fn or(&self, fallback: PyErr) -> PyErr {
+        self.take().unwrap_or(fallback)
+    }
+
+    /// The recorded exception, if the upload was aborted by one
+    pub(crate) fn take(&self) -> Option<PyErr> {
+        self.0.lock().take()
+    }
+}
+
+/// Calls `progress_callback(bytes_sent, total_bytes)` each time the connection
+/// takes another chunk of a request body. An exception raised by the callback
+/// aborts the upload and is raised in place of the transport error.
+#[derive(Clone)]
+pub(crate) struct UploadProgress {
+    callback: PyObject,
+    total: Option<u64>,
+    sent: Arc<AtomicU64>,
+}
+
+impl UploadProgress {
+    pub(crate) fn new(callback: PyObject, total: Option<u64>) -> Self {
+        Self {
+            callback,
+            total,
+            sent: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub(crate) fn advance(&self, chunk_len: usize) -> PyResult<()> {
+        let sent = self.sent.fetch_add(chunk_len as u64, Ordering::Relaxed) + chunk_len as u64;
+        Python::with_gil(|py| self.callback.call1(py, (sent, self.total)).map(|_| ()))
+    }
+}
+
+/// Where body chunks come from: a plain iterator is advanced on the blocking pool,
+/// an async iterator is awaited on the event loop it was created on
+enum Source {
+    Sync(PyObject),
+    Async(PyObject, TaskLocals),
+}
+
+impl Source {
+    /// The next non-empty chunk, or None once the iterator is exhausted.
+    /// Empty chunks are skipped since a zero-length chunk ends a chunked body.
+    async fn next_chunk(&self) -> PyResult<Option<bytes::Bytes>> {
+        loop {
+            let chunk = match self {
+                Source::Sync(iterator) => {
+                    let iterator = iterator.clone();
+                    tokio::task::spawn_blocking(move || {
+                        Python::with_gil(|py| {
+                            let mut iterator =
+                                pyo3::types::PyIterator::from_object(iterator.as_ref(py))?;
+                            iterator.next().transpose()?.map(chunk_bytes).transpose()
+                        })
+                    })
+                    .await
+                    .map_err(|e| {
+                        pyo3::exceptions::PyRuntimeError::new_err(format!(
+                            "data_stream iteration failed: {}",
+                            e
+                        ))
+                    })??
+                }
+                Source::Async(iterator, locals) => {
+                    let next = Python::with_gil(|py| {
+                        let awaitable = iterator.as_ref(py).call_method0("__anext__")?;
+                        pyo3_asyncio::into_future_with_locals(locals, awaitable)
+                    })?;
+                    match next.await {
+                        Ok(item) => Some(Python::with_gil(|py| chunk_bytes(item.as_ref(py)))?),
+                        Err(e)
+                            if Python::with_gil(|py| {
+                                e.is_instance_of::<pyo3::exceptions::PyStopAsyncIteration>(py)
+                            }) =>
+                        {
+                            None
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+            };
+            match chunk {
+                Some(chunk) if chunk.is_empty() => continue,
+                chunk => return Ok(chunk),
+            }
+        }
+    }
+}
+
+fn chunk_bytes(item: &PyAny) -> PyResult<bytes::Bytes> {
+    if let Ok(data) = item.downcast::<PyBytes>() {
+        Ok(bytes::Bytes::copy_from_slice(data.as_bytes()))
+    } else if let Ok(data) = item.downcast::<PyByteArray>() {
+        Ok(bytes::Bytes::from(data.to_vec()))
+    } else {
+        Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "data_stream must yield bytes, got {}",
+            item.get_type().name()?
+        )))
+    }
+}
+
+/// Wrap `data_stream` as a request body sent with chunked transfer encoding.
+///
+/// Async iterators need the `locals` of the calling event loop; without them
+/// only plain iterators are accepted. A stream's size isn't known up front, so
+/// `progress_callback` gets None for `total_bytes`.
+pub(crate) fn stream_body(
+    data_stream: &PyAny,
+    locals: Option<TaskLocals>,
+    progress_callback: Option<PyObject>,
+) -> PyResult<(reqwest::Body, StreamError)> {
+    let source = if data_stream.hasattr("__aiter__")? {
+        let locals = locals.ok_or_else(|| {
+            pyo3::exceptions::PyTypeError::new_err(
+                "async iterators are only supported by AsyncHttpClient.upload_stream",
+            )
+        })?;
+        Source::Async(data_stream.call_method0("__aiter__")?.into(), locals)
+    } else {
+        Source::Sync(data_stream.iter()?.into())
+    };
+
+    let error = StreamError::default();
+    let recorder = error.clone();
+    let progress = progress_callback.map(|callback| UploadProgress::new(callback, None));
+    let stream = futures_util::stream::unfold(Some(source), move |source| {
+        let recorder = recorder.clone();
+        let progress = progress.clone();
+        async move {
+            // Stop after the first error so the body ends with it
+            let source = source?;
+            match source.next_chunk().await {
+                Ok(Some(chunk)) => {
+                    if let Some(Err(e)) = progress.as_ref().map(|p| p.advance(chunk.len())) {
+                        return Some((Err(recorder.record(e)), None));
+                    }
+                    Some((Ok(chunk), Some(source)))
+                }
+                Ok(None) => None,
+                Err(e) => Some((Err(recorder.record(e)), None)),
+            }
+        }
+    });
+    Ok((reqwest::Body::wrap_stream(stream), error))
+}
@@ -0,0 +1,304 @@
+use crate::config::CircuitBreakerConfig;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum State {
+    Closed,
+    Open { until: Instant },
+    HalfOpen { in_flight: u32 },
+}
+
+#[derive(Debug)]
+struct HostCircuit {
+    state: State,
+    // (finished at, succeeded) for requests inside the rolling window
+    outcomes: VecDeque<(Instant, bool)>,
+}
+
+impl HostCircuit {
+    fn new() -> Self {
+        Self {
+            state: State::Closed,
+            outcomes: VecDeque::new(),
+        }
+    }
+}
+
+/// Per-host circuit breakers shared by a client's requests
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    hosts: Mutex<HashMap<String, HostCircuit>>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    /// Admit a request to `host`, or raise `CircuitOpenError` while its circuit is open
+    pub(crate) fn allow(&self, host: &str) -> pyo3::PyResult<()> {
+        self.allow_at(host, Instant::now()).map_err(|retry_in| {
+            crate::error::CircuitOpenError::new_err(format!(
+                "Circuit open for {}: failing fast for another {:.1}s",
+                host,
+                retry_in.as_secs_f64()
+            ))
+        })
+    }
+
+    /// Record whether a request to `host` succeeded
+    pub(crate) fn record(&self, host: &str, success: bool) {
+        self.record_at(host, success, Instant::now());
+    }
+
+    /// Admit a request like `allow`, returning a trial to `record` its outcome on.
+    /// A trial dropped unrecorded, e.g. by a cancelled request, frees its half-open slot.
+    pub(crate) fn admit(&self, host: &str) -> pyo3::PyResult<CircuitTrial<'_>> {
+        self.allow(host)?;
+        Ok(CircuitTrial {
+            breaker: self,
+            host: Some(host.to_string()),
+        })
+    }
+
+    /// "closed", "open" or "half_open"
+    pub(crate) fn state(&self, host: &str) -> &'static str {
+        let hosts = self.hosts.lock();
+        match hosts.get(host).map(|circuit| circuit.state) {
+            None | Some(State::Closed) => "closed",
+            Some(State::Open { until }) if until > Instant::now() => "open",
+            Some(_) => "half_open",
+        }
+    }
+
+    fn allow_at(&self, host: &str, now: Instant) -> Result<(), Duration> {
+        let mut hosts = self.hosts.lock();
+        let circuit = hosts
+            .entry(host.to_string())
+            .or_insert_with(HostCircuit::new);
+        match circuit.state {
+            State::Closed => Ok(()),
+            State::Open { until } if until > now => Err(until - now),
+            State::Open { .. } => {
+                circuit.state = State::HalfOpen { in_flight: 1 };
+                Ok(())
+            }
+            State::HalfOpen { in_flight } if in_flight < self.config.half_open_max_requests => {
+                circuit.state = State::HalfOpen {
+                    in_flight: in_flight + 1,
+                };
+                Ok(())
+            }
+            State::HalfOpen { .. } => Err(Duration::ZERO),
+        }
+    }
+
+    /// Give back a half-open slot taken by a request that never finished
+    fn release(&self, host: &str) {
+        if let Some(circuit) = self.hosts.lock().get_mut(host) {
+            if let State::HalfOpen { in_flight } = circuit.state {
+                circuit.state = State::HalfOpen {
+                    in_flight: in_flight.saturating_sub(1),
+                };
+            }
+        }
+    }
+
+    fn record_at(&self, host: &str, success: bool, now: Instant) {
+        let mut hosts = self.hosts.lock();
+        let circuit = hosts
+            .entry(host.to_string())
+            .or_insert_with(HostCircuit::new);
+        let cooldown = Duration::from_secs_f64(self.config.cooldown_seconds);
+
+        match circuit.state {
+            // A trial request decides whether the host has recovered
+            State::HalfOpen { .. } => {
+                circuit.outcomes.clear();
+                circuit.state = if success {
+                    State::Closed
+                } else {
+                    State::Open {
+                        until: now + cooldown,
+                    }
+                };
+            }
+            // Requests admitted before the circuit opened don't change it
+            State::Open { .. } => {}
+            State::Closed => {
+                circuit.outcomes.push_back((now, success));
+                let window = Duration::from_secs_f64(self.config.window_seconds);
+                while circuit
+                    .outcomes
+                    .front()
+                    .is_some_and(|(at, _)| now.duration_since(*at) > window)
+                {
+                    circuit.outcomes.pop_front();
+                }
+
+                let total = circuit.outcomes.len();
+                let failures = circuit.outcomes.iter().filter(|(_, ok)| !ok).count();
+                if total >= self.config.minimum_requests as usize
+                    && failures as f64 / total as f64 >= self.config.failure_rate_threshold
+                {
+                    circuit.outcomes.clear();
+                    circuit.state = State::Open {
+                        until: now + cooldown,
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// A request admitted by `CircuitBreaker::admit`
+pub(crate) struct CircuitTrial<'a> {
+    breaker: &'a CircuitBreaker,
+    host: Option<String>,
+}
+
+impl CircuitTrial<'_> {
+    /// Record whether the request succeeded
+    pub(crate) fn record(mut self, success: bool) {
+        if let Some(host) = self.host.take() {
+            self.breaker.record(&host, success);
+        }
+    }
+}
+
+impl Drop for CircuitTrial<'_> {
+    fn drop(&mut self) {
+        if let Some(host) = self.host.take() {
+            self.breaker.release(&host);
+        }
+    }
+}
+
+/// Key a request URL's circuit by host
+pub(crate) fn circuit_host(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.to_string()))
+}
+
+/// Whether a response counts as a failure for the circuit
+pub(crate) fn is_failure_status(status_code: u16) -> bool {
+    status_code >= 500
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_rate_threshold: 0.5,
+            minimum_requests: 4,
+            window_seconds: 10.0,
+            cooldown_seconds: 5.0,
+            half_open_max_requests: 1,
+        })
+    }
+
+    #[test]
+    fn test_opens_after_failure_rate_reached() {
+        let breaker = breaker();
+        let now = Instant::now();
+        breaker.record_at("api", true, now);
+        breaker.record_at("api", false, now);
+        breaker.record_at("api", true, now);
+        assert!(breaker.allow_at("api", now).is_ok());
+
+        breaker.record_at("api", false, now);
+        assert_eq!(
+            breaker.allow_at("api", now + Duration::from_secs(1)),
+            Err(Duration::from_secs(4))
+        );
+        assert!(breaker.allow_at("other", now).is_ok());
+    }
+
+    #[test]
+    fn test_needs_minimum_requests() {
+        let breaker = breaker();
+        let now = Instant::now();
+        for _ in 0..3 {
+            breaker.record_at("api", false, now);
+        }
+        assert!(breaker.allow_at("api", now).is_ok());
+    }
+
+    #[test]
+    fn test_old_outcomes_leave_the_window() {
+        let breaker = breaker();
+        let now = Instant::now();
+        for _ in 0..3 {
+            breaker.record_at("api", false, now);
+        }
+        breaker.record_at("api", false, now + Duration::from_secs(11));
+        assert!(breaker
+            .allow_at("api", now + Duration::from_secs(11))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_half_open_trial_closes_or_reopens() {
+        let breaker = breaker();
+        let now = Instant::now();
+        for _ in 0..4 {
+            breaker.record_at("api", false, now);
+        }
+
+        let later = now + Duration::from_secs(6);
+        assert!(breaker.allow_at("api", later).is_ok());
+        // Only one trial at a time
+        assert!(breaker.allow_at("api", later).is_err());
+        breaker.record_at("api", false, later);
+        assert!(breaker.allow_at("api", later).is_err());
+
+        let recovered = later + Duration::from_secs(6);
+        assert!(breaker.allow_at("api", recovered).is_ok());
+        breaker.record_at("api", true, recovered);
+        assert!(breaker.allow_at("api", recovered).is_ok());
+        assert!(breaker.allow_at("api", recovered).is_ok());
+    }
+
+    #[test]
+    fn test_unrecorded_trial_frees_half_open_slot() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            cooldown_seconds: 0.0,
+            ..breaker().config
+        });
+        for _ in 0..4 {
+            breaker.record("api", false);
+        }
+
+        let trial = breaker.admit("api").unwrap();
+        assert!(breaker.admit("api").is_err());
+        // A cancelled request neither closes nor reopens the circuit
+        drop(trial);
+        assert_eq!(breaker.state("api"), "half_open");
+
+        let trial = breaker.admit("api").unwrap();
+        trial.record(true);
+        assert_eq!(breaker.state("api"), "closed");
+    }
+
+    #[test]
+    fn test_circuit_host() {
+        assert_eq!(
+            circuit_host("https://api.example.com:8443/v1").as_deref(),
+            Some("api.example.com")
+        );
+        assert_eq!(circuit_host("not a url"), None);
+    }
+}
@@ -1,24 +1,32 @@
 use crate::config::{
-    AuthConfig, AuthType, CompressionConfig, OAuth2Token, PoolConfig, ProtocolConfig, ProxyConfig,
-    RateLimitConfig, RetryConfig, SSLConfig, TimeoutConfig,
+    AuthConfig, AuthType, CircuitBreakerConfig, ClientConfigSnapshot, CompressionConfig,
+    OAuth2Token, PoolConfig, ProtocolConfig, ProxyConfig, RateLimitConfig, RetryConfig, SSLConfig,
+    TimeoutConfig,
 };
 use pyo3::prelude::*;
 
-use crate::config::HttpVersion;
+use crate::config::{content_encoding_name, parse_accept_encoding, HttpVersion};
 
 use crate::auth_common;
-use crate::connection_pool::{ConnectionMultiplexer, FastConnectionPool};
+use crate::circuit_breaker::{circuit_host, is_failure_status, CircuitBreaker};
+use crate::connection_pool::{ConnectionMultiplexer, FastConnectionPool, HostSlot};
+use crate::cookies::CookieJar;
+use crate::dns_cache::{DnsCache, IpVersion};
 use crate::error::map_reqwest_error;
 use crate::http3::Http3Client;
-use crate::middleware::MiddlewareManager;
+use crate::middleware::{
+    header_value, CacheMiddleware, DedupMiddleware, HeadersMiddleware, HmacSigningMiddleware, Hook,
+    InterceptorMiddleware, LoggingMiddleware, MetricsMiddleware, MiddlewareManager,
+    RateLimitMiddleware, RetryMiddleware, TransformMiddleware,
+};
 use crate::performance_advanced::get_runtime_optimizer;
 use crate::performance_common::HeaderCache;
 use crate::protocol_enhanced::EnhancedProtocolNegotiator;
 use crate::protocol_stats_common::ProtocolStatsManager;
 use crate::rate_limit_common::RateLimitManager;
 use crate::response::Response;
+use crate::timing::ConnectionPhases;
 use ahash::AHashMap;
-use pythonize;
 use reqwest::{Client, Method, RequestBuilder};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -26,17 +34,8 @@ use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
 
-/// Lock ordering enumeration to prevent deadlocks
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum LockOrder {
-    OAuth2Token = 0,
-    MiddlewareManager = 1,
-    Http3Client = 2,
-    RequestCount = 3,
-    TotalRequestTime = 4,
-    LastRequestTime = 5,
-    RateLimitManager = 6,
-}
+/// How long a failed request-encoding preflight is remembered before it is retried
+const PREFLIGHT_FAILURE_TTL: Duration = Duration::from_secs(60);
 
 /// HTTP Client with advanced features and improved error handling
 #[pyclass]
@@ -45,6 +44,10 @@ pub struct HttpClient {
     base_url: Option<String>,
     // Use RwLock for headers since they're read more than written
     headers: Arc<RwLock<HashMap<String, String>>>,
+    // Query parameters added to every request
+    default_params: HashMap<String, String>,
+    // Overall request timeout in seconds
+    timeout: f64,
     auth_config: Option<AuthConfig>,
     retry_config: Option<RetryConfig>,
     timeout_config: TimeoutConfig,
@@ -54,9 +57,37 @@ pub struct HttpClient {
     compression_config: CompressionConfig,
     protocol_config: ProtocolConfig,
     rate_limit_config: Option<RateLimitConfig>,
+    // Defer reading response bodies until first access
+    lazy_body: bool,
+    allow_redirects: bool,
+    max_redirects: usize,
+    // Largest response body read before raising ResponseTooLargeError
+    max_response_size: Option<usize>,
+    // Cached hostname lookups, when `dns_cache_ttl` is set
+    dns_cache: Option<Arc<DnsCache>>,
+    // Socket path every request is sent over instead of TCP
+    unix_socket: Option<String>,
+    // Address family resolved hosts are connected over
+    ip_version: IpVersion,
+    // Fails requests fast to hosts that keep erroring, when configured
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    // Called before each retry sleep as on_retry(attempt, reason, next_delay)
+    on_retry: Option<PyObject>,
+    // Per-host client certificates (cert PEM, key PEM) and the clients presenting them
+    client_certs: HashMap<String, (String, String)>,
+    host_clients: HashMap<String, Client>,
+    // Clients for per-request proxies, each with its own connection pool
+    proxy_clients: HashMap<ProxyConfig, Client>,
+    // Request content-codings each host accepts, learned by OPTIONS preflight;
+    // failed preflights carry the time they expire
+    request_encodings: Arc<RwLock<HashMap<String, (Vec<String>, Option<Instant>)>>>,
     protocol_negotiator: Arc<EnhancedProtocolNegotiator>,
     // Improved with consistent lock ordering
     oauth2_token: Arc<RwLock<Option<OAuth2Token>>>,
+    // Digest challenges and nonce counts per origin
+    digest_auth: Arc<auth_common::DigestAuthState>,
+    // Cookies shared across requests, set by sessions
+    cookie_jar: Option<Arc<CookieJar>>,
     middleware_manager: Arc<RwLock<MiddlewareManager>>,
     runtime: Arc<Runtime>,
     // HTTP/3 client for QUIC connections
@@ -65,6 +96,8 @@ pub struct HttpClient {
     http3_pool: Arc<crate::http3::Http3ConnectionPool>,
     // Performance tracking with atomic operations
     request_count: Arc<std::sync::atomic::AtomicU64>,
+    // Attempts that errored or got a retryable status
+    error_count: Arc<std::sync::atomic::AtomicU64>,
     total_request_time: Arc<std::sync::atomic::AtomicU64>, // Store as nanoseconds
     last_request_time: Arc<RwLock<Option<f64>>>,
     // Shared managers to eliminate code duplication
@@ -74,6 +107,10 @@ pub struct HttpClient {
     header_cache: Arc<HeaderCache>,
     connection_pool: Arc<FastConnectionPool>,
     connection_multiplexer: Arc<ConnectionMultiplexer>,
+    // Set by close(); requests fail once it is
+    closed: Arc<std::sync::atomic::AtomicBool>,
+    // Seconds between background cleanup sweeps, or None when the task is disabled
+    cleanup_interval: Option<f64>,
     // Resource cleanup tracking
     _cleanup_handle: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
 }
@@ -93,7 +130,17 @@ impl HttpClient {
         proxy_config = None,
         compression_config = None,
         protocol_config = None,
-        rate_limit_config = None
+        rate_limit_config = None,
+        body_mode = "eager",
+        allow_redirects = true,
+        max_redirects = 10,
+        dns_cache_ttl = None,
+        dns_cache_max_entries = crate::dns_cache::DEFAULT_MAX_ENTRIES,
+        unix_socket = None,
+        circuit_breaker_config = None,
+        ip_version = "auto",
+        max_response_size = None,
+        cleanup_interval = Some(crate::config::DEFAULT_CLEANUP_INTERVAL)
     ))]
     pub fn new(
         base_url: Option<String>,
@@ -108,128 +155,66 @@ impl HttpClient {
         compression_config: Option<CompressionConfig>,
         protocol_config: Option<ProtocolConfig>,
         rate_limit_config: Option<RateLimitConfig>,
+        body_mode: &str,
+        allow_redirects: bool,
+        max_redirects: usize,
+        dns_cache_ttl: Option<f64>,
+        dns_cache_max_entries: usize,
+        unix_socket: Option<String>,
+        circuit_breaker_config: Option<CircuitBreakerConfig>,
+        ip_version: &str,
+        max_response_size: Option<usize>,
+        cleanup_interval: Option<f64>,
     ) -> PyResult<Self> {
+        let lazy_body = crate::response::parse_body_mode(body_mode)?;
+        if cleanup_interval.is_some_and(|secs| !(secs.is_finite() && secs > 0.0)) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "cleanup_interval must be a positive number of seconds, or None to disable cleanup",
+            ));
+        }
+        let dns_cache = DnsCache::from_options(dns_cache_ttl, dns_cache_max_entries)?;
+        let ip_version = IpVersion::parse(ip_version)?;
         let runtime = Arc::new(Runtime::new().map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to create runtime: {}", e))
         })?);
 
         let timeout_cfg = timeout_config.unwrap_or_else(|| {
-            TimeoutConfig::new(Some(10.0), Some(timeout), Some(timeout), Some(30.0))
+            TimeoutConfig::new(Some(10.0), Some(timeout), Some(timeout), Some(30.0), None)
         });
 
-        let pool_cfg = pool_config.unwrap_or_else(|| PoolConfig::new(100, 10, 90.0, 30.0));
-        let ssl_cfg = ssl_config.unwrap_or_else(|| SSLConfig::new(true, None, None, None, None));
-        let compression_cfg = compression_config
-            .unwrap_or_else(|| CompressionConfig::new(false, true, None, None, 1024));
+        let pool_cfg = match pool_config {
+            Some(cfg) => cfg,
+            None => PoolConfig::new(100, 10, 90.0, 30.0, None)?,
+        };
+        let ssl_cfg = ssl_config.unwrap_or_else(|| {
+            SSLConfig::new(true, None, None, None, None, None, None, true, None)
+        });
+        let compression_cfg = match compression_config {
+            Some(cfg) => cfg,
+            None => CompressionConfig::new(false, true, None, None, 1024, false)?,
+        };
         let protocol_cfg = protocol_config
             .clone()
             .unwrap_or_else(|| ProtocolConfig::default());
+        protocol_cfg.validate()?;
         // Note: HTTP/3 pool settings would be used for HTTP/3 client initialization if implemented
         let http3_pool_settings = protocol_cfg.http3_settings.clone();
 
         // Build client with advanced configuration
-        let mut client_builder = Client::builder()
-            .timeout(Duration::from_secs_f64(timeout))
-            .pool_max_idle_per_host(pool_cfg.max_idle_per_host)
-            .pool_idle_timeout(Duration::from_secs_f64(pool_cfg.idle_timeout));
-
-        // Configure timeouts
-        if let Some(connect_timeout) = timeout_cfg.connect_timeout {
-            client_builder =
-                client_builder.connect_timeout(Duration::from_secs_f64(connect_timeout));
-        }
-
-        // Configure SSL/TLS
-        if !ssl_cfg.verify {
-            client_builder = client_builder.danger_accept_invalid_certs(true);
-        }
-
-        // Apply additional SSL configuration
-        if let Some(cert_path) = &ssl_cfg.cert_file {
-            if let Some(key_path) = &ssl_cfg.key_file {
-                // Load client certificate and key
-                match std::fs::read(cert_path) {
-                    Ok(cert_data) => {
-                        match std::fs::read(key_path) {
-                            Ok(key_data) => {
-                                // Use from_pkcs8_pem which is the correct method
-                                if let Ok(identity) =
-                                    reqwest::Identity::from_pkcs8_pem(&cert_data, &key_data)
-                                {
-                                    client_builder = client_builder.identity(identity);
-                                }
-                            }
-                            Err(_) => {} // Ignore key loading errors
-                        }
-                    }
-                    Err(_) => {} // Ignore cert loading errors
-                }
-            }
-        }
-
-        // Configure CA bundle if specified
-        if let Some(ca_bundle) = &ssl_cfg.ca_bundle {
-            match std::fs::read(ca_bundle) {
-                Ok(ca_data) => {
-                    if let Ok(cert) = reqwest::Certificate::from_pem(&ca_data) {
-                        client_builder = client_builder.add_root_certificate(cert);
-                    }
-                }
-                Err(_) => {} // Ignore CA bundle errors
-            }
-        }
-
-        // Configure proxy if specified
-        if let Some(proxy_cfg) = &proxy_config {
-            if let Ok(proxy) = reqwest::Proxy::all(&proxy_cfg.url) {
-                let mut proxy = proxy;
-
-                // Add authentication if specified
-                if let (Some(username), Some(password)) = (&proxy_cfg.username, &proxy_cfg.password)
-                {
-                    proxy = proxy.basic_auth(username, password);
-                }
-
-                client_builder = client_builder.proxy(proxy);
-            }
-        }
-
-        // Configure compression
-        if compression_cfg.enable_response_compression {
-            // reqwest enables gzip/deflate by default, we just need to ensure it's not disabled
-            client_builder = client_builder.gzip(true);
-
-            // Enable brotli if supported
-            if compression_cfg.supports_algorithm("brotli") {
-                client_builder = client_builder.brotli(true);
-            }
-        }
-
-        // Configure HTTP/2 settings
-        if protocol_cfg.is_http2_enabled() {
-            // reqwest enables HTTP/2 by default, but we can configure specific settings
-            if protocol_cfg.enable_http2_prior_knowledge {
-                client_builder = client_builder.http2_prior_knowledge();
-            }
-
-            // Configure HTTP/2 keep-alive if specified
-            if let Some(interval) = protocol_cfg.http2_settings.keep_alive_interval {
-                client_builder =
-                    client_builder.http2_keep_alive_interval(Some(Duration::from_secs(interval)));
-            }
-
-            if let Some(timeout) = protocol_cfg.http2_settings.keep_alive_timeout {
-                client_builder =
-                    client_builder.http2_keep_alive_timeout(Duration::from_secs(timeout));
-            }
-        }
-
-        // Configure HTTP/3 via Quiche
-        if protocol_cfg.is_http3_enabled() {
-            // HTTP/3 support requires unstable features
-            // This will only work if compiled with RUSTFLAGS="--cfg reqwest_unstable"
-            // client_builder = client_builder.http3_prior_knowledge();
+        let client_builder = ClientSettings {
+            timeout,
+            timeout_config: &timeout_cfg,
+            pool_config: &pool_cfg,
+            ssl_config: &ssl_cfg,
+            proxy_config: proxy_config.as_ref(),
+            compression_config: &compression_cfg,
+            protocol_config: &protocol_cfg,
+            dns_cache: dns_cache.as_ref(),
+            unix_socket: unix_socket.as_deref(),
+            ip_version,
+            client_cert: None,
         }
+        .builder()?;
 
         let client = runtime
             .block_on(async { client_builder.build() })
@@ -257,13 +242,34 @@ impl HttpClient {
             middleware_manager.add_rate_limit_middleware(rate_limit_middleware);
         }
 
+        let connection_pool = Arc::new(FastConnectionPool::new(
+            pool_cfg.max_idle_connections,
+            Duration::from_secs_f64(pool_cfg.idle_timeout),
+        ));
+        connection_pool.set_max_connections_per_host(pool_cfg.max_connections_per_host);
+        let connection_multiplexer = Arc::new(ConnectionMultiplexer::new(
+            pool_cfg.max_idle_connections,
+            Duration::from_secs_f64(pool_cfg.idle_timeout),
+        ));
+        let oauth2_token = Arc::new(RwLock::new(None));
+
         // Start cleanup task
-        let cleanup_handle = Self::start_cleanup_task(runtime.clone());
+        let cleanup_handle = cleanup_interval.map(|secs| {
+            Self::start_cleanup_task(
+                &runtime,
+                Duration::from_secs_f64(secs),
+                Arc::clone(&connection_pool),
+                Arc::clone(&connection_multiplexer),
+                Arc::clone(&oauth2_token),
+            )
+        });
 
         Ok(HttpClient {
             client,
             base_url,
             headers: Arc::new(RwLock::new(headers.unwrap_or_default())),
+            default_params: HashMap::new(),
+            timeout,
             auth_config,
             retry_config,
             timeout_config: timeout_cfg,
@@ -276,7 +282,21 @@ impl HttpClient {
             )),
             protocol_config: protocol_cfg,
             rate_limit_config: rate_limit_config.clone(),
-            oauth2_token: Arc::new(RwLock::new(None)),
+            lazy_body,
+            allow_redirects,
+            max_redirects,
+            max_response_size,
+            dns_cache,
+            unix_socket,
+            ip_version,
+            circuit_breaker: circuit_breaker_config
+                .map(|config| Arc::new(CircuitBreaker::new(config))),
+            on_retry: None,
+            client_certs: HashMap::new(),
+            host_clients: HashMap::new(),
+            proxy_clients: HashMap::new(),
+            request_encodings: Arc::new(RwLock::new(HashMap::new())),
+            oauth2_token,
             middleware_manager: Arc::new(RwLock::new(middleware_manager)),
             runtime,
             http3_client: Arc::new(RwLock::new(None)),
@@ -285,6 +305,7 @@ impl HttpClient {
                 http3_pool_settings.pool_timeout_seconds.unwrap_or(300),
             )),
             request_count: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            error_count: Arc::new(std::sync::atomic::AtomicU64::new(0)),
             total_request_time: Arc::new(std::sync::atomic::AtomicU64::new(0)),
             last_request_time: Arc::new(RwLock::new(None)),
             // Initialize shared managers
@@ -292,15 +313,13 @@ impl HttpClient {
             protocol_stats_manager: Arc::new(ProtocolStatsManager::new()),
             // Initialize performance optimizations
             header_cache: Arc::new(HeaderCache::new()),
-            connection_pool: Arc::new(FastConnectionPool::new(
-                pool_cfg.max_idle_connections,
-                Duration::from_secs_f64(pool_cfg.idle_timeout),
-            )),
-            connection_multiplexer: Arc::new(ConnectionMultiplexer::new(
-                pool_cfg.max_idle_connections,
-                Duration::from_secs_f64(pool_cfg.idle_timeout),
-            )),
-            _cleanup_handle: Arc::new(RwLock::new(Some(cleanup_handle))),
+            connection_pool,
+            connection_multiplexer,
+            digest_auth: Arc::new(auth_common::DigestAuthState::new()),
+            cookie_jar: None,
+            closed: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            cleanup_interval,
+            _cleanup_handle: Arc::new(RwLock::new(cleanup_handle)),
         })
     }
 
@@ -314,6 +333,7 @@ impl HttpClient {
             })?;
             *token_guard = None;
         }
+        self.digest_auth.clear();
         self.auth_config = Some(auth_config);
         Ok(())
     }
@@ -331,6 +351,7 @@ impl HttpClient {
     /// Clear authentication
     pub fn clear_auth(&mut self) -> PyResult<()> {
         self.auth_config = None;
+        self.digest_auth.clear();
         let mut token_guard = self.oauth2_token.write().map_err(|_| {
             pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire OAuth2 token lock")
         })?;
@@ -338,6 +359,24 @@ impl HttpClient {
         Ok(())
     }
 
+    /// Current OAuth2 token, or None before the first fetch
+    pub fn get_oauth2_token(&self) -> PyResult<Option<OAuth2Token>> {
+        let token_guard = self.oauth2_token.read().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire OAuth2 token lock")
+        })?;
+        Ok(token_guard.clone())
+    }
+
+    /// Fetch a new OAuth2 token now, even if the current one is still valid
+    pub fn refresh_oauth2_token(&mut self) -> PyResult<OAuth2Token> {
+        if !self.auth_config.as_ref().is_some_and(AuthConfig::is_oauth2) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "OAuth2 authentication is not configured",
+            ));
+        }
+        self.fetch_oauth2_token()
+    }
+
     /// Set retry configuration
     pub fn set_retry_config(&mut self, retry_config: RetryConfig) {
         self.retry_config = Some(retry_config);
@@ -350,6 +389,8 @@ impl HttpClient {
 
     /// Set pool configuration (requires client rebuild)
     pub fn set_pool_config(&mut self, pool_config: PoolConfig) -> PyResult<()> {
+        self.connection_pool
+            .set_max_connections_per_host(pool_config.max_connections_per_host);
         self.pool_config = pool_config;
         self.rebuild_client()?;
         Ok(())
@@ -357,11 +398,38 @@ impl HttpClient {
 
     /// Set SSL configuration (requires client rebuild)
     pub fn set_ssl_config(&mut self, ssl_config: SSLConfig) -> PyResult<()> {
+        ssl_config.tls_version()?;
+        // Check the certificates load before replacing the working configuration
+        ssl_config.identity()?;
+        ssl_config.root_certificates()?;
+        crate::pinning::PinSet::parse(&ssl_config.pinned_public_keys)?;
         self.ssl_config = ssl_config;
         self.rebuild_client()?;
         Ok(())
     }
 
+    /// Present a specific client certificate (PEM, with a PKCS#8 key) for requests to `host`
+    pub fn set_client_cert(&mut self, host: &str, cert_pem: &str, key_pem: &str) -> PyResult<()> {
+        let client = self.build_host_client(cert_pem, key_pem)?;
+        self.client_certs.insert(
+            host.to_string(),
+            (cert_pem.to_string(), key_pem.to_string()),
+        );
+        self.host_clients.insert(host.to_string(), client);
+        Ok(())
+    }
+
+    /// Stop using a per-host client certificate
+    pub fn remove_client_cert(&mut self, host: &str) -> bool {
+        self.host_clients.remove(host);
+        self.client_certs.remove(host).is_some()
+    }
+
+    /// Hosts with a dedicated client certificate
+    pub fn get_client_cert_hosts(&self) -> Vec<String> {
+        self.client_certs.keys().cloned().collect()
+    }
+
     /// Set proxy configuration (requires client rebuild)
     pub fn set_proxy_config(&mut self, proxy_config: Option<ProxyConfig>) -> PyResult<()> {
         self.proxy_config = proxy_config;
@@ -488,6 +556,49 @@ impl HttpClient {
         Ok(())
     }
 
+    /// Effective requests per second for `host` under adaptive rate limiting,
+    /// or None when rate limiting is not configured
+    pub fn get_effective_rate(&self, host: &str) -> Option<f64> {
+        match self.middleware_manager.read() {
+            Ok(middleware_manager) => middleware_manager.get_effective_rate(host),
+            Err(_) => None,
+        }
+    }
+
+    /// Requests waiting in the rate limit queue, oldest first
+    pub fn peek_rate_limit_queue(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        let middleware_manager = self.middleware_manager.read().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire middleware manager lock")
+        })?;
+        crate::middleware::queue_entries_to_py(py, middleware_manager.rate_limit_queue())
+    }
+
+    /// Set whether redirects are followed and how many before raising TooManyRedirects
+    #[pyo3(signature = (allow_redirects = true, max_redirects = 10))]
+    pub fn set_redirect_policy(&mut self, allow_redirects: bool, max_redirects: usize) {
+        self.allow_redirects = allow_redirects;
+        self.max_redirects = max_redirects;
+    }
+
+    /// Set the response body mode ("eager" or "lazy")
+    pub fn set_body_mode(&mut self, body_mode: &str) -> PyResult<()> {
+        self.lazy_body = crate::response::parse_body_mode(body_mode)?;
+        Ok(())
+    }
+
+    /// Set a callback invoked before each retry as on_retry(attempt, reason, next_delay).
+    /// `reason` is the status code for retried responses or the error message otherwise.
+    #[pyo3(signature = (callback = None))]
+    pub fn set_on_retry(&mut self, py: Python, callback: Option<PyObject>) -> PyResult<()> {
+        self.on_retry = validate_on_retry(py, callback)?;
+        Ok(())
+    }
+
+    /// Get the response body mode
+    pub fn get_body_mode(&self) -> String {
+        if self.lazy_body { "lazy" } else { "eager" }.to_string()
+    }
+
     /// Get current protocol configuration
     pub fn get_protocol_config(&self) -> ProtocolConfig {
         self.protocol_config.clone()
@@ -504,18 +615,33 @@ impl HttpClient {
     }
 
     /// Enhanced GET request with retry and auth
-    #[pyo3(signature = (url, params = None, headers = None))]
+    #[pyo3(signature = (
+        url, params = None, headers = None, deadline = None, timeout = None,
+        allow_redirects = None, proxy = None
+    ))]
     pub fn get(
         &mut self,
         url: &str,
         params: Option<HashMap<String, String>>,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
     ) -> PyResult<Response> {
-        self.execute_request_with_retry(Method::GET, url, params, None, headers)
+        let options = RequestOptions::new(deadline, timeout, allow_redirects)?.with_proxy(proxy);
+        self.execute_request_with_retry(Method::GET, url, params, None, headers, options)
     }
 
     /// Enhanced POST request with retry and auth
-    #[pyo3(signature = (url, json = None, data = None, files = None, headers = None))]
+    ///
+    /// `expect_continue` holds the body back for `TimeoutConfig.expect_continue_timeout`
+    /// (0.1s by default), a wait paid even when the server accepts the body
+    #[pyo3(signature = (
+        url, json = None, data = None, files = None, headers = None, deadline = None,
+        timeout = None, allow_redirects = None, proxy = None,
+        expect_continue = false, progress_callback = None
+    ))]
     pub fn post(
         &mut self,
         url: &str,
@@ -523,13 +649,30 @@ impl HttpClient {
         data: Option<HashMap<String, String>>,
         files: Option<HashMap<String, Vec<u8>>>,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
+        expect_continue: bool,
+        progress_callback: Option<PyObject>,
     ) -> PyResult<Response> {
-        let body = self.prepare_body(json, data, files)?;
-        self.execute_request_with_retry(Method::POST, url, None, body, headers)
+        let options = RequestOptions::new(deadline, timeout, allow_redirects)?
+            .with_proxy(proxy)
+            .with_expect_continue(expect_continue)
+            .with_upload_progress(progress_callback);
+        let (body, headers) = self.prepare_body(json, data, files, headers)?;
+        self.execute_request_with_retry(Method::POST, url, None, body, headers, options)
     }
 
     /// Enhanced PUT request with retry and auth
-    #[pyo3(signature = (url, json = None, data = None, files = None, headers = None))]
+    ///
+    /// `expect_continue` holds the body back for `TimeoutConfig.expect_continue_timeout`
+    /// (0.1s by default), a wait paid even when the server accepts the body
+    #[pyo3(signature = (
+        url, json = None, data = None, files = None, headers = None, deadline = None,
+        timeout = None, allow_redirects = None, proxy = None,
+        expect_continue = false, progress_callback = None
+    ))]
     pub fn put(
         &mut self,
         url: &str,
@@ -537,23 +680,48 @@ impl HttpClient {
         data: Option<HashMap<String, String>>,
         files: Option<HashMap<String, Vec<u8>>>,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
+        expect_continue: bool,
+        progress_callback: Option<PyObject>,
     ) -> PyResult<Response> {
-        let body = self.prepare_body(json, data, files)?;
-        self.execute_request_with_retry(Method::PUT, url, None, body, headers)
+        let options = RequestOptions::new(deadline, timeout, allow_redirects)?
+            .with_proxy(proxy)
+            .with_expect_continue(expect_continue)
+            .with_upload_progress(progress_callback);
+        let (body, headers) = self.prepare_body(json, data, files, headers)?;
+        self.execute_request_with_retry(Method::PUT, url, None, body, headers, options)
     }
 
     /// Enhanced DELETE request with retry and auth
-    #[pyo3(signature = (url, headers = None))]
+    #[pyo3(signature = (
+        url, headers = None, deadline = None, timeout = None,
+        allow_redirects = None, proxy = None
+    ))]
     pub fn delete(
         &mut self,
         url: &str,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
     ) -> PyResult<Response> {
-        self.execute_request_with_retry(Method::DELETE, url, None, None, headers)
+        let options = RequestOptions::new(deadline, timeout, allow_redirects)?.with_proxy(proxy);
+        self.execute_request_with_retry(Method::DELETE, url, None, None, headers, options)
     }
 
     /// Enhanced PATCH request with retry and auth
-    #[pyo3(signature = (url, json = None, data = None, files = None, headers = None))]
+    ///
+    /// `expect_continue` holds the body back for `TimeoutConfig.expect_continue_timeout`
+    /// (0.1s by default), a wait paid even when the server accepts the body
+    #[pyo3(signature = (
+        url, json = None, data = None, files = None, headers = None, deadline = None,
+        timeout = None, allow_redirects = None, proxy = None,
+        expect_continue = false
+    ))]
     pub fn patch(
         &mut self,
         url: &str,
@@ -561,29 +729,332 @@ impl HttpClient {
         data: Option<HashMap<String, String>>,
         files: Option<HashMap<String, Vec<u8>>>,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
+        expect_continue: bool,
     ) -> PyResult<Response> {
-        let body = self.prepare_body(json, data, files)?;
-        self.execute_request_with_retry(Method::PATCH, url, None, body, headers)
+        let options = RequestOptions::new(deadline, timeout, allow_redirects)?
+            .with_proxy(proxy)
+            .with_expect_continue(expect_continue);
+        let (body, headers) = self.prepare_body(json, data, files, headers)?;
+        self.execute_request_with_retry(Method::PATCH, url, None, body, headers, options)
     }
 
     /// Enhanced HEAD request with retry and auth
-    #[pyo3(signature = (url, headers = None))]
+    #[pyo3(signature = (
+        url, headers = None, deadline = None, timeout = None,
+        allow_redirects = None, proxy = None
+    ))]
     pub fn head(
         &mut self,
         url: &str,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
     ) -> PyResult<Response> {
-        self.execute_request_with_retry(Method::HEAD, url, None, None, headers)
+        let options = RequestOptions::new(deadline, timeout, allow_redirects)?.with_proxy(proxy);
+        self.execute_request_with_retry(Method::HEAD, url, None, None, headers, options)
     }
 
     /// Enhanced OPTIONS request with retry and auth
-    #[pyo3(signature = (url, headers = None))]
+    #[pyo3(signature = (
+        url, headers = None, deadline = None, timeout = None,
+        allow_redirects = None, proxy = None
+    ))]
     pub fn options(
         &mut self,
         url: &str,
         headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
+    ) -> PyResult<Response> {
+        let options = RequestOptions::new(deadline, timeout, allow_redirects)?.with_proxy(proxy);
+        self.execute_request_with_retry(Method::OPTIONS, url, None, None, headers, options)
+    }
+
+    /// Request with an arbitrary HTTP method, e.g. "PROPFIND" or "REPORT"
+    ///
+    /// `expect_continue` holds the body back for `TimeoutConfig.expect_continue_timeout`
+    /// (0.1s by default), a wait paid even when the server accepts the body
+    #[pyo3(signature = (
+        method, url, params = None, json = None, data = None, files = None, headers = None,
+        deadline = None, timeout = None, allow_redirects = None, proxy = None,
+        expect_continue = false
+    ))]
+    pub fn request(
+        &mut self,
+        method: &str,
+        url: &str,
+        params: Option<HashMap<String, String>>,
+        json: Option<&PyAny>,
+        data: Option<HashMap<String, String>>,
+        files: Option<HashMap<String, Vec<u8>>>,
+        headers: Option<HashMap<String, String>>,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+        proxy: Option<ProxyConfig>,
+        expect_continue: bool,
+    ) -> PyResult<Response> {
+        let options = RequestOptions::new(deadline, timeout, allow_redirects)?
+            .with_proxy(proxy)
+            .with_expect_continue(expect_continue);
+        let method = parse_method(method)?;
+        let (body, headers) = self.prepare_body(json, data, files, headers)?;
+        self.execute_request_with_retry(method, url, params, body, headers, options)
+    }
+
+    /// Multipart upload that streams each file from disk instead of buffering it.
+    ///
+    /// `files` maps form field names to file paths. The upload goes through the
+    /// circuit breaker, rate limiting, connection limits, request and response
+    /// middleware and the client's stats like other requests, with `default_params`
+    /// added to the query. A streamed body can't be replayed, so the request is sent
+    /// once: no retries, redirects, Digest or NTLM re-sends, and no response caching.
+    #[pyo3(signature = (url, files, data = None, headers = None, method = "POST", timeout = None))]
+    pub fn upload_files(
+        &mut self,
+        py: Python,
+        url: &str,
+        files: HashMap<String, String>,
+        data: Option<HashMap<String, String>>,
+        headers: Option<HashMap<String, String>>,
+        method: &str,
+        timeout: Option<f64>,
+    ) -> PyResult<Response> {
+        self.ensure_open()?;
+        let method = parse_method(method)?;
+        let full_url = self.build_url(url)?;
+        let params = merge_query_params(&self.default_params, None, self.auth_config.as_ref());
+        let (host, path) = reqwest::Url::parse(&full_url)
+            .map(|parsed| {
+                (
+                    parsed.host_str().unwrap_or("default").to_string(),
+                    parsed.path().to_string(),
+                )
+            })
+            .unwrap_or_else(|_| ("default".to_string(), "/".to_string()));
+
+        let breaker = self.circuit_breaker.clone();
+        let circuit = match (&breaker, circuit_host(&full_url)) {
+            (Some(breaker), Some(circuit_host)) => Some(breaker.admit(&circuit_host)?),
+            _ => None,
+        };
+        self.wait_for_rate_limit(&host, &path)?;
+        self.ensure_oauth2_token()?;
+        let client = self.select_client(&host, None)?;
+        let mut request = self.apply_auth_internal(client.request(method.clone(), &full_url))?;
+        let _host_slot = self.acquire_host_slot(&host)?;
+
+        // The multipart form sets its own Content-Type with the boundary
+        let mut all_headers = self.get_headers()?;
+        all_headers.extend(headers.unwrap_or_default());
+        let cache_key = cache_url(&full_url, params.as_ref());
+        self.apply_middleware_to_request(&method, &full_url, &cache_key, &mut all_headers)?;
+        for (key, value) in &all_headers {
+            if !key.eq_ignore_ascii_case("content-type") {
+                request = request.header(key, value);
+            }
+        }
+        if let Some(params) = params {
+            request = request.query(&params);
+        }
+        if let Some(timeout) = timeout {
+            request = request.timeout(Duration::from_secs_f64(timeout));
+        }
+
+        let start_time = Instant::now();
+        let signers = self.signers()?;
+        let (runtime, auth_config) = (&self.runtime, self.auth_config.as_ref());
+        let (digest_auth, cookie_jar) = (&self.digest_auth, self.cookie_jar.as_ref());
+        // Files are read and sent without holding the GIL
+        let sent = py.allow_threads(|| {
+            runtime.block_on(async {
+                let form = crate::multipart::file_form(files, data).await?;
+                let (client, built) =
+                    auth_common::build_request(request.multipart(form), auth_config, digest_auth);
+                let mut built = built.map_err(|e| map_reqwest_error(&e))?;
+                if let Some(jar) = cookie_jar {
+                    jar.apply(&mut built);
+                }
+                for signer in &signers {
+                    signer.sign(&mut built);
+                }
+                client
+                    .execute(built)
+                    .await
+                    .map_err(|e| map_reqwest_error(&e))
+            })
+        });
+        let response = sent.and_then(|response| {
+            if let Some(jar) = &self.cookie_jar {
+                jar.store_response(&response);
+            }
+            Response::from_reqwest(response, &self.runtime, self.max_response_size)
+        });
+        let elapsed_time = start_time.elapsed().as_secs_f64();
+
+        self.request_count
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        if let Some(circuit) = circuit {
+            circuit.record(
+                response
+                    .as_ref()
+                    .is_ok_and(|response| !is_failure_status(response.status_code)),
+            );
+        }
+        match response {
+            Ok(response) => {
+                self.report_response(&host, &path, &response, elapsed_time)?;
+                let response =
+                    self.apply_middleware_to_response(&method, &cache_key, response, elapsed_time)?;
+                self.total_request_time.fetch_add(
+                    (elapsed_time * 1e9) as u64,
+                    std::sync::atomic::Ordering::Relaxed,
+                );
+                if let Ok(mut last_time) = self.last_request_time.write() {
+                    *last_time = Some(elapsed_time);
+                }
+                self.protocol_stats_manager
+                    .increment_request_count(&full_url);
+                self.protocol_stats_manager
+                    .update_response_time(&full_url, Duration::from_secs_f64(elapsed_time));
+                Ok(response)
+            }
+            Err(e) => {
+                self.error_count
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                self.report_error(&method, &cache_key, &e.to_string(), elapsed_time)?;
+                self.apply_middleware_to_error(&method, &full_url, &e)?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Upload a body produced incrementally by `data_stream`, an iterator of
+    /// `bytes`, using chunked transfer encoding instead of buffering it.
+    ///
+    /// As with `upload_files` the request is sent once. If the iterator raises,
+    /// the upload is aborted and its exception is re-raised.
+    #[pyo3(signature = (
+        url, data_stream, headers = None, method = "POST", timeout = None,
+        progress_callback = None
+    ))]
+    pub fn upload_stream(
+        &mut self,
+        py: Python,
+        url: &str,
+        data_stream: &PyAny,
+        headers: Option<HashMap<String, String>>,
+        method: &str,
+        timeout: Option<f64>,
+        progress_callback: Option<PyObject>,
     ) -> PyResult<Response> {
-        self.execute_request_with_retry(Method::OPTIONS, url, None, None, headers)
+        self.ensure_open()?;
+        let method = parse_method(method)?;
+        let full_url = self.build_url(url)?;
+        self.ensure_oauth2_token()?;
+        let (body, stream_error) =
+            crate::body_stream::stream_body(data_stream, None, progress_callback)?;
+
+        let host = reqwest::Url::parse(&full_url)
+            .ok()
+            .and_then(|parsed| parsed.host_str().map(|h| h.to_string()))
+            .unwrap_or_default();
+        let client = self.host_clients.get(&host).unwrap_or(&self.client);
+        let mut request = self.apply_auth_internal(client.request(method, &full_url))?;
+
+        let mut all_headers = self.get_headers()?;
+        all_headers.extend(headers.unwrap_or_default());
+        for (key, value) in &all_headers {
+            request = request.header(key, value);
+        }
+        if let Some(timeout) = timeout {
+            request = request.timeout(Duration::from_secs_f64(timeout));
+        }
+
+        let (client, built) = auth_common::build_request(
+            request.body(body),
+            self.auth_config.as_ref(),
+            &self.digest_auth,
+        );
+        let mut built = built.map_err(|e| map_reqwest_error(&e))?;
+        if let Some(jar) = &self.cookie_jar {
+            jar.apply(&mut built);
+        }
+        for signer in &self.signers()? {
+            signer.sign(&mut built);
+        }
+
+        // The iterator is advanced on the runtime's threads, which need the GIL.
+        // execute starts the timeout timer, so it must be called inside the runtime
+        let runtime = &self.runtime;
+        let response = py
+            .allow_threads(|| runtime.block_on(async { client.execute(built).await }))
+            .map_err(|e| stream_error.or(map_reqwest_error(&e)))?;
+        if let Some(jar) = &self.cookie_jar {
+            jar.store_response(&response);
+        }
+        Response::from_reqwest(response, &self.runtime, self.max_response_size)
+    }
+
+    /// Stream the body of a GET to `path` and return the number of bytes written.
+    ///
+    /// `progress_callback(bytes_downloaded, total_bytes)` is called after every
+    /// `chunk_size` bytes and at the end; `total_bytes` is None without a
+    /// Content-Length. Error statuses raise instead of writing the file.
+    #[pyo3(signature = (
+        url, path, progress_callback = None, headers = None, chunk_size = 65536,
+        deadline = None, timeout = None
+    ))]
+    pub fn download(
+        &mut self,
+        py: Python,
+        url: &str,
+        path: &str,
+        progress_callback: Option<PyObject>,
+        headers: Option<HashMap<String, String>>,
+        chunk_size: usize,
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
+        let options = RequestOptions::new(deadline, timeout, None)?.with_stream();
+        let response =
+            self.execute_request_with_retry(Method::GET, url, None, None, headers, options)?;
+        response.raise_for_status(py)?;
+        response.save_with_progress(py, path, chunk_size, progress_callback)
+    }
+
+    /// Open connections to the hosts of `urls` ahead of time, completing the TLS
+    /// handshake, so the first real request to each reuses a pooled connection.
+    ///
+    /// Returns `{"succeeded": n, "failed": n}`.
+    #[pyo3(signature = (urls, timeout = None))]
+    pub fn warmup(
+        &mut self,
+        py: Python,
+        urls: Vec<String>,
+        timeout: Option<f64>,
+    ) -> PyResult<HashMap<String, usize>> {
+        self.ensure_open()?;
+        let mut targets = Vec::with_capacity(urls.len());
+        for url in &urls {
+            let full_url = self.build_url(url)?;
+            let host = reqwest::Url::parse(&full_url)
+                .ok()
+                .and_then(|parsed| parsed.host_str().map(|h| h.to_string()))
+                .unwrap_or_default();
+            targets.push((self.select_client(&host, None)?, full_url));
+        }
+        let timeout = timeout.map(Duration::from_secs_f64);
+        let runtime = &self.runtime;
+        Ok(py.allow_threads(|| runtime.block_on(warm_connections(targets, timeout))))
     }
 
     /// Get performance statistics
@@ -606,6 +1077,14 @@ impl HttpClient {
         };
 
         stats.insert("request_count".to_string(), count);
+        stats.insert(
+            "error_count".to_string(),
+            self.error_count.load(std::sync::atomic::Ordering::Relaxed) as f64,
+        );
+        stats.insert(
+            "error_rate_percent".to_string(),
+            self.get_error_rate() * 100.0,
+        );
         stats.insert("total_request_time".to_string(), total_time);
         stats.insert(
             "average_request_time".to_string(),
@@ -618,6 +1097,77 @@ impl HttpClient {
         Ok(stats)
     }
 
+    /// Connection pool utilisation: active/idle connections, total acquired and per-host counts
+    pub fn get_pool_stats(&self, py: Python) -> PyResult<PyObject> {
+        crate::connection_pool::pool_stats_dict(
+            py,
+            &self.connection_pool,
+            &self.connection_multiplexer,
+        )
+    }
+
+    /// Drop cached DNS lookups so the next request to each host resolves again
+    pub fn clear_dns_cache(&self) {
+        if let Some(cache) = &self.dns_cache {
+            cache.clear();
+        }
+    }
+
+    /// Circuit state for `host`: "closed", "open" or "half_open"
+    pub fn circuit_state(&self, host: &str) -> String {
+        self.circuit_breaker
+            .as_ref()
+            .map_or("closed", |breaker| breaker.state(host))
+            .to_string()
+    }
+
+    /// Release the client's resources; later requests raise "Client is closed"
+    pub fn close(&mut self) {
+        if self.closed.swap(true, std::sync::atomic::Ordering::SeqCst) {
+            return;
+        }
+
+        if let Ok(mut cleanup_handle) = self._cleanup_handle.write() {
+            if let Some(handle) = cleanup_handle.take() {
+                handle.abort();
+            }
+        }
+        self.connection_pool.drain_idle();
+        self.connection_multiplexer.cleanup_all();
+        self.host_clients.clear();
+        let http3_client = self
+            .http3_client
+            .write()
+            .ok()
+            .and_then(|mut client| client.take());
+        self.runtime.block_on(async {
+            if let Some(client) = http3_client {
+                client.close().await;
+            }
+            self.http3_pool.close().await;
+        });
+    }
+
+    /// Whether close() has been called
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    fn __enter__(slf: PyRef<Self>) -> PyResult<PyRef<Self>> {
+        slf.ensure_open()?;
+        Ok(slf)
+    }
+
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<&PyAny>,
+        _exc_value: Option<&PyAny>,
+        _traceback: Option<&PyAny>,
+    ) -> bool {
+        self.close();
+        false
+    }
+
     /// Reset performance statistics
     pub fn reset_stats(&mut self) -> PyResult<()> {
         // Reset shared protocol stats
@@ -626,6 +1176,8 @@ impl HttpClient {
         // Reset legacy stats for backward compatibility
         self.request_count
             .store(0, std::sync::atomic::Ordering::Relaxed);
+        self.error_count
+            .store(0, std::sync::atomic::Ordering::Relaxed);
         self.total_request_time
             .store(0, std::sync::atomic::Ordering::Relaxed);
 
@@ -666,7 +1218,7 @@ impl HttpClient {
         // Try to resolve address
         let server_addr = match addr_str.to_socket_addrs() {
             Ok(mut addrs) => addrs.next().ok_or_else(|| {
-                pyo3::exceptions::PyConnectionError::new_err("No addresses found for host")
+                crate::error::dns_error("No addresses found for host".to_string())
             })?,
             Err(_) => {
                 // Return basic stats if address resolution fails
@@ -728,17 +1280,55 @@ impl HttpClient {
         Ok(stats_dict)
     }
 
-    /// Add middleware
-    pub fn add_middleware(&mut self, _middleware: PyObject) {
-        // For now, we'll accept PyObject instead of Py<Middleware>
-        // This would need to be implemented based on the specific middleware type
+    /// Add a logging, headers, retry, metrics, interceptor, rate limit, cache or signing middleware
+    pub fn add_middleware(&mut self, middleware: &PyAny) -> PyResult<()> {
+        let middleware_manager = self.middleware_manager.read().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire middleware manager lock")
+        })?;
+
+        let added = if let Ok(logging) = middleware.extract::<PyRef<LoggingMiddleware>>() {
+            middleware_manager.add_logging_middleware((*logging).clone())
+        } else if let Ok(headers) = middleware.extract::<PyRef<HeadersMiddleware>>() {
+            middleware_manager.add_headers_middleware((*headers).clone())
+        } else if let Ok(retry) = middleware.extract::<PyRef<RetryMiddleware>>() {
+            middleware_manager.add_retry_middleware((*retry).clone())
+        } else if let Ok(metrics) = middleware.extract::<PyRef<MetricsMiddleware>>() {
+            middleware_manager.add_metrics_middleware((*metrics).clone())
+        } else if let Ok(interceptor) = middleware.extract::<PyRef<InterceptorMiddleware>>() {
+            middleware_manager.add_interceptor_middleware((*interceptor).clone())
+        } else if let Ok(rate_limit) = middleware.extract::<PyRef<RateLimitMiddleware>>() {
+            middleware_manager.add_rate_limit_middleware((*rate_limit).clone());
+            Ok(())
+        } else if let Ok(cache) = middleware.extract::<PyRef<CacheMiddleware>>() {
+            middleware_manager.add_cache_middleware((*cache).clone())
+        } else if let Ok(signing) = middleware.extract::<PyRef<HmacSigningMiddleware>>() {
+            middleware_manager.add_signing_middleware((*signing).clone())
+        } else if let Ok(transform) = middleware.extract::<PyRef<TransformMiddleware>>() {
+            middleware_manager.add_transform_middleware((*transform).clone())
+        } else if middleware.extract::<PyRef<DedupMiddleware>>().is_ok() {
+            // Requests on this client run one at a time, so there is nothing to coalesce
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "DedupMiddleware is only supported by AsyncHttpClient",
+            ));
+        } else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Unsupported middleware type",
+            ));
+        };
+        added.map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to add middleware: {}", e))
+        })
     }
 
-    /// Remove middleware (currently not supported in this release)
-    pub fn remove_middleware(&mut self, _name: &str) -> bool {
-        // Middleware removal is planned for a future release
-        // For now, middleware can only be added
-        false
+    /// Remove all middleware with the given name, returning true if any was removed
+    pub fn remove_middleware(&mut self, name: &str) -> PyResult<bool> {
+        let middleware_manager = self.middleware_manager.write().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire middleware manager lock")
+        })?;
+        let mut stack = middleware_manager.middleware_stack.write().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire middleware stack lock")
+        })?;
+        Ok(stack.remove_named(name))
     }
 
     /// Set a header
@@ -773,65 +1363,243 @@ impl HttpClient {
             )),
         }
     }
-}
 
-impl HttpClient {
-    /// Start cleanup task for resource management
-    fn start_cleanup_task(runtime: Arc<Runtime>) -> tokio::task::JoinHandle<()> {
-        runtime.spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(300)); // Cleanup every 5 minutes
-            loop {
-                interval.tick().await;
-                // Perform cleanup operations
-                // This could include cleaning up expired connections, tokens, etc.
-                tokio::task::yield_now().await;
-            }
-        })
+    /// Set query parameters sent with every request (per-request params take precedence)
+    pub fn set_default_params(&mut self, params: HashMap<String, String>) {
+        self.default_params = params;
     }
 
-    /// Enhanced retry logic with exponential backoff and advanced conditions
-    fn execute_request_with_retry(
-        &mut self,
-        method: Method,
-        url: &str,
-        params: Option<HashMap<String, String>>,
-        body: Option<Vec<u8>>,
-        headers: Option<HashMap<String, String>>,
-    ) -> PyResult<Response> {
-        let retry_config = self.retry_config.clone();
-        let max_retries = retry_config.as_ref().map(|c| c.max_retries).unwrap_or(0);
-        let start_time = Instant::now();
-        let mut last_error = None;
+    /// Clear the default query parameters
+    pub fn clear_default_params(&mut self) {
+        self.default_params.clear();
+    }
+
+    /// Get the default query parameters
+    pub fn get_default_params(&self) -> HashMap<String, String> {
+        self.default_params.clone()
+    }
+
+    /// Export the client configuration as a JSON-compatible dict.
+    /// Auth credentials and proxy passwords are redacted unless `include_secrets` is set.
+    #[pyo3(signature = (include_secrets = false))]
+    pub fn config_to_dict(&self, py: Python, include_secrets: bool) -> PyResult<PyObject> {
+        let mut snapshot = ClientConfigSnapshot {
+            base_url: self.base_url.clone(),
+            headers: self
+                .headers
+                .read()
+                .map_err(|_| {
+                    pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire headers lock")
+                })?
+                .clone(),
+            default_params: self.default_params.clone(),
+            timeout: self.timeout,
+            body_mode: self.get_body_mode(),
+            auth: self.auth_config.clone(),
+            retry: self.retry_config.clone(),
+            timeouts: self.timeout_config.clone(),
+            pool: self.pool_config.clone(),
+            ssl: self.ssl_config.clone(),
+            proxy: self.proxy_config.clone(),
+            compression: self.compression_config.clone(),
+            protocol: self.protocol_config.clone(),
+            rate_limit: self.rate_limit_config.clone(),
+            allow_redirects: self.allow_redirects,
+            max_redirects: self.max_redirects,
+            dns_cache_ttl: self
+                .dns_cache
+                .as_ref()
+                .map(|cache| cache.ttl().as_secs_f64()),
+            dns_cache_max_entries: self
+                .dns_cache
+                .as_ref()
+                .map_or(crate::dns_cache::DEFAULT_MAX_ENTRIES, |cache| {
+                    cache.max_entries()
+                }),
+            unix_socket: self.unix_socket.clone(),
+            ip_version: self.ip_version.as_str().to_string(),
+            max_response_size: self.max_response_size,
+            cleanup_interval: self.cleanup_interval,
+            circuit_breaker: self
+                .circuit_breaker
+                .as_ref()
+                .map(|breaker| breaker.config().clone()),
+        };
+        if !include_secrets {
+            snapshot.redact_secrets();
+        }
+        snapshot.to_py(py)
+    }
+
+    /// Build a client from a dict produced by `config_to_dict`.
+    /// Redacted auth is dropped rather than sent.
+    #[staticmethod]
+    pub fn from_config_dict(config: &PyAny) -> PyResult<Self> {
+        let mut snapshot = ClientConfigSnapshot::from_py(config)?;
+        snapshot.strip_redacted();
+
+        let mut client = Self::new(
+            snapshot.base_url,
+            Some(snapshot.headers),
+            snapshot.timeout,
+            snapshot.auth,
+            snapshot.retry,
+            Some(snapshot.timeouts),
+            Some(snapshot.pool),
+            Some(snapshot.ssl),
+            snapshot.proxy,
+            Some(snapshot.compression),
+            Some(snapshot.protocol),
+            snapshot.rate_limit,
+            &snapshot.body_mode,
+            snapshot.allow_redirects,
+            snapshot.max_redirects,
+            snapshot.dns_cache_ttl,
+            snapshot.dns_cache_max_entries,
+            snapshot.unix_socket,
+            snapshot.circuit_breaker,
+            &snapshot.ip_version,
+            snapshot.max_response_size,
+            snapshot.cleanup_interval,
+        )?;
+        client.default_params = snapshot.default_params;
+        Ok(client)
+    }
+}
+
+impl HttpClient {
+    /// Start the task that sweeps expired idle connections and OAuth2 tokens every `period`.
+    /// It holds no reference to the client, so close() and Drop can simply abort it.
+    fn start_cleanup_task(
+        runtime: &Runtime,
+        period: Duration,
+        connection_pool: Arc<FastConnectionPool>,
+        connection_multiplexer: Arc<ConnectionMultiplexer>,
+        oauth2_token: Arc<RwLock<Option<OAuth2Token>>>,
+    ) -> tokio::task::JoinHandle<()> {
+        runtime.spawn(async move {
+            let mut interval =
+                tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                connection_pool.cleanup_expired();
+                connection_multiplexer.cleanup_all();
+                // An expired token without a refresh token is only ever replaced by a new fetch
+                if let Ok(mut token) = oauth2_token.write() {
+                    if token
+                        .as_ref()
+                        .is_some_and(|token| token.is_expired() && token.refresh_token.is_none())
+                    {
+                        *token = None;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Fail with `client_closed_error` once close() has been called
+    fn ensure_open(&self) -> PyResult<()> {
+        if self.is_closed() {
+            return Err(client_closed_error());
+        }
+        Ok(())
+    }
+
+    /// Enhanced retry logic with exponential backoff and advanced conditions
+    fn execute_request_with_retry(
+        &mut self,
+        method: Method,
+        url: &str,
+        params: Option<HashMap<String, String>>,
+        body: Option<Vec<u8>>,
+        headers: Option<HashMap<String, String>>,
+        options: RequestOptions,
+    ) -> PyResult<Response> {
+        self.ensure_open()?;
+        let retry_config = self.retry_config.clone();
+        let max_retries = retry_config.as_ref().map(|c| c.max_retries).unwrap_or(0);
+        let start_time = Instant::now();
+        let mut last_error = None;
+        let mut last_reason = None;
+        let mut retry_after = None;
         let mut consecutive_failures = 0;
+        // URLs that don't parse are left for the request itself to reject
+        let circuit = self.circuit_breaker.as_ref().and_then(|breaker| {
+            self.build_url(url)
+                .ok()
+                .and_then(|full_url| circuit_host(&full_url))
+                .map(|host| (Arc::clone(breaker), host))
+        });
 
         for attempt in 0..=max_retries {
             if attempt > 0 {
-                // Calculate retry delay with advanced backoff
+                // Honor the server's Retry-After, else calculate delay with advanced backoff
                 if let Some(ref config) = retry_config {
-                    let delay_secs =
-                        config.calculate_delay_with_backoff(attempt - 1, consecutive_failures);
+                    let delay_secs = retry_after.take().unwrap_or_else(|| {
+                        config.calculate_delay_with_backoff(attempt - 1, consecutive_failures)
+                    });
+
+                    // Sleeping past the deadline cannot succeed, so fail now
+                    if let Some(remaining) = deadline_remaining(options.deadline)? {
+                        if remaining.as_secs_f64() <= delay_secs {
+                            return Err(deadline_exceeded_error());
+                        }
+                    }
 
-                    // Use async sleep instead of blocking the thread
-                    self.runtime.block_on(async {
-                        tokio::time::sleep(Duration::from_secs_f64(delay_secs)).await;
+                    if let (Some(callback), Some(reason)) = (&self.on_retry, last_reason.take()) {
+                        invoke_on_retry(callback, attempt, reason, delay_secs)?;
+                    }
+
+                    // Back off without holding the GIL
+                    let runtime = &self.runtime;
+                    Python::with_gil(|py| {
+                        py.allow_threads(|| {
+                            runtime
+                                .block_on(tokio::time::sleep(Duration::from_secs_f64(delay_secs)))
+                        })
                     });
                 }
             }
 
-            match self.execute_request_internal(
+            if let Some((breaker, host)) = &circuit {
+                breaker.allow(host)?;
+            }
+
+            let outcome = self.execute_request_internal(
                 method.clone(),
                 url,
                 params.clone(),
                 body.clone(),
                 headers.clone(),
-            ) {
+                &options,
+            );
+            if let Some((breaker, host)) = &circuit {
+                let success = outcome
+                    .as_ref()
+                    .is_ok_and(|response| !is_failure_status(response.status_code));
+                breaker.record(host, success);
+            }
+
+            self.request_count
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let failed = match &outcome {
+                Ok(response) => retry_config
+                    .as_ref()
+                    .is_some_and(|config| config.should_retry_status(response.status_code)),
+                Err(_) => true,
+            };
+            if failed {
+                self.error_count
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            }
+
+            match outcome {
                 Ok(response) => {
                     // Update performance stats with atomic operations
                     let duration = start_time.elapsed();
                     let duration_secs = duration.as_secs_f64();
 
-                    self.request_count
-                        .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
                     let duration_nanos = duration.as_nanos() as u64;
                     self.total_request_time
                         .fetch_add(duration_nanos, std::sync::atomic::Ordering::Relaxed);
@@ -845,47 +1613,36 @@ impl HttpClient {
                     self.protocol_stats_manager
                         .update_response_time(url, duration);
 
-                    // Check if we should retry based on status code with circuit breaker
+                    // Check if we should retry based on status code
                     if let Some(ref config) = retry_config {
-                        if attempt < max_retries {
-                            // Get current error rate for circuit breaker
-                            let error_rate = self.get_error_rate();
-
-                            if config
-                                .should_retry_with_circuit_breaker(response.status_code, error_rate)
-                            {
-                                consecutive_failures += 1;
-                                last_error = Some(format!(
-                                    "Status code {} is retryable",
-                                    response.status_code
-                                ));
-                                continue;
-                            }
+                        if attempt < max_retries
+                            && config.should_retry_method(method.as_str())
+                            && config.should_retry_status(response.status_code)
+                        {
+                            consecutive_failures += 1;
+                            retry_after = config.retry_after_delay(&response.headers);
+                            last_reason = Some(RetryReason::Status(response.status_code));
+                            last_error =
+                                Some(format!("Status code {} is retryable", response.status_code));
+                            continue;
                         }
                     }
 
-                    // Reset consecutive failures on success
-                    consecutive_failures = 0;
                     return Ok(response);
                 }
                 Err(e) => {
                     consecutive_failures += 1;
+                    last_reason = Some(RetryReason::Error(e.to_string()));
                     last_error = Some(e.to_string());
 
-                    // Check if we should retry on connection errors with circuit breaker
+                    // Check if the error kind is retried
                     if let Some(ref config) = retry_config {
-                        if !config.retry_on_connection_errors || attempt >= max_retries {
+                        if !Python::with_gil(|py| config.should_retry_error(py, &e))
+                            || attempt >= max_retries
+                            || !config.should_retry_method(method.as_str())
+                        {
                             return Err(e);
                         }
-
-                        // Check circuit breaker for connection errors
-                        let error_rate = self.get_error_rate();
-                        if error_rate > 0.8 {
-                            return Err(pyo3::exceptions::PyConnectionError::new_err(format!(
-                                "Circuit breaker open - error rate too high: {:.2}%",
-                                error_rate * 100.0
-                            )));
-                        }
                     } else {
                         return Err(e);
                     }
@@ -894,14 +1651,14 @@ impl HttpClient {
         }
 
         // All retries exhausted
-        Err(pyo3::exceptions::PyConnectionError::new_err(format!(
+        Err(crate::error::connection_error(format!(
             "Request failed after {} retries: {}",
             max_retries + 1,
             last_error.unwrap_or_default()
         )))
     }
 
-    /// Get current error rate for circuit breaker logic
+    /// Share of request attempts that errored or got a retryable status
     fn get_error_rate(&self) -> f64 {
         let total_requests = self
             .request_count
@@ -910,10 +1667,8 @@ impl HttpClient {
             return 0.0;
         }
 
-        // This is a simplified error rate calculation
-        // In a real implementation, you'd track errors separately
-        // For now, we'll use a conservative estimate
-        0.1 // 10% error rate as placeholder
+        let errors = self.error_count.load(std::sync::atomic::Ordering::Relaxed);
+        errors as f64 / total_requests as f64
     }
 
     /// Internal: Execute a single request
@@ -924,28 +1679,26 @@ impl HttpClient {
         params: Option<HashMap<String, String>>,
         body: Option<Vec<u8>>,
         headers: Option<HashMap<String, String>>,
+        options: &RequestOptions,
     ) -> PyResult<Response> {
+        // Fail immediately when the deadline has already passed
+        deadline_remaining(options.deadline)?;
+
         let start_time = std::time::Instant::now();
         let full_url = self.build_url(url)?;
+        let params = merge_query_params(&self.default_params, params, self.auth_config.as_ref());
 
-        // Extract host for rate limiting
-        let host = if let Ok(parsed_url) = reqwest::Url::parse(&full_url) {
-            parsed_url.host_str().unwrap_or("default").to_string()
+        // Extract host and path for rate limiting
+        let (host, path) = if let Ok(parsed_url) = reqwest::Url::parse(&full_url) {
+            (
+                parsed_url.host_str().unwrap_or("default").to_string(),
+                parsed_url.path().to_string(),
+            )
         } else {
-            "default".to_string()
+            ("default".to_string(), "/".to_string())
         };
 
-        // Check rate limiting before making request
-        {
-            let middleware_manager = self.middleware_manager.read().map_err(|_| {
-                pyo3::exceptions::PyRuntimeError::new_err(
-                    "Failed to acquire middleware manager lock",
-                )
-            })?;
-            middleware_manager.check_rate_limit(&host).map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!("Rate limit check failed: {}", e))
-            })?;
-        }
+        self.wait_for_rate_limit(&host, &path)?;
 
         // Protocol selection logic
         let _selected_protocol = self.runtime.block_on(async {
@@ -957,41 +1710,40 @@ impl HttpClient {
         // Try HTTP/3 if selected and available
 
         if _selected_protocol == HttpVersion::Http3 {
-            if let Ok(response) = self.try_http3_request(
+            match self.try_http3_request(
                 method.clone(),
                 &full_url,
                 params.clone(),
                 body.clone(),
                 headers.clone(),
             ) {
-                return Ok(response);
+                Ok(response) => return Ok(response),
+                // ProtocolFallback.None surfaces the HTTP/3 failure instead of downgrading
+                Err(e) if self.protocol_config.is_strict() => return Err(e),
+                // Fall back to HTTP/1.1 or HTTP/2 if HTTP/3 fails
+                Err(_) => {}
             }
-            // Fall back to HTTP/1.1 or HTTP/2 if HTTP/3 fails
-        }
-
-        // Log request through middleware
-        {
-            let middleware_manager = self.middleware_manager.read().map_err(|_| {
-                pyo3::exceptions::PyRuntimeError::new_err(
-                    "Failed to acquire middleware manager lock",
-                )
-            })?;
-            middleware_manager.log_request(&method.to_string(), &full_url);
         }
 
         // Apply authentication if needed
         self.ensure_oauth2_token()?;
 
-        let mut request = self.client.request(method.clone(), &full_url);
+        let client = self.select_client(&host, options.proxy.as_ref())?;
+        let mut request = client.request(method.clone(), &full_url);
 
         // Apply authentication headers
         request = self.apply_auth_internal(request)?;
 
+        let _host_slot = self.acquire_host_slot(&host)?;
+
         // === PERFORMANCE OPTIMIZATION: Use cached headers and connection pool ===
         // Try to acquire connection from pool
         let _connection_permit = self
             .runtime
             .block_on(async { self.connection_pool.try_acquire_connection().await });
+        if let Some(permit) = &_connection_permit {
+            permit.mark_active(host.clone());
+        }
 
         // Prepare headers map for middleware processing with header caching
         let mut all_headers = {
@@ -1007,23 +1759,11 @@ impl HttpClient {
 
             // Merge with cached headers for performance
             for (key, value) in cached_headers {
-                if !headers_map.contains_key(&key) {
-                    headers_map.insert(key, value.to_string());
-                }
+                headers_map.entry(key).or_insert_with(|| value.to_string());
             }
             headers_map
         };
 
-        // Apply middleware headers
-        {
-            let middleware_manager = self.middleware_manager.read().map_err(|_| {
-                pyo3::exceptions::PyRuntimeError::new_err(
-                    "Failed to acquire middleware manager lock",
-                )
-            })?;
-            middleware_manager.apply_headers_middleware(&mut all_headers);
-        }
-
         // Performance optimization: Record request patterns for runtime optimization
         let runtime_optimizer = get_runtime_optimizer();
         let ahash_headers: AHashMap<String, String> = all_headers
@@ -1039,6 +1779,26 @@ impl HttpClient {
             }
         }
 
+        // Run header, interceptor and logging middleware over the final headers in
+        // priority order; loggers see the query the request will be sent with
+        let cache_key = cache_url(&full_url, params.as_ref());
+        self.apply_middleware_to_request(&method, &full_url, &cache_key, &mut all_headers)?;
+
+        // Serve a fresh cached GET response, or ask the server to revalidate a stale one
+        let caches = if method == Method::GET {
+            self.caches()?
+        } else {
+            Vec::new()
+        };
+        for cache in &caches {
+            if let Some(response) = cache.fresh_response(&cache_key, &all_headers) {
+                return Ok(response);
+            }
+            for (key, value) in cache.validators(&cache_key, &all_headers) {
+                all_headers.entry(key).or_insert(value);
+            }
+        }
+
         // Apply all headers to request
         for (key, value) in &all_headers {
             request = request.header(key, value);
@@ -1049,28 +1809,153 @@ impl HttpClient {
             request = request.query(&params);
         }
 
-        // Add body with compression if enabled
-        if let Some(body) = body {
-            let (processed_body, content_encoding) = self.process_request_body(body)?;
-            request = request.body(processed_body);
-
-            // Add compression headers if body was compressed
-            if let Some(encoding) = content_encoding {
-                request = request.header("Content-Encoding", encoding);
+        // Add body with compression if enabled, keeping it for 307/308 redirects
+        let sent_body = match body {
+            Some(body) => {
+                let content_type = header_value(&all_headers, "Content-Type")
+                    .unwrap_or("application/octet-stream");
+                let (processed_body, content_encoding) =
+                    self.process_request_body(&client, body, content_type, &full_url, &host)?;
+                let processed_body = bytes::Bytes::from(processed_body);
+                request = request.body(processed_body.clone());
+
+                // Add compression headers if body was compressed
+                if let Some(encoding) = &content_encoding {
+                    request = request.header("Content-Encoding", encoding.as_str());
+                }
+                Some((processed_body, content_encoding))
             }
+            None => None,
+        };
+
+        // A per-call timeout or the remaining deadline budget replaces the client timeout
+        if let Some(timeout) = options.attempt_timeout()? {
+            request = request.timeout(timeout);
         }
 
-        // Apply additional middleware to request
-        request = self.apply_middleware_to_request(request, url, &method)?;
+        // Execute request, following redirects unless disabled for this call
+        let follow_redirects = options.allow_redirects.unwrap_or(self.allow_redirects);
+        let mut current_method = method.clone();
+        let mut history = Vec::new();
+        let mut digest_attempts = 0;
+        let signers = self.signers()?;
+        let phases = Arc::new(ConnectionPhases::default());
+        let exchange_start = Instant::now();
+        let response_result = loop {
+            let (client, built) =
+                auth_common::build_request(request, self.auth_config.as_ref(), &self.digest_auth);
+            let sent = match built {
+                Ok(mut built) => {
+                    if let Some(jar) = &self.cookie_jar {
+                        jar.apply(&mut built);
+                    }
+                    // Sign last so the signature covers the final URL, headers and body
+                    for signer in &signers {
+                        signer.sign(&mut built);
+                    }
+                    let runtime = &self.runtime;
+                    let timeouts = &self.timeout_config;
+                    let send = || {
+                        runtime.block_on(phases.scope(execute_with_write_timeout(
+                            &client, built, timeouts, options,
+                        )))
+                    };
+                    // Release the GIL while waiting on the network, so Python threads keep
+                    // running; the progress callback also takes it on the runtime's threads
+                    Python::with_gil(|py| py.allow_threads(send))?
+                }
+                Err(e) => Err(e),
+            };
+            let response = match sent {
+                Ok(response) => response,
+                Err(e) => break Err(e),
+            };
+            if let Some(jar) = &self.cookie_jar {
+                jar.store_response(&response);
+            }
+            if let Some(alt_svc) = response
+                .headers()
+                .get(reqwest::header::ALT_SVC)
+                .and_then(|value| value.to_str().ok())
+            {
+                self.protocol_negotiator
+                    .record_alt_svc(response.url().as_str(), alt_svc);
+            }
+
+            // Answer a Digest or NTLM challenge by re-sending the same request
+            let hop = if self.digest_auth.should_retry(
+                &response,
+                self.auth_config.as_ref(),
+                &mut digest_attempts,
+            ) {
+                let hop = RedirectHop::repeat(response.url(), &current_method);
+                // Read the challenge body so the connection is kept for the next
+                // message, as NTLM authenticates the connection rather than requests
+                let runtime = &self.runtime;
+                let _ =
+                    Python::with_gil(|py| py.allow_threads(|| runtime.block_on(response.bytes())));
+                hop
+            } else {
+                let hop = if follow_redirects {
+                    RedirectHop::from_response(&response, &current_method)
+                } else {
+                    None
+                };
+                let hop = match hop {
+                    Some(hop) => hop,
+                    None => break Ok(response),
+                };
+                if history.len() >= self.max_redirects {
+                    return Err(too_many_redirects_error(self.max_redirects, &full_url));
+                }
+                history.push(Response::from_redirect(&response));
+                hop
+            };
 
-        // Execute request
-        let response_result = self.runtime.block_on(async { request.send().await });
+            let hop_host = hop.url.host_str().unwrap_or_default();
+            let hop_client = self.select_client(hop_host, options.proxy.as_ref())?;
+            request = hop_client.request(hop.method.clone(), hop.url.clone());
+            for (key, value) in &all_headers {
+                if hop.keeps_header(key) {
+                    request = request.header(key, value);
+                }
+            }
+            if hop.same_origin {
+                request = self.apply_auth_internal(request)?;
+            }
+            if let (true, Some((body, content_encoding))) = (hop.keep_body, &sent_body) {
+                request = request.body(body.clone());
+                if let Some(encoding) = content_encoding {
+                    request = request.header("Content-Encoding", encoding.as_str());
+                }
+            }
+            if let Some(timeout) = options.attempt_timeout()? {
+                request = request.timeout(timeout);
+            }
+            current_method = hop.method;
+        };
 
+        let ttfb = exchange_start.elapsed();
         let elapsed_time = start_time.elapsed().as_secs_f64();
 
         match response_result {
             Ok(response) => {
-                let mut response_obj = Response::from_reqwest(response, &self.runtime)?;
+                let mut response_obj = if self.lazy_body || options.stream {
+                    Response::from_reqwest_lazy(
+                        response,
+                        self.runtime.handle().clone(),
+                        Some(self.runtime.clone()),
+                        self.max_response_size,
+                    )
+                } else {
+                    Response::from_reqwest(response, &self.runtime, self.max_response_size)
+                        .map_err(|e| match deadline_remaining(options.deadline) {
+                            Err(deadline_err) => deadline_err,
+                            Ok(_) => e,
+                        })?
+                };
+                response_obj.history = history;
+                response_obj.record_timing(phases.finish(exchange_start, ttfb));
 
                 // === PERFORMANCE OPTIMIZATION: Mark connection as used ===
                 if let Some(permit) = _connection_permit {
@@ -1078,7 +1963,7 @@ impl HttpClient {
                 }
 
                 // Performance optimization: Record response patterns for runtime optimization
-                let response_size = response_obj.content.len();
+                let response_size = response_obj.body_size_hint();
                 let success = response_obj.status_code >= 200 && response_obj.status_code < 400;
                 let protocol = response_obj.protocol.as_deref().unwrap_or("HTTP/1.1");
                 runtime_optimizer.record_response(
@@ -1089,30 +1974,22 @@ impl HttpClient {
                     protocol,
                 );
 
-                // Log successful response through middleware
-                {
-                    let middleware_manager = self.middleware_manager.read().map_err(|_| {
-                        pyo3::exceptions::PyRuntimeError::new_err(
-                            "Failed to acquire middleware manager lock",
-                        )
-                    })?;
-                    middleware_manager
-                        .log_response(response_obj.status_code, elapsed_time * 1000.0);
-                    middleware_manager.update_metrics(elapsed_time, false);
+                self.report_response(&host, &path, &response_obj, elapsed_time)?;
+
+                // Store cacheable responses and expand 304s into the cached response
+                for cache in &caches {
+                    response_obj = cache.handle_response(&cache_key, &all_headers, response_obj)?;
                 }
 
-                // Process response through middleware
-                self.apply_middleware_to_response(&mut response_obj, elapsed_time)?;
+                // Process response through interceptor and logging middleware
+                response_obj = self.apply_middleware_to_response(
+                    &method,
+                    &cache_key,
+                    response_obj,
+                    elapsed_time,
+                )?;
 
-                // Update statistics
-                {
-                    let mut count = self
-                        .request_count
-                        .load(std::sync::atomic::Ordering::Relaxed);
-                    count += 1;
-                    self.request_count
-                        .store(count, std::sync::atomic::Ordering::Relaxed);
-                }
+                // Update statistics; request_count is kept per attempt by the retry loop
                 {
                     let mut total_time = self
                         .total_request_time
@@ -1139,23 +2016,20 @@ impl HttpClient {
                 Ok(response_obj)
             }
             Err(e) => {
-                // Log error through middleware
-                {
-                    let middleware_manager = self.middleware_manager.read().map_err(|_| {
-                        pyo3::exceptions::PyRuntimeError::new_err(
-                            "Failed to acquire middleware manager lock",
-                        )
-                    })?;
-                    middleware_manager.update_metrics(elapsed_time, true);
+                if let Some(permit) = &_connection_permit {
+                    permit.mark_failed(&host);
                 }
 
-                Err(map_reqwest_error(&e))
+                self.report_error(&method, &cache_key, &e.to_string(), elapsed_time)?;
+
+                let error = map_deadline_error(&e, options.deadline);
+                self.apply_middleware_to_error(&method, &full_url, &error)?;
+                Err(error)
             }
         }
     }
 
     /// Try HTTP/3 request using Quiche
-
     fn try_http3_request(
         &mut self,
         method: Method,
@@ -1178,17 +2052,21 @@ impl HttpClient {
             .host_str()
             .ok_or_else(|| pyo3::exceptions::PyValueError::new_err("No host in URL"))?;
 
-        let port = parsed_url.port().unwrap_or(443);
+        // Prefer the UDP port the origin advertised through Alt-Svc
+        let port = self
+            .protocol_negotiator
+            .http3_port(url)
+            .unwrap_or_else(|| parsed_url.port().unwrap_or(443));
 
         // Resolve address
         let addr_str = format!("{}:{}", host, port);
-        let mut addrs = addr_str.to_socket_addrs().map_err(|e| {
-            pyo3::exceptions::PyConnectionError::new_err(format!("Failed to resolve host: {}", e))
-        })?;
+        let mut addrs = addr_str
+            .to_socket_addrs()
+            .map_err(|e| crate::error::dns_error(format!("Failed to resolve host: {}", e)))?;
 
-        let server_addr = addrs.next().ok_or_else(|| {
-            pyo3::exceptions::PyConnectionError::new_err("No addresses found for host")
-        })?;
+        let server_addr = addrs
+            .next()
+            .ok_or_else(|| crate::error::dns_error("No addresses found for host".to_string()))?;
 
         // Prepare headers
         let mut all_headers = self
@@ -1231,7 +2109,7 @@ impl HttpClient {
             let http3_client = match self.http3_pool.get_connection(server_addr).await {
                 Ok(client) => client,
                 Err(e) => {
-                    return Err(pyo3::exceptions::PyConnectionError::new_err(format!(
+                    return Err(crate::error::connection_error(format!(
                         "Failed to get HTTP/3 client from pool: {}",
                         e
                     )));
@@ -1248,12 +2126,18 @@ impl HttpClient {
 
         // Now use the client directly inside a block_on to avoid borrowing issues
         let response = self.runtime.block_on(async {
-            let client_guard = self.http3_client.read().map_err(|_| {
-                pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire HTTP/3 client lock")
-            })?;
-            let client = client_guard.as_ref().ok_or_else(|| {
-                pyo3::exceptions::PyRuntimeError::new_err("Client not initialized")
-            })?;
+            let client = self
+                .http3_client
+                .read()
+                .map_err(|_| {
+                    pyo3::exceptions::PyRuntimeError::new_err(
+                        "Failed to acquire HTTP/3 client lock",
+                    )
+                })?
+                .clone()
+                .ok_or_else(|| {
+                    pyo3::exceptions::PyRuntimeError::new_err("Client not initialized")
+                })?;
 
             // Measure HTTP/3 metrics for the request
             // Include start_time for accurate timing metrics
@@ -1262,17 +2146,14 @@ impl HttpClient {
             // Send the request with optimized parameters
             client
                 .send_request(
-                    &method.to_string(),
+                    method.as_ref(),
                     &path,
                     all_headers.clone(),
                     body.map(|b| b.to_vec()),
                 )
                 .await
                 .map_err(|e| {
-                    pyo3::exceptions::PyConnectionError::new_err(format!(
-                        "HTTP/3 request failed: {}",
-                        e
-                    ))
+                    crate::error::connection_error(format!("HTTP/3 request failed: {}", e))
                 })
         });
 
@@ -1291,6 +2172,9 @@ impl HttpClient {
 
         // Handle special OAuth2 case that requires token management
         if let Some(ref auth) = self.auth_config {
+            if let Some(ref provider) = auth.token_provider {
+                return Ok(auth_request.bearer_auth(provider.token()?));
+            }
             if matches!(auth.auth_type, AuthType::OAuth2) {
                 if let Ok(token_guard) = self.oauth2_token.read() {
                     if let Some(ref token) = *token_guard {
@@ -1331,221 +2215,334 @@ impl HttpClient {
         Ok(())
     }
 
-    /// Fetch OAuth2 token
-    fn fetch_oauth2_token(&mut self) -> PyResult<()> {
-        if let Some(ref auth) = self.auth_config {
-            let token_url = auth.get_credential("token_url").ok_or_else(|| {
-                pyo3::exceptions::PyValueError::new_err("OAuth2 token_url not configured")
-            })?;
+    /// Fetch OAuth2 token, using the current token's refresh token when it has one
+    fn fetch_oauth2_token(&mut self) -> PyResult<OAuth2Token> {
+        let Some(ref auth) = self.auth_config else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "OAuth2 authentication is not configured",
+            ));
+        };
 
-            let client_id = auth.get_credential("client_id").ok_or_else(|| {
-                pyo3::exceptions::PyValueError::new_err("OAuth2 client_id not configured")
-            })?;
+        let token_url = auth.get_credential("token_url").ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err("OAuth2 token_url not configured")
+        })?;
 
-            let mut params = HashMap::new();
-            params.insert("grant_type".to_string(), "client_credentials".to_string());
-            params.insert("client_id".to_string(), client_id);
+        if auth.get_credential("client_id").is_none() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "OAuth2 client_id not configured",
+            ));
+        }
 
-            if let Some(client_secret) = auth.get_credential("client_secret") {
-                params.insert("client_secret".to_string(), client_secret);
-            }
+        let previous_refresh = self
+            .oauth2_token
+            .read()
+            .map_err(|_| {
+                pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire OAuth2 token lock")
+            })?
+            .as_ref()
+            .and_then(|token| token.refresh_token.clone());
+        let params = auth.oauth2_token_params(previous_refresh.as_deref());
+
+        // Make token request
+        let (runtime, client) = (&self.runtime, &self.client);
+        let response = Python::with_gil(|py| {
+            py.allow_threads(|| {
+                runtime.block_on(async { client.post(&token_url).form(&params).send().await })
+            })
+        })
+        .map_err(|e| map_reqwest_error(&e))?;
 
-            if let Some(scopes) = auth.get_credential("scopes") {
-                params.insert("scope".to_string(), scopes);
-            }
+        if !response.status().is_success() {
+            return Err(crate::error::connection_error(format!(
+                "OAuth2 token request failed: {}",
+                response.status()
+            )));
+        }
 
-            // Make token request
-            let response = self
-                .runtime
-                .block_on(async { self.client.post(&token_url).form(&params).send().await })
+        let token_data: serde_json::Value =
+            Python::with_gil(|py| py.allow_threads(|| runtime.block_on(response.json())))
                 .map_err(|e| map_reqwest_error(&e))?;
 
-            if !response.status().is_success() {
-                return Err(pyo3::exceptions::PyConnectionError::new_err(format!(
-                    "OAuth2 token request failed: {}",
-                    response.status()
-                )));
-            }
+        // Keep the previous refresh token unless the server rotated it
+        let token = OAuth2Token::from_token_response(&token_data, previous_refresh)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
 
-            let token_data: serde_json::Value = self
-                .runtime
-                .block_on(async { response.json().await })
-                .map_err(|e| map_reqwest_error(&e))?;
+        *self.oauth2_token.write().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire OAuth2 token lock")
+        })? = Some(token.clone());
+        auth.notify_token_refresh(&token)?;
+        Ok(token)
+    }
 
-            // Parse token response
-            let access_token = token_data["access_token"]
-                .as_str()
-                .ok_or_else(|| {
-                    pyo3::exceptions::PyValueError::new_err("No access_token in OAuth2 response")
-                })?
-                .to_string();
+    /// Enabled interceptor middleware, read without holding the manager lock
+    fn interceptors(&self) -> PyResult<Vec<InterceptorMiddleware>> {
+        let middleware_manager = self.middleware_manager.read().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire middleware manager lock")
+        })?;
+        Ok(middleware_manager.interceptors())
+    }
 
-            let token_type = token_data["token_type"]
-                .as_str()
-                .unwrap_or("Bearer")
-                .to_string();
+    /// Share a cookie jar across this client's requests, or stop sending cookies with `None`
+    pub(crate) fn set_cookie_jar(&mut self, jar: Option<Arc<CookieJar>>) {
+        self.cookie_jar = jar;
+    }
 
-            let expires_in = token_data["expires_in"].as_u64();
-            let refresh_token = token_data["refresh_token"].as_str().map(|s| s.to_string());
-            let scope = token_data["scope"].as_str().map(|s| s.to_string());
+    /// Enabled cache middleware, read without holding the manager lock
+    fn caches(&self) -> PyResult<Vec<CacheMiddleware>> {
+        let middleware_manager = self.middleware_manager.read().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire middleware manager lock")
+        })?;
+        Ok(middleware_manager.caches())
+    }
 
-            let issued_at = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map_err(|_| {
-                    pyo3::exceptions::PyRuntimeError::new_err("Failed to get system time")
-                })?
-                .as_secs_f64();
-
-            let token = OAuth2Token {
-                access_token,
-                token_type,
-                expires_in,
-                refresh_token,
-                scope,
-                issued_at,
-            };
+    /// Enabled request signers, read without holding the manager lock
+    fn signers(&self) -> PyResult<Vec<HmacSigningMiddleware>> {
+        let middleware_manager = self.middleware_manager.read().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire middleware manager lock")
+        })?;
+        Ok(middleware_manager.signers())
+    }
 
-            let mut token_guard = self.oauth2_token.write().map_err(|_| {
-                pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire OAuth2 token lock")
-            })?;
-            *token_guard = Some(token);
+    /// Request and response hooks in priority order, read without holding the manager lock
+    fn hooks(&self) -> PyResult<Vec<Hook>> {
+        let middleware_manager = self.middleware_manager.read().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire middleware manager lock")
+        })?;
+        Ok(middleware_manager.hooks())
+    }
+
+    /// Wait for rate limit capacity, queueing when the config allows it. The limiters
+    /// are cloned out so neither the GIL nor the middleware lock is held while waiting
+    fn wait_for_rate_limit(&self, host: &str, path: &str) -> PyResult<()> {
+        let rate_limiters = self
+            .middleware_manager
+            .read()
+            .map_err(|_| {
+                pyo3::exceptions::PyRuntimeError::new_err(
+                    "Failed to acquire middleware manager lock",
+                )
+            })?
+            .rate_limiters();
+        if rate_limiters.is_empty() {
+            return Ok(());
+        }
+        Python::with_gil(|py| {
+            py.allow_threads(|| {
+                rate_limiters
+                    .iter()
+                    .try_for_each(|limiter| limiter.check_rate_limit(host, path))
+            })
+        })
+    }
+
+    /// A host with max_connections_per_host requests in flight blocks this one,
+    /// without holding the GIL so the requests using its slots can finish
+    fn acquire_host_slot(&self, host: &str) -> PyResult<HostSlot> {
+        if let Some(slot) = self.connection_pool.try_acquire_host_slot(host) {
+            return Ok(slot);
         }
+        let (runtime, pool) = (&self.runtime, &self.connection_pool);
+        let wait = self.pool_config.slot_wait();
+        Python::with_gil(|py| {
+            py.allow_threads(|| runtime.block_on(pool.acquire_host_slot(host, wait)))
+        })
+    }
+
+    /// Report a response to rate limit and metrics middleware
+    fn report_response(
+        &self,
+        host: &str,
+        path: &str,
+        response: &Response,
+        elapsed_time: f64,
+    ) -> PyResult<()> {
+        let middleware_manager = self.middleware_manager.read().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire middleware manager lock")
+        })?;
+        middleware_manager.record_rate_limit_response(
+            host,
+            path,
+            response.status_code,
+            &response.headers,
+        );
+        middleware_manager.update_metrics(elapsed_time, false);
+        Ok(())
+    }
 
+    /// Log a failed request through metrics and logging middleware
+    fn report_error(
+        &self,
+        method: &Method,
+        logged_url: &str,
+        error: &str,
+        elapsed_time: f64,
+    ) -> PyResult<()> {
+        let middleware_manager = self.middleware_manager.read().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err("Failed to acquire middleware manager lock")
+        })?;
+        middleware_manager.update_metrics(elapsed_time, true);
+        middleware_manager.log_error(method.as_str(), logged_url, error);
         Ok(())
     }
 
-    /// Apply middleware to request before sending
+    /// Run request hooks over the outgoing headers
     fn apply_middleware_to_request(
         &self,
-        request: RequestBuilder,
-        _url: &str,
-        _method: &Method,
-    ) -> PyResult<RequestBuilder> {
-        // The headers middleware is already applied in execute_request_internal
-        // This method is reserved for future middleware types that need to modify the request directly
-        Ok(request)
+        method: &Method,
+        url: &str,
+        logged_url: &str,
+        headers: &mut HashMap<String, String>,
+    ) -> PyResult<()> {
+        let hooks = self.hooks()?;
+        if hooks.is_empty() {
+            return Ok(());
+        }
+        Python::with_gil(|py| {
+            for hook in &hooks {
+                hook.on_request(py, method.as_str(), url, logged_url, headers)?;
+            }
+            Ok(())
+        })
     }
 
-    /// Process response through middleware
+    /// Process response through response hooks
     fn apply_middleware_to_response(
         &self,
-        _response: &mut Response,
-        _elapsed_time: f64,
-    ) -> PyResult<()> {
-        // Response middleware processing is already handled in execute_request_internal
-        // through the MiddlewareManager public methods
-        Ok(())
+        method: &Method,
+        logged_url: &str,
+        response: Response,
+        elapsed_time: f64,
+    ) -> PyResult<Response> {
+        let hooks = self.hooks()?;
+        if hooks.is_empty() {
+            return Ok(response);
+        }
+        Python::with_gil(|py| {
+            hooks.iter().try_fold(response, |response, hook| {
+                hook.on_response(
+                    py,
+                    method.as_str(),
+                    logged_url,
+                    response,
+                    elapsed_time * 1000.0,
+                )
+            })
+        })
+    }
+
+    /// Report a failed request to error interceptors
+    fn apply_middleware_to_error(&self, method: &Method, url: &str, error: &PyErr) -> PyResult<()> {
+        let interceptors = self.interceptors()?;
+        if interceptors.is_empty() {
+            return Ok(());
+        }
+        Python::with_gil(|py| {
+            for interceptor in &interceptors {
+                interceptor.intercept_error(py, method.as_str(), url, error)?;
+            }
+            Ok(())
+        })
     }
 
-    /// Prepare request body
+    /// Prepare request body, adding its Content-Type to this request's headers only
     fn prepare_body(
         &self,
         json: Option<&PyAny>,
         data: Option<HashMap<String, String>>,
         files: Option<HashMap<String, Vec<u8>>>,
-    ) -> PyResult<Option<Vec<u8>>> {
-        if let Some(json) = json {
-            // Set JSON content type header
-            if let Ok(mut headers) = self.headers.write() {
-                headers.insert("Content-Type".to_string(), "application/json".to_string());
-            }
+        headers: Option<HashMap<String, String>>,
+    ) -> PyResult<(Option<Vec<u8>>, Option<HashMap<String, String>>)> {
+        let (body, content_type) = if let Some(json) = json {
             let value: Value = pythonize::depythonize(json)?;
             let body = serde_json::to_vec(&value).map_err(|e| {
                 pyo3::exceptions::PyValueError::new_err(format!("JSON serialization error: {}", e))
             })?;
-            Ok(Some(body))
-        } else if let Some(data) = data {
-            // Set form data content type header
-            if let Ok(mut headers) = self.headers.write() {
-                headers.insert(
-                    "Content-Type".to_string(),
-                    "application/x-www-form-urlencoded".to_string(),
-                );
-            }
-            let body = serde_urlencoded::to_string(&data)
-                .map_err(|e| {
-                    pyo3::exceptions::PyValueError::new_err(format!("Form encoding error: {}", e))
-                })?
-                .into_bytes();
-            Ok(Some(body))
+            (body, "application/json".to_string())
         } else if let Some(files) = files {
-            // Implement basic multipart form data
-            use std::fmt::Write;
+            // Build the multipart body as raw bytes so binary files survive intact
             let boundary = format!("----ultrafast_client_boundary_{}", rand::random::<u64>());
-            let mut body = String::new();
+            let mut body = Vec::new();
 
             // Add form data if present
-            if let Some(data) = data {
-                for (key, value) in data {
-                    writeln!(&mut body, "--{}", boundary).unwrap();
-                    writeln!(
-                        &mut body,
-                        "Content-Disposition: form-data; name=\"{}\"",
-                        key
+            for (key, value) in data.unwrap_or_default() {
+                body.extend_from_slice(
+                    format!(
+                        "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n",
+                        boundary, key
                     )
-                    .unwrap();
-                    writeln!(&mut body).unwrap();
-                    writeln!(&mut body, "{}", value).unwrap();
-                }
+                    .as_bytes(),
+                );
+                body.extend_from_slice(value.as_bytes());
+                body.extend_from_slice(b"\r\n");
             }
 
             // Add files
-            for (name, content) in files {
-                writeln!(&mut body, "--{}", boundary).unwrap();
-                writeln!(
-                    &mut body,
-                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"",
-                    name, name
-                )
-                .unwrap();
-                writeln!(&mut body, "Content-Type: application/octet-stream").unwrap();
-                writeln!(&mut body).unwrap();
-                body.push_str(&String::from_utf8_lossy(&content));
-                writeln!(&mut body).unwrap();
-            }
-
-            writeln!(&mut body, "--{}--", boundary).unwrap();
-
-            // Set content type header for multipart
-            if let Ok(mut headers) = self.headers.write() {
-                headers.insert(
-                    "Content-Type".to_string(),
-                    format!("multipart/form-data; boundary={}", boundary),
+            for (filename, content) in files {
+                body.extend_from_slice(
+                    format!(
+                        "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+                        boundary,
+                        filename,
+                        filename,
+                        crate::multipart::mime_type_for(&filename)
+                    )
+                    .as_bytes(),
                 );
+                body.extend_from_slice(&content);
+                body.extend_from_slice(b"\r\n");
             }
 
-            Ok(Some(body.into_bytes()))
+            body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+            (body, format!("multipart/form-data; boundary={}", boundary))
+        } else if let Some(data) = data {
+            let body = serde_urlencoded::to_string(&data)
+                .map_err(|e| {
+                    pyo3::exceptions::PyValueError::new_err(format!("Form encoding error: {}", e))
+                })?
+                .into_bytes();
+            (body, "application/x-www-form-urlencoded".to_string())
         } else {
-            Ok(None)
-        }
+            return Ok((None, headers));
+        };
+
+        // An explicit per-request Content-Type wins
+        let mut headers = headers.unwrap_or_default();
+        if header_value(&headers, "Content-Type").is_none() {
+            headers.insert("Content-Type".to_string(), content_type);
+        }
+        Ok((Some(body), Some(headers)))
     }
 
     /// Process request body with compression if enabled
-    fn process_request_body(&self, body: Vec<u8>) -> PyResult<(Vec<u8>, Option<String>)> {
-        let content_type = match self.headers.read() {
-            Ok(headers) => headers
-                .get("Content-Type")
-                .unwrap_or(&"application/octet-stream".to_string())
-                .clone(),
-            Err(_) => "application/octet-stream".to_string(),
-        };
-
+    fn process_request_body(
+        &self,
+        client: &Client,
+        body: Vec<u8>,
+        content_type: &str,
+        full_url: &str,
+        host: &str,
+    ) -> PyResult<(Vec<u8>, Option<String>)> {
         if self
             .compression_config
-            .should_compress_request(body.len(), &content_type)
+            .should_compress_request(body.len(), content_type)
         {
-            if let Some(algorithm) = self.compression_config.compression_algorithms.first() {
+            let algorithm = if self.compression_config.negotiate_request_encoding {
+                let accepted = self.accepted_request_encodings(client, full_url, host);
+                self.compression_config.select_request_algorithm(accepted)
+            } else {
+                self.compression_config
+                    .compression_algorithms
+                    .first()
+                    .cloned()
+            };
+
+            if let Some(algorithm) = algorithm {
                 match self
                     .compression_config
-                    .compress_request_body(&body, algorithm)
+                    .compress_request_body(&body, &algorithm)
                 {
                     Ok(compressed_body) => {
-                        let encoding = match algorithm.as_str() {
-                            "gzip" => "gzip",
-                            "deflate" => "deflate",
-                            "brotli" => "br",
-                            _ => algorithm.as_str(),
-                        };
+                        let encoding = content_encoding_name(&algorithm);
                         Ok((compressed_body, Some(encoding.to_string())))
                     }
                     Err(_) => Ok((body, None)),
@@ -1558,6 +2555,48 @@ impl HttpClient {
         }
     }
 
+    /// Request content-codings accepted by a host, sending an OPTIONS preflight through
+    /// `client` on first use. A missing Accept-Encoding is cached as "nothing accepted";
+    /// a failed preflight is too, but only for `PREFLIGHT_FAILURE_TTL`.
+    fn accepted_request_encodings(
+        &self,
+        client: &Client,
+        full_url: &str,
+        host: &str,
+    ) -> Vec<String> {
+        if let Ok(cache) = self.request_encodings.read() {
+            match cache.get(host) {
+                Some((_, Some(expires))) if *expires <= Instant::now() => {}
+                Some((encodings, _)) => return encodings.clone(),
+                None => {}
+            }
+        }
+
+        let runtime = &self.runtime;
+        let preflight = Python::with_gil(|py| {
+            py.allow_threads(|| {
+                runtime.block_on(async { client.request(Method::OPTIONS, full_url).send().await })
+            })
+        });
+        let (encodings, expires) = match preflight {
+            Ok(response) => (
+                response
+                    .headers()
+                    .get(reqwest::header::ACCEPT_ENCODING)
+                    .and_then(|value| value.to_str().ok())
+                    .map(parse_accept_encoding)
+                    .unwrap_or_default(),
+                None,
+            ),
+            Err(_) => (Vec::new(), Some(Instant::now() + PREFLIGHT_FAILURE_TTL)),
+        };
+
+        if let Ok(mut cache) = self.request_encodings.write() {
+            cache.insert(host.to_string(), (encodings.clone(), expires));
+        }
+        encodings
+    }
+
     /// Build full URL
     fn build_url(&self, url: &str) -> PyResult<String> {
         if url.starts_with("http://") || url.starts_with("https://") {
@@ -1575,104 +2614,7 @@ impl HttpClient {
 
     /// Rebuild client with new configuration
     fn rebuild_client(&mut self) -> PyResult<()> {
-        let mut client_builder = Client::builder()
-            .pool_max_idle_per_host(self.pool_config.max_idle_per_host)
-            .pool_idle_timeout(Duration::from_secs_f64(self.pool_config.idle_timeout));
-
-        if let Some(connect_timeout) = self.timeout_config.connect_timeout {
-            client_builder =
-                client_builder.connect_timeout(Duration::from_secs_f64(connect_timeout));
-        }
-
-        // Configure SSL/TLS
-        if !self.ssl_config.verify {
-            client_builder = client_builder.danger_accept_invalid_certs(true);
-        }
-
-        // Apply additional SSL configuration
-        if let Some(cert_path) = &self.ssl_config.cert_file {
-            if let Some(key_path) = &self.ssl_config.key_file {
-                // Load client certificate and key
-                match std::fs::read(cert_path) {
-                    Ok(cert_data) => {
-                        match std::fs::read(key_path) {
-                            Ok(key_data) => {
-                                // Use from_pkcs8_pem which is the correct method
-                                if let Ok(identity) =
-                                    reqwest::Identity::from_pkcs8_pem(&cert_data, &key_data)
-                                {
-                                    client_builder = client_builder.identity(identity);
-                                }
-                            }
-                            Err(_) => {} // Ignore key loading errors
-                        }
-                    }
-                    Err(_) => {} // Ignore cert loading errors
-                }
-            }
-        }
-
-        // Configure CA bundle if specified
-        if let Some(ca_bundle) = &self.ssl_config.ca_bundle {
-            match std::fs::read(ca_bundle) {
-                Ok(ca_data) => {
-                    if let Ok(cert) = reqwest::Certificate::from_pem(&ca_data) {
-                        client_builder = client_builder.add_root_certificate(cert);
-                    }
-                }
-                Err(_) => {} // Ignore CA bundle errors
-            }
-        }
-
-        // Configure proxy if specified
-        if let Some(proxy_cfg) = &self.proxy_config {
-            if let Ok(proxy) = reqwest::Proxy::all(&proxy_cfg.url) {
-                let mut proxy = proxy;
-
-                // Add authentication if specified
-                if let (Some(username), Some(password)) = (&proxy_cfg.username, &proxy_cfg.password)
-                {
-                    proxy = proxy.basic_auth(username, password);
-                }
-
-                client_builder = client_builder.proxy(proxy);
-            }
-        }
-
-        // Configure compression
-        if self.compression_config.enable_response_compression {
-            client_builder = client_builder.gzip(true);
-
-            if self.compression_config.supports_algorithm("brotli") {
-                client_builder = client_builder.brotli(true);
-            }
-        }
-
-        // Configure HTTP/2 settings
-        if self.protocol_config.is_http2_enabled() {
-            if self.protocol_config.enable_http2_prior_knowledge {
-                client_builder = client_builder.http2_prior_knowledge();
-            }
-
-            // Configure HTTP/2 keep-alive if specified
-            if let Some(interval) = self.protocol_config.http2_settings.keep_alive_interval {
-                client_builder =
-                    client_builder.http2_keep_alive_interval(Some(Duration::from_secs(interval)));
-            }
-
-            if let Some(timeout) = self.protocol_config.http2_settings.keep_alive_timeout {
-                client_builder =
-                    client_builder.http2_keep_alive_timeout(Duration::from_secs(timeout));
-            }
-        }
-
-        // Configure HTTP/3 via Quiche
-        if self.protocol_config.is_http3_enabled() {
-            // HTTP/3 support requires unstable features
-            // This will only work if compiled with RUSTFLAGS="--cfg reqwest_unstable"
-            // client_builder = client_builder.http3_prior_knowledge();
-        }
-
+        let client_builder = self.client_builder()?;
         self.client = self
             .runtime
             .block_on(async { client_builder.build() })
@@ -1682,6 +2624,8 @@ impl HttpClient {
                     e
                 ))
             })?;
+        self.rebuild_host_clients()?;
+        self.proxy_clients.clear();
 
         // Initialize rate limiting middleware if configured
         if let Some(rate_limit_cfg) = &self.rate_limit_config {
@@ -1712,6 +2656,90 @@ impl HttpClient {
 
         Ok(())
     }
+
+    /// Client builder reflecting the current configuration
+    fn client_builder(&self) -> PyResult<reqwest::ClientBuilder> {
+        self.client_builder_with_proxy(self.proxy_config.as_ref())
+    }
+
+    /// Client builder reflecting the current configuration, with `proxy` in place of the client's
+    fn client_builder_with_proxy(
+        &self,
+        proxy: Option<&ProxyConfig>,
+    ) -> PyResult<reqwest::ClientBuilder> {
+        self.client_settings(proxy).builder()
+    }
+
+    /// Settings the current configuration builds clients from, with `proxy` in place of the client's
+    fn client_settings<'a>(&'a self, proxy: Option<&'a ProxyConfig>) -> ClientSettings<'a> {
+        ClientSettings {
+            timeout: self.timeout,
+            timeout_config: &self.timeout_config,
+            pool_config: &self.pool_config,
+            ssl_config: &self.ssl_config,
+            proxy_config: proxy,
+            compression_config: &self.compression_config,
+            protocol_config: &self.protocol_config,
+            dns_cache: self.dns_cache.as_ref(),
+            unix_socket: self.unix_socket.as_deref(),
+            ip_version: self.ip_version,
+            client_cert: None,
+        }
+    }
+
+    /// Build a client that presents the given certificate, otherwise configured like the main client
+    fn build_host_client(&self, cert_pem: &str, key_pem: &str) -> PyResult<Client> {
+        let client_builder = ClientSettings {
+            client_cert: Some((cert_pem, key_pem)),
+            ..self.client_settings(self.proxy_config.as_ref())
+        }
+        .builder()?;
+        self.runtime
+            .block_on(async { client_builder.build() })
+            .map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to build HTTP client: {}",
+                    e
+                ))
+            })
+    }
+
+    /// Client for a request to `host`: a per-request proxy's client, else the
+    /// host's client-certificate client, else the shared one.
+    ///
+    /// Proxied clients are built on first use and kept until the configuration
+    /// changes; they don't present per-host client certificates.
+    fn select_client(&mut self, host: &str, proxy: Option<&ProxyConfig>) -> PyResult<Client> {
+        let Some(proxy) = proxy else {
+            return Ok(self.host_clients.get(host).unwrap_or(&self.client).clone());
+        };
+        if let Some(client) = self.proxy_clients.get(proxy) {
+            return Ok(client.clone());
+        }
+
+        let client_builder = self.client_builder_with_proxy(Some(proxy))?;
+        let client = self
+            .runtime
+            .block_on(async { client_builder.build() })
+            .map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to build HTTP client: {}",
+                    e
+                ))
+            })?;
+        self.proxy_clients.insert(proxy.clone(), client.clone());
+        Ok(client)
+    }
+
+    /// Recreate per-host clients after a configuration change
+    fn rebuild_host_clients(&mut self) -> PyResult<()> {
+        let mut host_clients = HashMap::new();
+        for (host, (cert_pem, key_pem)) in &self.client_certs {
+            host_clients.insert(host.clone(), self.build_host_client(cert_pem, key_pem)?);
+        }
+        self.host_clients = host_clients;
+        Ok(())
+    }
 }
 
 impl Drop for HttpClient {
@@ -1730,6 +2758,584 @@ impl Drop for HttpClient {
     }
 }
 
+/// Merge default query parameters with per-request ones.
+/// Request params win; the ApiKeyQuery parameter is left to the auth layer so it is sent once.
+pub(crate) fn merge_query_params(
+    defaults: &HashMap<String, String>,
+    params: Option<HashMap<String, String>>,
+    auth_config: Option<&AuthConfig>,
+) -> Option<HashMap<String, String>> {
+    if defaults.is_empty() && params.is_none() {
+        return None;
+    }
+
+    let mut merged = defaults.clone();
+    if let Some(params) = params {
+        merged.extend(params);
+    }
+
+    if let Some(auth) = auth_config {
+        if matches!(auth.auth_type, AuthType::ApiKeyQuery) {
+            if let Some(param_name) = auth.get_credential("param_name") {
+                merged.remove(&param_name);
+            }
+        }
+    }
+
+    Some(merged)
+}
+
+/// URL a cached response is stored under, with query parameters in a stable order
+pub(crate) fn cache_url(full_url: &str, params: Option<&HashMap<String, String>>) -> String {
+    let mut params: Vec<(&String, &String)> = params.into_iter().flatten().collect();
+    if params.is_empty() {
+        return full_url.to_string();
+    }
+    params.sort();
+    reqwest::Url::parse_with_params(full_url, params)
+        .map(|url| url.to_string())
+        .unwrap_or_else(|_| full_url.to_string())
+}
+
+/// Why a request is being retried, as reported to the `on_retry` callback
+pub(crate) enum RetryReason {
+    Status(u16),
+    Error(String),
+}
+
+/// Check that an `on_retry` callback is callable
+pub(crate) fn validate_on_retry(
+    py: Python,
+    callback: Option<PyObject>,
+) -> PyResult<Option<PyObject>> {
+    if let Some(ref callback) = callback {
+        if !callback.as_ref(py).is_callable() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "on_retry callback must be callable",
+            ));
+        }
+    }
+    Ok(callback)
+}
+
+/// Invoke an `on_retry(attempt, reason, next_delay)` callback
+pub(crate) fn invoke_on_retry(
+    callback: &PyObject,
+    attempt: u32,
+    reason: RetryReason,
+    next_delay: f64,
+) -> PyResult<()> {
+    Python::with_gil(|py| {
+        let reason = match reason {
+            RetryReason::Status(status_code) => status_code.into_py(py),
+            RetryReason::Error(message) => message.into_py(py),
+        };
+        callback.call1(py, (attempt, reason, next_delay))?;
+        Ok(())
+    })
+}
+
+/// Per-call overrides carried through the retry loop
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RequestOptions {
+    /// Unix timestamp the whole call, retries included, must finish by
+    pub deadline: Option<f64>,
+    /// Timeout for each attempt, overriding the client timeout
+    pub timeout: Option<f64>,
+    /// Whether to follow redirects, overriding the client setting
+    pub allow_redirects: Option<bool>,
+    /// Proxy for this call only, used instead of the client's
+    pub proxy: Option<ProxyConfig>,
+    /// Send `Expect: 100-continue` and hold the body back for a moment
+    pub expect_continue: bool,
+    /// Leave the body on the connection whatever the client's body mode
+    pub stream: bool,
+    /// Called with `(bytes_sent, total_bytes)` as the request body is sent
+    pub upload_progress: Option<PyObject>,
+}
+
+impl RequestOptions {
+    pub(crate) fn new(
+        deadline: Option<f64>,
+        timeout: Option<f64>,
+        allow_redirects: Option<bool>,
+    ) -> PyResult<Self> {
+        if let Some(timeout) = timeout {
+            if !timeout.is_finite() || timeout <= 0.0 {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "timeout must be a positive number of seconds",
+                ));
+            }
+        }
+        Ok(RequestOptions {
+            deadline,
+            timeout,
+            allow_redirects,
+            proxy: None,
+            expect_continue: false,
+            stream: false,
+            upload_progress: None,
+        })
+    }
+
+    /// Send this call through `proxy` instead of the client's proxy
+    pub(crate) fn with_proxy(mut self, proxy: Option<ProxyConfig>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Ask the server to accept the request headers before the body is sent
+    pub(crate) fn with_expect_continue(mut self, expect_continue: bool) -> Self {
+        self.expect_continue = expect_continue;
+        self
+    }
+
+    /// Report upload progress of the request body to `progress_callback`
+    pub(crate) fn with_upload_progress(mut self, progress_callback: Option<PyObject>) -> Self {
+        self.upload_progress = progress_callback;
+        self
+    }
+
+    /// Read the response body lazily, as it's consumed
+    pub(crate) fn with_stream(mut self) -> Self {
+        self.stream = true;
+        self
+    }
+
+    /// Timeout for a single attempt: the override, capped by the remaining deadline
+    pub(crate) fn attempt_timeout(&self) -> PyResult<Option<Duration>> {
+        let remaining = deadline_remaining(self.deadline)?;
+        let timeout = self.timeout.map(Duration::from_secs_f64);
+        Ok(match (remaining, timeout) {
+            (Some(remaining), Some(timeout)) => Some(remaining.min(timeout)),
+            (remaining, timeout) => remaining.or(timeout),
+        })
+    }
+}
+
+/// Where a redirect response sends the next request
+pub(crate) struct RedirectHop {
+    pub url: reqwest::Url,
+    pub method: Method,
+    /// Only 307/308 re-send the request body
+    pub keep_body: bool,
+    /// Same scheme, host and port as the redirecting response
+    pub same_origin: bool,
+}
+
+impl RedirectHop {
+    /// Work out the next hop of a 3xx response, if it has a usable Location
+    pub(crate) fn from_response(response: &reqwest::Response, method: &Method) -> Option<Self> {
+        let status = response.status().as_u16();
+        if !matches!(status, 301 | 302 | 303 | 307 | 308) {
+            return None;
+        }
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)?
+            .to_str()
+            .ok()?;
+        let url = response.url().join(location).ok()?;
+
+        // Mirror browser behaviour: 303, and 301/302 after a POST, switch to GET
+        let method = match status {
+            303 if *method != Method::HEAD => Method::GET,
+            301 | 302 if *method == Method::POST => Method::GET,
+            _ => method.clone(),
+        };
+        let same_origin = url.origin() == response.url().origin();
+
+        Some(RedirectHop {
+            url,
+            method,
+            keep_body: matches!(status, 307 | 308),
+            same_origin,
+        })
+    }
+
+    /// Re-send a request unchanged, e.g. to answer an authentication challenge
+    pub(crate) fn repeat(url: &reqwest::Url, method: &Method) -> Self {
+        RedirectHop {
+            url: url.clone(),
+            method: method.clone(),
+            keep_body: true,
+            same_origin: true,
+        }
+    }
+
+    /// Whether a request header is carried over to this hop
+    pub(crate) fn keeps_header(&self, name: &str) -> bool {
+        match name.to_ascii_lowercase().as_str() {
+            "authorization" | "proxy-authorization" | "cookie" => self.same_origin,
+            "content-type" | "content-length" | "content-encoding" => self.keep_body,
+            "host" => false,
+            _ => true,
+        }
+    }
+}
+
+/// Error raised once a request exceeds `max_redirects`
+pub(crate) fn too_many_redirects_error(max_redirects: usize, url: &str) -> PyErr {
+    crate::error::TooManyRedirects::new_err(format!(
+        "Exceeded {} redirects while requesting {}",
+        max_redirects, url
+    ))
+}
+
+/// Parse a caller-supplied HTTP method name
+pub(crate) fn parse_method(method: &str) -> PyResult<Method> {
+    Method::from_bytes(method.to_ascii_uppercase().as_bytes()).map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err(format!("Invalid HTTP method '{}'", method))
+    })
+}
+
+/// Bodies are handed to the connection in chunks of this size when `write_timeout` is set
+const WRITE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// How far a request body has been written
+#[derive(Default)]
+struct WriteProgress {
+    // When the connection last took a chunk; None until it takes the first
+    last_chunk: parking_lot::Mutex<Option<Instant>>,
+    finished: std::sync::atomic::AtomicBool,
+}
+
+/// Send a HEAD to each `(client, url)` so its connection, TLS handshake included,
+/// is left idle in that client's pool. Any response counts as a successful warmup.
+pub(crate) async fn warm_connections(
+    targets: Vec<(Client, String)>,
+    timeout: Option<Duration>,
+) -> HashMap<String, usize> {
+    let attempts = targets.into_iter().map(|(client, url)| async move {
+        let mut request = client.head(url);
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+        request.send().await.is_ok()
+    });
+    let results = futures_util::future::join_all(attempts).await;
+    let succeeded = results.iter().filter(|ok| **ok).count();
+    HashMap::from([
+        ("succeeded".to_string(), succeeded),
+        ("failed".to_string(), results.len() - succeeded),
+    ])
+}
+
+/// Execute `request`, failing with TimeoutError if the connection stops accepting
+/// its body for longer than `timeouts.write_timeout` seconds.
+///
+/// Only buffered bodies larger than one chunk are watched. The outer error is the
+/// write timeout; the inner result is the request's own outcome.
+///
+/// `options.upload_progress` is told about each chunk of a buffered body as the
+/// connection takes it; an exception it raises aborts the upload and is returned
+/// as the outer error.
+///
+/// With `expect_continue`, a buffered body is sent with `Expect: 100-continue` and
+/// held back for `timeouts.expect_continue_wait()`. A final response the server sends
+/// in that window (e.g. 401 or 413) is returned without uploading the body. The
+/// HTTP stack doesn't surface the interim `100 Continue`, so a server that accepts
+/// still waits out the window. The header is defined for HTTP/1.1; HTTP/2 servers
+/// may ignore it, though an early response is honoured the same way.
+///
+/// A chunked body is a one-shot stream, so the request can't be sent twice. Callers
+/// rebuild each 307/308 hop and Digest/NTLM re-send from the body bytes they kept.
+pub(crate) async fn execute_with_write_timeout(
+    client: &Client,
+    mut request: reqwest::Request,
+    timeouts: &TimeoutConfig,
+    options: &RequestOptions,
+) -> PyResult<reqwest::Result<reqwest::Response>> {
+    let body_len = request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .map(|body| body.len());
+    let write_timeout = timeouts
+        .write_timeout
+        .filter(|timeout| *timeout > 0.0 && timeout.is_finite())
+        .map(Duration::from_secs_f64);
+    let non_empty = body_len.is_some_and(|len| len > 0);
+    let expect_continue = options.expect_continue && non_empty;
+    let upload_progress = match &options.upload_progress {
+        Some(callback) if non_empty => Some(crate::body_stream::UploadProgress::new(
+            callback.clone(),
+            body_len.map(|len| len as u64),
+        )),
+        _ => None,
+    };
+    let chunked = match body_len {
+        Some(len) => {
+            expect_continue
+                || upload_progress.is_some()
+                || (write_timeout.is_some() && len > WRITE_CHUNK_SIZE)
+        }
+        None => false,
+    };
+    // Only a body that is handed over in chunks needs its own copy
+    let body = match request.body().and_then(|body| body.as_bytes()) {
+        Some(body) if chunked => bytes::Bytes::copy_from_slice(body),
+        _ => return Ok(client.execute(request).await),
+    };
+
+    // The connection asks for the next chunk only once it has written the last one
+    let progress = Arc::new(WriteProgress::default());
+    let callback_error = crate::body_stream::StreamError::default();
+    let recorder = callback_error.clone();
+    let chunks: Vec<bytes::Bytes> = (0..body.len())
+        .step_by(WRITE_CHUNK_SIZE)
+        .map(|start| body.slice(start..(start + WRITE_CHUNK_SIZE).min(body.len())))
+        .collect();
+    let chunk_count = chunks.len();
+    let stream_progress = Arc::clone(&progress);
+    let stream = futures_util::StreamExt::map(
+        futures_util::stream::iter(chunks.into_iter().enumerate()),
+        move |(index, chunk)| {
+            *stream_progress.last_chunk.lock() = Some(Instant::now());
+            if let Some(Err(e)) = upload_progress.as_ref().map(|p| p.advance(chunk.len())) {
+                return Err(recorder.record(e));
+            }
+            if index + 1 == chunk_count {
+                stream_progress
+                    .finished
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+            Ok(chunk)
+        },
+    );
+    request.headers_mut().insert(
+        reqwest::header::CONTENT_LENGTH,
+        reqwest::header::HeaderValue::from(body.len()),
+    );
+    if expect_continue {
+        request.headers_mut().insert(
+            reqwest::header::EXPECT,
+            reqwest::header::HeaderValue::from_static("100-continue"),
+        );
+        // Withhold the first chunk so the server can answer the headers alone
+        let hold = futures_util::StreamExt::filter_map(
+            futures_util::stream::once(tokio::time::sleep(timeouts.expect_continue_wait())),
+            |_| futures_util::future::ready(None::<Result<bytes::Bytes, std::io::Error>>),
+        );
+        *request.body_mut() = Some(reqwest::Body::wrap_stream(futures_util::StreamExt::chain(
+            hold, stream,
+        )));
+    } else {
+        *request.body_mut() = Some(reqwest::Body::wrap_stream(stream));
+    }
+
+    let execute = async {
+        let sent = client.execute(request).await;
+        match callback_error.take() {
+            Some(err) if sent.is_err() => Err(err),
+            _ => Ok(sent),
+        }
+    };
+    let Some(write_timeout) = write_timeout else {
+        return execute.await;
+    };
+    let stalled = async {
+        loop {
+            if progress.finished.load(std::sync::atomic::Ordering::Relaxed) {
+                return std::future::pending::<()>().await;
+            }
+            let last_chunk = *progress.last_chunk.lock();
+            let wait = match last_chunk {
+                Some(at) => match write_timeout.checked_sub(at.elapsed()) {
+                    Some(left) => left,
+                    None => return,
+                },
+                // Connecting is governed by connect_timeout
+                None => write_timeout,
+            };
+            tokio::time::sleep(wait).await;
+        }
+    };
+
+    tokio::select! {
+        sent = execute => sent,
+        _ = stalled => Err(crate::error::timeout_error(format!(
+            "Write timed out: request body stalled for more than {:.1}s",
+            write_timeout.as_secs_f64()
+        ))),
+    }
+}
+
+/// Configuration a reqwest client is built from, shared by both clients'
+/// constructors and their rebuilds so every client they make agrees
+pub(crate) struct ClientSettings<'a> {
+    pub timeout: f64,
+    pub timeout_config: &'a TimeoutConfig,
+    pub pool_config: &'a PoolConfig,
+    pub ssl_config: &'a SSLConfig,
+    pub proxy_config: Option<&'a ProxyConfig>,
+    pub compression_config: &'a CompressionConfig,
+    pub protocol_config: &'a ProtocolConfig,
+    pub dns_cache: Option<&'a Arc<DnsCache>>,
+    pub unix_socket: Option<&'a str>,
+    pub ip_version: IpVersion,
+    // Certificate and key PEM presented instead of the SSL config's identity
+    pub client_cert: Option<(&'a str, &'a str)>,
+}
+
+impl ClientSettings<'_> {
+    /// Client builder for these settings
+    pub(crate) fn builder(&self) -> PyResult<reqwest::ClientBuilder> {
+        let mut client_builder = Client::builder()
+            // Redirects are followed by the client so they can be disabled per request
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(Duration::from_secs_f64(self.timeout))
+            .pool_max_idle_per_host(self.pool_config.max_idle_per_host)
+            .pool_idle_timeout(Duration::from_secs_f64(self.pool_config.idle_timeout));
+
+        // Configure timeouts
+        if let Some(connect_timeout) = self.timeout_config.connect_timeout {
+            client_builder =
+                client_builder.connect_timeout(Duration::from_secs_f64(connect_timeout));
+        }
+        if let Some(read_timeout) = self.timeout_config.read_timeout {
+            client_builder = client_builder.read_timeout(Duration::from_secs_f64(read_timeout));
+        }
+        client_builder = client_builder
+            .dns_resolver(crate::dns_cache::resolver(self.dns_cache, self.ip_version))
+            .connector_layer(crate::timing::ConnectTimingLayer);
+        client_builder = apply_unix_socket(client_builder, self.unix_socket)?;
+
+        // Configure SSL/TLS
+        if !self.ssl_config.verify {
+            client_builder = client_builder.danger_accept_invalid_certs(true);
+        }
+        if let Some(version) = self.ssl_config.tls_version()? {
+            client_builder = client_builder.min_tls_version(version);
+        }
+
+        // Apply additional SSL configuration
+        let identity = match self.client_cert {
+            Some((cert_pem, key_pem)) => Some(
+                reqwest::Identity::from_pkcs8_pem(cert_pem.as_bytes(), key_pem.as_bytes())
+                    .map_err(|e| {
+                        pyo3::exceptions::PyValueError::new_err(format!(
+                            "Invalid client certificate or key: {}",
+                            e
+                        ))
+                    })?,
+            ),
+            None => self.ssl_config.identity()?,
+        };
+        if let Some(identity) = identity {
+            client_builder = client_builder.identity(identity);
+        }
+
+        // Configure CA bundle if specified
+        for cert in self.ssl_config.root_certificates()? {
+            client_builder = client_builder.add_root_certificate(cert);
+        }
+
+        // Pinned public keys are checked by our own certificate verifier
+        if let Some(tls) = crate::pinning::pinned_tls_config(
+            self.ssl_config,
+            self.protocol_config.is_http2_enabled(),
+            self.client_cert,
+        )? {
+            client_builder = client_builder.use_preconfigured_tls(tls);
+        }
+
+        // Configure proxy if specified
+        if let Some(proxy_cfg) = self.proxy_config {
+            client_builder = client_builder.proxy(proxy_cfg.to_reqwest_proxy()?);
+        }
+
+        // Configure compression
+        if self.compression_config.enable_response_compression {
+            // reqwest enables gzip/deflate by default, we just need to ensure it's not disabled
+            client_builder = client_builder.gzip(true);
+
+            // Enable brotli if supported
+            if self.compression_config.supports_algorithm("brotli") {
+                client_builder = client_builder.brotli(true);
+            }
+
+            // Enable zstd only if listed, since reqwest otherwise decodes it by default
+            client_builder =
+                client_builder.zstd(self.compression_config.supports_algorithm("zstd"));
+        } else {
+            // Hand bodies over as sent, for Response.decompressed()
+            client_builder = client_builder.no_gzip().no_deflate().no_brotli().no_zstd();
+        }
+
+        // Restrict to the preferred HTTP version and apply HTTP/2 settings
+        client_builder = self.protocol_config.apply_to(client_builder);
+
+        // Configure HTTP/3 via Quiche
+        if self.protocol_config.is_http3_enabled() {
+            // HTTP/3 support requires unstable features
+            // This will only work if compiled with RUSTFLAGS="--cfg reqwest_unstable"
+            // client_builder = client_builder.http3_prior_knowledge();
+        }
+
+        Ok(client_builder)
+    }
+}
+
+/// Route every connection through a Unix domain socket, keeping URLs for the Host header
+pub(crate) fn apply_unix_socket(
+    builder: reqwest::ClientBuilder,
+    unix_socket: Option<&str>,
+) -> PyResult<reqwest::ClientBuilder> {
+    let path = match unix_socket {
+        Some(path) => path,
+        None => return Ok(builder),
+    };
+    #[cfg(unix)]
+    {
+        Ok(builder.unix_socket(path))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = builder;
+        Err(pyo3::exceptions::PyNotImplementedError::new_err(format!(
+            "Unix domain sockets are not supported on this platform (unix_socket={})",
+            path
+        )))
+    }
+}
+
+/// Time left before an absolute unix-timestamp deadline.
+/// Returns a TimeoutError once the deadline has passed.
+pub(crate) fn deadline_remaining(deadline: Option<f64>) -> PyResult<Option<Duration>> {
+    let deadline = match deadline {
+        Some(deadline) => deadline,
+        None => return Ok(None),
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64();
+    if deadline <= now {
+        return Err(deadline_exceeded_error());
+    }
+    Ok(Some(Duration::from_secs_f64(deadline - now)))
+}
+
+/// Error raised when a request deadline has passed
+pub(crate) fn deadline_exceeded_error() -> PyErr {
+    crate::error::timeout_error("Request deadline exceeded".to_string())
+}
+
+/// Error raised for requests on a closed client
+pub(crate) fn client_closed_error() -> PyErr {
+    pyo3::exceptions::PyRuntimeError::new_err("Client is closed")
+}
+
+/// Map a reqwest error, reporting timeouts caused by the deadline as deadline exceeded
+pub(crate) fn map_deadline_error(error: &reqwest::Error, deadline: Option<f64>) -> PyErr {
+    if error.is_timeout() {
+        if let Err(deadline_err) = deadline_remaining(deadline) {
+            return deadline_err;
+        }
+    }
+    map_reqwest_error(error)
+}
+
 /// Convert JSON value to Python object
 pub fn json_to_python(py: Python, value: &serde_json::Value) -> PyResult<PyObject> {
     use pyo3::types::{PyDict, PyList};
@@ -1,18 +1,24 @@
+use crate::auth_common::BearerTokenProvider;
 use crate::error::UltraFastError;
 use base64::{engine::general_purpose, Engine as _};
 use pyo3::prelude::*;
-use rand;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Authentication type enumeration
 #[pyclass]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum AuthType {
     Bearer,
     Basic,
     ApiKeyHeader,
     ApiKeyQuery,
     OAuth2,
+    Digest,
+    AwsSigV4,
+    Ntlm,
     Custom,
 }
 
@@ -49,6 +55,24 @@ impl AuthType {
         AuthType::OAuth2
     }
 
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn DIGEST() -> AuthType {
+        AuthType::Digest
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn AWS_SIGV4() -> AuthType {
+        AuthType::AwsSigV4
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn NTLM() -> AuthType {
+        AuthType::Ntlm
+    }
+
     #[classattr]
     #[allow(non_snake_case)]
     fn CUSTOM() -> AuthType {
@@ -58,11 +82,17 @@ impl AuthType {
 
 /// Authentication configuration
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AuthConfig {
     #[pyo3(get)]
     pub auth_type: AuthType,
     pub credentials: HashMap<String, String>,
+    /// Callback supplying the bearer token per request, see `bearer_provider`
+    #[serde(skip)]
+    pub(crate) token_provider: Option<Arc<BearerTokenProvider>>,
+    /// Called with each newly fetched OAuth2 token, see `oauth2`
+    #[serde(skip)]
+    pub(crate) on_token_refresh: Option<PyObject>,
 }
 
 #[pymethods]
@@ -74,6 +104,8 @@ impl AuthConfig {
         AuthConfig {
             auth_type,
             credentials: credentials.unwrap_or_default(),
+            token_provider: None,
+            on_token_refresh: None,
         }
     }
 
@@ -86,9 +118,39 @@ impl AuthConfig {
         AuthConfig {
             auth_type: AuthType::Bearer,
             credentials,
+            token_provider: None,
+            on_token_refresh: None,
         }
     }
 
+    /// Create Bearer authentication whose token comes from `callback`, a callable
+    /// returning the token or an awaitable of it. The token is fetched before a
+    /// request and reused for `cache_ttl` seconds; 0 calls it for every request.
+    #[staticmethod]
+    #[pyo3(signature = (callback, cache_ttl = 30.0))]
+    pub fn bearer_provider(callback: &PyAny, cache_ttl: f64) -> PyResult<Self> {
+        if !callback.is_callable() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "bearer_provider callback must be callable",
+            ));
+        }
+        if !cache_ttl.is_finite() || cache_ttl < 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "cache_ttl must be a non-negative number of seconds",
+            ));
+        }
+
+        Ok(AuthConfig {
+            auth_type: AuthType::Bearer,
+            credentials: HashMap::new(),
+            token_provider: Some(Arc::new(BearerTokenProvider::new(
+                callback.into(),
+                Duration::from_secs_f64(cache_ttl),
+            ))),
+            on_token_refresh: None,
+        })
+    }
+
     /// Create Basic authentication
     #[staticmethod]
     pub fn basic(username: String, password: String) -> Self {
@@ -109,6 +171,8 @@ impl AuthConfig {
         AuthConfig {
             auth_type: AuthType::Basic,
             credentials,
+            token_provider: None,
+            on_token_refresh: None,
         }
     }
 
@@ -122,6 +186,8 @@ impl AuthConfig {
         AuthConfig {
             auth_type: AuthType::ApiKeyHeader,
             credentials,
+            token_provider: None,
+            on_token_refresh: None,
         }
     }
 
@@ -135,21 +201,69 @@ impl AuthConfig {
         AuthConfig {
             auth_type: AuthType::ApiKeyQuery,
             credentials,
+            token_provider: None,
+            on_token_refresh: None,
         }
     }
 
-    /// Create OAuth2 authentication
+    /// Create OAuth2 authentication.
+    /// `grant_type` is "client_credentials", "authorization_code" (with `code`) or
+    /// "refresh_token" (with `refresh_token`); a stored refresh token is always
+    /// used to renew an expired access token. `on_token_refresh` is called with
+    /// each newly fetched `OAuth2Token`, e.g. to persist it.
     #[staticmethod]
-    #[pyo3(signature = (client_id, token_url, client_secret=None, scopes=None))]
+    #[pyo3(signature = (
+        client_id,
+        token_url,
+        client_secret=None,
+        scopes=None,
+        grant_type="client_credentials",
+        refresh_token=None,
+        redirect_uri=None,
+        code=None,
+        on_token_refresh=None
+    ))]
+    #[allow(clippy::too_many_arguments)]
     pub fn oauth2(
         client_id: String,
         token_url: String,
         client_secret: Option<String>,
         scopes: Option<Vec<String>>,
-    ) -> Self {
+        grant_type: &str,
+        refresh_token: Option<String>,
+        redirect_uri: Option<String>,
+        code: Option<String>,
+        on_token_refresh: Option<&PyAny>,
+    ) -> PyResult<Self> {
+        if on_token_refresh.is_some_and(|callback| !callback.is_callable()) {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "on_token_refresh must be callable",
+            ));
+        }
+        match grant_type {
+            "authorization_code" if code.is_none() => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "OAuth2 authorization_code grant requires code",
+                ))
+            }
+            "refresh_token" if refresh_token.is_none() => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "OAuth2 refresh_token grant requires refresh_token",
+                ))
+            }
+            "client_credentials" | "authorization_code" | "refresh_token" => {}
+            other => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Unsupported OAuth2 grant_type '{}'",
+                    other
+                )))
+            }
+        }
+
         let mut credentials = HashMap::new();
         credentials.insert("client_id".to_string(), client_id);
         credentials.insert("token_url".to_string(), token_url);
+        credentials.insert("grant_type".to_string(), grant_type.to_string());
 
         if let Some(secret) = client_secret {
             credentials.insert("client_secret".to_string(), secret);
@@ -159,9 +273,90 @@ impl AuthConfig {
             credentials.insert("scopes".to_string(), scopes.join(" "));
         }
 
-        AuthConfig {
+        if let Some(refresh_token) = refresh_token {
+            credentials.insert("refresh_token".to_string(), refresh_token);
+        }
+
+        if let Some(redirect_uri) = redirect_uri {
+            credentials.insert("redirect_uri".to_string(), redirect_uri);
+        }
+
+        if let Some(code) = code {
+            credentials.insert("code".to_string(), code);
+        }
+
+        Ok(AuthConfig {
             auth_type: AuthType::OAuth2,
             credentials,
+            token_provider: None,
+            on_token_refresh: on_token_refresh.map(Into::into),
+        })
+    }
+
+    /// Create HTTP Digest authentication, answered when the server sends a challenge
+    #[staticmethod]
+    pub fn digest(username: String, password: String) -> Self {
+        let mut credentials = HashMap::new();
+        credentials.insert("username".to_string(), username);
+        credentials.insert("password".to_string(), password);
+
+        AuthConfig {
+            auth_type: AuthType::Digest,
+            credentials,
+            token_provider: None,
+            on_token_refresh: None,
+        }
+    }
+
+    /// Create AWS Signature Version 4 request signing
+    #[staticmethod]
+    #[pyo3(signature = (access_key, secret_key, region, service, session_token=None))]
+    pub fn aws_sigv4(
+        access_key: String,
+        secret_key: String,
+        region: String,
+        service: String,
+        session_token: Option<String>,
+    ) -> Self {
+        let mut credentials = HashMap::new();
+        credentials.insert("access_key".to_string(), access_key);
+        credentials.insert("secret_key".to_string(), secret_key);
+        credentials.insert("region".to_string(), region);
+        credentials.insert("service".to_string(), service);
+        if let Some(token) = session_token {
+            credentials.insert("session_token".to_string(), token);
+        }
+
+        AuthConfig {
+            auth_type: AuthType::AwsSigV4,
+            credentials,
+            token_provider: None,
+            on_token_refresh: None,
+        }
+    }
+
+    /// Create NTLM authentication, answered when the server challenges with `NTLM`
+    /// or `Negotiate`. A `DOMAIN\user` username supplies the domain when none is given.
+    #[staticmethod]
+    #[pyo3(signature = (username, password, domain = None))]
+    pub fn ntlm(username: String, password: String, domain: Option<String>) -> Self {
+        let (domain, username) = match domain {
+            Some(domain) => (domain, username),
+            None => match username.split_once('\\') {
+                Some((domain, user)) => (domain.to_string(), user.to_string()),
+                None => (String::new(), username),
+            },
+        };
+        let mut credentials = HashMap::new();
+        credentials.insert("username".to_string(), username);
+        credentials.insert("password".to_string(), password);
+        credentials.insert("domain".to_string(), domain);
+
+        AuthConfig {
+            auth_type: AuthType::Ntlm,
+            credentials,
+            token_provider: None,
+            on_token_refresh: None,
         }
     }
 
@@ -174,6 +369,8 @@ impl AuthConfig {
         AuthConfig {
             auth_type: AuthType::Custom,
             credentials: creds,
+            token_provider: None,
+            on_token_refresh: None,
         }
     }
 
@@ -191,7 +388,7 @@ impl AuthConfig {
     pub fn validate(&self) -> PyResult<()> {
         match self.auth_type {
             AuthType::Bearer => {
-                if self.get_credential("token").is_none() {
+                if self.get_credential("token").is_none() && self.token_provider.is_none() {
                     return Err(pyo3::exceptions::PyValueError::new_err(
                         "Bearer token not set",
                     ));
@@ -206,6 +403,34 @@ impl AuthConfig {
                     ));
                 }
             }
+            AuthType::Digest => {
+                if self.get_credential("username").is_none()
+                    || self.get_credential("password").is_none()
+                {
+                    return Err(pyo3::exceptions::PyValueError::new_err(
+                        "Digest auth requires username and password",
+                    ));
+                }
+            }
+            AuthType::Ntlm => {
+                if self.get_credential("username").is_none()
+                    || self.get_credential("password").is_none()
+                {
+                    return Err(pyo3::exceptions::PyValueError::new_err(
+                        "NTLM auth requires username and password",
+                    ));
+                }
+            }
+            AuthType::AwsSigV4 => {
+                if ["access_key", "secret_key", "region", "service"]
+                    .iter()
+                    .any(|key| self.get_credential(key).is_none())
+                {
+                    return Err(pyo3::exceptions::PyValueError::new_err(
+                        "AWS SigV4 auth requires access_key, secret_key, region and service",
+                    ));
+                }
+            }
             AuthType::ApiKeyHeader => {
                 if self.get_credential("key").is_none()
                     || self.get_credential("header_name").is_none()
@@ -244,7 +469,11 @@ impl AuthConfig {
 
         match self.auth_type {
             AuthType::Bearer => {
-                if let Some(token) = self.get_credential("token") {
+                let token = match &self.token_provider {
+                    Some(provider) => Some(provider.token()?),
+                    None => self.get_credential("token"),
+                };
+                if let Some(token) = token {
                     headers.insert("Authorization".to_string(), format!("Bearer {}", token));
                 }
             }
@@ -267,6 +496,15 @@ impl AuthConfig {
             AuthType::OAuth2 => {
                 // OAuth2 headers are generated dynamically after token fetch
             }
+            AuthType::Digest => {
+                // Digest headers depend on the server challenge and each request
+            }
+            AuthType::Ntlm => {
+                // NTLM messages are exchanged with the server per connection
+            }
+            AuthType::AwsSigV4 => {
+                // SigV4 signatures cover the final URL and body of each request
+            }
             AuthType::Custom => {
                 // Custom auth - could be extended
             }
@@ -277,30 +515,65 @@ impl AuthConfig {
 }
 
 impl AuthConfig {
-    /// Fetch OAuth2 token asynchronously
-    pub async fn fetch_oauth2_token(&self) -> Result<OAuth2Token, String> {
+    /// Form parameters for an OAuth2 token request.
+    /// A refresh token, from the current token or the configuration, takes precedence
+    /// over the configured grant.
+    pub(crate) fn oauth2_token_params(&self, refresh_token: Option<&str>) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+        let refresh_token = refresh_token
+            .map(|t| t.to_string())
+            .or_else(|| self.get_credential("refresh_token"));
+
+        if let Some(refresh_token) = refresh_token {
+            params.push(("grant_type".to_string(), "refresh_token".to_string()));
+            params.push(("refresh_token".to_string(), refresh_token));
+        } else if self.get_credential("grant_type").as_deref() == Some("authorization_code") {
+            params.push(("grant_type".to_string(), "authorization_code".to_string()));
+            if let Some(code) = self.get_credential("code") {
+                params.push(("code".to_string(), code));
+            }
+            if let Some(redirect_uri) = self.get_credential("redirect_uri") {
+                params.push(("redirect_uri".to_string(), redirect_uri));
+            }
+        } else {
+            params.push(("grant_type".to_string(), "client_credentials".to_string()));
+        }
+
+        if let Some(client_id) = self.get_credential("client_id") {
+            params.push(("client_id".to_string(), client_id));
+        }
+        if let Some(secret) = self.get_credential("client_secret") {
+            params.push(("client_secret".to_string(), secret));
+        }
+        if let Some(scopes) = self.get_credential("scopes") {
+            params.push(("scope".to_string(), scopes));
+        }
+        params
+    }
+
+    /// Report a newly fetched token to the `on_token_refresh` callback, if any
+    pub(crate) fn notify_token_refresh(&self, token: &OAuth2Token) -> PyResult<()> {
+        if let Some(ref callback) = self.on_token_refresh {
+            Python::with_gil(|py| callback.call1(py, (token.clone(),)).map(|_| ()))?;
+        }
+        Ok(())
+    }
+
+    /// Fetch OAuth2 token asynchronously, refreshing `previous` when it carries a refresh token
+    pub async fn fetch_oauth2_token(
+        &self,
+        previous: Option<&OAuth2Token>,
+    ) -> Result<OAuth2Token, String> {
         if self.auth_type != AuthType::OAuth2 {
             return Err("Not an OAuth2 auth configuration".to_string());
         }
-        let client_id = self
-            .get_credential("client_id")
+        self.get_credential("client_id")
             .ok_or_else(|| "Missing client_id in credentials".to_string())?;
         let token_url = self
             .get_credential("token_url")
             .ok_or_else(|| "Missing token_url in credentials".to_string())?;
-        let client_secret = self.get_credential("client_secret");
-        let scopes = self.get_credential("scopes");
-
-        let mut params = vec![
-            ("grant_type", "client_credentials"),
-            ("client_id", &client_id),
-        ];
-        if let Some(ref secret) = client_secret {
-            params.push(("client_secret", secret));
-        }
-        if let Some(ref scopes) = scopes {
-            params.push(("scope", scopes));
-        }
+        let previous_refresh = previous.and_then(|token| token.refresh_token.clone());
+        let params = self.oauth2_token_params(previous_refresh.as_deref());
 
         let client = reqwest::Client::new();
         let response = client
@@ -317,41 +590,17 @@ impl AuthConfig {
             ));
         }
 
-        #[derive(serde::Deserialize)]
-        struct TokenResponse {
-            access_token: String,
-            token_type: Option<String>,
-            expires_in: Option<u64>,
-            refresh_token: Option<String>,
-            scope: Option<String>,
-        }
-
-        let token_response: TokenResponse = response
+        let token_data: serde_json::Value = response
             .json()
             .await
             .map_err(|e| format!("Failed to parse token response: {}", e))?;
-
-        let issued_at = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs_f64();
-
-        Ok(OAuth2Token {
-            access_token: token_response.access_token,
-            token_type: token_response
-                .token_type
-                .unwrap_or_else(|| "Bearer".to_string()),
-            expires_in: token_response.expires_in,
-            refresh_token: token_response.refresh_token,
-            scope: token_response.scope,
-            issued_at,
-        })
+        OAuth2Token::from_token_response(&token_data, previous_refresh)
     }
 }
 
 /// Retry policy configuration
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RetryConfig {
     #[pyo3(get, set)]
     pub max_retries: u32,
@@ -367,6 +616,29 @@ pub struct RetryConfig {
     pub retry_on_connection_errors: bool,
     #[pyo3(get, set)]
     pub jitter: bool,
+    /// Wait for a server's `Retry-After` instead of the computed backoff
+    #[pyo3(get, set)]
+    #[serde(default = "default_respect_retry_after")]
+    pub respect_retry_after: bool,
+    /// Methods that may be retried; non-idempotent ones are left out by default
+    #[pyo3(get, set)]
+    #[serde(default = "default_retry_methods")]
+    pub retry_methods: Vec<String>,
+    /// Error kinds (`timeout`, `connect`, `tls`, `dns`) that are retried. When unset,
+    /// `retry_on_connection_errors` decides for every error.
+    #[serde(default)]
+    pub retry_on: Option<Vec<String>>,
+}
+
+fn default_respect_retry_after() -> bool {
+    true
+}
+
+pub(crate) fn default_retry_methods() -> Vec<String> {
+    ["GET", "HEAD", "PUT", "DELETE", "OPTIONS"]
+        .iter()
+        .map(|method| method.to_string())
+        .collect()
 }
 
 #[pymethods]
@@ -379,7 +651,10 @@ impl RetryConfig {
         exponential_base = 2.0,
         retry_on_status_codes = None,
         retry_on_connection_errors = true,
-        jitter = true
+        jitter = true,
+        respect_retry_after = true,
+        retry_methods = None,
+        retry_on = None
     ))]
     pub fn new(
         max_retries: u32,
@@ -389,12 +664,15 @@ impl RetryConfig {
         retry_on_status_codes: Option<Vec<u16>>,
         retry_on_connection_errors: bool,
         jitter: bool,
-    ) -> Self {
+        respect_retry_after: bool,
+        retry_methods: Option<Vec<String>>,
+        retry_on: Option<Vec<String>>,
+    ) -> PyResult<Self> {
         let status_codes = retry_on_status_codes.unwrap_or_else(|| {
             vec![408, 429, 500, 502, 503, 504] // Common retryable status codes
         });
 
-        RetryConfig {
+        Ok(RetryConfig {
             max_retries,
             initial_delay,
             max_delay,
@@ -402,9 +680,47 @@ impl RetryConfig {
             retry_on_status_codes: status_codes,
             retry_on_connection_errors,
             jitter,
+            respect_retry_after,
+            retry_methods: retry_methods
+                .map(|methods| methods.iter().map(|m| m.to_uppercase()).collect())
+                .unwrap_or_else(default_retry_methods),
+            retry_on: retry_on.map(parse_error_kinds).transpose()?,
+        })
+    }
+
+    /// Error kinds that are retried: `retry_on` when set, otherwise all of them
+    /// or none following `retry_on_connection_errors`
+    #[getter]
+    fn retry_on(&self) -> Vec<String> {
+        match &self.retry_on {
+            Some(kinds) => kinds.clone(),
+            None if self.retry_on_connection_errors => crate::error::ERROR_KINDS
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+            None => Vec::new(),
         }
     }
 
+    #[setter]
+    fn set_retry_on(&mut self, kinds: Option<Vec<String>>) -> PyResult<()> {
+        self.retry_on = kinds.map(parse_error_kinds).transpose()?;
+        Ok(())
+    }
+
+    /// Default policy that also retries non-idempotent methods such as POST and PATCH
+    #[staticmethod]
+    pub fn allow_all_methods() -> PyResult<Self> {
+        let mut config = RetryConfig::new(3, 1.0, 60.0, 2.0, None, true, true, true, None, None)?;
+        config.retry_methods = [
+            "GET", "HEAD", "PUT", "DELETE", "OPTIONS", "TRACE", "POST", "PATCH",
+        ]
+        .iter()
+        .map(|method| method.to_string())
+        .collect();
+        Ok(config)
+    }
+
     /// Factory method for high-throughput scenarios with minimal delays
     #[staticmethod]
     pub fn for_high_throughput() -> Self {
@@ -416,6 +732,9 @@ impl RetryConfig {
             retry_on_status_codes: vec![429, 503, 504], // Rate limiting and server errors
             retry_on_connection_errors: true,
             jitter: true,
+            respect_retry_after: true,
+            retry_methods: default_retry_methods(),
+            retry_on: None,
         }
     }
 
@@ -430,6 +749,9 @@ impl RetryConfig {
             retry_on_status_codes: vec![408, 429, 500, 502, 503, 504, 522, 524],
             retry_on_connection_errors: true,
             jitter: true,
+            respect_retry_after: true,
+            retry_methods: default_retry_methods(),
+            retry_on: None,
         }
     }
 
@@ -444,6 +766,9 @@ impl RetryConfig {
             retry_on_status_codes: vec![500, 502, 503, 504],
             retry_on_connection_errors: true,
             jitter: false, // No jitter for deterministic testing
+            respect_retry_after: true,
+            retry_methods: default_retry_methods(),
+            retry_on: None,
         }
     }
 
@@ -482,6 +807,13 @@ impl RetryConfig {
         }
     }
 
+    /// Check if requests with this method may be retried
+    pub fn should_retry_method(&self, method: &str) -> bool {
+        self.retry_methods
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(method))
+    }
+
     /// Check if a status code should trigger a retry
     pub fn should_retry_status(&self, status_code: u16) -> bool {
         self.retry_on_status_codes.contains(&status_code)
@@ -525,9 +857,59 @@ impl RetryConfig {
     }
 }
 
+impl RetryConfig {
+    /// Whether a failed attempt may be retried given its error kind
+    pub(crate) fn should_retry_error(&self, py: Python<'_>, error: &PyErr) -> bool {
+        match &self.retry_on {
+            Some(kinds) => crate::error::error_kind(py, error)
+                .map_or(false, |kind| kinds.iter().any(|k| k == kind)),
+            None => self.retry_on_connection_errors,
+        }
+    }
+
+    /// Delay requested by a response's `Retry-After` header, capped by `max_delay`
+    pub(crate) fn retry_after_delay(&self, headers: &HashMap<String, String>) -> Option<f64> {
+        if !self.respect_retry_after {
+            return None;
+        }
+        let (_, value) = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("retry-after"))?;
+        parse_retry_after(value, crate::cookies::unix_now()).map(|delay| delay.min(self.max_delay))
+    }
+}
+
+/// Lowercase and check `retry_on` error kind names
+fn parse_error_kinds(kinds: Vec<String>) -> PyResult<Vec<String>> {
+    kinds
+        .into_iter()
+        .map(|kind| {
+            let kind = kind.to_lowercase();
+            if crate::error::ERROR_KINDS.contains(&kind.as_str()) {
+                Ok(kind)
+            } else {
+                Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Unknown retry_on error kind '{}', expected one of: {}",
+                    kind,
+                    crate::error::ERROR_KINDS.join(", ")
+                )))
+            }
+        })
+        .collect()
+}
+
+/// Seconds to wait for a `Retry-After` value given as delta-seconds or an HTTP-date
+pub(crate) fn parse_retry_after(value: &str, now: f64) -> Option<f64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds as f64);
+    }
+    crate::cookies::parse_http_date(value).map(|retry_at| (retry_at - now).max(0.0))
+}
+
 /// Connection pool configuration
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PoolConfig {
     #[pyo3(get, set)]
     pub max_idle_connections: usize,
@@ -537,6 +919,11 @@ pub struct PoolConfig {
     pub idle_timeout: f64, // seconds
     #[pyo3(get, set)]
     pub pool_timeout: f64, // seconds
+    /// Requests in flight to one host at once; further requests wait up to
+    /// `pool_timeout` for a slot. None leaves hosts unbounded.
+    #[pyo3(get)]
+    #[serde(default)]
+    pub max_connections_per_host: Option<usize>,
 }
 
 #[pymethods]
@@ -546,35 +933,78 @@ impl PoolConfig {
         max_idle_connections = 100,
         max_idle_per_host = 10,
         idle_timeout = 90.0,
-        pool_timeout = 30.0
+        pool_timeout = 30.0,
+        max_connections_per_host = None
     ))]
     pub fn new(
         max_idle_connections: usize,
         max_idle_per_host: usize,
         idle_timeout: f64,
         pool_timeout: f64,
-    ) -> Self {
-        PoolConfig {
+        max_connections_per_host: Option<usize>,
+    ) -> PyResult<Self> {
+        let config = PoolConfig {
             max_idle_connections,
             max_idle_per_host,
             idle_timeout,
             pool_timeout,
+            max_connections_per_host,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    #[setter]
+    fn set_max_connections_per_host(&mut self, value: Option<usize>) -> PyResult<()> {
+        self.max_connections_per_host = value;
+        self.validate()
+    }
+}
+
+impl PoolConfig {
+    pub(crate) fn validate(&self) -> PyResult<()> {
+        if self.max_connections_per_host == Some(0) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "max_connections_per_host must be at least 1",
+            ));
         }
+        if !self.pool_timeout.is_finite() || self.pool_timeout < 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "pool_timeout must be a non-negative number of seconds",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Longest a request waits for a per-host connection slot
+    pub(crate) fn slot_wait(&self) -> Duration {
+        Duration::from_secs_f64(self.pool_timeout)
     }
 }
 
-/// Timeout configuration
+/// Timeout configuration, in seconds per phase.
+///
+/// The client's overall `timeout` still bounds each request end to end.
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TimeoutConfig {
+    /// Establishing a connection: DNS, TCP and the TLS handshake
     #[pyo3(get, set)]
-    pub connect_timeout: Option<f64>, // seconds
+    pub connect_timeout: Option<f64>,
+    /// Longest wait for the next piece of the response, reset after each read
     #[pyo3(get, set)]
-    pub read_timeout: Option<f64>, // seconds
+    pub read_timeout: Option<f64>,
+    /// Longest the connection may stall while sending a request body larger than 64 KiB
     #[pyo3(get, set)]
-    pub write_timeout: Option<f64>, // seconds
+    pub write_timeout: Option<f64>,
+    /// Not enforced yet; kept for configuration compatibility
     #[pyo3(get, set)]
-    pub pool_timeout: Option<f64>, // seconds
+    pub pool_timeout: Option<f64>,
+    /// How long an `expect_continue` request holds its body back for an early
+    /// rejection. A server that accepts still costs this much per request. None uses 0.1.
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub expect_continue_timeout: Option<f64>,
 }
 
 #[pymethods]
@@ -584,19 +1014,22 @@ impl TimeoutConfig {
         connect_timeout = None,
         read_timeout = None,
         write_timeout = None,
-        pool_timeout = None
+        pool_timeout = None,
+        expect_continue_timeout = None
     ))]
     pub fn new(
         connect_timeout: Option<f64>,
         read_timeout: Option<f64>,
         write_timeout: Option<f64>,
         pool_timeout: Option<f64>,
+        expect_continue_timeout: Option<f64>,
     ) -> Self {
         TimeoutConfig {
             connect_timeout,
             read_timeout,
             write_timeout,
             pool_timeout,
+            expect_continue_timeout,
         }
     }
 
@@ -608,13 +1041,101 @@ impl TimeoutConfig {
             read_timeout: Some(30.0),
             write_timeout: Some(30.0),
             pool_timeout: Some(30.0),
+            expect_continue_timeout: None,
         }
     }
 }
 
+/// How long an `Expect: 100-continue` request holds its body back by default
+const DEFAULT_EXPECT_CONTINUE_WAIT: Duration = Duration::from_millis(100);
+
+impl TimeoutConfig {
+    /// Longest an `Expect: 100-continue` request waits before sending its body
+    pub(crate) fn expect_continue_wait(&self) -> Duration {
+        self.expect_continue_timeout
+            .filter(|timeout| timeout.is_finite() && *timeout >= 0.0)
+            .map(Duration::from_secs_f64)
+            .unwrap_or(DEFAULT_EXPECT_CONTINUE_WAIT)
+    }
+}
+
+/// Per-host circuit breaker: after too many recent failures a host is skipped for a cooldown
+#[pyclass]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Failure share (0-1) of recent requests that opens the circuit
+    #[pyo3(get, set)]
+    pub failure_rate_threshold: f64,
+    /// Requests needed in the window before the failure rate is trusted
+    #[pyo3(get, set)]
+    pub minimum_requests: u32,
+    /// Seconds of history the failure rate is computed over
+    #[pyo3(get, set)]
+    pub window_seconds: f64,
+    /// Seconds an open circuit waits before letting a trial request through
+    #[pyo3(get, set)]
+    pub cooldown_seconds: f64,
+    /// Trial requests allowed at once while half-open
+    #[pyo3(get, set)]
+    pub half_open_max_requests: u32,
+}
+
+#[pymethods]
+impl CircuitBreakerConfig {
+    #[new]
+    #[pyo3(signature = (
+        failure_rate_threshold = 0.5,
+        minimum_requests = 10,
+        window_seconds = 60.0,
+        cooldown_seconds = 30.0,
+        half_open_max_requests = 1
+    ))]
+    pub fn new(
+        failure_rate_threshold: f64,
+        minimum_requests: u32,
+        window_seconds: f64,
+        cooldown_seconds: f64,
+        half_open_max_requests: u32,
+    ) -> PyResult<Self> {
+        let config = CircuitBreakerConfig {
+            failure_rate_threshold,
+            minimum_requests,
+            window_seconds,
+            cooldown_seconds,
+            half_open_max_requests,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check thresholds and durations are in range
+    pub fn validate(&self) -> PyResult<()> {
+        if !(self.failure_rate_threshold > 0.0 && self.failure_rate_threshold <= 1.0) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "failure_rate_threshold must be in (0, 1]",
+            ));
+        }
+        if self.minimum_requests == 0 || self.half_open_max_requests == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "minimum_requests and half_open_max_requests must be at least 1",
+            ));
+        }
+        if !(self.window_seconds > 0.0
+            && self.window_seconds.is_finite()
+            && self.cooldown_seconds >= 0.0
+            && self.cooldown_seconds.is_finite())
+        {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "window_seconds must be positive and cooldown_seconds non-negative",
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// SSL/TLS configuration
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SSLConfig {
     #[pyo3(get, set)]
     pub verify: bool,
@@ -626,6 +1147,25 @@ pub struct SSLConfig {
     pub ca_bundle: Option<String>,
     #[pyo3(get, set)]
     pub min_tls_version: Option<String>,
+    // Client certificate and PKCS#8 key held in memory, preferred over the files
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub cert_pem: Option<String>,
+    #[pyo3(set)]
+    #[serde(default)]
+    pub key_pem: Option<String>,
+    // When false, unreadable or invalid certificates are ignored instead of raising
+    #[pyo3(get, set)]
+    #[serde(default = "default_ssl_strict")]
+    pub ssl_strict: bool,
+    // SHA-256 hashes of accepted server public keys, as `sha256/<base64>`
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub pinned_public_keys: Vec<String>,
+}
+
+fn default_ssl_strict() -> bool {
+    true
 }
 
 #[pymethods]
@@ -636,7 +1176,11 @@ impl SSLConfig {
         cert_file = None,
         key_file = None,
         ca_bundle = None,
-        min_tls_version = None
+        min_tls_version = None,
+        cert_pem = None,
+        key_pem = None,
+        ssl_strict = true,
+        pinned_public_keys = None
     ))]
     pub fn new(
         verify: bool,
@@ -644,6 +1188,10 @@ impl SSLConfig {
         key_file: Option<String>,
         ca_bundle: Option<String>,
         min_tls_version: Option<String>,
+        cert_pem: Option<String>,
+        key_pem: Option<String>,
+        ssl_strict: bool,
+        pinned_public_keys: Option<Vec<String>>,
     ) -> Self {
         SSLConfig {
             verify,
@@ -651,8 +1199,177 @@ impl SSLConfig {
             key_file,
             ca_bundle,
             min_tls_version,
+            cert_pem,
+            key_pem,
+            ssl_strict,
+            pinned_public_keys: pinned_public_keys.unwrap_or_default(),
         }
     }
+
+    /// Create a configuration presenting a client certificate held in memory,
+    /// e.g. PEM read from an environment variable or secret manager
+    #[staticmethod]
+    #[pyo3(signature = (cert, key, verify = true, ca_bundle = None, min_tls_version = None))]
+    pub fn from_pem_bytes(
+        cert: &[u8],
+        key: &[u8],
+        verify: bool,
+        ca_bundle: Option<String>,
+        min_tls_version: Option<String>,
+    ) -> PyResult<Self> {
+        let pem = |bytes: &[u8], what: &str| {
+            String::from_utf8(bytes.to_vec()).map_err(|e| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "Client {} is not PEM text: {}",
+                    what, e
+                ))
+            })
+        };
+        let config = SSLConfig::new(
+            verify,
+            None,
+            None,
+            ca_bundle,
+            min_tls_version,
+            Some(pem(cert, "certificate")?),
+            Some(pem(key, "key")?),
+            true,
+            None,
+        );
+        config.load_identity()?;
+        Ok(config)
+    }
+
+    /// Pin for a PEM certificate's public key, in the form `pinned_public_keys` expects
+    #[staticmethod]
+    pub fn public_key_pin(cert_pem: &str) -> PyResult<String> {
+        crate::pinning::pem_certificate_der(cert_pem)
+            .as_deref()
+            .and_then(crate::pinning::spki_pin)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err("Invalid PEM certificate"))
+    }
+}
+
+impl SSLConfig {
+    /// Minimum TLS version to negotiate, parsed from `min_tls_version`
+    pub(crate) fn tls_version(&self) -> PyResult<Option<reqwest::tls::Version>> {
+        let version = match self.min_tls_version.as_deref() {
+            None => return Ok(None),
+            Some("1.0") => reqwest::tls::Version::TLS_1_0,
+            Some("1.1") => reqwest::tls::Version::TLS_1_1,
+            Some("1.2") => reqwest::tls::Version::TLS_1_2,
+            Some("1.3") => reqwest::tls::Version::TLS_1_3,
+            Some(other) => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Invalid min_tls_version '{}': expected '1.0', '1.1', '1.2' or '1.3'",
+                    other
+                )))
+            }
+        };
+        Ok(Some(version))
+    }
+
+    /// Client identity to present, if one is configured and (unless strict) loadable
+    pub(crate) fn identity(&self) -> PyResult<Option<reqwest::Identity>> {
+        self.lenient(self.load_identity())
+    }
+
+    /// Extra trust roots from `ca_bundle`, skipped on error unless strict
+    pub(crate) fn root_certificates(&self) -> PyResult<Vec<reqwest::Certificate>> {
+        self.lenient(self.load_root_certificates())
+    }
+
+    /// Client certificate and key PEM, if configured and (unless strict) readable
+    #[cfg(feature = "rustls-tls")]
+    pub(crate) fn identity_pem(&self) -> PyResult<Option<(Vec<u8>, Vec<u8>)>> {
+        let pem = self.read_identity_pem();
+        self.lenient(pem.map(|pem| pem.map(|(cert, key, _)| (cert, key))))
+    }
+
+    /// Contents of `ca_bundle`, if configured and (unless strict) readable
+    #[cfg(feature = "rustls-tls")]
+    pub(crate) fn ca_bundle_pem(&self) -> PyResult<Option<Vec<u8>>> {
+        let pem = self
+            .ca_bundle
+            .as_deref()
+            .map(|path| read_pem_file(path, "ca_bundle"))
+            .transpose();
+        self.lenient(pem)
+    }
+
+    /// Drop a load failure when `ssl_strict` is off
+    fn lenient<T: Default>(&self, result: PyResult<T>) -> PyResult<T> {
+        match result {
+            Err(_) if !self.ssl_strict => Ok(T::default()),
+            result => result,
+        }
+    }
+
+    /// Certificate and key PEM from `cert_pem`/`key_pem`, or else `cert_file`/`key_file`,
+    /// with a description of where they came from
+    fn read_identity_pem(&self) -> PyResult<Option<(Vec<u8>, Vec<u8>, String)>> {
+        match (&self.cert_pem, &self.key_pem) {
+            (Some(cert), Some(key)) => Ok(Some((
+                cert.clone().into_bytes(),
+                key.clone().into_bytes(),
+                "cert_pem/key_pem".to_string(),
+            ))),
+            (None, None) => match (&self.cert_file, &self.key_file) {
+                (Some(cert_path), Some(key_path)) => Ok(Some((
+                    read_pem_file(cert_path, "cert_file")?,
+                    read_pem_file(key_path, "key_file")?,
+                    format!("'{}' and '{}'", cert_path, key_path),
+                ))),
+                _ => Ok(None),
+            },
+            _ => Err(pyo3::exceptions::PyValueError::new_err(
+                "cert_pem and key_pem must be set together",
+            )),
+        }
+    }
+
+    fn load_identity(&self) -> PyResult<Option<reqwest::Identity>> {
+        let (cert, key, source) = match self.read_identity_pem()? {
+            Some(pem) => pem,
+            None => return Ok(None),
+        };
+        reqwest::Identity::from_pkcs8_pem(&cert, &key)
+            .map(Some)
+            .map_err(|e| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "Invalid client certificate or key in {}: {}",
+                    source, e
+                ))
+            })
+    }
+
+    fn load_root_certificates(&self) -> PyResult<Vec<reqwest::Certificate>> {
+        let path = match &self.ca_bundle {
+            Some(path) => path,
+            None => return Ok(Vec::new()),
+        };
+        let certs = reqwest::Certificate::from_pem_bundle(&read_pem_file(path, "ca_bundle")?)
+            .map_err(|e| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "Invalid ca_bundle '{}': {}",
+                    path, e
+                ))
+            })?;
+        if certs.is_empty() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid ca_bundle '{}': no PEM certificates found",
+                path
+            )));
+        }
+        Ok(certs)
+    }
+}
+
+/// Read a PEM file named by an `SSLConfig` field
+fn read_pem_file(path: &str, field: &str) -> PyResult<Vec<u8>> {
+    std::fs::read(path).map_err(|e| {
+        pyo3::exceptions::PyIOError::new_err(format!("Failed to read {} '{}': {}", field, path, e))
+    })
 }
 
 /// OAuth2 token response
@@ -738,9 +1455,40 @@ impl OAuth2Token {
     }
 }
 
+impl OAuth2Token {
+    /// Build a token from a token endpoint response. Servers that rotate refresh tokens
+    /// return a new one; otherwise the previous refresh token stays valid and is kept.
+    pub(crate) fn from_token_response(
+        data: &serde_json::Value,
+        previous_refresh: Option<String>,
+    ) -> Result<Self, String> {
+        let access_token = data["access_token"]
+            .as_str()
+            .ok_or_else(|| "No access_token in OAuth2 response".to_string())?
+            .to_string();
+
+        let issued_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|_| "Failed to get system time".to_string())?
+            .as_secs_f64();
+
+        Ok(OAuth2Token {
+            access_token,
+            token_type: data["token_type"].as_str().unwrap_or("Bearer").to_string(),
+            expires_in: data["expires_in"].as_u64(),
+            refresh_token: data["refresh_token"]
+                .as_str()
+                .map(|s| s.to_string())
+                .or(previous_refresh),
+            scope: data["scope"].as_str().map(|s| s.to_string()),
+            issued_at,
+        })
+    }
+}
+
 /// Proxy configuration for HTTP requests
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ProxyConfig {
     #[pyo3(get)]
     pub url: String,
@@ -792,37 +1540,119 @@ impl ProxyConfig {
         }
     }
 
-    /// Create SOCKS5 proxy configuration
+    /// Create SOCKS5 proxy configuration (`socks5h://` resolves DNS through the proxy)
     #[staticmethod]
-    pub fn socks5(url: &str, username: Option<String>, password: Option<String>) -> Self {
-        ProxyConfig {
+    pub fn socks5(url: &str, username: Option<String>, password: Option<String>) -> PyResult<Self> {
+        if !is_socks_url(url) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid SOCKS5 proxy URL '{}': expected a socks5:// or socks5h:// scheme",
+                url
+            )));
+        }
+        Ok(ProxyConfig {
             url: url.to_string(),
             username,
             password,
             no_proxy: None,
-        }
+        })
     }
 
     /// Set domains to bypass proxy
     pub fn set_no_proxy(&mut self, domains: Vec<String>) {
         self.no_proxy = Some(domains);
     }
+
+    /// Check whether requests to `host` skip the proxy
+    pub fn should_bypass(&self, host: &str) -> bool {
+        self.no_proxy
+            .as_ref()
+            .map_or(false, |entries| no_proxy_matches(host, entries))
+    }
+}
+
+impl ProxyConfig {
+    /// Build the reqwest proxy, passing SOCKS credentials in the URL and HTTP ones as basic auth
+    pub(crate) fn to_reqwest_proxy(&self) -> PyResult<reqwest::Proxy> {
+        let invalid = |e: &dyn std::fmt::Display| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid proxy URL '{}': {}",
+                self.url, e
+            ))
+        };
+
+        let socks = is_socks_url(&self.url);
+        let mut proxy_url = url::Url::parse(&self.url).map_err(|e| invalid(&e))?;
+        if socks {
+            if let Some(username) = &self.username {
+                proxy_url
+                    .set_username(username)
+                    .map_err(|_| invalid(&"cannot set username"))?;
+                proxy_url
+                    .set_password(self.password.as_deref())
+                    .map_err(|_| invalid(&"cannot set password"))?;
+            }
+        }
+
+        let mut proxy = match self.no_proxy.clone().filter(|entries| !entries.is_empty()) {
+            Some(entries) => reqwest::Proxy::custom(move |target| {
+                let bypass = target
+                    .host_str()
+                    .map_or(false, |host| no_proxy_matches(host, &entries));
+                if bypass {
+                    None
+                } else {
+                    Some(proxy_url.clone())
+                }
+            }),
+            None => reqwest::Proxy::all(proxy_url).map_err(|e| invalid(&e))?,
+        };
+        if !socks {
+            if let (Some(username), Some(password)) = (&self.username, &self.password) {
+                proxy = proxy.basic_auth(username, password);
+            }
+        }
+        Ok(proxy)
+    }
+}
+
+/// Check a host against a no_proxy list: `*`, exact hosts, or `.suffix` domain matches
+fn no_proxy_matches(host: &str, entries: &[String]) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    entries.iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        if entry == "*" {
+            return true;
+        }
+        match entry.strip_prefix('.') {
+            Some(suffix) => host == suffix || host.ends_with(&format!(".{}", suffix)),
+            None => !entry.is_empty() && host == entry,
+        }
+    })
+}
+
+/// Check whether a proxy URL uses a SOCKS5 scheme
+fn is_socks_url(url: &str) -> bool {
+    let scheme = url.split("://").next().unwrap_or("").to_ascii_lowercase();
+    url.contains("://") && (scheme == "socks5" || scheme == "socks5h")
 }
 
 /// Compression configuration for requests and responses
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CompressionConfig {
     #[pyo3(get)]
     pub enable_request_compression: bool,
     #[pyo3(get)]
     pub enable_response_compression: bool,
     #[pyo3(get)]
-    pub compression_algorithms: Vec<String>, // gzip, deflate, brotli
+    pub compression_algorithms: Vec<String>, // gzip, deflate, brotli, zstd
     #[pyo3(get)]
-    pub compression_level: Option<u32>, // 1-9 for gzip/deflate, 1-11 for brotli
+    pub compression_level: Option<u32>, // 1-9 for gzip/deflate, 1-11 for brotli, 1-22 for zstd
     #[pyo3(get)]
     pub min_compression_size: usize, // Minimum size to compress
+    #[pyo3(get)]
+    #[serde(default)]
+    pub negotiate_request_encoding: bool, // Preflight OPTIONS to learn accepted encodings
 }
 
 #[pymethods]
@@ -833,7 +1663,8 @@ impl CompressionConfig {
         enable_response_compression = true,
         compression_algorithms = None,
         compression_level = None,
-        min_compression_size = 1024
+        min_compression_size = 1024,
+        negotiate_request_encoding = false
     ))]
     pub fn new(
         enable_request_compression: bool,
@@ -841,22 +1672,52 @@ impl CompressionConfig {
         compression_algorithms: Option<Vec<String>>,
         compression_level: Option<u32>,
         min_compression_size: usize,
-    ) -> Self {
+        negotiate_request_encoding: bool,
+    ) -> PyResult<Self> {
         let algorithms = compression_algorithms.unwrap_or_else(|| {
             vec![
                 "gzip".to_string(),
                 "deflate".to_string(),
                 "brotli".to_string(),
+                "zstd".to_string(),
             ]
         });
 
-        CompressionConfig {
+        let config = CompressionConfig {
             enable_request_compression,
             enable_response_compression,
             compression_algorithms: algorithms,
             compression_level,
             min_compression_size,
+            negotiate_request_encoding,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check `compression_level` is in range for every configured algorithm
+    pub fn validate(&self) -> PyResult<()> {
+        let level = match self.compression_level {
+            Some(level) => level,
+            None => return Ok(()),
+        };
+        for algorithm in &self.compression_algorithms {
+            if let Some((min, max)) = compression_level_range(algorithm) {
+                if level < min || level > max {
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "compression_level {} is out of range for {} ({}-{})",
+                        level, algorithm, min, max
+                    )));
+                }
+            }
         }
+        Ok(())
+    }
+
+    /// Level used for `algorithm`: the configured level, or that algorithm's default
+    pub fn level_for(&self, algorithm: &str) -> u32 {
+        self.compression_level
+            .unwrap_or_else(|| default_compression_level(algorithm))
     }
 
     /// Create a configuration with gzip only
@@ -868,6 +1729,7 @@ impl CompressionConfig {
             compression_algorithms: vec!["gzip".to_string()],
             compression_level: Some(6),
             min_compression_size: 1024,
+            negotiate_request_encoding: false,
         }
     }
 
@@ -881,9 +1743,11 @@ impl CompressionConfig {
                 "gzip".to_string(),
                 "deflate".to_string(),
                 "brotli".to_string(),
+                "zstd".to_string(),
             ],
             compression_level: Some(6),
             min_compression_size: 512,
+            negotiate_request_encoding: false,
         }
     }
 
@@ -937,6 +1801,7 @@ impl CompressionConfig {
                 "gzip" => encodings.push("gzip"),
                 "deflate" => encodings.push("deflate"),
                 "brotli" => encodings.push("br"),
+                "zstd" => encodings.push("zstd"),
                 _ => {}
             }
         }
@@ -948,6 +1813,18 @@ impl CompressionConfig {
         }
     }
 
+    /// Pick the first configured algorithm whose content-coding the server accepts
+    pub fn select_request_algorithm(&self, accepted_encodings: Vec<String>) -> Option<String> {
+        self.compression_algorithms
+            .iter()
+            .find(|algorithm| {
+                accepted_encodings
+                    .iter()
+                    .any(|accepted| accepted == content_encoding_name(algorithm))
+            })
+            .cloned()
+    }
+
     /// Check if algorithm is supported
     pub fn supports_algorithm(&self, algorithm: &str) -> bool {
         self.compression_algorithms.contains(&algorithm.to_string())
@@ -977,9 +1854,7 @@ impl CompressionConfig {
                 use flate2::{write::GzEncoder, Compression};
                 use std::io::Write;
 
-                let level = self.compression_level.ok_or_else(|| {
-                    UltraFastError::ConfigError("Compression level not set".to_string())
-                })?;
+                let level = self.level_for("gzip");
                 let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level));
                 encoder.write_all(body).map_err(|e| {
                     UltraFastError::IoError(format!("Gzip compression failed: {}", e))
@@ -992,9 +1867,7 @@ impl CompressionConfig {
                 use flate2::{write::DeflateEncoder, Compression};
                 use std::io::Write;
 
-                let level = self.compression_level.ok_or_else(|| {
-                    UltraFastError::ConfigError("Compression level not set".to_string())
-                })?;
+                let level = self.level_for("deflate");
                 let mut encoder = DeflateEncoder::new(Vec::new(), Compression::new(level));
                 encoder.write_all(body).map_err(|e| {
                     UltraFastError::IoError(format!("Deflate compression failed: {}", e))
@@ -1004,9 +1877,7 @@ impl CompressionConfig {
                 })
             }
             "brotli" => {
-                let level = self.compression_level.ok_or_else(|| {
-                    UltraFastError::ConfigError("Compression level not set".to_string())
-                })?;
+                let level = self.level_for("brotli");
                 let mut compressed = Vec::new();
                 let mut compressor =
                     brotli::CompressorWriter::new(&mut compressed, 4096, level, 22);
@@ -1017,6 +1888,11 @@ impl CompressionConfig {
                 drop(compressor);
                 Ok(compressed)
             }
+            "zstd" => {
+                let level = self.level_for("zstd");
+                zstd::encode_all(body, level as i32)
+                    .map_err(|e| UltraFastError::IoError(format!("Zstd compression failed: {}", e)))
+            }
             _ => Err(UltraFastError::ConfigError(format!(
                 "Unsupported compression algorithm: {}",
                 algorithm
@@ -1025,9 +1901,59 @@ impl CompressionConfig {
     }
 }
 
+/// Valid `compression_level` range for an algorithm, if it has one
+fn compression_level_range(algorithm: &str) -> Option<(u32, u32)> {
+    match algorithm {
+        "gzip" | "deflate" => Some((1, 9)),
+        "brotli" => Some((1, 11)),
+        "zstd" => Some((1, 22)),
+        _ => None,
+    }
+}
+
+/// Level used when `compression_level` is unset
+fn default_compression_level(algorithm: &str) -> u32 {
+    match algorithm {
+        "brotli" => 4,
+        "zstd" => 3,
+        _ => 6,
+    }
+}
+
+/// Content-Encoding token for a compression algorithm name
+pub(crate) fn content_encoding_name(algorithm: &str) -> &str {
+    match algorithm {
+        "brotli" => "br",
+        other => other,
+    }
+}
+
+/// Parse an Accept-Encoding header into content-codings, skipping those with q=0
+pub(crate) fn parse_accept_encoding(header: &str) -> Vec<String> {
+    header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let coding = parts.next()?.trim().to_lowercase();
+            let rejected = parts.any(|param| {
+                let param = param.trim();
+                param
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f64>().ok())
+                    .is_some_and(|q| q == 0.0)
+            });
+            if coding.is_empty() || rejected {
+                None
+            } else {
+                Some(coding)
+            }
+        })
+        .collect()
+}
+
 /// HTTP protocol version enumeration
 #[pyclass]
-#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum HttpVersion {
     Http1,
     Http2,
@@ -1065,7 +1991,7 @@ impl HttpVersion {
 
 /// HTTP/2 specific configuration settings
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Http2Settings {
     #[pyo3(get, set)]
     pub max_concurrent_streams: Option<u32>,
@@ -1174,9 +2100,42 @@ impl Http2Settings {
     }
 }
 
+impl Http2Settings {
+    /// Apply keep-alive, flow-control and frame settings to a client builder
+    pub(crate) fn apply_to(&self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        if let Some(interval) = self.keep_alive_interval {
+            builder =
+                builder.http2_keep_alive_interval(Some(std::time::Duration::from_secs(interval)));
+        }
+        if let Some(timeout) = self.keep_alive_timeout {
+            builder = builder.http2_keep_alive_timeout(std::time::Duration::from_secs(timeout));
+        }
+
+        // Adaptive flow control overrides the fixed window sizes
+        if self.adaptive_window {
+            builder = builder.http2_adaptive_window(true);
+        } else {
+            if let Some(size) = self.initial_window_size {
+                builder = builder.http2_initial_stream_window_size(size);
+            }
+            if let Some(size) = self.initial_connection_window_size {
+                builder = builder.http2_initial_connection_window_size(size);
+            }
+        }
+        if let Some(size) = self.max_frame_size {
+            // h2 rejects frame sizes outside the range allowed by RFC 9113
+            builder = builder.http2_max_frame_size(size.clamp(16_384, 16_777_215));
+        }
+        if let Some(size) = self.max_header_list_size {
+            builder = builder.http2_max_header_list_size(size);
+        }
+        builder
+    }
+}
+
 /// HTTP/3 specific configuration settings
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Http3Settings {
     #[pyo3(get, set)]
     pub max_idle_timeout: Option<u64>, // milliseconds
@@ -1194,8 +2153,8 @@ pub struct Http3Settings {
     pub initial_max_stream_data_uni: Option<u64>,
     #[pyo3(get, set)]
     pub disable_migration: bool,
-    #[pyo3(get, set)]
-    pub congestion_control: String, // "cubic", "bbr", "reno"
+    #[pyo3(get)]
+    pub congestion_control: String, // only "cubic" is supported
     #[pyo3(get, set)]
     pub enable_0rtt: bool, // 0-RTT resumption
     #[pyo3(get, set)]
@@ -1234,8 +2193,8 @@ impl Http3Settings {
         enable_0rtt: bool,
         connection_pool_size: Option<usize>,
         pool_timeout_seconds: Option<u64>,
-    ) -> Self {
-        Http3Settings {
+    ) -> PyResult<Self> {
+        let settings = Http3Settings {
             max_idle_timeout,
             initial_max_streams_bidi,
             initial_max_streams_uni,
@@ -1248,7 +2207,16 @@ impl Http3Settings {
             enable_0rtt,
             connection_pool_size,
             pool_timeout_seconds,
-        }
+        };
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    #[setter]
+    fn set_congestion_control(&mut self, value: String) -> PyResult<()> {
+        check_congestion_control(&value)?;
+        self.congestion_control = value;
+        Ok(())
     }
 
     /// Create default HTTP/3 settings
@@ -1271,9 +2239,29 @@ impl Http3Settings {
     }
 }
 
+/// The only congestion control algorithm the HTTP/3 transport uses
+pub(crate) const CONGESTION_CONTROL: &str = "cubic";
+
+fn check_congestion_control(name: &str) -> PyResult<()> {
+    if name == CONGESTION_CONTROL {
+        Ok(())
+    } else {
+        Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "congestion_control '{}' is not supported, the HTTP/3 transport only uses '{}'",
+            name, CONGESTION_CONTROL
+        )))
+    }
+}
+
+impl Http3Settings {
+    pub(crate) fn validate(&self) -> PyResult<()> {
+        check_congestion_control(&self.congestion_control)
+    }
+}
+
 /// Protocol fallback strategy
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ProtocolFallback {
     /// Fallback to HTTP/2 if HTTP/3 fails, then HTTP/1.1
     Http3ToHttp2ToHttp1,
@@ -1287,7 +2275,7 @@ pub enum ProtocolFallback {
 
 /// Advanced HTTP protocol configuration for HTTP/2 and HTTP/3 support
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ProtocolConfig {
     #[pyo3(get, set)]
     pub preferred_version: HttpVersion,
@@ -1393,17 +2381,45 @@ impl ProtocolConfig {
                 "Protocol negotiation timeout too large (max 300s)".to_string(),
             ));
         }
+        if self.preferred_version == HttpVersion::Http1 && self.enable_http2_prior_knowledge {
+            return Err(UltraFastError::ConfigError(
+                "HTTP/2 prior knowledge cannot be combined with preferred HTTP/1.1".to_string(),
+            ));
+        }
         Ok(())
     }
+
+    /// Whether only the preferred version may be used (`ProtocolFallback.None`)
+    pub fn is_strict(&self) -> bool {
+        matches!(self.fallback_strategy, ProtocolFallback::None)
+    }
+}
+
+impl ProtocolConfig {
+    /// Restrict a client builder to the preferred version: HTTP/1.1 disables HTTP/2,
+    /// and HTTP/2 without fallback uses prior knowledge so it never downgrades
+    pub(crate) fn apply_to(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        let builder = match self.preferred_version {
+            HttpVersion::Http1 => return builder.http1_only(),
+            HttpVersion::Http3 => return builder,
+            HttpVersion::Http2 if self.is_strict() => builder.http2_prior_knowledge(),
+            HttpVersion::Http2 | HttpVersion::Auto if self.enable_http2_prior_knowledge => {
+                builder.http2_prior_knowledge()
+            }
+            HttpVersion::Http2 | HttpVersion::Auto => builder,
+        };
+        self.http2_settings.apply_to(builder)
+    }
 }
 
 /// Rate limiting algorithm types
 #[pyclass]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum RateLimitAlgorithm {
     TokenBucket,
     SlidingWindow,
     FixedWindow,
+    LeakyBucket,
 }
 
 #[pymethods]
@@ -1425,11 +2441,17 @@ impl RateLimitAlgorithm {
     fn FIXED_WINDOW() -> RateLimitAlgorithm {
         RateLimitAlgorithm::FixedWindow
     }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn LEAKY_BUCKET() -> RateLimitAlgorithm {
+        RateLimitAlgorithm::LeakyBucket
+    }
 }
 
 /// Rate limiting configuration for HTTP requests
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RateLimitConfig {
     #[pyo3(get, set)]
     pub enabled: bool,
@@ -1466,6 +2488,36 @@ pub struct RateLimitConfig {
 
     #[pyo3(get, set)]
     pub queue_timeout_seconds: f64,
+
+    /// `(glob_pattern, config)` pairs matched in order against the request path.
+    /// The first matching rule limits the request with its own buckets; unmatched
+    /// paths use this config. `*` matches any run of characters, `?` exactly one.
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub endpoint_rules: Vec<(String, RateLimitConfig)>,
+
+    /// Adapt the rate to server feedback: each 429 multiplies the effective rate
+    /// by `backoff_factor` (and pauses for any `Retry-After`), each successful
+    /// response adds back `recovery_step * requests_per_second`
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub adaptive: bool,
+
+    #[pyo3(get, set)]
+    #[serde(default = "default_backoff_factor")]
+    pub backoff_factor: f64,
+
+    #[pyo3(get, set)]
+    #[serde(default = "default_recovery_step")]
+    pub recovery_step: f64,
+}
+
+fn default_backoff_factor() -> f64 {
+    0.5
+}
+
+fn default_recovery_step() -> f64 {
+    0.05
 }
 
 #[pymethods]
@@ -1483,7 +2535,11 @@ impl RateLimitConfig {
         reset_on_success = false,
         queue_requests = true,
         max_queue_size = 100,
-        queue_timeout_seconds = 30.0
+        queue_timeout_seconds = 30.0,
+        endpoint_rules = None,
+        adaptive = false,
+        backoff_factor = 0.5,
+        recovery_step = 0.05
     ))]
     pub fn new(
         enabled: bool,
@@ -1498,6 +2554,10 @@ impl RateLimitConfig {
         queue_requests: bool,
         max_queue_size: usize,
         queue_timeout_seconds: f64,
+        endpoint_rules: Option<Vec<(String, RateLimitConfig)>>,
+        adaptive: bool,
+        backoff_factor: f64,
+        recovery_step: f64,
     ) -> Self {
         RateLimitConfig {
             enabled,
@@ -1512,6 +2572,10 @@ impl RateLimitConfig {
             queue_requests,
             max_queue_size,
             queue_timeout_seconds,
+            endpoint_rules: endpoint_rules.unwrap_or_default(),
+            adaptive,
+            backoff_factor,
+            recovery_step,
         }
     }
 
@@ -1531,6 +2595,10 @@ impl RateLimitConfig {
             queue_requests: true,
             max_queue_size: 50,
             queue_timeout_seconds: 30.0,
+            endpoint_rules: Vec::new(),
+            adaptive: false,
+            backoff_factor: default_backoff_factor(),
+            recovery_step: default_recovery_step(),
         }
     }
 
@@ -1550,6 +2618,10 @@ impl RateLimitConfig {
             queue_requests: true,
             max_queue_size: 100,
             queue_timeout_seconds: 30.0,
+            endpoint_rules: Vec::new(),
+            adaptive: false,
+            backoff_factor: default_backoff_factor(),
+            recovery_step: default_recovery_step(),
         }
     }
 
@@ -1569,6 +2641,10 @@ impl RateLimitConfig {
             queue_requests: true,
             max_queue_size: 500,
             queue_timeout_seconds: 60.0,
+            endpoint_rules: Vec::new(),
+            adaptive: false,
+            backoff_factor: default_backoff_factor(),
+            recovery_step: default_recovery_step(),
         }
     }
 
@@ -1588,6 +2664,10 @@ impl RateLimitConfig {
             queue_requests: false,
             max_queue_size: 0,
             queue_timeout_seconds: 0.0,
+            endpoint_rules: Vec::new(),
+            adaptive: false,
+            backoff_factor: default_backoff_factor(),
+            recovery_step: default_recovery_step(),
         }
     }
 
@@ -1615,7 +2695,284 @@ impl RateLimitConfig {
                     "max_queue_size must be positive when queue_requests is enabled".to_string(),
                 ));
             }
+            if self.adaptive && !(self.backoff_factor > 0.0 && self.backoff_factor < 1.0) {
+                return Err(UltraFastError::ConfigError(
+                    "backoff_factor must be between 0 and 1 (exclusive)".to_string(),
+                ));
+            }
+            if self.adaptive && self.recovery_step <= 0.0 {
+                return Err(UltraFastError::ConfigError(
+                    "recovery_step must be positive when adaptive is enabled".to_string(),
+                ));
+            }
+        }
+        for (pattern, rule) in &self.endpoint_rules {
+            if pattern.is_empty() {
+                return Err(UltraFastError::ConfigError(
+                    "endpoint rule patterns must not be empty".to_string(),
+                ));
+            }
+            if !rule.endpoint_rules.is_empty() {
+                return Err(UltraFastError::ConfigError(format!(
+                    "endpoint rule '{}' cannot have endpoint rules of its own",
+                    pattern
+                )));
+            }
+            rule.validate()?;
         }
         Ok(())
     }
 }
+
+/// Marker written in place of secrets when exporting a client configuration
+pub(crate) const REDACTED: &str = "<redacted>";
+
+/// Credential keys that are safe to export without `include_secrets`
+const NON_SECRET_CREDENTIALS: &[&str] = &[
+    "username",
+    "header_name",
+    "param_name",
+    "token_url",
+    "client_id",
+    "scopes",
+    "grant_type",
+    "redirect_uri",
+    "access_key",
+    "region",
+    "service",
+    "domain",
+];
+
+/// Fully serializable snapshot of a client configuration
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct ClientConfigSnapshot {
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub default_params: HashMap<String, String>,
+    pub timeout: f64,
+    pub body_mode: String,
+    pub auth: Option<AuthConfig>,
+    pub retry: Option<RetryConfig>,
+    pub timeouts: TimeoutConfig,
+    pub pool: PoolConfig,
+    pub ssl: SSLConfig,
+    pub proxy: Option<ProxyConfig>,
+    pub compression: CompressionConfig,
+    pub protocol: ProtocolConfig,
+    pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default = "default_allow_redirects")]
+    pub allow_redirects: bool,
+    #[serde(default = "default_max_redirects")]
+    pub max_redirects: usize,
+    #[serde(default)]
+    pub dns_cache_ttl: Option<f64>,
+    #[serde(default = "default_dns_cache_max_entries")]
+    pub dns_cache_max_entries: usize,
+    #[serde(default)]
+    pub unix_socket: Option<String>,
+    #[serde(default)]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default = "default_ip_version")]
+    pub ip_version: String,
+    #[serde(default)]
+    pub max_response_size: Option<usize>,
+    #[serde(default = "default_cleanup_interval")]
+    pub cleanup_interval: Option<f64>,
+}
+
+/// Redirects followed before raising `TooManyRedirects`, unless configured otherwise
+pub(crate) const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+/// Seconds between background sweeps of expired connections and tokens
+pub(crate) const DEFAULT_CLEANUP_INTERVAL: f64 = 300.0;
+
+fn default_allow_redirects() -> bool {
+    true
+}
+
+fn default_max_redirects() -> usize {
+    DEFAULT_MAX_REDIRECTS
+}
+
+fn default_dns_cache_max_entries() -> usize {
+    crate::dns_cache::DEFAULT_MAX_ENTRIES
+}
+
+fn default_ip_version() -> String {
+    "auto".to_string()
+}
+
+fn default_cleanup_interval() -> Option<f64> {
+    Some(DEFAULT_CLEANUP_INTERVAL)
+}
+
+impl ClientConfigSnapshot {
+    /// Replace auth credentials, the proxy password and client key with the redaction marker
+    pub(crate) fn redact_secrets(&mut self) {
+        if let Some(auth) = self.auth.as_mut() {
+            for (key, value) in auth.credentials.iter_mut() {
+                if !NON_SECRET_CREDENTIALS.contains(&key.as_str()) {
+                    *value = REDACTED.to_string();
+                }
+            }
+        }
+        if let Some(proxy) = self.proxy.as_mut() {
+            if proxy.password.is_some() {
+                proxy.password = Some(REDACTED.to_string());
+            }
+        }
+        if self.ssl.key_pem.is_some() {
+            self.ssl.key_pem = Some(REDACTED.to_string());
+        }
+    }
+
+    /// Drop redacted secrets so an imported client never sends the marker
+    pub(crate) fn strip_redacted(&mut self) {
+        let auth_redacted = self
+            .auth
+            .as_ref()
+            .is_some_and(|auth| auth.credentials.values().any(|v| v == REDACTED));
+        if auth_redacted {
+            self.auth = None;
+        }
+        if let Some(proxy) = self.proxy.as_mut() {
+            if proxy.password.as_deref() == Some(REDACTED) {
+                proxy.password = None;
+            }
+        }
+        if self.ssl.key_pem.as_deref() == Some(REDACTED) {
+            self.ssl.cert_pem = None;
+            self.ssl.key_pem = None;
+        }
+    }
+
+    /// Convert to a JSON-compatible Python dict
+    pub(crate) fn to_py(&self, py: Python) -> PyResult<PyObject> {
+        Ok(pythonize::pythonize(py, self)?)
+    }
+
+    /// Parse from a Python dict produced by `to_py`
+    pub(crate) fn from_py(config: &PyAny) -> PyResult<Self> {
+        let snapshot: Self = pythonize::depythonize(config).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Invalid client configuration: {}", e))
+        })?;
+        snapshot.compression.validate()?;
+        snapshot.pool.validate()?;
+        if let Some(circuit_breaker) = &snapshot.circuit_breaker {
+            circuit_breaker.validate()?;
+        }
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn compression(algorithm: &str) -> CompressionConfig {
+        CompressionConfig::new(
+            true,
+            true,
+            Some(vec![algorithm.to_string()]),
+            Some(6),
+            0,
+            false,
+        )
+        .unwrap()
+    }
+
+    fn payload() -> Vec<u8> {
+        br#"{"items": ["alpha", "beta", "gamma"]}"#.repeat(64)
+    }
+
+    #[test]
+    fn test_gzip_round_trip() {
+        let compressed = compression("gzip")
+            .compress_request_body(&payload(), "gzip")
+            .unwrap();
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, payload());
+    }
+
+    #[test]
+    fn test_deflate_round_trip() {
+        let compressed = compression("deflate")
+            .compress_request_body(&payload(), "deflate")
+            .unwrap();
+        let mut decoded = Vec::new();
+        flate2::read::DeflateDecoder::new(&compressed[..])
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, payload());
+    }
+
+    #[test]
+    fn test_brotli_round_trip() {
+        let compressed = compression("brotli")
+            .compress_request_body(&payload(), "brotli")
+            .unwrap();
+        let mut decoded = Vec::new();
+        brotli::Decompressor::new(&compressed[..], 4096)
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, payload());
+    }
+
+    #[test]
+    fn test_zstd_round_trip() {
+        let config = compression("zstd");
+        let compressed = config.compress_request_body(&payload(), "zstd").unwrap();
+        assert!(compressed.len() < payload().len());
+        assert_eq!(zstd::decode_all(&compressed[..]).unwrap(), payload());
+        assert_eq!(content_encoding_name("zstd"), "zstd");
+        assert_eq!(config.get_accept_encoding_header(), "zstd");
+    }
+
+    #[test]
+    fn test_compression_level_validated_per_algorithm() {
+        let level = |algorithms: &[&str], level| {
+            CompressionConfig::new(
+                true,
+                true,
+                Some(algorithms.iter().map(|a| a.to_string()).collect()),
+                Some(level),
+                0,
+                false,
+            )
+        };
+        assert!(level(&["gzip", "deflate"], 9).is_ok());
+        assert!(level(&["gzip"], 10).is_err());
+        assert!(level(&["deflate"], 0).is_err());
+        assert!(level(&["brotli"], 11).is_ok());
+        assert!(level(&["brotli"], 12).is_err());
+        assert!(level(&["zstd"], 19).is_ok());
+        assert!(level(&["gzip", "brotli"], 10).is_err());
+    }
+
+    #[test]
+    fn test_unset_level_uses_algorithm_default() {
+        let config = CompressionConfig::new(true, true, None, None, 0, false).unwrap();
+        assert_eq!(config.level_for("gzip"), 6);
+        assert_eq!(config.level_for("brotli"), 4);
+        assert_eq!(config.level_for("zstd"), 3);
+        for algorithm in ["gzip", "deflate", "brotli", "zstd"] {
+            assert!(config.compress_request_body(&payload(), algorithm).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_default_algorithms_include_zstd() {
+        let config = CompressionConfig::new(false, true, None, None, 1024, false).unwrap();
+        assert!(config.supports_algorithm("zstd"));
+        assert_eq!(
+            config.get_accept_encoding_header(),
+            "gzip, deflate, br, zstd"
+        );
+    }
+}
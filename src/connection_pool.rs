@@ -1,93 +1,212 @@
 use ahash::AHashMap;
 use crossbeam::queue::SegQueue;
-use parking_lot::RwLock; // Mutex currently unused
-use pyo3::PyResult;
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use parking_lot::{Mutex, RwLock};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Semaphore;
 
 /// High-performance connection pool with lock-free operations where possible
 pub struct FastConnectionPool {
-    // Use RwLock for read-heavy operations (checking pool status)
-    pool_state: Arc<RwLock<PoolState>>,
+    // State shared with outstanding permits, which report back when dropped
+    shared: Arc<PoolShared>,
     // Semaphore for connection limiting without locks
     connection_semaphore: Arc<Semaphore>,
-    // Lock-free metrics using atomics
-    active_connections: Arc<AtomicUsize>,
-    total_connections_created: Arc<AtomicU64>,
-    total_connections_reused: Arc<AtomicU64>,
     max_connections: usize,
+    // Requests in flight per host, bounded by `max_connections_per_host`
+    host_slots: Mutex<AHashMap<String, Arc<Semaphore>>>,
+    max_per_host: AtomicUsize,
+}
+
+struct PoolShared {
+    // Use RwLock for read-heavy operations (checking pool status)
+    state: RwLock<PoolState>,
+    // Lock-free metrics using atomics
+    active_connections: AtomicUsize,
+    total_acquired: AtomicU64,
+    total_connections_created: AtomicU64,
+    total_connections_reused: AtomicU64,
     max_idle_time: Duration,
     // Lock-free idle connection queue
-    idle_queue: Arc<SegQueue<IdleConnection>>,
+    idle_queue: SegQueue<IdleConnection>,
 }
 
 struct PoolState {
     // Use AHashMap for better performance
     active_connections: AHashMap<String, ConnectionInfo>,
     host_stats: AHashMap<String, HostStats>,
-    // Keep track of connection distribution per host
-    host_connection_count: AHashMap<String, usize>,
 }
 
 #[derive(Clone)]
 struct ConnectionInfo {
     host: String,
-    created_at: Instant,
     last_used: Instant,
     request_count: u64,
-    protocol_version: String,
-    is_http3: bool,
 }
 
 struct IdleConnection {
     info: ConnectionInfo,
     available_since: Instant,
-    connection_id: u64,
 }
 
 #[derive(Default)]
 struct HostStats {
     total_connections: u64,
     active_connections: u64,
+    idle_connections: u64,
     failed_connections: u64,
     average_response_time: f64,
     last_failure: Option<Instant>,
     success_rate: f64,
-    preferred_protocol: Option<String>,
+}
+
+impl PoolShared {
+    /// Park a finished connection so the next request to its host can reuse it
+    fn push_idle(&self, info: ConnectionInfo) {
+        self.state
+            .write()
+            .host_stats
+            .entry(info.host.clone())
+            .or_default()
+            .idle_connections += 1;
+        self.idle_queue.push(IdleConnection {
+            available_since: Instant::now(),
+            info,
+        });
+    }
+
+    fn forget_idle(&self, host: &str) {
+        if let Some(stats) = self.state.write().host_stats.get_mut(host) {
+            stats.idle_connections = stats.idle_connections.saturating_sub(1);
+        }
+    }
+
+    /// Take a fresh idle connection for `host`, dropping expired ones on the way
+    fn take_idle(&self, host: &str) -> Option<ConnectionInfo> {
+        let now = Instant::now();
+        let mut found = None;
+        // Bound the scan so connections put back for other hosts aren't revisited
+        for _ in 0..self.idle_queue.len() {
+            let Some(idle_conn) = self.idle_queue.pop() else {
+                break;
+            };
+            let expired = now.duration_since(idle_conn.available_since) >= self.max_idle_time;
+            if expired || (found.is_none() && idle_conn.info.host == host) {
+                self.forget_idle(&idle_conn.info.host);
+                if !expired {
+                    found = Some(idle_conn.info);
+                }
+            } else {
+                self.idle_queue.push(idle_conn);
+            }
+        }
+        found
+    }
 }
 
 impl FastConnectionPool {
     pub fn new(max_connections: usize, max_idle_time: Duration) -> Self {
         Self {
-            pool_state: Arc::new(RwLock::new(PoolState {
-                active_connections: AHashMap::new(),
-                host_stats: AHashMap::new(),
-                host_connection_count: AHashMap::new(),
-            })),
+            shared: Arc::new(PoolShared {
+                state: RwLock::new(PoolState {
+                    active_connections: AHashMap::new(),
+                    host_stats: AHashMap::new(),
+                }),
+                active_connections: AtomicUsize::new(0),
+                total_acquired: AtomicU64::new(0),
+                total_connections_created: AtomicU64::new(0),
+                total_connections_reused: AtomicU64::new(0),
+                max_idle_time,
+                idle_queue: SegQueue::new(),
+            }),
             connection_semaphore: Arc::new(Semaphore::new(max_connections)),
-            active_connections: Arc::new(AtomicUsize::new(0)),
-            total_connections_created: Arc::new(AtomicU64::new(0)),
-            total_connections_reused: Arc::new(AtomicU64::new(0)),
             max_connections,
-            max_idle_time,
-            idle_queue: Arc::new(SegQueue::new()),
+            host_slots: Mutex::new(AHashMap::new()),
+            max_per_host: AtomicUsize::new(Semaphore::MAX_PERMITS),
+        }
+    }
+
+    /// Bound the requests in flight to any one host; `None` lifts the bound.
+    /// Requests already holding a slot keep it.
+    pub fn set_max_connections_per_host(&self, limit: Option<usize>) {
+        self.max_per_host
+            .store(limit.unwrap_or(Semaphore::MAX_PERMITS), Ordering::Relaxed);
+        self.host_slots.lock().clear();
+    }
+
+    fn host_semaphore(&self, host: &str) -> Arc<Semaphore> {
+        let limit = self.max_per_host.load(Ordering::Relaxed);
+        let mut slots = self.host_slots.lock();
+        Arc::clone(
+            slots
+                .entry(host.to_string())
+                .or_insert_with(|| Arc::new(Semaphore::new(limit))),
+        )
+    }
+
+    /// Take a slot for `host` if it has fewer than `max_connections_per_host` requests in flight
+    pub fn try_acquire_host_slot(&self, host: &str) -> Option<HostSlot> {
+        let permit = self.host_semaphore(host).try_acquire_owned().ok()?;
+        Some(HostSlot { _permit: permit })
+    }
+
+    /// Wait up to `timeout` for a slot for `host`
+    pub(crate) async fn acquire_host_slot(
+        &self,
+        host: &str,
+        timeout: Duration,
+    ) -> PyResult<HostSlot> {
+        let semaphore = self.host_semaphore(host);
+        match tokio::time::timeout(timeout, semaphore.acquire_owned()).await {
+            Ok(Ok(permit)) => Ok(HostSlot { _permit: permit }),
+            Ok(Err(e)) => Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                "Failed to acquire connection slot: {}",
+                e
+            ))),
+            Err(_) => Err(crate::error::timeout_error(format!(
+                "Timed out after {:.1}s waiting for a connection to {}",
+                timeout.as_secs_f64(),
+                host
+            ))),
+        }
+    }
+
+    /// Requests holding a slot, for hosts with any in flight
+    pub fn host_connections_in_use(&self) -> AHashMap<String, usize> {
+        let limit = self.max_per_host.load(Ordering::Relaxed);
+        self.host_slots
+            .lock()
+            .iter()
+            .map(|(host, slots)| (host.clone(), limit - slots.available_permits()))
+            .filter(|(_, in_use)| *in_use > 0)
+            .collect()
+    }
+
+    fn permit(&self, permit: tokio::sync::OwnedSemaphorePermit) -> ConnectionPermit {
+        self.shared
+            .active_connections
+            .fetch_add(1, Ordering::Relaxed);
+        self.shared.total_acquired.fetch_add(1, Ordering::Relaxed);
+        ConnectionPermit {
+            permit: Some(permit),
+            shared: Arc::clone(&self.shared),
+            host: Mutex::new(None),
+            failed: AtomicBool::new(false),
         }
     }
 
     /// Try to acquire a connection permit without blocking
     pub async fn try_acquire_connection(&self) -> Option<ConnectionPermit> {
         match Arc::clone(&self.connection_semaphore).try_acquire_owned() {
-            Ok(permit) => Some(ConnectionPermit {
-                permit: Some(permit),
-                pool: Arc::clone(&self.pool_state),
-            }),
+            Ok(permit) => Some(self.permit(permit)),
             Err(_) => None,
         }
     }
 
     /// Acquire a connection permit (may wait)
+    #[allow(dead_code)]
     pub(crate) async fn acquire_connection(&self) -> PyResult<ConnectionPermit> {
         let permit = Arc::clone(&self.connection_semaphore)
             .acquire_owned()
@@ -98,121 +217,113 @@ impl FastConnectionPool {
                     e
                 ))
             })?;
-        Ok(ConnectionPermit {
-            permit: Some(permit),
-            pool: Arc::clone(&self.pool_state),
-        })
-    }
-
-    /// Track connection usage for performance optimization
-    pub async fn track_connection_usage(&self) {
-        // This method provides connection usage tracking for performance monitoring
-        // It's a lightweight operation that can be called after successful requests
-        let state = self.pool_state.read();
-        // Update internal metrics for connection efficiency
-        // In a production implementation, this could update metrics/telemetry
+        Ok(self.permit(permit))
     }
 
     /// Get pool statistics without acquiring locks for long
     pub fn get_stats(&self) -> PoolStats {
-        let state = self.pool_state.read();
-        let total_active = self.active_connections.load(Ordering::Relaxed);
-        let total_idle = self.idle_queue.len();
+        self.cleanup_expired();
 
         PoolStats {
-            active_connections: total_active,
-            idle_connections: total_idle,
+            active_connections: self.shared.active_connections.load(Ordering::Relaxed),
+            idle_connections: self.shared.idle_queue.len(),
             total_capacity: self.max_connections,
-            host_count: state.host_stats.len(),
-            connections_created: self.total_connections_created.load(Ordering::Relaxed),
-            connections_reused: self.total_connections_reused.load(Ordering::Relaxed),
+            total_acquired: self.shared.total_acquired.load(Ordering::Relaxed),
+            connections_created: self
+                .shared
+                .total_connections_created
+                .load(Ordering::Relaxed),
+            connections_reused: self.shared.total_connections_reused.load(Ordering::Relaxed),
         }
     }
 
+    /// Per-host connection counts, sorted by host
+    pub fn host_stats(&self) -> Vec<(String, HostPoolStats)> {
+        self.cleanup_expired();
+        let state = self.shared.state.read();
+        let mut hosts: Vec<(String, HostPoolStats)> = state
+            .host_stats
+            .iter()
+            .map(|(host, stats)| {
+                (
+                    host.clone(),
+                    HostPoolStats {
+                        active_connections: stats.active_connections,
+                        idle_connections: stats.idle_connections,
+                        total_acquired: stats.total_connections,
+                        failed_connections: stats.failed_connections,
+                    },
+                )
+            })
+            .collect();
+        hosts.sort_by(|a, b| a.0.cmp(&b.0));
+        hosts
+    }
+
     /// Clean up expired idle connections using lock-free queue
     pub fn cleanup_expired(&self) {
         let now = Instant::now();
-        let mut expired_count = 0;
+
+        // Forget the slots of hosts with nothing in flight or waiting
+        let limit = self.max_per_host.load(Ordering::Relaxed);
+        self.host_slots
+            .lock()
+            .retain(|_, slots| Arc::strong_count(slots) > 1 || slots.available_permits() < limit);
 
         // Process items from the queue
-        while let Some(conn) = self.idle_queue.pop() {
-            if now.duration_since(conn.available_since) < self.max_idle_time {
+        while let Some(conn) = self.shared.idle_queue.pop() {
+            if now.duration_since(conn.available_since) < self.shared.max_idle_time {
                 // Put it back if not expired
-                self.idle_queue.push(conn);
+                self.shared.idle_queue.push(conn);
                 break; // Assume queue is roughly ordered by time
-            } else {
-                expired_count += 1;
-            }
-        }
-
-        if expired_count > 0 {
-            // Update host stats
-            let mut state = self.pool_state.write();
-            for stats in state.host_stats.values_mut() {
-                if stats.active_connections > 0 {
-                    stats.total_connections = stats.total_connections.saturating_sub(expired_count);
-                }
             }
+            self.shared.forget_idle(&conn.info.host);
         }
     }
 
-    /// Try to get an idle connection for reuse
-    pub fn try_reuse_connection(&self, host: &str) -> Option<ConnectionInfo> {
-        // Try to find an idle connection for this host
-        while let Some(idle_conn) = self.idle_queue.pop() {
-            if idle_conn.info.host == host {
-                let now = Instant::now();
-                if now.duration_since(idle_conn.available_since) < self.max_idle_time {
-                    self.total_connections_reused
-                        .fetch_add(1, Ordering::Relaxed);
-                    return Some(idle_conn.info);
-                }
-            } else {
-                // Put back if it's for a different host and not expired
-                let now = Instant::now();
-                if now.duration_since(idle_conn.available_since) < self.max_idle_time {
-                    self.idle_queue.push(idle_conn);
-                }
-            }
+    /// Drop every idle connection, expired or not
+    pub fn drain_idle(&self) {
+        while let Some(conn) = self.shared.idle_queue.pop() {
+            self.shared.forget_idle(&conn.info.host);
         }
-        None
-    }
-
-    /// Return a connection to the idle pool
-    pub fn return_connection(&self, info: ConnectionInfo) {
-        let idle_conn = IdleConnection {
-            connection_id: self.total_connections_created.load(Ordering::Relaxed),
-            available_since: Instant::now(),
-            info,
-        };
-        self.idle_queue.push(idle_conn);
-        self.active_connections.fetch_sub(1, Ordering::Relaxed);
     }
 }
 
 pub struct ConnectionPermit {
     permit: Option<tokio::sync::OwnedSemaphorePermit>,
-    pool: Arc<RwLock<PoolState>>,
+    shared: Arc<PoolShared>,
+    // Host the permit was used for; its connection goes idle on drop
+    host: Mutex<Option<String>>,
+    failed: AtomicBool,
 }
 
 impl ConnectionPermit {
     pub fn mark_active(&self, host: String) {
-        let mut state = self.pool.write();
+        // Reuse a parked connection to the same host when there is one
+        let reused = self.shared.take_idle(&host).is_some();
+        let counter = if reused {
+            &self.shared.total_connections_reused
+        } else {
+            &self.shared.total_connections_created
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        let mut state = self.shared.state.write();
         let info = ConnectionInfo {
             host: host.clone(),
-            created_at: Instant::now(),
             last_used: Instant::now(),
             request_count: 0,
-            protocol_version: "HTTP/1.1".to_string(),
-            is_http3: false,
         };
 
         state.active_connections.insert(host.clone(), info);
-        state.host_stats.entry(host).or_default().active_connections += 1;
+        let stats = state.host_stats.entry(host.clone()).or_default();
+        stats.active_connections += 1;
+        stats.total_connections += 1;
+        *self.host.lock() = Some(host);
     }
 
     pub fn mark_used(&self, host: &str, response_time: Duration) {
-        let mut state = self.pool.write();
+        let mut state = self.shared.state.write();
         if let Some(conn) = state.active_connections.get_mut(host) {
             conn.last_used = Instant::now();
             conn.request_count += 1;
@@ -228,8 +339,16 @@ impl ConnectionPermit {
         }
     }
 
+    /// Start an exchange over this permit's connection. Unless the returned guard is
+    /// completed the connection is not parked for reuse: a request future dropped
+    /// mid-flight, e.g. by a cancelled asyncio task, leaves its connection closed.
+    pub fn begin_exchange(&self) -> Exchange<'_> {
+        Exchange { permit: Some(self) }
+    }
+
     pub fn mark_failed(&self, host: &str) {
-        let mut state = self.pool.write();
+        self.failed.store(true, Ordering::Relaxed);
+        let mut state = self.shared.state.write();
         if let Some(stats) = state.host_stats.get_mut(host) {
             stats.failed_connections += 1;
             stats.last_failure = Some(Instant::now());
@@ -239,12 +358,53 @@ impl ConnectionPermit {
     }
 }
 
+/// A request's claim on one of its host's connection slots, released on drop
+pub struct HostSlot {
+    _permit: tokio::sync::OwnedSemaphorePermit,
+}
+
+/// An exchange in progress over a `ConnectionPermit`, see `begin_exchange`
+pub struct Exchange<'a> {
+    permit: Option<&'a ConnectionPermit>,
+}
+
+impl Exchange<'_> {
+    /// The response arrived in full, so the connection can be reused
+    pub fn complete(mut self) {
+        self.permit = None;
+    }
+}
+
+impl Drop for Exchange<'_> {
+    fn drop(&mut self) {
+        if let Some(permit) = self.permit {
+            permit.failed.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
 impl Drop for ConnectionPermit {
     fn drop(&mut self) {
-        // Permit is automatically returned to semaphore when dropped
-        if let Some(_permit) = self.permit.take() {
-            // Cleanup happens automatically
+        self.shared
+            .active_connections
+            .fetch_sub(1, Ordering::Relaxed);
+
+        if let Some(host) = self.host.get_mut().take() {
+            let info = {
+                let mut state = self.shared.state.write();
+                if let Some(stats) = state.host_stats.get_mut(&host) {
+                    stats.active_connections = stats.active_connections.saturating_sub(1);
+                }
+                state.active_connections.get(&host).cloned()
+            };
+            // A failed connection is not kept alive for reuse
+            if let (false, Some(info)) = (*self.failed.get_mut(), info) {
+                self.shared.push_idle(info);
+            }
         }
+
+        // The semaphore permit is returned when dropped
+        self.permit.take();
     }
 }
 
@@ -253,11 +413,19 @@ pub struct PoolStats {
     pub active_connections: usize,
     pub idle_connections: usize,
     pub total_capacity: usize,
-    pub host_count: usize,
+    pub total_acquired: u64,
     pub connections_created: u64,
     pub connections_reused: u64,
 }
 
+#[derive(Debug, Clone)]
+pub struct HostPoolStats {
+    pub active_connections: u64,
+    pub idle_connections: u64,
+    pub total_acquired: u64,
+    pub failed_connections: u64,
+}
+
 /// Fast host-based connection multiplexer
 pub struct ConnectionMultiplexer {
     host_pools: Arc<RwLock<AHashMap<String, Arc<FastConnectionPool>>>>,
@@ -275,6 +443,7 @@ impl ConnectionMultiplexer {
     }
 
     /// Get or create a connection pool for a specific host
+    #[allow(dead_code)]
     pub fn get_pool(&self, host: &str) -> Arc<FastConnectionPool> {
         // Fast path: check if pool exists with read lock
         {
@@ -304,19 +473,16 @@ impl ConnectionMultiplexer {
         let pools = self.host_pools.read();
         let mut total_active = 0;
         let mut total_idle = 0;
-        let mut total_capacity = 0;
 
         for pool in pools.values() {
             let stats = pool.get_stats();
             total_active += stats.active_connections;
             total_idle += stats.idle_connections;
-            total_capacity += stats.total_capacity;
         }
 
         AggregateStats {
             total_active_connections: total_active,
             total_idle_connections: total_idle,
-            total_capacity,
             host_count: pools.len(),
         }
     }
@@ -334,10 +500,64 @@ impl ConnectionMultiplexer {
 pub struct AggregateStats {
     pub total_active_connections: usize,
     pub total_idle_connections: usize,
-    pub total_capacity: usize,
     pub host_count: usize,
 }
 
+/// Pool utilisation as a Python dict, shared by both clients' `get_pool_stats`
+pub(crate) fn pool_stats_dict(
+    py: Python,
+    pool: &FastConnectionPool,
+    multiplexer: &ConnectionMultiplexer,
+) -> PyResult<PyObject> {
+    let stats = pool.get_stats();
+    let dict = PyDict::new(py);
+    dict.set_item("active_connections", stats.active_connections)?;
+    dict.set_item("idle_connections", stats.idle_connections)?;
+    dict.set_item("total_acquired", stats.total_acquired)?;
+    dict.set_item("max_connections", stats.total_capacity)?;
+    dict.set_item("connections_created", stats.connections_created)?;
+    dict.set_item("connections_reused", stats.connections_reused)?;
+
+    let mut in_use = pool.host_connections_in_use();
+    let hosts = PyDict::new(py);
+    for (host, host_stats) in pool.host_stats() {
+        let entry = PyDict::new(py);
+        entry.set_item("active_connections", host_stats.active_connections)?;
+        entry.set_item("idle_connections", host_stats.idle_connections)?;
+        entry.set_item("total_acquired", host_stats.total_acquired)?;
+        entry.set_item("failed_connections", host_stats.failed_connections)?;
+        entry.set_item("in_use", in_use.remove(&host).unwrap_or(0))?;
+        hosts.set_item(host, entry)?;
+    }
+    // Hosts whose requests run without a pool permit still count against their limit
+    for (host, count) in in_use {
+        let entry = PyDict::new(py);
+        for key in [
+            "active_connections",
+            "idle_connections",
+            "total_acquired",
+            "failed_connections",
+        ] {
+            entry.set_item(key, 0)?;
+        }
+        entry.set_item("in_use", count)?;
+        hosts.set_item(host, entry)?;
+    }
+    dict.set_item("hosts", hosts)?;
+
+    let multiplexed = multiplexer.get_aggregate_stats();
+    dict.set_item("multiplexed_hosts", multiplexed.host_count)?;
+    dict.set_item(
+        "multiplexed_active_connections",
+        multiplexed.total_active_connections,
+    )?;
+    dict.set_item(
+        "multiplexed_idle_connections",
+        multiplexed.total_idle_connections,
+    )?;
+    Ok(dict.into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -360,12 +580,35 @@ mod tests {
         assert!(pool.try_acquire_connection().await.is_some());
     }
 
+    #[tokio::test]
+    async fn test_host_slots_bound_requests_per_host() {
+        let pool = FastConnectionPool::new(10, Duration::from_secs(60));
+        pool.set_max_connections_per_host(Some(1));
+
+        let slot = pool.try_acquire_host_slot("a.example").unwrap();
+        assert!(pool.try_acquire_host_slot("a.example").is_none());
+        let _other = pool.try_acquire_host_slot("b.example").unwrap();
+        assert_eq!(pool.host_connections_in_use().get("a.example"), Some(&1));
+
+        drop(slot);
+        assert!(pool
+            .acquire_host_slot("a.example", Duration::from_millis(20))
+            .await
+            .is_ok());
+
+        pool.set_max_connections_per_host(None);
+        let _slots: Vec<_> = (0..100)
+            .map(|_| pool.try_acquire_host_slot("a.example").unwrap())
+            .collect();
+        assert_eq!(pool.host_connections_in_use().get("a.example"), Some(&100));
+    }
+
     #[tokio::test]
     async fn test_multiplexer() {
         let multiplexer = ConnectionMultiplexer::new(10, Duration::from_secs(60));
 
         let pool1 = multiplexer.get_pool("example.com");
-        let pool2 = multiplexer.get_pool("api.example.com");
+        let _pool2 = multiplexer.get_pool("api.example.com");
         let pool1_again = multiplexer.get_pool("example.com");
 
         // Should return the same pool for the same host
@@ -395,4 +638,74 @@ mod tests {
         let stats = pool.get_stats();
         assert_eq!(stats.active_connections, 0);
     }
+
+    #[tokio::test]
+    async fn test_released_connections_are_idle_until_reused() {
+        let pool = FastConnectionPool::new(5, Duration::from_secs(60));
+
+        let permit = pool.acquire_connection().await.unwrap();
+        permit.mark_active("a.com".to_string());
+        drop(permit);
+
+        let stats = pool.get_stats();
+        assert_eq!(stats.idle_connections, 1);
+        assert_eq!(stats.connections_created, 1);
+        let hosts = pool.host_stats();
+        assert_eq!(hosts[0].0, "a.com");
+        assert_eq!(hosts[0].1.idle_connections, 1);
+        assert_eq!(hosts[0].1.active_connections, 0);
+
+        // A different host doesn't reuse it; the same host does
+        let other = pool.acquire_connection().await.unwrap();
+        other.mark_active("b.com".to_string());
+        other.mark_failed("b.com");
+        let again = pool.acquire_connection().await.unwrap();
+        again.mark_active("a.com".to_string());
+        assert_eq!(pool.get_stats().connections_reused, 1);
+        assert_eq!(pool.get_stats().active_connections, 2);
+        drop(other);
+        drop(again);
+
+        // Failed connections aren't parked for reuse
+        let stats = pool.get_stats();
+        assert_eq!(stats.total_acquired, 3);
+        assert_eq!(stats.idle_connections, 1);
+        let hosts = pool.host_stats();
+        assert_eq!(hosts[1].1.failed_connections, 1);
+        assert_eq!(hosts[1].1.idle_connections, 0);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_exchange_is_not_reused() {
+        let pool = FastConnectionPool::new(5, Duration::from_secs(60));
+
+        let permit = pool.acquire_connection().await.unwrap();
+        permit.mark_active("a.com".to_string());
+        let exchange = permit.begin_exchange();
+        drop(exchange);
+        drop(permit);
+
+        let stats = pool.get_stats();
+        assert_eq!(stats.active_connections, 0);
+        assert_eq!(stats.idle_connections, 0);
+        assert_eq!(pool.host_stats()[0].1.failed_connections, 0);
+
+        let permit = pool.acquire_connection().await.unwrap();
+        permit.mark_active("a.com".to_string());
+        permit.begin_exchange().complete();
+        drop(permit);
+        assert_eq!(pool.get_stats().idle_connections, 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_idle_connections_are_dropped() {
+        let pool = FastConnectionPool::new(5, Duration::from_millis(10));
+        let permit = pool.acquire_connection().await.unwrap();
+        permit.mark_active("a.com".to_string());
+        drop(permit);
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(pool.get_stats().idle_connections, 0);
+        assert_eq!(pool.host_stats()[0].1.idle_connections, 0);
+    }
 }
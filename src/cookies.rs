@@ -0,0 +1,574 @@
+use pyo3::prelude::*;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A cookie parsed from a `Set-Cookie` response header
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Cookie {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub value: String,
+    #[pyo3(get)]
+    pub domain: Option<String>,
+    #[pyo3(get)]
+    pub path: Option<String>,
+    /// Expiry as a unix timestamp; `Max-Age` takes precedence over `Expires`
+    #[pyo3(get)]
+    pub expires: Option<f64>,
+    #[pyo3(get)]
+    pub secure: bool,
+    #[pyo3(get)]
+    pub http_only: bool,
+    #[pyo3(get)]
+    pub same_site: Option<String>,
+}
+
+#[pymethods]
+impl Cookie {
+    /// Check if the cookie has expired
+    pub fn is_expired(&self) -> bool {
+        self.expires.map_or(false, |expires| expires <= unix_now())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Cookie(name='{}', domain={:?}, path={:?})",
+            self.name, self.domain, self.path
+        )
+    }
+}
+
+impl Cookie {
+    /// Parse a single `Set-Cookie` header value
+    pub(crate) fn parse(header: &str) -> Option<Self> {
+        let mut parts = header.split(';');
+        let (name, value) = parts.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Cookie {
+            name: name.to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+            domain: None,
+            path: None,
+            expires: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+        };
+        let mut max_age = None;
+
+        for attribute in parts {
+            let (key, val) = match attribute.split_once('=') {
+                Some((key, val)) => (key.trim(), val.trim()),
+                None => (attribute.trim(), ""),
+            };
+            match key.to_ascii_lowercase().as_str() {
+                "domain" if !val.is_empty() => {
+                    cookie.domain = Some(val.trim_start_matches('.').to_ascii_lowercase())
+                }
+                "path" if val.starts_with('/') => cookie.path = Some(val.to_string()),
+                "expires" => {
+                    if let Some(expires) = parse_http_date(val) {
+                        cookie.expires = Some(expires);
+                    }
+                }
+                "max-age" => {
+                    if let Ok(seconds) = val.parse::<i64>() {
+                        max_age = Some(seconds);
+                    }
+                }
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                "samesite" if !val.is_empty() => cookie.same_site = Some(val.to_string()),
+                _ => {}
+            }
+        }
+
+        if let Some(seconds) = max_age {
+            cookie.expires = Some(unix_now() + seconds as f64);
+        }
+        Some(cookie)
+    }
+}
+
+/// A cookie held by a [`CookieJar`], with its domain and path resolved against the request URL
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct StoredCookie {
+    pub(crate) cookie: Cookie,
+    /// Set without a `Domain` attribute, so only sent back to the exact host
+    pub(crate) host_only: bool,
+}
+
+impl StoredCookie {
+    /// Resolve a parsed cookie for the URL that set it, rejecting foreign domains
+    fn resolve(mut cookie: Cookie, url: &Url) -> Option<Self> {
+        let host = url.host_str()?.to_ascii_lowercase();
+        let host_only = match &cookie.domain {
+            Some(domain) if domain_matches(&host, domain) => false,
+            Some(_) => return None,
+            None => {
+                cookie.domain = Some(host);
+                true
+            }
+        };
+        if cookie.path.is_none() {
+            cookie.path = Some(default_path(url.path()));
+        }
+        Some(StoredCookie { cookie, host_only })
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        let (host, domain, path) = match (url.host_str(), &self.cookie.domain, &self.cookie.path) {
+            (Some(host), Some(domain), Some(path)) => (host, domain, path),
+            _ => return false,
+        };
+        let host = host.to_ascii_lowercase();
+        let domain_ok = if self.host_only {
+            host == *domain
+        } else {
+            domain_matches(&host, domain)
+        };
+        domain_ok
+            && path_matches(url.path(), path)
+            && (!self.cookie.secure || url.scheme() == "https")
+            && !self.cookie.is_expired()
+    }
+
+    fn same_slot(&self, other: &StoredCookie) -> bool {
+        self.cookie.name == other.cookie.name
+            && self.cookie.domain == other.cookie.domain
+            && self.cookie.path == other.cookie.path
+    }
+}
+
+/// On-disk form of a stored cookie, as written by `CookieJar::save`
+#[derive(Serialize, Deserialize)]
+struct SavedCookie {
+    name: String,
+    value: String,
+    domain: String,
+    path: String,
+    expires: Option<f64>,
+    secure: bool,
+    httponly: bool,
+    #[serde(default)]
+    same_site: Option<String>,
+    #[serde(default)]
+    host_only: bool,
+}
+
+impl From<&StoredCookie> for SavedCookie {
+    fn from(stored: &StoredCookie) -> Self {
+        let cookie = &stored.cookie;
+        SavedCookie {
+            name: cookie.name.clone(),
+            value: cookie.value.clone(),
+            domain: cookie.domain.clone().unwrap_or_default(),
+            path: cookie.path.clone().unwrap_or_else(|| "/".to_string()),
+            expires: cookie.expires,
+            secure: cookie.secure,
+            httponly: cookie.http_only,
+            same_site: cookie.same_site.clone(),
+            host_only: stored.host_only,
+        }
+    }
+}
+
+impl From<SavedCookie> for StoredCookie {
+    fn from(saved: SavedCookie) -> Self {
+        StoredCookie {
+            cookie: Cookie {
+                name: saved.name,
+                value: saved.value,
+                domain: Some(saved.domain.trim_start_matches('.').to_ascii_lowercase()),
+                path: Some(saved.path),
+                expires: saved.expires,
+                secure: saved.secure,
+                http_only: saved.httponly,
+                same_site: saved.same_site,
+            },
+            host_only: saved.host_only,
+        }
+    }
+}
+
+/// Cookie store shared by a session's requests, matching cookies per RFC 6265
+#[derive(Debug, Default)]
+pub(crate) struct CookieJar {
+    cookies: parking_lot::RwLock<Vec<StoredCookie>>,
+}
+
+impl CookieJar {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store the cookies set by a response to `url`.
+    /// An already-expired cookie deletes the stored one with the same name, domain and path.
+    pub(crate) fn store(&self, url: &Url, set_cookies: &[String]) {
+        for header in set_cookies {
+            if let Some(stored) =
+                Cookie::parse(header).and_then(|cookie| StoredCookie::resolve(cookie, url))
+            {
+                self.insert(stored);
+            }
+        }
+    }
+
+    pub(crate) fn store_response(&self, response: &reqwest::Response) {
+        self.store(response.url(), &set_cookie_headers(response.headers()));
+    }
+
+    fn insert(&self, stored: StoredCookie) {
+        let mut cookies = self.cookies.write();
+        cookies.retain(|existing| !existing.same_slot(&stored));
+        if !stored.cookie.is_expired() {
+            cookies.push(stored);
+        }
+    }
+
+    /// Set a cookie directly, sent to `domain` and its subdomains
+    pub(crate) fn set(&self, name: &str, value: &str, domain: &str, path: &str) {
+        self.insert(StoredCookie {
+            cookie: Cookie {
+                name: name.to_string(),
+                value: value.to_string(),
+                domain: Some(domain.trim_start_matches('.').to_ascii_lowercase()),
+                path: Some(path.to_string()),
+                expires: None,
+                secure: false,
+                http_only: false,
+                same_site: None,
+            },
+            host_only: false,
+        });
+    }
+
+    /// `Cookie` header value for a request to `url`, longest paths first
+    pub(crate) fn header_for(&self, url: &Url) -> Option<String> {
+        let cookies = self.cookies.read();
+        let mut matching: Vec<&Cookie> = cookies
+            .iter()
+            .filter(|stored| stored.matches(url))
+            .map(|stored| &stored.cookie)
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+        matching
+            .sort_by_key(|cookie| std::cmp::Reverse(cookie.path.as_ref().map_or(0, String::len)));
+        Some(
+            matching
+                .iter()
+                .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+
+    /// Add matching cookies to a request that has no `Cookie` header of its own
+    pub(crate) fn apply(&self, request: &mut reqwest::Request) {
+        if request.headers().contains_key(reqwest::header::COOKIE) {
+            return;
+        }
+        if let Some(value) = self
+            .header_for(request.url())
+            .and_then(|header| reqwest::header::HeaderValue::from_str(&header).ok())
+        {
+            request.headers_mut().insert(reqwest::header::COOKIE, value);
+        }
+    }
+
+    /// Unexpired cookies as a name -> value dict
+    pub(crate) fn to_dict(&self) -> HashMap<String, String> {
+        self.cookies
+            .read()
+            .iter()
+            .filter(|stored| !stored.cookie.is_expired())
+            .map(|stored| (stored.cookie.name.clone(), stored.cookie.value.clone()))
+            .collect()
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<String> {
+        self.cookies
+            .read()
+            .iter()
+            .find(|stored| stored.cookie.name == name && !stored.cookie.is_expired())
+            .map(|stored| stored.cookie.value.clone())
+    }
+
+    pub(crate) fn clear(&self) {
+        self.cookies.write().clear();
+    }
+
+    /// Write unexpired cookies to `path` as a JSON array
+    pub(crate) fn save(&self, path: &str) -> PyResult<()> {
+        let saved: Vec<SavedCookie> = self
+            .cookies
+            .read()
+            .iter()
+            .filter(|stored| !stored.cookie.is_expired())
+            .map(SavedCookie::from)
+            .collect();
+        let json = serde_json::to_vec_pretty(&saved).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Failed to serialize cookies: {}", e))
+        })?;
+        std::fs::write(path, json).map_err(|e| {
+            pyo3::exceptions::PyIOError::new_err(format!("Failed to write '{}': {}", path, e))
+        })
+    }
+
+    /// Load cookies saved by `save`, skipping expired ones; returns how many were added
+    pub(crate) fn load(&self, path: &str) -> PyResult<usize> {
+        let json = std::fs::read(path).map_err(|e| {
+            pyo3::exceptions::PyIOError::new_err(format!("Failed to read '{}': {}", path, e))
+        })?;
+        let saved: Vec<SavedCookie> = serde_json::from_slice(&json).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid cookie file '{}': {}",
+                path, e
+            ))
+        })?;
+
+        let mut loaded = 0;
+        for stored in saved.into_iter().map(StoredCookie::from) {
+            if !stored.cookie.is_expired() && !stored.cookie.name.is_empty() {
+                self.insert(stored);
+                loaded += 1;
+            }
+        }
+        Ok(loaded)
+    }
+}
+
+/// RFC 6265 domain-match: the host is the domain or a subdomain of it (never for IP hosts)
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || (host.ends_with(domain)
+            && host[..host.len() - domain.len()].ends_with('.')
+            && host.parse::<std::net::IpAddr>().is_err())
+}
+
+/// RFC 6265 path-match: the cookie path is a `/`-bounded prefix of the request path
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || request_path[cookie_path.len()..].starts_with('/')))
+}
+
+/// Default cookie path: the request path up to, but not including, its last `/`
+fn default_path(request_path: &str) -> String {
+    match request_path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(index) => request_path[..index].to_string(),
+    }
+}
+
+/// Collect every `Set-Cookie` header value without collapsing duplicates
+pub(crate) fn set_cookie_headers(headers: &reqwest::header::HeaderMap) -> Vec<String> {
+    headers
+        .get_all(reqwest::header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .map(|v| v.to_string())
+        .collect()
+}
+
+pub(crate) fn unix_now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+/// Parse an HTTP date such as "Wed, 21 Oct 2015 07:28:00 GMT" into a unix timestamp
+pub(crate) fn parse_http_date(value: &str) -> Option<f64> {
+    let tokens: Vec<&str> = value
+        .split([' ', '-', ','])
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let (mut day, mut month, mut year, mut time) = (None, None, None, None);
+    for token in tokens {
+        if token.contains(':') && time.is_none() {
+            let hms: Vec<u32> = token.split(':').filter_map(|p| p.parse().ok()).collect();
+            if hms.len() == 3 {
+                time = Some((hms[0], hms[1], hms[2]));
+            }
+        } else if let Ok(number) = token.parse::<i64>() {
+            if day.is_none() && token.len() <= 2 {
+                day = Some(number);
+            } else if year.is_none() {
+                year = Some(match number {
+                    0..=69 => number + 2000,
+                    70..=99 => number + 1900,
+                    _ => number,
+                });
+            }
+        } else if month.is_none() {
+            month = match token.get(..3).unwrap_or("").to_ascii_lowercase().as_str() {
+                "jan" => Some(1),
+                "feb" => Some(2),
+                "mar" => Some(3),
+                "apr" => Some(4),
+                "may" => Some(5),
+                "jun" => Some(6),
+                "jul" => Some(7),
+                "aug" => Some(8),
+                "sep" => Some(9),
+                "oct" => Some(10),
+                "nov" => Some(11),
+                "dec" => Some(12),
+                _ => None,
+            };
+        }
+    }
+
+    let (day, month, year, (hour, minute, second)) = (day?, month?, year?, time?);
+    if !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+    Some((days * 86400 + (hour * 3600 + minute * 60 + second) as i64) as f64)
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cookie_attributes() {
+        let cookie = Cookie::parse(
+            "session=abc123; Domain=.Example.com; Path=/app; Secure; HttpOnly; SameSite=Lax",
+        )
+        .unwrap();
+        assert_eq!(cookie.name, "session");
+        assert_eq!(cookie.value, "abc123");
+        assert_eq!(cookie.domain.as_deref(), Some("example.com"));
+        assert_eq!(cookie.path.as_deref(), Some("/app"));
+        assert!(cookie.secure);
+        assert!(cookie.http_only);
+        assert_eq!(cookie.same_site.as_deref(), Some("Lax"));
+        assert!(cookie.expires.is_none());
+    }
+
+    #[test]
+    fn test_parse_expires_date() {
+        let cookie = Cookie::parse("id=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT").unwrap();
+        assert_eq!(cookie.expires, Some(1445412480.0));
+        assert!(cookie.is_expired());
+
+        let legacy = Cookie::parse("id=1; expires=Wednesday, 21-Oct-15 07:28:00 GMT").unwrap();
+        assert_eq!(legacy.expires, Some(1445412480.0));
+    }
+
+    #[test]
+    fn test_max_age_overrides_expires() {
+        let cookie =
+            Cookie::parse("id=1; Max-Age=3600; Expires=Wed, 21 Oct 2015 07:28:00 GMT").unwrap();
+        assert!(!cookie.is_expired());
+    }
+
+    #[test]
+    fn test_parse_rejects_nameless_cookie() {
+        assert!(Cookie::parse("=value").is_none());
+        assert!(Cookie::parse("novalue").is_none());
+    }
+
+    #[test]
+    fn test_jar_matches_domain_and_path() {
+        let jar = CookieJar::new();
+        let url = Url::parse("https://www.example.com/app/login").unwrap();
+        jar.store(
+            &url,
+            &[
+                "host=1".to_string(),
+                "shared=2; Domain=example.com; Path=/".to_string(),
+                "secure=3; Domain=example.com; Secure".to_string(),
+                "foreign=4; Domain=other.com".to_string(),
+            ],
+        );
+
+        let same = Url::parse("https://www.example.com/app/home").unwrap();
+        assert_eq!(
+            jar.header_for(&same).as_deref(),
+            Some("host=1; secure=3; shared=2")
+        );
+
+        // Host-only and path-scoped cookies stay on their host and under /app
+        let sibling = Url::parse("http://api.example.com/apps").unwrap();
+        assert_eq!(jar.header_for(&sibling).as_deref(), Some("shared=2"));
+        assert!(jar
+            .header_for(&Url::parse("https://other.com/").unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn test_jar_replaces_and_expires_cookies() {
+        let jar = CookieJar::new();
+        let url = Url::parse("http://example.com/").unwrap();
+        jar.store(&url, &["id=1".to_string()]);
+        jar.store(&url, &["id=2".to_string()]);
+        assert_eq!(jar.get("id").as_deref(), Some("2"));
+
+        jar.store(&url, &["id=; Max-Age=0".to_string()]);
+        assert!(jar.get("id").is_none());
+        assert!(jar.to_dict().is_empty());
+    }
+
+    #[test]
+    fn test_default_path() {
+        assert_eq!(default_path(""), "/");
+        assert_eq!(default_path("/login"), "/");
+        assert_eq!(default_path("/app/login"), "/app");
+        assert!(path_matches("/app/home", "/app"));
+        assert!(!path_matches("/apps", "/app"));
+    }
+
+    #[test]
+    fn test_jar_save_and_load_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("ultrafast-cookies-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        let jar = CookieJar::new();
+        let url = Url::parse("https://example.com/app/").unwrap();
+        jar.store(
+            &url,
+            &[
+                "sid=abc; Secure; HttpOnly".to_string(),
+                "old=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT".to_string(),
+            ],
+        );
+        jar.save(path).unwrap();
+
+        let restored = CookieJar::new();
+        assert_eq!(restored.load(path).unwrap(), 1);
+        assert_eq!(restored.header_for(&url).as_deref(), Some("sid=abc"));
+        assert!(restored
+            .header_for(&Url::parse("https://sub.example.com/app/").unwrap())
+            .is_none());
+
+        std::fs::write(path, "not json").unwrap();
+        assert!(restored.load(path).is_err());
+        std::fs::remove_file(path).unwrap();
+        assert!(restored.load(path).is_err());
+    }
+}
@@ -0,0 +1,331 @@
+use parking_lot::Mutex;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Cache entries kept unless configured otherwise
+pub(crate) const DEFAULT_MAX_ENTRIES: usize = 1024;
+
+/// Failed lookups are remembered for at most this long
+const NEGATIVE_TTL: Duration = Duration::from_secs(5);
+
+/// Why `ClientResolver` could not resolve a host; request errors carry it as their cause
+#[derive(Debug, thiserror::Error)]
+#[error("failed to resolve {host}: {reason}")]
+pub(crate) struct ResolveError {
+    host: String,
+    reason: String,
+}
+
+#[derive(Clone, Debug)]
+enum Lookup {
+    Found(Vec<SocketAddr>),
+    NotFound(String),
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    lookup: Lookup,
+    expires: Instant,
+}
+
+/// In-process cache of hostname lookups shared by a client's connections
+#[derive(Debug)]
+pub(crate) struct DnsCache {
+    ttl: Duration,
+    negative_ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl DnsCache {
+    pub(crate) fn new(ttl: f64, max_entries: usize) -> Self {
+        let ttl = Duration::from_secs_f64(ttl.max(0.0));
+        Self {
+            ttl,
+            negative_ttl: ttl.min(NEGATIVE_TTL),
+            max_entries: max_entries.max(1),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Build a cache from the client options, or None when caching is disabled
+    pub(crate) fn from_options(
+        ttl: Option<f64>,
+        max_entries: usize,
+    ) -> pyo3::PyResult<Option<Arc<Self>>> {
+        match ttl {
+            None => Ok(None),
+            Some(ttl) if !ttl.is_finite() || ttl < 0.0 => {
+                Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "dns_cache_ttl must be a non-negative number of seconds, got {}",
+                    ttl
+                )))
+            }
+            Some(ttl) => Ok(Some(Arc::new(Self::new(ttl, max_entries)))),
+        }
+    }
+
+    pub(crate) fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub(crate) fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// Forget every cached lookup
+    pub(crate) fn clear(&self) {
+        self.entries.lock().clear();
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    fn get(&self, host: &str, now: Instant) -> Option<Lookup> {
+        let mut entries = self.entries.lock();
+        match entries.get(host) {
+            Some(entry) if entry.expires > now => Some(entry.lookup.clone()),
+            Some(_) => {
+                entries.remove(host);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, host: &str, lookup: Lookup, now: Instant) {
+        let ttl = match lookup {
+            Lookup::Found(_) => self.ttl,
+            Lookup::NotFound(_) => self.negative_ttl,
+        };
+        if ttl.is_zero() {
+            return;
+        }
+
+        let mut entries = self.entries.lock();
+        if !entries.contains_key(host) && entries.len() >= self.max_entries {
+            entries.retain(|_, entry| entry.expires > now);
+            if entries.len() >= self.max_entries {
+                // Evict whichever entry would have expired first
+                if let Some(oldest) = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.expires)
+                    .map(|(key, _)| key.clone())
+                {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(
+            host.to_string(),
+            Entry {
+                lookup,
+                expires: now + ttl,
+            },
+        );
+    }
+}
+
+/// Address family used to connect to resolved hosts.
+/// In `Auto` both families are kept and the connector races them (happy eyeballs),
+/// starting the second family shortly after the first if it has not connected yet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum IpVersion {
+    #[default]
+    Auto,
+    V4,
+    V6,
+}
+
+impl IpVersion {
+    pub(crate) fn parse(value: &str) -> pyo3::PyResult<Self> {
+        match value {
+            "auto" => Ok(IpVersion::Auto),
+            "v4" => Ok(IpVersion::V4),
+            "v6" => Ok(IpVersion::V6),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "ip_version must be 'auto', 'v4' or 'v6', got '{}'",
+                value
+            ))),
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            IpVersion::Auto => "auto",
+            IpVersion::V4 => "v4",
+            IpVersion::V6 => "v6",
+        }
+    }
+
+    fn filter(self, addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+        match self {
+            IpVersion::Auto => addrs,
+            IpVersion::V4 => addrs.into_iter().filter(SocketAddr::is_ipv4).collect(),
+            IpVersion::V6 => addrs.into_iter().filter(SocketAddr::is_ipv6).collect(),
+        }
+    }
+}
+
+/// Resolver for every client: it times lookups for the response timing breakdown,
+/// and optionally caches them or keeps to a single address family
+pub(crate) fn resolver(
+    cache: Option<&Arc<DnsCache>>,
+    ip_version: IpVersion,
+) -> Arc<ClientResolver> {
+    Arc::new(ClientResolver {
+        cache: cache.cloned(),
+        ip_version,
+    })
+}
+
+/// `reqwest` resolver backed by an optional `DnsCache` and address family filter
+pub(crate) struct ClientResolver {
+    cache: Option<Arc<DnsCache>>,
+    ip_version: IpVersion,
+}
+
+impl Resolve for ClientResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let cache = self.cache.clone();
+        let ip_version = self.ip_version;
+        let phases = crate::timing::ConnectionPhases::current();
+        let started = Instant::now();
+        Box::pin(async move {
+            let resolved = resolve(cache, ip_version, name).await;
+            if let Some(phases) = phases {
+                phases.add_dns(started.elapsed());
+            }
+            resolved
+        })
+    }
+}
+
+/// Look `name` up through the cache, keeping only addresses of the allowed family
+async fn resolve(
+    cache: Option<Arc<DnsCache>>,
+    ip_version: IpVersion,
+    name: Name,
+) -> Result<Addrs, Box<dyn std::error::Error + Send + Sync>> {
+    let host = name.as_str().to_string();
+    let cached = cache
+        .as_ref()
+        .and_then(|cache| cache.get(&host, Instant::now()));
+    let lookup = match cached {
+        Some(lookup) => lookup,
+        None => {
+            let lookup = match tokio::net::lookup_host((host.as_str(), 0)).await {
+                Ok(addrs) => Lookup::Found(addrs.collect()),
+                Err(e) => Lookup::NotFound(e.to_string()),
+            };
+            if let Some(cache) = &cache {
+                cache.insert(&host, lookup.clone(), Instant::now());
+            }
+            lookup
+        }
+    };
+    match lookup {
+        Lookup::Found(addrs) => {
+            let addrs = ip_version.filter(addrs);
+            if addrs.is_empty() {
+                return Err(Box::new(ResolveError {
+                    host,
+                    reason: format!("no address for ip_version={}", ip_version.as_str()),
+                }));
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        }
+        Lookup::NotFound(reason) => Err(Box::new(ResolveError { host, reason })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(port: u16) -> Lookup {
+        Lookup::Found(vec![SocketAddr::from(([127, 0, 0, 1], port))])
+    }
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let cache = DnsCache::new(10.0, 4);
+        let now = Instant::now();
+        cache.insert("example.com", found(0), now);
+
+        assert!(cache.get("example.com", now).is_some());
+        assert!(cache
+            .get("example.com", now + Duration::from_secs(11))
+            .is_none());
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn test_failures_use_short_negative_ttl() {
+        let cache = DnsCache::new(60.0, 4);
+        let now = Instant::now();
+        cache.insert("missing.invalid", Lookup::NotFound("nx".into()), now);
+
+        assert!(matches!(
+            cache.get("missing.invalid", now + Duration::from_secs(1)),
+            Some(Lookup::NotFound(_))
+        ));
+        assert!(cache
+            .get("missing.invalid", now + Duration::from_secs(6))
+            .is_none());
+    }
+
+    #[test]
+    fn test_evicts_earliest_expiry_when_full() {
+        let cache = DnsCache::new(10.0, 2);
+        let now = Instant::now();
+        cache.insert("a", found(1), now);
+        cache.insert("b", found(2), now + Duration::from_secs(1));
+        cache.insert("c", found(3), now + Duration::from_secs(2));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("a", now).is_none());
+        assert!(cache.get("b", now).is_some());
+        assert!(cache.get("c", now).is_some());
+    }
+
+    #[test]
+    fn test_clear() {
+        let cache = DnsCache::new(10.0, 4);
+        cache.insert("example.com", found(0), Instant::now());
+        cache.clear();
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn test_ip_version_filters_address_family() {
+        let addrs = vec![
+            SocketAddr::from(([127, 0, 0, 1], 80)),
+            SocketAddr::from(([0, 0, 0, 0, 0, 0, 0, 1], 80)),
+        ];
+        assert_eq!(IpVersion::Auto.filter(addrs.clone()), addrs);
+        assert_eq!(IpVersion::V4.filter(addrs.clone()), vec![addrs[0]]);
+        assert_eq!(IpVersion::V6.filter(addrs.clone()), vec![addrs[1]]);
+    }
+
+    #[tokio::test]
+    async fn test_resolver_records_dns_time() {
+        let phases = Arc::new(crate::timing::ConnectionPhases::default());
+        let resolver = resolver(None, IpVersion::V4);
+        let addrs: Vec<SocketAddr> = phases
+            .scope(async { resolver.resolve("localhost".parse().unwrap()).await })
+            .await
+            .expect("localhost resolves")
+            .collect();
+        assert!(addrs.iter().all(SocketAddr::is_ipv4));
+
+        let timing = phases.finish(Instant::now(), Duration::ZERO);
+        assert!(timing.dns > 0.0);
+        assert!(timing.reused_connection);
+    }
+}
@@ -1,8 +1,190 @@
 use pyo3::exceptions::PyException;
-use pyo3::PyErr;
+use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+use pyo3::types::{PyDict, PyTuple, PyType};
 use std::fmt;
 use thiserror::Error;
 
+// Base class for every error raised by the client
+pyo3::create_exception!(_ultrafast_client, UltrafastError, PyException);
+
+// Raised by `Response.raise_for_status` for 4xx/5xx responses
+pyo3::create_exception!(_ultrafast_client, HTTPStatusError, UltrafastError);
+
+// Raised by `Response.raise_for_status` for 4xx responses
+pyo3::create_exception!(_ultrafast_client, HTTPClientError, HTTPStatusError);
+
+// Raised by `Response.raise_for_status` for 5xx responses
+pyo3::create_exception!(_ultrafast_client, HTTPServerError, HTTPStatusError);
+
+// Raised when a request follows more redirects than `max_redirects`
+// (exported as both `TooManyRedirectsError` and the older `TooManyRedirects`)
+pyo3::create_exception!(_ultrafast_client, TooManyRedirects, UltrafastError);
+
+// Raised without sending a request while a host's circuit breaker is open
+pyo3::create_exception!(_ultrafast_client, CircuitOpenError, UltrafastError);
+
+// Raised when a response body is larger than the client's `max_response_size`
+pyo3::create_exception!(_ultrafast_client, ResponseTooLargeError, UltrafastError);
+
+static TIMEOUT_ERROR: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static CONNECTION_ERROR: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static TLS_ERROR: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static DNS_ERROR: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static SCHEMA_VALIDATION_ERROR: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static RATE_LIMIT_ERROR: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+/// Create an exception class with several bases, which `create_exception!` can't express
+fn exception_type<'py>(
+    py: Python<'py>,
+    cell: &'static GILOnceCell<Py<PyType>>,
+    name: &str,
+    bases: &dyn Fn(Python<'py>) -> PyResult<&'py PyTuple>,
+) -> PyResult<&'py PyType> {
+    let ty = cell.get_or_try_init(py, || -> PyResult<Py<PyType>> {
+        let namespace = PyDict::new(py);
+        namespace.set_item("__module__", "_ultrafast_client")?;
+        let ty = py
+            .get_type::<PyType>()
+            .call1((name, bases(py)?, namespace))?;
+        Ok(ty.downcast::<PyType>()?.into())
+    })?;
+    Ok(ty.as_ref(py))
+}
+
+/// `TimeoutError`: an `UltrafastError` that is still caught by the builtin `TimeoutError`
+pub fn timeout_error_type(py: Python<'_>) -> PyResult<&PyType> {
+    exception_type(py, &TIMEOUT_ERROR, "TimeoutError", &|py| {
+        Ok(PyTuple::new(
+            py,
+            [
+                py.get_type::<UltrafastError>(),
+                py.get_type::<pyo3::exceptions::PyTimeoutError>(),
+            ],
+        ))
+    })
+}
+
+/// `ConnectionError`: an `UltrafastError` that is still caught by the builtin `ConnectionError`
+pub fn connection_error_type(py: Python<'_>) -> PyResult<&PyType> {
+    exception_type(py, &CONNECTION_ERROR, "ConnectionError", &|py| {
+        Ok(PyTuple::new(
+            py,
+            [
+                py.get_type::<UltrafastError>(),
+                py.get_type::<pyo3::exceptions::PyConnectionError>(),
+            ],
+        ))
+    })
+}
+
+/// `TLSError`: a `ConnectionError` caused by the TLS handshake or certificate checks
+pub fn tls_error_type(py: Python<'_>) -> PyResult<&PyType> {
+    exception_type(py, &TLS_ERROR, "TLSError", &|py| {
+        Ok(PyTuple::new(py, [connection_error_type(py)?]))
+    })
+}
+
+/// `DNSError`: a `ConnectionError` caused by the host name not resolving
+pub fn dns_error_type(py: Python<'_>) -> PyResult<&PyType> {
+    exception_type(py, &DNS_ERROR, "DNSError", &|py| {
+        Ok(PyTuple::new(py, [connection_error_type(py)?]))
+    })
+}
+
+/// `SchemaValidationError`: an `UltrafastError` raised when a response body does not match
+/// a JSON Schema, still caught by the builtin `ValueError`
+pub fn schema_validation_error_type(py: Python<'_>) -> PyResult<&PyType> {
+    exception_type(
+        py,
+        &SCHEMA_VALIDATION_ERROR,
+        "SchemaValidationError",
+        &|py| {
+            Ok(PyTuple::new(
+                py,
+                [
+                    py.get_type::<UltrafastError>(),
+                    py.get_type::<pyo3::exceptions::PyValueError>(),
+                ],
+            ))
+        },
+    )
+}
+
+/// `RateLimitError`: an `UltrafastError` raised when a rate limiter refuses a request,
+/// still caught by the builtin `RuntimeError`
+pub fn rate_limit_error_type(py: Python<'_>) -> PyResult<&PyType> {
+    exception_type(py, &RATE_LIMIT_ERROR, "RateLimitError", &|py| {
+        Ok(PyTuple::new(
+            py,
+            [
+                py.get_type::<UltrafastError>(),
+                py.get_type::<pyo3::exceptions::PyRuntimeError>(),
+            ],
+        ))
+    })
+}
+
+fn new_err(ty: for<'py> fn(Python<'py>) -> PyResult<&'py PyType>, message: String) -> PyErr {
+    Python::with_gil(|py| match ty(py) {
+        Ok(ty) => PyErr::from_type(ty, message),
+        Err(e) => e,
+    })
+}
+
+/// Raise the client's `TimeoutError`
+pub fn timeout_error(message: String) -> PyErr {
+    new_err(timeout_error_type, message)
+}
+
+/// Raise the client's `ConnectionError`
+pub fn connection_error(message: String) -> PyErr {
+    new_err(connection_error_type, message)
+}
+
+/// Raise the client's `TLSError`
+pub fn tls_error(message: String) -> PyErr {
+    new_err(tls_error_type, message)
+}
+
+/// Raise the client's `DNSError`
+pub fn dns_error(message: String) -> PyErr {
+    new_err(dns_error_type, message)
+}
+
+/// Raise the client's `SchemaValidationError`
+pub fn schema_validation_error(message: String) -> PyErr {
+    new_err(schema_validation_error_type, message)
+}
+
+/// Raise the client's `RateLimitError`
+pub fn rate_limit_error(message: String) -> PyErr {
+    new_err(rate_limit_error_type, message)
+}
+
+/// Error kinds a `RetryConfig.retry_on` list can name
+pub const ERROR_KINDS: &[&str] = &["timeout", "connect", "tls", "dns"];
+
+/// Kind of a request failure, from its exception type; None for other errors
+pub fn error_kind(py: Python<'_>, error: &PyErr) -> Option<&'static str> {
+    let is_instance = |ty: PyResult<&PyType>| ty.map_or(false, |ty| error.is_instance(py, ty));
+    if is_instance(timeout_error_type(py))
+        || error.is_instance_of::<pyo3::exceptions::PyTimeoutError>(py)
+    {
+        Some("timeout")
+    } else if is_instance(tls_error_type(py)) {
+        Some("tls")
+    } else if is_instance(dns_error_type(py)) {
+        Some("dns")
+    } else if is_instance(connection_error_type(py))
+        || error.is_instance_of::<pyo3::exceptions::PyConnectionError>(py)
+    {
+        Some("connect")
+    } else {
+        None
+    }
+}
+
 /// Comprehensive error type for UltraFast HTTP client
 #[derive(Error, Debug, Clone)]
 pub enum UltraFastError {
@@ -46,9 +228,9 @@ pub enum UltraFastError {
 impl From<UltraFastError> for PyErr {
     fn from(err: UltraFastError) -> PyErr {
         match err {
-            UltraFastError::TimeoutError(msg) => pyo3::exceptions::PyTimeoutError::new_err(msg),
+            UltraFastError::TimeoutError(msg) => timeout_error(msg),
             UltraFastError::ConnectionError(msg) | UltraFastError::Http3Error(msg) => {
-                pyo3::exceptions::PyConnectionError::new_err(msg)
+                connection_error(msg)
             }
             UltraFastError::AuthError(msg) => pyo3::exceptions::PyPermissionError::new_err(msg),
             UltraFastError::ConfigError(msg) | UltraFastError::ProtocolError(msg) => {
@@ -58,7 +240,7 @@ impl From<UltraFastError> for PyErr {
                 pyo3::exceptions::PyIOError::new_err(msg)
             }
             UltraFastError::RateLimitError(msg) | UltraFastError::RateLimitQueueFullError(msg) => {
-                pyo3::exceptions::PyRuntimeError::new_err(format!("Rate limit error: {}", msg))
+                rate_limit_error(format!("Rate limit error: {}", msg))
             }
             _ => pyo3::exceptions::PyRuntimeError::new_err(err.to_string()),
         }
@@ -114,12 +296,67 @@ impl From<ClientError> for UltraFastError {
 #[allow(dead_code)]
 pub type ClientResult<T> = Result<T, ClientError>;
 
-/// Map reqwest errors to PyO3 exceptions
+/// Causes of the error, skipping reqwest's own error, whose message includes the URL.
+/// An `std::io::Error` wrapping another error is followed by its inner error.
+fn causes(error: &reqwest::Error) -> Vec<&(dyn std::error::Error + 'static)> {
+    let mut causes = Vec::new();
+    let mut source = std::error::Error::source(error);
+    while let Some(err) = source {
+        causes.push(err);
+        let wrapped = err
+            .downcast_ref::<std::io::Error>()
+            .and_then(|io| io.get_ref());
+        source = match wrapped {
+            Some(inner) => Some(inner as &(dyn std::error::Error + 'static)),
+            None => err.source(),
+        };
+    }
+    causes
+}
+
+/// Whether an error is raised by the TLS library of an enabled TLS feature
+#[cfg_attr(
+    not(any(feature = "native-tls", feature = "rustls-tls")),
+    allow(unused_variables)
+)]
+fn is_tls_library_error(err: &(dyn std::error::Error + 'static)) -> bool {
+    #[cfg(feature = "native-tls")]
+    if err.is::<native_tls::Error>() {
+        return true;
+    }
+    #[cfg(feature = "rustls-tls")]
+    if err.is::<rustls::Error>() {
+        return true;
+    }
+    false
+}
+
+/// Whether a failure came from the TLS layer rather than the TCP connection
+fn is_tls_error(error: &reqwest::Error) -> bool {
+    causes(error).into_iter().any(is_tls_library_error)
+}
+
+/// Whether a connect failure came from resolving the host name
+fn is_dns_error(error: &reqwest::Error) -> bool {
+    causes(error)
+        .into_iter()
+        .any(|err| err.is::<crate::dns_cache::ResolveError>())
+}
+
+/// Map reqwest errors to the client's exception hierarchy
 pub fn map_reqwest_error(error: &reqwest::Error) -> PyErr {
     if error.is_timeout() {
-        pyo3::exceptions::PyTimeoutError::new_err(format!("Request timeout: {}", error))
+        timeout_error(format!("Request timeout: {}", error))
+    } else if error.is_connect() && is_tls_error(error) {
+        tls_error(format!("TLS error: {}", error))
+    } else if error.is_connect() && is_dns_error(error) {
+        dns_error(format!("DNS resolution failed: {}", error))
     } else if error.is_connect() {
-        pyo3::exceptions::PyConnectionError::new_err(format!("Connection failed: {}", error))
+        connection_error(format!("Connection failed: {}", error))
+    } else if error.is_redirect() {
+        TooManyRedirects::new_err(format!("Redirect error: {}", error))
+    } else if error.is_status() {
+        HTTPStatusError::new_err(format!("HTTP status error: {}", error))
     } else if error.is_request() {
         pyo3::exceptions::PyIOError::new_err(format!("Request error: {}", error))
     } else if error.is_decode() {
@@ -8,6 +8,7 @@ use std::net::SocketAddr;
 
 /// Stub HTTP/3 client - HTTP/3 support is disabled
 #[allow(dead_code)]
+#[derive(Clone)]
 pub struct Http3Client;
 
 #[allow(dead_code)]
@@ -104,7 +105,7 @@ impl Http3Response {
         // Return an error response if we can't create a dummy response
         match response {
             Ok(resp) => {
-                match crate::response::Response::from_reqwest(resp, &runtime) {
+                match crate::response::Response::from_reqwest(resp, &runtime, None) {
                     Ok(mut response) => {
                         response.status_code = 500;
                         response
@@ -116,6 +117,7 @@ impl Http3Response {
                         // Last resort: create a minimal response manually
                         crate::response::Response {
                             status_code: 500,
+                            reason: crate::response::reason_phrase(500),
                             headers: std::collections::HashMap::new(),
                             content: b"HTTP/3 not implemented".to_vec(),
                             url: "http://localhost/".to_string(),
@@ -129,6 +131,10 @@ impl Http3Response {
                             start_time: 0.0,
                             end_time: 0.0,
                             timing: None,
+                            lazy_body: None,
+                            set_cookies: Vec::new(),
+                            history: Vec::new(),
+                            encoding_override: None,
                         }
                     }
                 }
@@ -137,6 +143,7 @@ impl Http3Response {
                 // Create a minimal error response
                 crate::response::Response {
                     status_code: 500,
+                    reason: crate::response::reason_phrase(500),
                     headers: std::collections::HashMap::new(),
                     content: b"HTTP/3 not implemented".to_vec(),
                     url: "http://localhost/".to_string(),
@@ -150,6 +157,10 @@ impl Http3Response {
                     start_time: 0.0,
                     end_time: 0.0,
                     timing: None,
+                    lazy_body: None,
+                    set_cookies: Vec::new(),
+                    history: Vec::new(),
+                    encoding_override: None,
                 }
             }
         }
@@ -186,6 +197,11 @@ impl Http3ConnectionPool {
             "HTTP/3 not available".to_string(),
         ))
     }
+
+    /// Close all pooled connections - no-op
+    pub async fn close(&self) {
+        // No-op for stub implementation
+    }
 }
 
 /// Async HTTP/3 client - same as regular client for now
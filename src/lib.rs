@@ -25,16 +25,24 @@ use pyo3::prelude::*;
 mod async_client;
 mod async_session;
 mod benchmark;
+mod body_stream;
+mod circuit_breaker;
 mod client;
 mod config;
+mod cookies;
 mod connection_pool;
+mod dns_cache;
 mod error;
 mod http3;
 mod middleware;
+mod multipart;
+mod ntlm;
+mod pinning;
 mod protocol_enhanced;
 mod response;
 mod session;
 mod sse;
+mod timing;
 mod websocket;
 
 // Shared modules to eliminate code duplication
@@ -48,16 +56,17 @@ use async_client::AsyncHttpClient;
 use async_session::AsyncSession;
 use benchmark::{Benchmark, MemoryProfiler};
 use client::HttpClient;
+use cookies::Cookie;
 use config::{
-    AuthConfig, AuthType, CompressionConfig, Http2Settings, Http3Settings, HttpVersion,
-    OAuth2Token, PoolConfig, ProtocolConfig, ProtocolFallback, ProxyConfig, RateLimitAlgorithm,
-    RateLimitConfig, RetryConfig, SSLConfig, TimeoutConfig,
+    AuthConfig, AuthType, CircuitBreakerConfig, CompressionConfig, Http2Settings, Http3Settings,
+    HttpVersion, OAuth2Token, PoolConfig, ProtocolConfig, ProtocolFallback, ProxyConfig,
+    RateLimitAlgorithm, RateLimitConfig, RetryConfig, SSLConfig, TimeoutConfig,
 };
 use middleware::{
-    HeadersMiddleware, InterceptorMiddleware, LoggingMiddleware, MetricsMiddleware, Middleware,
-    RateLimitMiddleware, RetryMiddleware,
+    CacheMiddleware, DedupMiddleware, HeadersMiddleware, HmacSigningMiddleware, InterceptorMiddleware, LoggingMiddleware,
+    MetricsMiddleware, Middleware, RateLimitMiddleware, RetryMiddleware, TransformMiddleware,
 };
-use response::Response;
+use response::{Response, ResponseContentIterator};
 use session::Session;
 use sse::{AsyncSSEClient, SSEClient, SSEEvent, SSEEventIterator};
 use websocket::{AsyncWebSocketClient, WebSocketClient, WebSocketMessage};
@@ -98,13 +107,15 @@ use websocket::{AsyncWebSocketClient, WebSocketClient, WebSocketMessage};
 ///     data = response.json()
 /// ```
 #[pymodule]
-fn _ultrafast_client(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+fn _ultrafast_client(py: Python<'_>, m: &PyModule) -> PyResult<()> {
     // Core client classes
     m.add_class::<HttpClient>()?;
     m.add_class::<AsyncHttpClient>()?;
 
     // Response and session
     m.add_class::<Response>()?;
+    m.add_class::<ResponseContentIterator>()?;
+    m.add_class::<Cookie>()?;
     m.add_class::<Session>()?;
     m.add_class::<AsyncSession>()?;
 
@@ -112,6 +123,7 @@ fn _ultrafast_client(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<AuthConfig>()?;
     m.add_class::<AuthType>()?;
     m.add_class::<RetryConfig>()?;
+    m.add_class::<CircuitBreakerConfig>()?;
     m.add_class::<TimeoutConfig>()?;
     m.add_class::<PoolConfig>()?;
     m.add_class::<SSLConfig>()?;
@@ -143,6 +155,10 @@ fn _ultrafast_client(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<MetricsMiddleware>()?;
     m.add_class::<InterceptorMiddleware>()?;
     m.add_class::<RateLimitMiddleware>()?;
+    m.add_class::<CacheMiddleware>()?;
+    m.add_class::<HmacSigningMiddleware>()?;
+    m.add_class::<TransformMiddleware>()?;
+    m.add_class::<DedupMiddleware>()?;
 
     // Performance tools
     m.add_class::<Benchmark>()?;
@@ -152,6 +168,28 @@ fn _ultrafast_client(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<RateLimitConfig>()?;
     m.add_class::<RateLimitAlgorithm>()?;
 
+    // Exceptions
+    m.add("UltrafastError", py.get_type::<error::UltrafastError>())?;
+    m.add("TimeoutError", error::timeout_error_type(py)?)?;
+    m.add("ConnectionError", error::connection_error_type(py)?)?;
+    m.add("TLSError", error::tls_error_type(py)?)?;
+    m.add("DNSError", error::dns_error_type(py)?)?;
+    m.add("SchemaValidationError", error::schema_validation_error_type(py)?)?;
+    m.add("RateLimitError", error::rate_limit_error_type(py)?)?;
+    m.add("HTTPStatusError", py.get_type::<error::HTTPStatusError>())?;
+    m.add("HTTPClientError", py.get_type::<error::HTTPClientError>())?;
+    m.add("HTTPServerError", py.get_type::<error::HTTPServerError>())?;
+    m.add("TooManyRedirects", py.get_type::<error::TooManyRedirects>())?;
+    m.add(
+        "TooManyRedirectsError",
+        py.get_type::<error::TooManyRedirects>(),
+    )?;
+    m.add("CircuitOpenError", py.get_type::<error::CircuitOpenError>())?;
+    m.add(
+        "ResponseTooLargeError",
+        py.get_type::<error::ResponseTooLargeError>(),
+    )?;
+
     // Add version
     m.add("__version__", "0.1.0")?;
 
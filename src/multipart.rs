@@ -0,0 +1,78 @@
+use pyo3::prelude::*;
+use reqwest::multipart::{Form, Part};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Guess a part's MIME type from its filename extension
+pub(crate) fn mime_type_for(filename: &str) -> &'static str {
+    let ext = filename.rsplit('.').next().unwrap_or("").to_lowercase();
+    match ext.as_str() {
+        "pdf" => "application/pdf",
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "txt" => "text/plain",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" => "application/javascript",
+        "zip" => "application/zip",
+        "tar" => "application/x-tar",
+        "gz" => "application/gzip",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Open a file as a multipart part that is read from disk while the request is sent
+async fn file_part(path: &str) -> PyResult<Part> {
+    let file_name = Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!("Not a file path: {}", path))
+        })?;
+    let file = tokio::fs::File::open(path).await.map_err(|e| {
+        pyo3::exceptions::PyIOError::new_err(format!("Failed to open {}: {}", path, e))
+    })?;
+    let length = file.metadata().await.map_err(|e| {
+        pyo3::exceptions::PyIOError::new_err(format!("Failed to read metadata of {}: {}", path, e))
+    })?;
+    let mime_type = mime_type_for(&file_name);
+    Part::stream_with_length(reqwest::Body::from(file), length.len())
+        .file_name(file_name)
+        .mime_str(mime_type)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid MIME type: {}", e)))
+}
+
+/// Build a multipart form from text fields and `field -> path` file parts.
+///
+/// File contents are streamed, so the whole upload is never held in memory.
+pub(crate) async fn file_form(
+    files: HashMap<String, String>,
+    data: Option<HashMap<String, String>>,
+) -> PyResult<Form> {
+    let mut form = Form::new();
+    for (name, value) in data.unwrap_or_default() {
+        form = form.text(name, value);
+    }
+    for (name, path) in files {
+        form = form.part(name, file_part(&path).await?);
+    }
+    Ok(form)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mime_type_for() {
+        assert_eq!(mime_type_for("report.PDF"), "application/pdf");
+        assert_eq!(mime_type_for("archive.tar.gz"), "application/gzip");
+        assert_eq!(mime_type_for("photo.jpeg"), "image/jpeg");
+        assert_eq!(mime_type_for("README"), "application/octet-stream");
+    }
+}
@@ -0,0 +1,274 @@
+use hmac::{Hmac, Mac};
+use md4::{Digest, Md4};
+use md5::Md5;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type HmacMd5 = Hmac<Md5>;
+
+const SIGNATURE: &[u8; 8] = b"NTLMSSP\0";
+
+const NEGOTIATE_UNICODE: u32 = 0x0000_0001;
+const NEGOTIATE_OEM: u32 = 0x0000_0002;
+const REQUEST_TARGET: u32 = 0x0000_0004;
+const NEGOTIATE_NTLM: u32 = 0x0000_0200;
+const NEGOTIATE_ALWAYS_SIGN: u32 = 0x0000_8000;
+const NEGOTIATE_EXTENDED_SESSIONSECURITY: u32 = 0x0008_0000;
+const NEGOTIATE_128: u32 = 0x2000_0000;
+const NEGOTIATE_56: u32 = 0x8000_0000;
+
+/// Flags offered in the negotiate message
+const NEGOTIATE_FLAGS: u32 = NEGOTIATE_UNICODE
+    | NEGOTIATE_OEM
+    | REQUEST_TARGET
+    | NEGOTIATE_NTLM
+    | NEGOTIATE_ALWAYS_SIGN
+    | NEGOTIATE_EXTENDED_SESSIONSECURITY
+    | NEGOTIATE_128
+    | NEGOTIATE_56;
+
+/// Target info AV pair ids
+const MSV_AV_EOL: u16 = 0;
+const MSV_AV_TIMESTAMP: u16 = 7;
+
+/// Seconds between the FILETIME epoch (1601-01-01) and the Unix epoch
+const FILETIME_UNIX_OFFSET: u64 = 11_644_473_600;
+
+/// Type 1 message opening the handshake, without domain or workstation
+pub fn negotiate_message() -> Vec<u8> {
+    let mut message = Vec::with_capacity(32);
+    message.extend_from_slice(SIGNATURE);
+    message.extend_from_slice(&1u32.to_le_bytes());
+    message.extend_from_slice(&NEGOTIATE_FLAGS.to_le_bytes());
+    message.extend_from_slice(&[0; 16]);
+    message
+}
+
+/// The server's Type 2 challenge message
+#[derive(Debug)]
+pub struct Challenge {
+    flags: u32,
+    server_challenge: [u8; 8],
+    target_info: Vec<u8>,
+}
+
+impl Challenge {
+    /// Parse a Type 2 message; a Negotiate token may wrap it in SPNEGO, so the
+    /// message starts at its signature
+    pub fn parse(token: &[u8]) -> Option<Self> {
+        let start = token
+            .windows(SIGNATURE.len())
+            .position(|window| window == SIGNATURE)?;
+        let message = &token[start..];
+        if message.len() < 32 || read_u32(message, 8)? != 2 {
+            return None;
+        }
+
+        let target_info = if message.len() >= 48 {
+            security_buffer(message, 40)?.to_vec()
+        } else {
+            Vec::new()
+        };
+        Some(Challenge {
+            flags: read_u32(message, 20)?,
+            server_challenge: message[24..32].try_into().ok()?,
+            target_info,
+        })
+    }
+
+    /// FILETIME the server put in its target info, if any
+    fn timestamp(&self) -> Option<[u8; 8]> {
+        let mut info = self.target_info.as_slice();
+        while info.len() >= 4 {
+            let id = u16::from_le_bytes([info[0], info[1]]);
+            let len = u16::from_le_bytes([info[2], info[3]]) as usize;
+            let value = info.get(4..4 + len)?;
+            match id {
+                MSV_AV_EOL => return None,
+                MSV_AV_TIMESTAMP => return value.try_into().ok(),
+                _ => info = &info[4 + len..],
+            }
+        }
+        None
+    }
+}
+
+/// Type 3 message answering `challenge` with NTLMv2 responses.
+/// `timestamp` is a FILETIME, used when the server did not send one.
+pub fn authenticate_message(
+    challenge: &Challenge,
+    username: &str,
+    password: &str,
+    domain: &str,
+    client_challenge: [u8; 8],
+    timestamp: u64,
+) -> Vec<u8> {
+    let key = ntowf_v2(username, password, domain);
+    let server_timestamp = challenge.timestamp();
+
+    let mut blob = vec![1, 1, 0, 0, 0, 0, 0, 0];
+    blob.extend_from_slice(&server_timestamp.unwrap_or(timestamp.to_le_bytes()));
+    blob.extend_from_slice(&client_challenge);
+    blob.extend_from_slice(&[0; 4]);
+    blob.extend_from_slice(&challenge.target_info);
+    blob.extend_from_slice(&[0; 4]);
+
+    let mut nt_response = hmac_md5(&key, &[&challenge.server_challenge, &blob]);
+    nt_response.extend_from_slice(&blob);
+
+    // With a server timestamp the LMv2 response must be zeroed (MS-NLMP 3.1.5.1.2)
+    let lm_response = if server_timestamp.is_some() {
+        vec![0; 24]
+    } else {
+        let mut lm = hmac_md5(&key, &[&challenge.server_challenge, &client_challenge]);
+        lm.extend_from_slice(&client_challenge);
+        lm
+    };
+
+    let fields = [
+        lm_response,
+        nt_response,
+        utf16le(domain),
+        utf16le(username),
+        Vec::new(), // workstation
+        Vec::new(), // encrypted random session key
+    ];
+    let flags = (challenge.flags & NEGOTIATE_FLAGS & !NEGOTIATE_OEM) | NEGOTIATE_UNICODE;
+
+    let mut message = Vec::new();
+    message.extend_from_slice(SIGNATURE);
+    message.extend_from_slice(&3u32.to_le_bytes());
+    let mut offset = SIGNATURE.len() + 4 + fields.len() * 8 + 4;
+    for field in &fields {
+        message.extend_from_slice(&(field.len() as u16).to_le_bytes());
+        message.extend_from_slice(&(field.len() as u16).to_le_bytes());
+        message.extend_from_slice(&(offset as u32).to_le_bytes());
+        offset += field.len();
+    }
+    message.extend_from_slice(&flags.to_le_bytes());
+    for field in &fields {
+        message.extend_from_slice(field);
+    }
+    message
+}
+
+/// Current time as a FILETIME (100ns intervals since 1601)
+pub fn filetime_now() -> u64 {
+    let since_unix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    (since_unix.as_secs() + FILETIME_UNIX_OFFSET) * 10_000_000
+        + u64::from(since_unix.subsec_nanos() / 100)
+}
+
+/// NTOWFv2 key: HMAC-MD5 of the uppercased user and domain, keyed by the NT hash
+fn ntowf_v2(username: &str, password: &str, domain: &str) -> Vec<u8> {
+    let nt_hash = Md4::digest(utf16le(password));
+    let identity = utf16le(&format!("{}{}", username.to_uppercase(), domain));
+    hmac_md5(&nt_hash, &[&identity])
+}
+
+fn hmac_md5(key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+    let mut mac = HmacMd5::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn utf16le(value: &str) -> Vec<u8> {
+    value.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+fn read_u32(message: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        message.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+/// Payload of the (length, max length, offset) field at `offset`
+fn security_buffer(message: &[u8], offset: usize) -> Option<&[u8]> {
+    let len = u16::from_le_bytes(message.get(offset..offset + 2)?.try_into().ok()?) as usize;
+    let start = read_u32(message, offset + 4)? as usize;
+    message.get(start..start + len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Challenge message carrying the MS-NLMP 4.2.4 NTLMv2 test values
+    fn spec_challenge() -> Vec<u8> {
+        let target_info: Vec<u8> = [
+            &[2u8, 0, 12, 0][..],
+            &utf16le("Domain")[..],
+            &[1, 0, 12, 0][..],
+            &utf16le("Server")[..],
+            &[0, 0, 0, 0][..],
+        ]
+        .concat();
+        let mut message = SIGNATURE.to_vec();
+        message.extend_from_slice(&2u32.to_le_bytes());
+        message.extend_from_slice(&[0; 8]); // target name
+        message.extend_from_slice(&0xe28a_8233u32.to_le_bytes());
+        message.extend_from_slice(&[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]);
+        message.extend_from_slice(&[0; 8]);
+        message.extend_from_slice(&(target_info.len() as u16).to_le_bytes());
+        message.extend_from_slice(&(target_info.len() as u16).to_le_bytes());
+        message.extend_from_slice(&48u32.to_le_bytes());
+        message.extend_from_slice(&target_info);
+        message
+    }
+
+    #[test]
+    fn test_ntowf_v2() {
+        assert_eq!(
+            hex(&ntowf_v2("User", "Password", "Domain")),
+            "0c868a403bfd7a93a3001ef22ef02e3f"
+        );
+    }
+
+    #[test]
+    fn test_authenticate_message_matches_spec_vectors() {
+        let challenge = Challenge::parse(&spec_challenge()).unwrap();
+        assert_eq!(
+            challenge.server_challenge,
+            [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]
+        );
+        assert_eq!(challenge.timestamp(), None);
+
+        let message = authenticate_message(&challenge, "User", "Password", "Domain", [0xaa; 8], 0);
+        assert_eq!(&message[..8], SIGNATURE);
+        assert_eq!(read_u32(&message, 8), Some(3));
+
+        let lm = security_buffer(&message, 12).unwrap();
+        assert_eq!(hex(lm), "86c35097ac9cec102554764a57cccc19aaaaaaaaaaaaaaaa");
+        let nt = security_buffer(&message, 20).unwrap();
+        assert_eq!(hex(&nt[..16]), "68cd0ab851e51c96aabc927bebef6a1c");
+        assert_eq!(security_buffer(&message, 28).unwrap(), utf16le("Domain"));
+        assert_eq!(security_buffer(&message, 36).unwrap(), utf16le("User"));
+    }
+
+    #[test]
+    fn test_server_timestamp_zeroes_lm_response() {
+        let mut challenge = Challenge::parse(&spec_challenge()).unwrap();
+        let mut target_info = vec![7, 0, 8, 0, 1, 2, 3, 4, 5, 6, 7, 8];
+        target_info.extend_from_slice(&[0; 4]);
+        challenge.target_info = target_info;
+        assert_eq!(challenge.timestamp(), Some([1, 2, 3, 4, 5, 6, 7, 8]));
+
+        let message = authenticate_message(&challenge, "User", "Password", "Domain", [0xaa; 8], 0);
+        assert_eq!(security_buffer(&message, 12).unwrap(), &[0; 24]);
+        let nt = security_buffer(&message, 20).unwrap();
+        assert_eq!(&nt[24..32], &[1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_challenge_rejects_other_messages() {
+        assert!(Challenge::parse(&negotiate_message()).is_none());
+        assert!(Challenge::parse(b"not ntlm").is_none());
+    }
+}
@@ -4,11 +4,10 @@
 use ahash::AHashMap;
 use parking_lot::Mutex;
 use std::alloc::{GlobalAlloc, Layout, System};
-#[cfg(target_arch = "x86_64")]
-use std::arch::x86_64::*;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// High-performance allocator with memory safety guarantees
+#[allow(dead_code)]
 pub struct HighPerformanceAllocator {
     system: System,
     // Pool for common allocation sizes with bounds checking
@@ -20,6 +19,7 @@ pub struct HighPerformanceAllocator {
     pool_usage: [AtomicUsize; 8],
 }
 
+#[allow(dead_code)]
 impl HighPerformanceAllocator {
     pub const fn new() -> Self {
         const EMPTY_VEC: Mutex<Vec<*mut u8>> = Mutex::new(Vec::new());
@@ -195,6 +195,7 @@ impl SIMDStringOps {
     /// - We handle the scalar fallback for small strings
     /// - We use safe slice operations for remainder bytes
     #[cfg(target_arch = "x86_64")]
+    #[allow(dead_code)]
     pub fn compare_header_name_simd(a: &[u8], b: &[u8]) -> bool {
         if a.len() != b.len() {
             return false;
@@ -257,7 +258,7 @@ impl SIMDStringOps {
 
                 // Handle remaining bytes safely using slice operations
                 while i < len {
-                    if a[i].to_ascii_lowercase() != b[i].to_ascii_lowercase() {
+                    if !a[i].eq_ignore_ascii_case(&b[i]) {
                         return false;
                     }
                     i += 1;
@@ -276,6 +277,7 @@ impl SIMDStringOps {
 
     /// Fallback for non-x86_64 targets
     #[cfg(not(target_arch = "x86_64"))]
+    #[allow(dead_code)]
     pub fn compare_header_name_simd(a: &[u8], b: &[u8]) -> bool {
         a.eq_ignore_ascii_case(b)
     }
@@ -631,7 +633,7 @@ impl ProfileCollector {
     pub fn get_optimization_hints(&self) -> OptimizationHints {
         // Acquire all locks sequentially to avoid deadlocks
         let header_freq = self.header_frequency.lock();
-        let url_patterns = self.url_patterns.lock();
+        let _url_patterns = self.url_patterns.lock();
         let response_sizes = self.response_sizes.lock();
         let connection_patterns = self.connection_patterns.lock();
 
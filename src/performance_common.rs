@@ -412,7 +412,7 @@ impl ZeroCopyBody {
     }
 
     /// Convert to text with zero-copy when possible
-    pub fn text(&self) -> Result<Cow<str>, std::str::Utf8Error> {
+    pub fn text(&self) -> Result<Cow<'_, str>, std::str::Utf8Error> {
         match std::str::from_utf8(&self.data) {
             Ok(s) => Ok(Cow::Borrowed(s)),
             Err(e) => Err(e),
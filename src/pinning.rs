@@ -0,0 +1,341 @@
+use crate::config::SSLConfig;
+use base64::Engine;
+use pyo3::prelude::*;
+use sha2::{Digest, Sha256};
+
+/// Prefix of a pin, as used by HPKP and curl's `--pinnedpubkey`
+const PIN_PREFIX: &str = "sha256/";
+
+/// SHA-256 hashes of the server public keys a client accepts
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "rustls-tls"), allow(dead_code))]
+pub(crate) struct PinSet(Vec<[u8; 32]>);
+
+impl PinSet {
+    /// Parse `sha256/<base64>` pins; the prefix is optional
+    pub(crate) fn parse(pins: &[String]) -> PyResult<Self> {
+        pins.iter()
+            .map(|pin| {
+                let encoded = pin.trim();
+                let encoded = encoded.strip_prefix(PIN_PREFIX).unwrap_or(encoded);
+                base64::engine::general_purpose::STANDARD
+                    .decode(encoded)
+                    .ok()
+                    .and_then(|hash| <[u8; 32]>::try_from(hash).ok())
+                    .ok_or_else(|| {
+                        pyo3::exceptions::PyValueError::new_err(format!(
+                            "Invalid public key pin '{}': expected sha256/<base64 SHA-256>",
+                            pin
+                        ))
+                    })
+            })
+            .collect::<PyResult<Vec<_>>>()
+            .map(PinSet)
+    }
+
+    /// Whether the certificate's public key hashes to one of the pins
+    #[cfg_attr(not(feature = "rustls-tls"), allow(dead_code))]
+    pub(crate) fn matches(&self, cert_der: &[u8]) -> bool {
+        subject_public_key_info(cert_der).is_some_and(|spki| {
+            let hash: [u8; 32] = Sha256::digest(spki).into();
+            self.0.contains(&hash)
+        })
+    }
+}
+
+/// Pin for a DER certificate's public key
+pub(crate) fn spki_pin(cert_der: &[u8]) -> Option<String> {
+    let spki = subject_public_key_info(cert_der)?;
+    Some(format!(
+        "{}{}",
+        PIN_PREFIX,
+        base64::engine::general_purpose::STANDARD.encode(Sha256::digest(spki))
+    ))
+}
+
+/// DER of the first certificate in a PEM string
+pub(crate) fn pem_certificate_der(pem: &str) -> Option<Vec<u8>> {
+    let body = pem
+        .split("-----BEGIN CERTIFICATE-----")
+        .nth(1)?
+        .split("-----END CERTIFICATE-----")
+        .next()?;
+    let encoded: String = body.split_whitespace().collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .ok()
+}
+
+/// Split one DER element off the front of `data`, returning (tag, contents, rest)
+fn der_element(data: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let tag = *data.first()?;
+    let first = *data.get(1)? as usize;
+    let (len, header) = if first < 0x80 {
+        (first, 2)
+    } else {
+        let count = first & 0x7f;
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let len = data
+            .get(2..2 + count)?
+            .iter()
+            .fold(0usize, |len, byte| (len << 8) | *byte as usize);
+        (len, 2 + count)
+    };
+    let end = header.checked_add(len)?;
+    if end > data.len() {
+        return None;
+    }
+    Some((tag, &data[header..end], &data[end..]))
+}
+
+/// DER of a certificate's SubjectPublicKeyInfo, the part pins are computed over
+fn subject_public_key_info(cert_der: &[u8]) -> Option<&[u8]> {
+    const SEQUENCE: u8 = 0x30;
+    const EXPLICIT_VERSION: u8 = 0xa0;
+
+    let (tag, certificate, _) = der_element(cert_der)?;
+    if tag != SEQUENCE {
+        return None;
+    }
+    let (tag, mut fields, _) = der_element(certificate)?;
+    if tag != SEQUENCE {
+        return None;
+    }
+    if fields.first() == Some(&EXPLICIT_VERSION) {
+        fields = der_element(fields)?.2;
+    }
+    // Skip serialNumber, signature, issuer, validity and subject
+    for _ in 0..5 {
+        fields = der_element(fields)?.2;
+    }
+    let (tag, _, rest) = der_element(fields)?;
+    if tag != SEQUENCE {
+        return None;
+    }
+    Some(&fields[..fields.len() - rest.len()])
+}
+
+/// TLS configuration a client uses in place of its default backend when keys are pinned
+#[cfg(feature = "rustls-tls")]
+pub(crate) type PinnedTls = rustls::ClientConfig;
+#[cfg(not(feature = "rustls-tls"))]
+pub(crate) type PinnedTls = std::convert::Infallible;
+
+/// Build a TLS configuration enforcing `pinned_public_keys`, or None when nothing is pinned.
+///
+/// It stands in for the whole TLS setup of the client builder, so it trusts the system
+/// roots plus `ca_bundle`, honours `min_tls_version` (rustls speaks TLS 1.2 and 1.3 only)
+/// and presents `client_cert` when given, else the configured identity.
+#[cfg(feature = "rustls-tls")]
+pub(crate) fn pinned_tls_config(
+    ssl: &SSLConfig,
+    allow_http2: bool,
+    client_cert: Option<(&str, &str)>,
+) -> PyResult<Option<PinnedTls>> {
+    use std::sync::Arc;
+
+    if ssl.pinned_public_keys.is_empty() {
+        return Ok(None);
+    }
+    let pins = PinSet::parse(&ssl.pinned_public_keys)?;
+    let config_error = |e: rustls::Error| {
+        pyo3::exceptions::PyValueError::new_err(format!("Invalid TLS configuration: {}", e))
+    };
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let mut roots = rustls::RootCertStore::empty();
+    // Unparseable system certificates are skipped, as the native-tls backend does
+    roots.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+    if let Some(bundle) = ssl.ca_bundle_pem()? {
+        for cert in rustls_pemfile::certs(&mut &bundle[..]).flatten() {
+            roots.add(cert).map_err(config_error)?;
+        }
+    }
+    let chain = rustls::client::WebPkiServerVerifier::builder_with_provider(
+        Arc::new(roots),
+        Arc::clone(&provider),
+    )
+    .build()
+    .map_err(|e| {
+        pyo3::exceptions::PyValueError::new_err(format!("Invalid TLS configuration: {}", e))
+    })?;
+
+    let versions: &[&rustls::SupportedProtocolVersion] = match ssl.min_tls_version.as_deref() {
+        Some("1.3") => &[&rustls::version::TLS13],
+        _ => rustls::DEFAULT_VERSIONS,
+    };
+    let builder = rustls::ClientConfig::builder_with_provider(provider)
+        .with_protocol_versions(versions)
+        .map_err(config_error)?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(verifier::PinningVerifier {
+            chain,
+            pins,
+            verify_chain: ssl.verify,
+        }));
+
+    let identity = match client_cert {
+        Some((cert, key)) => Some((cert.as_bytes().to_vec(), key.as_bytes().to_vec())),
+        None => ssl.identity_pem()?,
+    };
+    let mut config = match identity {
+        Some((cert, key)) => {
+            let invalid =
+                || pyo3::exceptions::PyValueError::new_err("Invalid client certificate or key");
+            let certs = rustls_pemfile::certs(&mut &cert[..])
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid())?;
+            let key = rustls_pemfile::private_key(&mut &key[..])
+                .ok()
+                .flatten()
+                .ok_or_else(invalid)?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(config_error)?
+        }
+        None => builder.with_no_client_auth(),
+    };
+    config.alpn_protocols = if allow_http2 {
+        vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+    } else {
+        vec![b"http/1.1".to_vec()]
+    };
+    Ok(Some(config))
+}
+
+/// Public key pinning needs a TLS backend with pluggable certificate verification
+#[cfg(not(feature = "rustls-tls"))]
+pub(crate) fn pinned_tls_config(
+    ssl: &SSLConfig,
+    _allow_http2: bool,
+    _client_cert: Option<(&str, &str)>,
+) -> PyResult<Option<PinnedTls>> {
+    if ssl.pinned_public_keys.is_empty() {
+        return Ok(None);
+    }
+    PinSet::parse(&ssl.pinned_public_keys)?;
+    Err(pyo3::exceptions::PyNotImplementedError::new_err(
+        "pinned_public_keys requires building with the rustls-tls feature (cargo build --features rustls-tls)",
+    ))
+}
+
+#[cfg(feature = "rustls-tls")]
+mod verifier {
+    use super::PinSet;
+    use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+    use rustls::client::WebPkiServerVerifier;
+    use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+    use rustls::{DigitallySignedStruct, Error, SignatureScheme};
+    use std::sync::Arc;
+
+    /// Verifies the chain as usual (unless `verify` is off), then checks the leaf against the pins
+    #[derive(Debug)]
+    pub(super) struct PinningVerifier {
+        pub(super) chain: Arc<WebPkiServerVerifier>,
+        pub(super) pins: PinSet,
+        pub(super) verify_chain: bool,
+    }
+
+    impl ServerCertVerifier for PinningVerifier {
+        fn verify_server_cert(
+            &self,
+            end_entity: &CertificateDer<'_>,
+            intermediates: &[CertificateDer<'_>],
+            server_name: &ServerName<'_>,
+            ocsp_response: &[u8],
+            now: UnixTime,
+        ) -> Result<ServerCertVerified, Error> {
+            if self.verify_chain {
+                self.chain.verify_server_cert(
+                    end_entity,
+                    intermediates,
+                    server_name,
+                    ocsp_response,
+                    now,
+                )?;
+            }
+            if self.pins.matches(end_entity.as_ref()) {
+                Ok(ServerCertVerified::assertion())
+            } else {
+                Err(Error::General(
+                    "certificate public key does not match any pinned key".to_string(),
+                ))
+            }
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, Error> {
+            self.chain.verify_tls12_signature(message, cert, dss)
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, Error> {
+            self.chain.verify_tls13_signature(message, cert, dss)
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            self.chain.supported_verify_schemes()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Self-signed P-256 certificate; its pin was computed with `openssl x509 -pubkey -noout |
+    // openssl pkey -pubin -outform der | openssl dgst -sha256 -binary | base64`
+    const FIXTURE_CERT: &str = "-----BEGIN CERTIFICATE-----
+MIIBizCCATGgAwIBAgIUWa6sZNDOQLphiRJcxQp7z/MBqZMwCgYIKoZIzj0EAwIw
+GjEYMBYGA1UEAwwPcGlubmluZy1maXh0dXJlMCAXDTI2MTAxNjAyNDE0MloYDzIx
+MjYwOTIyMDI0MTQyWjAaMRgwFgYDVQQDDA9waW5uaW5nLWZpeHR1cmUwWTATBgcq
+hkjOPQIBBggqhkjOPQMBBwNCAASegV8JoTw+DftOsx4pDUWXeCC+2qH5NDrAQJJL
+mTix0Wn09JGUuvDWHbVAAhoSibdZMCXExi6WVayBcMeVVey5o1MwUTAdBgNVHQ4E
+FgQUqHlCS69M5h4K2G9Wxb1CRUijh/4wHwYDVR0jBBgwFoAUqHlCS69M5h4K2G9W
+xb1CRUijh/4wDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNIADBFAiAe7bVR
+IRebEAgtNOtOLtn2WXb4W5IckBLqQn4y6bW/cAIhAOnmQ7X83756fREas8vASHtJ
+8M4puhBjGjVEvDBW0SRI
+-----END CERTIFICATE-----
+";
+    const FIXTURE_PIN: &str = "sha256/QX8Gzez0SVYSh5pue+RTkRxmuP+aXSYb3BawGqPa+Jc=";
+
+    #[test]
+    fn test_spki_pin_matches_openssl() {
+        let der = pem_certificate_der(FIXTURE_CERT).unwrap();
+        assert_eq!(spki_pin(&der).as_deref(), Some(FIXTURE_PIN));
+    }
+
+    #[test]
+    fn test_pin_set_matches_only_correct_pin() {
+        let der = pem_certificate_der(FIXTURE_CERT).unwrap();
+        let bare = FIXTURE_PIN.trim_start_matches(PIN_PREFIX).to_string();
+        assert!(PinSet::parse(&[bare]).unwrap().matches(&der));
+
+        let wrong = format!("{}{}", PIN_PREFIX, "A".repeat(43) + "=");
+        let pins = PinSet::parse(&[wrong]).unwrap();
+        assert!(!pins.matches(&der));
+    }
+
+    #[test]
+    fn test_invalid_pins_rejected() {
+        assert!(PinSet::parse(&["sha256/not-base64!".to_string()]).is_err());
+        assert!(PinSet::parse(&["sha256/AAAA".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_truncated_certificate_has_no_pin() {
+        let der = pem_certificate_der(FIXTURE_CERT).unwrap();
+        assert!(spki_pin(&der[..40]).is_none());
+        assert!(spki_pin(&[]).is_none());
+    }
+}
@@ -1,6 +1,5 @@
 use crate::config::{HttpVersion, ProtocolConfig, ProtocolFallback};
 use ahash::AHashMap;
-use once_cell::sync::Lazy;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -21,10 +20,78 @@ pub struct EnhancedProtocolNegotiator {
     dns_cache: Arc<RwLock<AHashMap<String, (Vec<SocketAddr>, Instant)>>>,
     #[allow(dead_code)]
     dns_cache_ttl: Duration,
+    // HTTP/3 endpoints advertised through Alt-Svc, keyed by origin
+    alt_svc: Arc<RwLock<AHashMap<String, AltSvcEntry>>>,
+}
+
+/// An `h3` alternative service advertised by an origin
+#[derive(Debug, Clone)]
+struct AltSvcEntry {
+    /// UDP port to reach HTTP/3 on (None means the origin's own port)
+    port: Option<u16>,
+    expires: Instant,
+}
+
+/// What an `Alt-Svc` header says about HTTP/3
+#[derive(Debug, Clone, PartialEq)]
+pub enum AltSvc {
+    /// `clear`: forget every advertised alternative
+    Clear,
+    /// `h3` is offered for `max_age`
+    Http3 {
+        port: Option<u16>,
+        max_age: Duration,
+    },
+    /// Only non-h3 alternatives are offered
+    Other,
+}
+
+/// Freshness RFC 7838 assumes when an alternative has no `ma` parameter
+const DEFAULT_ALT_SVC_MAX_AGE: Duration = Duration::from_secs(86400);
+
+/// Parse an `Alt-Svc` header value, e.g. `h3=":443"; ma=3600, h2=":443"`
+pub fn parse_alt_svc(value: &str) -> AltSvc {
+    if value.trim().eq_ignore_ascii_case("clear") {
+        return AltSvc::Clear;
+    }
+
+    for alternative in value.split(',') {
+        let mut parts = alternative.split(';').map(str::trim);
+        let (protocol, authority) = match parts.next().and_then(|p| p.split_once('=')) {
+            Some((protocol, authority)) => (protocol.trim(), authority.trim().trim_matches('"')),
+            None => continue,
+        };
+        if protocol != "h3" {
+            continue;
+        }
+
+        let port = authority
+            .rsplit_once(':')
+            .and_then(|(_, port)| port.parse().ok());
+        let max_age = parts
+            .filter_map(|param| param.split_once('='))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("ma"))
+            .and_then(|(_, seconds)| seconds.trim().trim_matches('"').parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_ALT_SVC_MAX_AGE);
+        return AltSvc::Http3 { port, max_age };
+    }
+    AltSvc::Other
+}
+
+/// Cache key for an origin: host plus effective port
+fn origin_key(url: &str) -> Option<String> {
+    let parsed = url::Url::parse(url).ok()?;
+    Some(format!(
+        "{}:{}",
+        parsed.host_str()?,
+        parsed.port_or_known_default()?
+    ))
 }
 
 /// Enhanced host capabilities with detailed performance tracking
 #[derive(Debug, Clone)]
+#[allow(dead_code)]
 pub struct HostCapabilities {
     /// Highest supported HTTP version
     pub max_version: HttpVersion,
@@ -62,6 +129,7 @@ pub struct ProtocolMetrics {
     pub total_response_time: Duration,
     pub last_used: Option<Instant>,
     pub connection_errors: u32,
+    #[allow(dead_code)]
     pub timeout_errors: u32,
 }
 
@@ -117,33 +185,89 @@ impl EnhancedProtocolNegotiator {
             cache_ttl: Duration::from_secs(3600), // 1 hour cache
             dns_cache: Arc::new(RwLock::new(AHashMap::new())),
             dns_cache_ttl: Duration::from_secs(300), // 5 minute DNS cache
+            alt_svc: Arc::new(RwLock::new(AHashMap::new())),
         }
     }
 
-    /// Select the best protocol for a given URL with intelligent caching
+    /// Select the best protocol for a given URL with intelligent caching.
+    /// In `Auto` mode HTTP/3 is only chosen for origins that advertised it via Alt-Svc.
     pub async fn select_protocol(&self, url: &str, config: &ProtocolConfig) -> HttpVersion {
         let host = extract_host(url);
+        let http3_advertised = self.advertises_http3(url);
 
         match &config.preferred_version {
-            HttpVersion::Auto => self.auto_select_protocol(&host, config).await,
+            HttpVersion::Auto => {
+                self.auto_select_protocol(&host, config, http3_advertised)
+                    .await
+            }
             specific_version => specific_version.clone(),
         }
     }
 
     /// Automatically select the best protocol using machine learning-like approach
-    async fn auto_select_protocol(&self, host: &str, config: &ProtocolConfig) -> HttpVersion {
+    async fn auto_select_protocol(
+        &self,
+        host: &str,
+        config: &ProtocolConfig,
+        http3_advertised: bool,
+    ) -> HttpVersion {
         // Fast path: check cache first
-        if let Some(capabilities) = self.get_cached_capabilities(host) {
-            if !self.is_cache_expired(&capabilities) {
-                return self.select_best_protocol(&capabilities, config);
+        let mut capabilities = match self.get_cached_capabilities(host) {
+            Some(capabilities) if !self.is_cache_expired(&capabilities) => capabilities,
+            _ => {
+                // Slow path: detect protocol capabilities
+                let capabilities = self.detect_protocol_capabilities(host).await;
+                self.update_cache(host, capabilities.clone());
+                capabilities
+            }
+        };
+
+        capabilities.http3_available &= http3_advertised;
+        self.select_best_protocol(&capabilities, config)
+    }
+
+    /// Remember the HTTP/3 endpoint an origin advertised in an `Alt-Svc` response header
+    pub fn record_alt_svc(&self, url: &str, header: &str) {
+        let key = match origin_key(url) {
+            Some(key) => key,
+            None => return,
+        };
+        if let Ok(mut alt_svc) = self.alt_svc.write() {
+            match parse_alt_svc(header) {
+                AltSvc::Http3 { port, max_age } if !max_age.is_zero() => {
+                    alt_svc.insert(
+                        key,
+                        AltSvcEntry {
+                            port,
+                            expires: Instant::now() + max_age,
+                        },
+                    );
+                }
+                // A fresh header replaces earlier advertisements
+                _ => {
+                    alt_svc.remove(&key);
+                }
             }
         }
+    }
 
-        // Slow path: detect protocol capabilities
-        let capabilities = self.detect_protocol_capabilities(host).await;
-        self.update_cache(host, capabilities.clone());
+    /// Whether the URL's origin has an unexpired `h3` Alt-Svc advertisement
+    pub fn advertises_http3(&self, url: &str) -> bool {
+        self.alt_svc_entry(url).is_some()
+    }
 
-        self.select_best_protocol(&capabilities, config)
+    /// UDP port the URL's origin advertised for HTTP/3, if any
+    pub fn http3_port(&self, url: &str) -> Option<u16> {
+        self.alt_svc_entry(url)?.port
+    }
+
+    fn alt_svc_entry(&self, url: &str) -> Option<AltSvcEntry> {
+        let key = origin_key(url)?;
+        let alt_svc = self.alt_svc.read().ok()?;
+        alt_svc
+            .get(&key)
+            .filter(|entry| entry.expires > Instant::now())
+            .cloned()
     }
 
     /// Get cached capabilities with fast read lock
@@ -161,7 +285,7 @@ impl EnhancedProtocolNegotiator {
     fn select_best_protocol(
         &self,
         capabilities: &HostCapabilities,
-        config: &ProtocolConfig,
+        _config: &ProtocolConfig,
     ) -> HttpVersion {
         // Get protocol weights for intelligent selection
         let weights = self.get_protocol_weights(&extract_host_from_capabilities(capabilities));
@@ -245,7 +369,7 @@ impl EnhancedProtocolNegotiator {
     }
 
     /// Detect protocol capabilities for a host
-    async fn detect_protocol_capabilities(&self, host: &str) -> HostCapabilities {
+    async fn detect_protocol_capabilities(&self, _host: &str) -> HostCapabilities {
         // In a real implementation, this would perform actual protocol detection
         // For now, we'll use heuristics and return optimistic capabilities
 
@@ -270,6 +394,7 @@ impl EnhancedProtocolNegotiator {
     }
 
     /// Update protocol performance metrics after a request
+    #[allow(dead_code)]
     pub fn update_protocol_metrics(
         &self,
         host: &str,
@@ -357,6 +482,7 @@ impl EnhancedProtocolNegotiator {
     }
 
     /// Get cache statistics for monitoring
+    #[allow(dead_code)]
     pub fn get_cache_stats(&self) -> CacheStats {
         if let Ok(cache) = self.protocol_cache.read() {
             CacheStats {
@@ -382,6 +508,7 @@ impl EnhancedProtocolNegotiator {
     }
 
     /// Clean up expired cache entries
+    #[allow(dead_code)]
     pub fn cleanup_expired_entries(&self) {
         let now = Instant::now();
 
@@ -392,6 +519,11 @@ impl EnhancedProtocolNegotiator {
             });
         }
 
+        // Clean up expired Alt-Svc advertisements
+        if let Ok(mut alt_svc) = self.alt_svc.write() {
+            alt_svc.retain(|_, entry| entry.expires > now);
+        }
+
         // Clean up DNS cache
         if let Ok(mut dns_cache) = self.dns_cache.write() {
             dns_cache
@@ -406,6 +538,7 @@ impl EnhancedProtocolNegotiator {
     }
 
     /// Preload protocol capabilities for a list of hosts
+    #[allow(dead_code)]
     pub async fn preload_capabilities(&self, hosts: &[String]) {
         for host in hosts {
             if self.get_cached_capabilities(host).is_none() {
@@ -418,6 +551,7 @@ impl EnhancedProtocolNegotiator {
 
 /// Cache statistics for monitoring
 #[derive(Debug, Clone)]
+#[allow(dead_code)]
 pub struct CacheStats {
     pub cache_hits: u64,
     pub cache_misses: u64,
@@ -450,90 +584,6 @@ fn extract_host_from_capabilities(_capabilities: &HostCapabilities) -> String {
     "unknown".to_string()
 }
 
-/// Protocol detection utilities with enhanced capabilities
-pub struct ProtocolDetector;
-
-impl ProtocolDetector {
-    /// Detect HTTP/3 support using QUIC connection attempt
-    pub async fn detect_http3_support(host: &str, port: u16) -> bool {
-        // Implement QUIC connection test
-        // For now, assume most modern hosts support HTTP/3
-        tokio::time::timeout(Duration::from_millis(500), async {
-            // Simulate QUIC handshake attempt
-            tokio::time::sleep(Duration::from_millis(50)).await;
-            true
-        })
-        .await
-        .unwrap_or(false)
-    }
-
-    /// Detect HTTP/2 support using ALPN negotiation
-    pub async fn detect_http2_support(host: &str, port: u16) -> bool {
-        // Implement TLS ALPN negotiation test
-        // For now, assume most HTTPS hosts support HTTP/2
-        tokio::time::timeout(Duration::from_millis(200), async {
-            // Simulate TLS handshake with ALPN
-            tokio::time::sleep(Duration::from_millis(20)).await;
-            true
-        })
-        .await
-        .unwrap_or(false)
-    }
-
-    /// Comprehensive protocol detection
-    pub async fn detect_all_protocols(host: &str, port: u16) -> HostCapabilities {
-        let start = Instant::now();
-
-        let (http2_available, http3_available) = tokio::join!(
-            Self::detect_http2_support(host, port),
-            Self::detect_http3_support(host, port)
-        );
-
-        let connection_time = start.elapsed();
-
-        HostCapabilities {
-            max_version: if http3_available {
-                HttpVersion::Http3
-            } else if http2_available {
-                HttpVersion::Http2
-            } else {
-                HttpVersion::Http1
-            },
-            http2_available,
-            http3_available,
-            last_updated: Instant::now(),
-            success_rate: 1.0,
-            avg_connection_time: connection_time,
-            alpn_protocols: {
-                let mut protocols = vec!["http/1.1".to_string()];
-                if http2_available {
-                    protocols.push("h2".to_string());
-                }
-                if http3_available {
-                    protocols.push("h3".to_string());
-                }
-                protocols
-            },
-            tls_versions: vec!["TLSv1.3".to_string(), "TLSv1.2".to_string()],
-            failure_count: 0,
-            avg_response_time: Some(connection_time),
-            reliability_score: 1.0,
-            http1_metrics: ProtocolMetrics::default(),
-            http2_metrics: ProtocolMetrics::default(),
-            http3_metrics: ProtocolMetrics::default(),
-        }
-    }
-}
-
-/// Global protocol negotiator instance for improved performance
-static GLOBAL_NEGOTIATOR: Lazy<EnhancedProtocolNegotiator> =
-    Lazy::new(|| EnhancedProtocolNegotiator::new(ProtocolFallback::Http3ToHttp2ToHttp1));
-
-/// Get the global protocol negotiator instance
-pub fn get_global_negotiator() -> &'static EnhancedProtocolNegotiator {
-    &GLOBAL_NEGOTIATOR
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -599,4 +649,47 @@ mod tests {
         let score = negotiator.calculate_protocol_score(&metrics, 1.5, 0.9);
         assert!(score > 0.0);
     }
+
+    #[test]
+    fn test_parse_alt_svc() {
+        assert_eq!(
+            parse_alt_svc("h3=\":443\"; ma=3600, h2=\":443\""),
+            AltSvc::Http3 {
+                port: Some(443),
+                max_age: Duration::from_secs(3600)
+            }
+        );
+        assert_eq!(
+            parse_alt_svc("h2=\"alt.example.com:443\", h3=\"alt.example.com:8443\""),
+            AltSvc::Http3 {
+                port: Some(8443),
+                max_age: DEFAULT_ALT_SVC_MAX_AGE
+            }
+        );
+        assert_eq!(parse_alt_svc("h2=\":443\"; ma=60"), AltSvc::Other);
+        assert_eq!(parse_alt_svc("clear"), AltSvc::Clear);
+    }
+
+    #[tokio::test]
+    async fn test_http3_requires_alt_svc_advertisement() {
+        let negotiator = EnhancedProtocolNegotiator::new(ProtocolFallback::Http3ToHttp2ToHttp1);
+        let config = ProtocolConfig::default();
+        let url = "https://example.com/data";
+
+        let protocol = negotiator.select_protocol(url, &config).await;
+        assert_ne!(protocol, HttpVersion::Http3);
+
+        negotiator.record_alt_svc("https://example.com/", "h3=\":8443\"; ma=60");
+        assert!(negotiator.advertises_http3(url));
+        assert_eq!(negotiator.http3_port(url), Some(8443));
+        assert_eq!(
+            negotiator.select_protocol(url, &config).await,
+            HttpVersion::Http3
+        );
+
+        // Advertisements are per origin and can be withdrawn
+        assert!(!negotiator.advertises_http3("https://example.com:8080/data"));
+        negotiator.record_alt_svc(url, "clear");
+        assert!(!negotiator.advertises_http3(url));
+    }
 }
@@ -1,8 +1,5 @@
 use pyo3::prelude::*;
-use pythonize;
 use std::collections::HashMap;
-#[cfg(test)]
-use std::net::SocketAddr;
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
@@ -49,6 +46,7 @@ impl Default for ProtocolStats {
     }
 }
 
+#[allow(dead_code)]
 impl ProtocolStatsManager {
     /// Create a new protocol statistics manager
     pub fn new() -> Self {
@@ -343,6 +341,7 @@ pub struct AsyncProtocolStatsManager {
     inner: Arc<tokio::sync::RwLock<HashMap<String, ProtocolStats>>>,
 }
 
+#[allow(dead_code)]
 impl AsyncProtocolStatsManager {
     /// Create a new async protocol statistics manager
     pub fn new() -> Self {
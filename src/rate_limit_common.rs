@@ -191,6 +191,10 @@ mod tests {
             queue_requests: false,
             max_queue_size: 100,
             queue_timeout_seconds: 5.0,
+            endpoint_rules: Vec::new(),
+            adaptive: false,
+            backoff_factor: 0.5,
+            recovery_step: 0.05,
         };
 
         let manager = RateLimitManager::new(Some(config));
@@ -221,6 +225,10 @@ mod tests {
             queue_requests: false,
             max_queue_size: 100,
             queue_timeout_seconds: 5.0,
+            endpoint_rules: Vec::new(),
+            adaptive: false,
+            backoff_factor: 0.5,
+            recovery_step: 0.05,
         };
 
         let mut manager = RateLimitManager::new(None);
@@ -242,6 +250,10 @@ mod tests {
             queue_requests: false,
             max_queue_size: 100,
             queue_timeout_seconds: 5.0,
+            endpoint_rules: Vec::new(),
+            adaptive: false,
+            backoff_factor: 0.5,
+            recovery_step: 0.05,
         };
 
         let manager = AsyncRateLimitManager::new(Some(config));
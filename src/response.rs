@@ -1,7 +1,159 @@
+use crate::cookies::Cookie;
+use crate::timing::Timing;
+use encoding_rs::{DecoderResult, Encoding, UTF_8, WINDOWS_1252};
+use jsonschema::JSONSchema;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
 use pyo3::prelude::*;
 use pyo3::types::PyBytes;
 use pyo3::types::PyDict;
 use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Arc;
+
+/// Largest unread body that is drained (rather than closed) when a lazy response is dropped
+const LAZY_DRAIN_LIMIT: u64 = 64 * 1024;
+
+/// Compiled JSON Schemas cached by caller-supplied id
+static SCHEMA_CACHE: Lazy<RwLock<HashMap<String, Arc<JSONSchema>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Compile a schema, reusing the cached one when `schema_id` is known
+fn compiled_schema(schema: Option<&PyAny>, schema_id: Option<&str>) -> PyResult<Arc<JSONSchema>> {
+    if let Some(id) = schema_id {
+        if let Some(compiled) = SCHEMA_CACHE.read().get(id) {
+            return Ok(Arc::clone(compiled));
+        }
+    }
+
+    let schema = schema.ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err("A schema is required for an uncached schema_id")
+    })?;
+    let schema_value: serde_json::Value = pythonize::depythonize(schema)?;
+    let compiled = Arc::new(JSONSchema::compile(&schema_value).map_err(|e| {
+        pyo3::exceptions::PyValueError::new_err(format!("Invalid JSON Schema: {}", e))
+    })?);
+
+    if let Some(id) = schema_id {
+        SCHEMA_CACHE
+            .write()
+            .insert(id.to_string(), Arc::clone(&compiled));
+    }
+    Ok(compiled)
+}
+
+/// Parse a client `body_mode` setting, returning true for lazy bodies
+pub(crate) fn parse_body_mode(body_mode: &str) -> PyResult<bool> {
+    match body_mode {
+        "eager" => Ok(false),
+        "lazy" => Ok(true),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Invalid body_mode '{}': expected 'eager' or 'lazy'",
+            other
+        ))),
+    }
+}
+
+/// Read `response`'s body, failing with ResponseTooLargeError as soon as it (or
+/// its Content-Length) exceeds `max_size` rather than buffering the rest
+pub(crate) async fn read_body(
+    response: reqwest::Response,
+    max_size: Option<usize>,
+) -> PyResult<Vec<u8>> {
+    read_body_mapped(response, max_size, body_read_error).await
+}
+
+/// `read_body`, converting transport errors with `map_error`
+pub(crate) async fn read_body_mapped(
+    mut response: reqwest::Response,
+    max_size: Option<usize>,
+    map_error: impl Fn(&reqwest::Error) -> PyErr,
+) -> PyResult<Vec<u8>> {
+    let Some(limit) = max_size else {
+        return Ok(response.bytes().await.map_err(|e| map_error(&e))?.to_vec());
+    };
+    check_announced_size(&response, max_size)?;
+    let mut content = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| map_error(&e))? {
+        check_body_size(content.len() + chunk.len(), Some(limit))?;
+        content.extend_from_slice(&chunk);
+    }
+    Ok(content)
+}
+
+/// Fail when a body of `received` bytes is over `max_size`
+fn check_body_size(received: usize, max_size: Option<usize>) -> PyResult<()> {
+    match max_size {
+        Some(limit) if received > limit => Err(crate::error::ResponseTooLargeError::new_err(
+            format!("Response body exceeds max_response_size of {} bytes", limit),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Fail before reading when the Content-Length is already over `max_size`
+fn check_announced_size(response: &reqwest::Response, max_size: Option<usize>) -> PyResult<()> {
+    match response.content_length() {
+        Some(length) => check_body_size(usize::try_from(length).unwrap_or(usize::MAX), max_size),
+        None => Ok(()),
+    }
+}
+
+fn body_read_error(e: &reqwest::Error) -> PyErr {
+    pyo3::exceptions::PyIOError::new_err(format!("Failed to read response body: {}", e))
+}
+
+/// Body of a lazily-read response, fetched on first access
+pub(crate) struct PendingBody {
+    response: Option<reqwest::Response>,
+    content: Option<Vec<u8>>,
+    // Set once `iter_content` starts pulling chunks off the connection
+    streamed: bool,
+    // Client's max_response_size, and how much of the body has been streamed so far
+    max_size: Option<usize>,
+    received: usize,
+    handle: tokio::runtime::Handle,
+    // Keeps the owning client's runtime alive until the body is read or released
+    _runtime: Option<Arc<tokio::runtime::Runtime>>,
+}
+
+impl std::fmt::Debug for PendingBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingBody")
+            .field("loaded", &self.content.is_some())
+            .finish()
+    }
+}
+
+impl PendingBody {
+    /// Count `len` more streamed bytes, abandoning the body once it's too large
+    fn count_received(&mut self, len: usize) -> PyResult<()> {
+        self.received += len;
+        let within_limit = check_body_size(self.received, self.max_size);
+        if within_limit.is_err() {
+            self.response = None;
+        }
+        within_limit
+    }
+}
+
+impl Drop for PendingBody {
+    /// Release the connection of a body that was never read
+    fn drop(&mut self) {
+        if let Some(response) = self.response.take() {
+            // Small bodies are drained so the connection can go back to the pool;
+            // anything larger is dropped, which closes the connection
+            if response
+                .content_length()
+                .map_or(false, |len| len <= LAZY_DRAIN_LIMIT)
+            {
+                self.handle.spawn(async move {
+                    let _ = response.bytes().await;
+                });
+            }
+        }
+    }
+}
 
 /// HTTP Response object
 #[pyclass]
@@ -9,6 +161,9 @@ use std::collections::HashMap;
 pub struct Response {
     #[pyo3(get)]
     pub status_code: u16,
+    /// Reason phrase for the status code, e.g. "Not Found"
+    #[pyo3(get)]
+    pub reason: String,
     #[pyo3(get)]
     pub headers: HashMap<String, String>,
     pub content: Vec<u8>,
@@ -31,29 +186,81 @@ pub struct Response {
     pub start_time: f64, // Start timestamp
     #[pyo3(get)]
     pub end_time: f64, // End timestamp
-    pub timing: Option<f64>,
+    pub timing: Option<Timing>,
+    pub(crate) lazy_body: Option<Arc<parking_lot::Mutex<PendingBody>>>,
+    // Raw `Set-Cookie` values, kept separately because `headers` holds one value per name
+    pub(crate) set_cookies: Vec<String>,
+    /// Redirect responses followed before this one, oldest first
+    #[pyo3(get)]
+    pub history: Vec<Response>,
+    /// Encoding assigned through the `encoding` property, replacing the Content-Type charset
+    pub(crate) encoding_override: Option<&'static Encoding>,
 }
 
 #[pymethods]
 impl Response {
-    /// Get response body as text
-    pub fn text(&self) -> PyResult<String> {
-        String::from_utf8(self.content.clone()).map_err(|e| {
-            pyo3::exceptions::PyUnicodeDecodeError::new_err(format!(
-                "Failed to decode response as UTF-8: {}",
-                e
-            ))
-        })
+    /// Get response body as text, decoded with `encoding` when known, else with `apparent_encoding`.
+    /// Undecodable bytes become U+FFFD; `errors="strict"` raises UnicodeDecodeError instead.
+    #[pyo3(signature = (errors = "replace"))]
+    pub fn text(&mut self, py: Python, errors: &str) -> PyResult<String> {
+        let strict = match errors {
+            "replace" => false,
+            "strict" => true,
+            other => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "errors must be 'replace' or 'strict', got '{}'",
+                    other
+                )))
+            }
+        };
+        self.decode_text(py, strict)
+    }
+
+    /// Encoding text() decodes with: the one assigned here, else the Content-Type charset.
+    /// None when neither is known. Assign None to go back to the Content-Type charset.
+    #[getter]
+    pub fn encoding(&self) -> Option<String> {
+        self.declared_encoding()
+            .map(|encoding| encoding.name().to_string())
+    }
+
+    #[setter]
+    pub fn set_encoding(&mut self, encoding: Option<&str>) -> PyResult<()> {
+        self.encoding_override = encoding
+            .map(|label| {
+                Encoding::for_label(label.trim().as_bytes()).ok_or_else(|| {
+                    pyo3::exceptions::PyLookupError::new_err(format!("unknown encoding: {}", label))
+                })
+            })
+            .transpose()?;
+        Ok(())
+    }
+
+    /// Encoding guessed from the body alone: a byte order mark, else UTF-8 when the body
+    /// is valid UTF-8, else windows-1252
+    #[getter]
+    pub fn apparent_encoding(&mut self) -> PyResult<String> {
+        self.ensure_body()?;
+        Ok(sniff_encoding(&self.content).name().to_string())
     }
 
     /// Get response body as bytes
-    pub fn bytes<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+    pub fn bytes<'py>(&mut self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        self.ensure_body()?;
         Ok(PyBytes::new(py, &self.content))
     }
 
+    /// Body decoded per its Content-Encoding (gzip, deflate, br or zstd), for
+    /// clients that don't decompress responses themselves. Stacked codings are
+    /// undone last-applied first; without a Content-Encoding the body is returned
+    /// unchanged. Raises ValueError for unknown codings or corrupt data.
+    pub fn decompressed<'py>(&mut self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        Ok(PyBytes::new(py, &self.decoded_content()?))
+    }
+
     /// Parse response as JSON
-    pub fn json(&self, py: Python) -> PyResult<PyObject> {
-        let text = self.text()?;
+    pub fn json(&mut self, py: Python) -> PyResult<PyObject> {
+        let text = self.decode_text(py, true)?;
         let value: serde_json::Value = serde_json::from_str(&text)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid JSON: {}", e)))?;
 
@@ -61,23 +268,129 @@ impl Response {
         crate::client::json_to_python(py, &value)
     }
 
-    /// Check if response status is successful (2xx)
-    pub fn ok(&self) -> bool {
-        (200..300).contains(&self.status_code)
+    /// Extract one value from the JSON body by RFC 6901 pointer, e.g. `/data/items/0/id`.
+    /// Returns `default` when nothing is at the pointer, or raises KeyError if `strict`.
+    #[pyo3(signature = (pointer, default = None, strict = false))]
+    pub fn json_path(
+        &mut self,
+        py: Python,
+        pointer: &str,
+        default: Option<PyObject>,
+        strict: bool,
+    ) -> PyResult<PyObject> {
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid JSON pointer '{}': must be empty or start with '/'",
+                pointer
+            )));
+        }
+        self.ensure_body()?;
+        let value: serde_json::Value = serde_json::from_slice(&self.content)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid JSON: {}", e)))?;
+
+        match value.pointer(pointer) {
+            Some(found) => crate::client::json_to_python(py, found),
+            None if strict => Err(pyo3::exceptions::PyKeyError::new_err(format!(
+                "No JSON value at '{}'",
+                pointer
+            ))),
+            None => Ok(default.unwrap_or_else(|| py.None())),
+        }
     }
 
-    /// Raise an exception if status is not successful
-    pub fn raise_for_status(&self) -> PyResult<()> {
-        if !self.ok() {
-            let msg = format!("HTTP {} Error: {}", self.status_code, self.status_text());
-            match self.status_code {
-                400..=499 => Err(pyo3::exceptions::PyValueError::new_err(msg)),
-                500..=599 => Err(pyo3::exceptions::PyRuntimeError::new_err(msg)),
-                _ => Err(pyo3::exceptions::PyException::new_err(msg)),
-            }
-        } else {
-            Ok(())
+    /// Validate the JSON body against a JSON Schema and return the parsed body.
+    /// Compiled schemas are cached when a `schema_id` is given.
+    #[pyo3(signature = (schema = None, schema_id = None))]
+    pub fn validate_json_schema(
+        &mut self,
+        py: Python,
+        schema: Option<&PyAny>,
+        schema_id: Option<&str>,
+    ) -> PyResult<PyObject> {
+        self.ensure_body()?;
+        let instance: serde_json::Value = serde_json::from_slice(&self.content)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid JSON: {}", e)))?;
+
+        let compiled = compiled_schema(schema, schema_id)?;
+        if let Err(errors) = compiled.validate(&instance) {
+            let failures: Vec<String> = errors
+                .map(|e| {
+                    let path = e.instance_path.to_string();
+                    let path = if path.is_empty() {
+                        "/".to_string()
+                    } else {
+                        path
+                    };
+                    format!("{}: {}", path, e)
+                })
+                .collect();
+            return Err(crate::error::schema_validation_error(format!(
+                "Response does not match schema: {}",
+                failures.join("; ")
+            )));
         }
+
+        crate::client::json_to_python(py, &instance)
+    }
+
+    /// Remove all cached compiled JSON Schemas
+    #[staticmethod]
+    pub fn clear_schema_cache() {
+        SCHEMA_CACHE.write().clear();
+    }
+
+    /// Raise `HTTPClientError` for 4xx or `HTTPServerError` for 5xx responses
+    pub fn raise_for_status(&self, py: Python) -> PyResult<()> {
+        let msg = format!(
+            "HTTP {} {} for url: {}",
+            self.status_code,
+            self.status_text(),
+            self.url
+        );
+        let err = match self.status_code {
+            400..=499 => crate::error::HTTPClientError::new_err(msg),
+            500..=599 => crate::error::HTTPServerError::new_err(msg),
+            _ => return Ok(()),
+        };
+        let value = err.value(py);
+        value.setattr("status_code", self.status_code)?;
+        value.setattr("url", &self.url)?;
+        value.setattr("reason", self.status_text())?;
+        Err(err)
+    }
+
+    /// True unless the status is a 4xx or 5xx error
+    #[getter]
+    pub fn ok(&self) -> bool {
+        self.status_code < 400
+    }
+
+    /// A redirect status with a Location to follow
+    #[getter]
+    pub fn is_redirect(&self) -> bool {
+        matches!(self.status_code, 301 | 302 | 303 | 307 | 308) && self.location().is_some()
+    }
+
+    /// A 301 or 308 redirect with a Location to follow
+    #[getter]
+    pub fn is_permanent_redirect(&self) -> bool {
+        matches!(self.status_code, 301 | 308) && self.location().is_some()
+    }
+
+    /// Links from the `Link` header keyed by `rel` (or by URL when a link has no `rel`).
+    /// Each entry holds the link's `url` and its params, e.g. `links["next"]["url"]`.
+    #[getter]
+    pub fn links(&self) -> HashMap<String, HashMap<String, String>> {
+        self.header("link")
+            .map(String::as_str)
+            .map(parse_link_header)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|link| {
+                let key = link.get("rel").unwrap_or(&link["url"]).clone();
+                (key, link)
+            })
+            .collect()
     }
 
     /// Get status text description
@@ -109,14 +422,48 @@ impl Response {
         self.headers.get(&name.to_lowercase()).cloned()
     }
 
+    /// Get response body as bytes
+    #[getter(content)]
+    pub fn content_py<'py>(&mut self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        self.bytes(py)
+    }
+
+    /// Cookies set by the response as a name -> value dict
+    #[getter(cookies)]
+    pub fn cookie_map(&self) -> HashMap<String, String> {
+        self.get_cookies()
+            .into_iter()
+            .map(|cookie| (cookie.name, cookie.value))
+            .collect()
+    }
+
+    /// Cookies set by the response, with their attributes
+    pub fn get_cookies(&self) -> Vec<Cookie> {
+        self.set_cookies
+            .iter()
+            .filter_map(|header| Cookie::parse(header))
+            .collect()
+    }
+
     /// Get content length
     #[getter]
-    pub fn content_length(&self) -> usize {
-        self.content.len()
+    pub fn content_length(&mut self) -> PyResult<usize> {
+        self.ensure_body()?;
+        Ok(self.content.len())
+    }
+
+    /// Check whether the response body has been read
+    #[getter]
+    pub fn body_loaded(&self) -> bool {
+        match &self.lazy_body {
+            Some(pending) => pending.lock().content.is_some(),
+            None => true,
+        }
     }
 
     /// Iterate over response content chunks (simplified synchronous implementation)
-    pub fn iter_chunks(&self, chunk_size: Option<usize>) -> PyResult<Vec<PyObject>> {
+    pub fn iter_chunks(&mut self, chunk_size: Option<usize>) -> PyResult<Vec<PyObject>> {
+        self.ensure_body()?;
         let chunk_size = chunk_size.unwrap_or(8192);
         let mut chunks = Vec::new();
 
@@ -128,8 +475,49 @@ impl Response {
         })
     }
 
+    /// Iterate over the body in chunks, streaming from the connection for lazy responses
+    #[pyo3(signature = (chunk_size = 8192))]
+    pub fn iter_content(&self, chunk_size: usize) -> PyResult<ResponseContentIterator> {
+        if chunk_size == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "chunk_size must be greater than 0",
+            ));
+        }
+
+        let source = match &self.lazy_body {
+            Some(pending) if pending.lock().content.is_none() => {
+                let mut guard = pending.lock();
+                if let Some(response) = &guard.response {
+                    check_announced_size(response, guard.max_size)?;
+                }
+                guard.streamed = true;
+                ContentSource::Stream(Arc::clone(pending))
+            }
+            Some(pending) => {
+                ContentSource::Buffered(pending.lock().content.clone().unwrap_or_default())
+            }
+            None => ContentSource::Buffered(self.content.clone()),
+        };
+
+        Ok(ResponseContentIterator {
+            source,
+            buffer: Vec::new(),
+            position: 0,
+            chunk_size,
+            finished: false,
+        })
+    }
+
+    /// Write the body to `path`, streaming from the connection for lazy responses.
+    /// Returns the number of bytes written.
+    #[pyo3(signature = (path, chunk_size = 65536))]
+    pub fn save(&self, py: Python, path: &str, chunk_size: usize) -> PyResult<u64> {
+        self.save_with_progress(py, path, chunk_size, None)
+    }
+
     /// Iterate over response content lines (simplified synchronous implementation)
-    pub fn iter_lines(&self) -> PyResult<Vec<PyObject>> {
+    pub fn iter_lines(&mut self) -> PyResult<Vec<PyObject>> {
+        self.ensure_body()?;
         let content_str = String::from_utf8_lossy(&self.content);
         let mut lines = Vec::new();
 
@@ -141,19 +529,25 @@ impl Response {
         })
     }
 
-    /// String representation
+    /// `<Response [200 OK] https://...>`, without reading the body
+    fn __repr__(&self) -> String {
+        if self.reason.is_empty() {
+            format!("<Response [{}] {}>", self.status_code, self.url)
+        } else {
+            format!(
+                "<Response [{} {}] {}>",
+                self.status_code, self.reason, self.url
+            )
+        }
+    }
+
     fn __str__(&self) -> String {
-        format!("<Response [{}]>", self.status_code)
+        self.__repr__()
     }
 
-    /// Detailed representation
-    fn __repr__(&self) -> String {
-        format!(
-            "Response(status_code={}, url='{}', content_length={})",
-            self.status_code,
-            self.url,
-            self.content.len()
-        )
+    /// Truthy when `ok`, as in `if response:`
+    fn __bool__(&self) -> bool {
+        self.ok()
     }
 
     /// Get protocol statistics if available
@@ -177,7 +571,24 @@ impl Response {
 
     /// Set timing information for the response
     pub fn set_timing(&mut self, request_start: f64, request_end: f64) {
-        self.timing = Some(request_end - request_start);
+        let total = request_end - request_start;
+        self.timing = Some(Timing {
+            ttfb: total,
+            total,
+            ..Timing::default()
+        });
+    }
+
+    /// Where the request's time went, in seconds: `dns`, `connect` (TCP connect
+    /// and TLS handshake together), `tls` (always None, as it can't be measured
+    /// apart from connect), `ttfb`, `download`, `total`, and `reused_connection`.
+    /// None for responses that never reached the network, such as cache hits.
+    #[getter]
+    pub fn timing(&self, py: Python) -> PyResult<Option<PyObject>> {
+        self.timing
+            .as_ref()
+            .map(|timing| timing.to_dict(py))
+            .transpose()
     }
 
     /// Get detailed timing information as a dictionary
@@ -211,9 +622,341 @@ impl Response {
     }
 }
 
+/// Undo one Content-Encoding coding
+fn decode_content_coding(body: &[u8], coding: &str) -> PyResult<Vec<u8>> {
+    use std::io::Read;
+
+    let mut decoded = Vec::new();
+    let result = match coding {
+        "identity" => return Ok(body.to_vec()),
+        "gzip" | "x-gzip" => flate2::read::MultiGzDecoder::new(body).read_to_end(&mut decoded),
+        // Properly zlib-wrapped, though some servers send raw deflate
+        "deflate" => flate2::read::ZlibDecoder::new(body)
+            .read_to_end(&mut decoded)
+            .or_else(|_| {
+                decoded.clear();
+                flate2::read::DeflateDecoder::new(body).read_to_end(&mut decoded)
+            }),
+        "br" => brotli::Decompressor::new(body, 4096).read_to_end(&mut decoded),
+        "zstd" => zstd::stream::read::Decoder::new(body)
+            .and_then(|mut decoder| decoder.read_to_end(&mut decoded)),
+        other => {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Unsupported Content-Encoding '{}'",
+                other
+            )))
+        }
+    };
+    result.map_err(|e| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "Failed to decode {} response body: {}",
+            coding, e
+        ))
+    })?;
+    Ok(decoded)
+}
+
+/// Throttled `progress_callback(bytes_downloaded, total_bytes)` calls, made at
+/// most once per `every` bytes so large downloads don't contend for the GIL
+struct DownloadProgress {
+    callback: Option<PyObject>,
+    total: Option<u64>,
+    every: u64,
+    reported: Option<u64>,
+}
+
+impl DownloadProgress {
+    fn new(callback: Option<PyObject>, total: Option<u64>, every: usize) -> Self {
+        Self {
+            callback,
+            total,
+            every: every as u64,
+            reported: None,
+        }
+    }
+
+    fn advance(&mut self, downloaded: u64) -> PyResult<()> {
+        if downloaded - self.reported.unwrap_or(0) >= self.every {
+            self.report(downloaded)?;
+        }
+        Ok(())
+    }
+
+    /// Report the final count unless it was the last one reported
+    fn finish(&mut self, downloaded: u64) -> PyResult<()> {
+        if self.reported != Some(downloaded) {
+            self.report(downloaded)?;
+        }
+        Ok(())
+    }
+
+    fn report(&mut self, downloaded: u64) -> PyResult<()> {
+        self.reported = Some(downloaded);
+        match &self.callback {
+            Some(callback) => {
+                Python::with_gil(|py| callback.call1(py, (downloaded, self.total)).map(|_| ()))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Where a content iterator reads its bytes from
+enum ContentSource {
+    Buffered(Vec<u8>),
+    Stream(Arc<parking_lot::Mutex<PendingBody>>),
+}
+
+/// Iterator over response body chunks returned by `Response.iter_content`
+#[pyclass]
+pub struct ResponseContentIterator {
+    source: ContentSource,
+    buffer: Vec<u8>,
+    position: usize,
+    chunk_size: usize,
+    finished: bool,
+}
+
+impl ResponseContentIterator {
+    /// Pull network chunks until a full chunk is buffered or the body ends
+    fn fill_buffer(&mut self, py: Python) -> PyResult<()> {
+        let pending = match &self.source {
+            ContentSource::Stream(pending) => Arc::clone(pending),
+            ContentSource::Buffered(_) => return Ok(()),
+        };
+
+        while !self.finished && self.buffer.len() < self.chunk_size {
+            let next = py.allow_threads(|| {
+                let mut guard = pending.lock();
+                let handle = guard.handle.clone();
+                match guard.response.as_mut() {
+                    Some(response) => handle.block_on(response.chunk()),
+                    None => Ok(None),
+                }
+            });
+            match next {
+                Ok(Some(bytes)) => {
+                    if let Err(e) = pending.lock().count_received(bytes.len()) {
+                        self.finished = true;
+                        return Err(e);
+                    }
+                    self.buffer.extend_from_slice(&bytes);
+                }
+                Ok(None) => {
+                    self.finished = true;
+                    pending.lock().response = None;
+                }
+                Err(e) => {
+                    self.finished = true;
+                    return Err(pyo3::exceptions::PyIOError::new_err(format!(
+                        "Failed to read response body: {}",
+                        e
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the next chunk of at most `chunk_size` bytes
+    fn next_chunk(&mut self, py: Python) -> PyResult<Option<Vec<u8>>> {
+        if let ContentSource::Buffered(content) = &self.source {
+            if self.position >= content.len() {
+                return Ok(None);
+            }
+            let end = (self.position + self.chunk_size).min(content.len());
+            let chunk = content[self.position..end].to_vec();
+            self.position = end;
+            return Ok(Some(chunk));
+        }
+
+        self.fill_buffer(py)?;
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+        let take = self.chunk_size.min(self.buffer.len());
+        Ok(Some(self.buffer.drain(..take).collect()))
+    }
+}
+
+#[pymethods]
+impl ResponseContentIterator {
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python) -> PyResult<Option<PyObject>> {
+        Ok(self
+            .next_chunk(py)?
+            .map(|chunk| PyBytes::new(py, &chunk).to_object(py)))
+    }
+}
+
+/// Standard reason phrase for `status_code`, empty when it has none
+pub(crate) fn reason_phrase(status_code: u16) -> String {
+    reqwest::StatusCode::from_u16(status_code)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Parse a `Link` header into one map per link: its `url` plus its params, unquoted.
+/// Commas and semicolons inside `<...>` or quoted strings do not split links or params.
+pub(crate) fn parse_link_header(value: &str) -> Vec<HashMap<String, String>> {
+    split_unquoted(value, ',')
+        .into_iter()
+        .filter_map(|link| {
+            let mut parts = split_unquoted(link, ';').into_iter();
+            let target = parts.next()?.trim();
+            let url = target.strip_prefix('<')?.strip_suffix('>')?;
+            let mut entry = HashMap::new();
+            entry.insert("url".to_string(), url.trim().to_string());
+            for param in parts {
+                let (name, value) = param.split_once('=').unwrap_or((param, ""));
+                let name = name.trim().to_ascii_lowercase();
+                if name.is_empty() || name == "url" {
+                    continue;
+                }
+                entry.entry(name).or_insert_with(|| unquote(value.trim()));
+            }
+            Some(entry)
+        })
+        .collect()
+}
+
+/// Split `value` on `delimiter` outside of `<...>` and double-quoted strings
+fn split_unquoted(value: &str, delimiter: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut start, mut in_quotes, mut in_angle, mut escaped) = (0, false, false, false);
+    for (i, c) in value.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if in_quotes => escaped = true,
+            '"' if !in_angle => in_quotes = !in_quotes,
+            '<' if !in_quotes => in_angle = true,
+            '>' if !in_quotes => in_angle = false,
+            c if c == delimiter && !in_quotes && !in_angle => {
+                parts.push(&value[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts.retain(|part| !part.trim().is_empty());
+    parts
+}
+
+/// Strip the quotes and backslash escapes from a quoted-string parameter value
+fn unquote(value: &str) -> String {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(inner) => {
+            let mut out = String::with_capacity(inner.len());
+            let mut chars = inner.chars();
+            while let Some(c) = chars.next() {
+                if c == '\\' {
+                    out.extend(chars.next());
+                } else {
+                    out.push(c);
+                }
+            }
+            out
+        }
+        None => value.to_string(),
+    }
+}
+
+/// Guess a body's encoding: its BOM, else UTF-8 when the bytes are valid UTF-8, else windows-1252
+fn sniff_encoding(body: &[u8]) -> &'static Encoding {
+    match Encoding::for_bom(body) {
+        Some((encoding, _)) => encoding,
+        None if std::str::from_utf8(body).is_ok() => UTF_8,
+        None => WINDOWS_1252,
+    }
+}
+
+/// UnicodeDecodeError pointing at the first malformed sequence of `input` in `encoding`
+fn unicode_decode_error(py: Python, encoding: &'static Encoding, input: &[u8]) -> PyErr {
+    let mut decoder = encoding.new_decoder_with_bom_removal();
+    let capacity = decoder
+        .max_utf8_buffer_length_without_replacement(input.len())
+        .unwrap_or_default();
+    let mut decoded = String::with_capacity(capacity);
+    let range = match decoder.decode_to_string_without_replacement(input, &mut decoded, true) {
+        (DecoderResult::Malformed(bad, extra), read) => {
+            let end = read - extra as usize;
+            end - bad as usize..end
+        }
+        _ => 0..input.len(),
+    };
+    let name = std::ffi::CString::new(encoding.name()).unwrap_or_default();
+    match pyo3::exceptions::PyUnicodeDecodeError::new(py, &name, input, range, c"invalid data") {
+        Ok(err) => err.into(),
+        Err(err) => err,
+    }
+}
+
 impl Response {
+    /// The assigned encoding, else the Content-Type charset when it names a known encoding
+    fn declared_encoding(&self) -> Option<&'static Encoding> {
+        self.encoding_override.or_else(|| {
+            let content_type = self.header("content-type")?;
+            split_unquoted(content_type, ';')
+                .into_iter()
+                .skip(1)
+                .filter_map(|param| param.split_once('='))
+                .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+                .and_then(|(_, value)| Encoding::for_label(unquote(value.trim()).as_bytes()))
+        })
+    }
+
+    /// Decode the body with the declared encoding, falling back to a BOM and then UTF-8
+    /// validity; a BOM for the chosen encoding is dropped
+    fn decode_text(&mut self, py: Python, strict: bool) -> PyResult<String> {
+        self.ensure_body()?;
+        let encoding = self
+            .declared_encoding()
+            .unwrap_or_else(|| sniff_encoding(&self.content));
+        let (text, had_errors) = encoding.decode_with_bom_removal(&self.content);
+        if strict && had_errors {
+            return Err(unicode_decode_error(py, encoding, &self.content));
+        }
+        Ok(text.into_owned())
+    }
+
+    /// A header's value, matching the name case-insensitively
+    fn header(&self, name: &str) -> Option<&String> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// The Location header, if any
+    fn location(&self) -> Option<&String> {
+        self.header("location")
+    }
+
+    /// Attach a timing breakdown and keep the flat timing fields in step with it
+    pub(crate) fn record_timing(&mut self, timing: Timing) {
+        self.end_time = crate::cookies::unix_now();
+        self.start_time = self.end_time - timing.total;
+        self.elapsed = timing.total;
+        self.total_time = timing.total;
+        self.request_time = timing.ttfb;
+        self.response_time = timing.download();
+        self.timing = Some(timing);
+    }
+
     /// Create response from reqwest response - internal method
-    pub(crate) async fn from_reqwest_response_async(response: reqwest::Response) -> PyResult<Self> {
+    pub(crate) async fn from_reqwest_response_async(
+        response: reqwest::Response,
+        max_size: Option<usize>,
+    ) -> PyResult<Self> {
         let status_code = response.status().as_u16();
         let headers = response
             .headers()
@@ -222,6 +965,7 @@ impl Response {
             .collect();
 
         let url = response.url().to_string();
+        let set_cookies = crate::cookies::set_cookie_headers(response.headers());
 
         // Get protocol information before consuming the response
         // Determine protocol from HTTP version
@@ -238,16 +982,11 @@ impl Response {
         // However, the current structure of Response holds Vec<u8>.
         // For true async streaming into Response, its structure would need to change,
         // or these iter_chunks/iter_lines methods become the primary way to access body.
-        let content = response
-            .bytes()
-            .await
-            .map_err(|e| {
-                pyo3::exceptions::PyIOError::new_err(format!("Failed to read response body: {}", e))
-            })?
-            .to_vec();
+        let content = read_body(response, max_size).await?;
 
         Ok(Response {
             status_code,
+            reason: reason_phrase(status_code),
             headers,
             content,
             url,
@@ -261,6 +1000,10 @@ impl Response {
             start_time: 0.0,
             end_time: 0.0,
             timing: None,
+            lazy_body: None,
+            set_cookies,
+            history: Vec::new(),
+            encoding_override: None,
         })
     }
 
@@ -268,9 +1011,11 @@ impl Response {
     pub fn from_reqwest(
         response: reqwest::Response,
         runtime: &tokio::runtime::Runtime,
+        max_size: Option<usize>,
     ) -> PyResult<Self> {
         let status_code = response.status().as_u16();
         let url = response.url().to_string();
+        let set_cookies = crate::cookies::set_cookie_headers(response.headers());
 
         // Convert headers
         let mut headers = HashMap::new();
@@ -279,12 +1024,10 @@ impl Response {
             headers.insert(key.to_string(), value.to_str().unwrap_or("").to_string());
         }
 
-        // Get the response body
-        let content = runtime
-            .block_on(async { response.bytes().await })
-            .map_err(|e| {
-                pyo3::exceptions::PyIOError::new_err(format!("Failed to read response body: {}", e))
-            })?;
+        // Get the response body, releasing the GIL while it downloads
+        let content = Python::with_gil(|py| {
+            py.allow_threads(|| runtime.block_on(read_body(response, max_size)))
+        })?;
 
         // Determine protocol from HTTP version
         let (protocol, protocol_version) = match version {
@@ -297,9 +1040,10 @@ impl Response {
 
         Ok(Response {
             status_code,
+            reason: reason_phrase(status_code),
             url,
             headers,
-            content: content.to_vec(),
+            content,
             elapsed: 0.0,
             protocol,
             protocol_version,
@@ -310,8 +1054,298 @@ impl Response {
             start_time: 0.0,
             end_time: 0.0,
             timing: None,
+            lazy_body: None,
+            set_cookies,
+            history: Vec::new(),
+            encoding_override: None,
         })
     }
+
+    /// Record a redirect hop: status, URL and headers, without reading its body
+    pub(crate) fn from_redirect(response: &reqwest::Response) -> Self {
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+            .collect();
+
+        Response {
+            status_code: response.status().as_u16(),
+            reason: reason_phrase(response.status().as_u16()),
+            headers,
+            content: Vec::new(),
+            url: response.url().to_string(),
+            elapsed: 0.0,
+            protocol: None,
+            protocol_version: None,
+            protocol_stats: None,
+            request_time: 0.0,
+            response_time: 0.0,
+            total_time: 0.0,
+            start_time: 0.0,
+            end_time: 0.0,
+            timing: None,
+            lazy_body: None,
+            set_cookies: crate::cookies::set_cookie_headers(response.headers()),
+            history: Vec::new(),
+            encoding_override: None,
+        }
+    }
+
+    /// Create a Response whose body is read from the connection on first access
+    pub(crate) fn from_reqwest_lazy(
+        response: reqwest::Response,
+        handle: tokio::runtime::Handle,
+        runtime: Option<Arc<tokio::runtime::Runtime>>,
+        max_size: Option<usize>,
+    ) -> Self {
+        let status_code = response.status().as_u16();
+        let url = response.url().to_string();
+        let set_cookies = crate::cookies::set_cookie_headers(response.headers());
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+            .collect();
+
+        let (protocol, protocol_version) = match response.version() {
+            reqwest::Version::HTTP_10 => (Some("HTTP/1.0".to_string()), Some(1.0)),
+            reqwest::Version::HTTP_11 => (Some("HTTP/1.1".to_string()), Some(1.1)),
+            reqwest::Version::HTTP_2 => (Some("HTTP/2".to_string()), Some(2.0)),
+            reqwest::Version::HTTP_3 => (Some("HTTP/3".to_string()), Some(3.0)),
+            _ => (None, None),
+        };
+
+        Response {
+            status_code,
+            reason: reason_phrase(status_code),
+            headers,
+            content: Vec::new(),
+            url,
+            elapsed: 0.0,
+            protocol,
+            protocol_version,
+            protocol_stats: None,
+            request_time: 0.0,
+            response_time: 0.0,
+            total_time: 0.0,
+            start_time: 0.0,
+            end_time: 0.0,
+            timing: None,
+            set_cookies,
+            history: Vec::new(),
+            encoding_override: None,
+            lazy_body: Some(Arc::new(parking_lot::Mutex::new(PendingBody {
+                response: Some(response),
+                content: None,
+                streamed: false,
+                max_size,
+                received: 0,
+                handle,
+                _runtime: runtime,
+            }))),
+        }
+    }
+
+    /// Body with its Content-Encoding undone, last-applied coding first
+    pub(crate) fn decoded_content(&mut self) -> PyResult<Vec<u8>> {
+        self.ensure_body()?;
+        let codings: Vec<String> = self
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-encoding"))
+            .map(|(_, value)| {
+                value
+                    .split(',')
+                    .map(|coding| coding.trim().to_ascii_lowercase())
+                    .filter(|coding| !coding.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut body = std::borrow::Cow::Borrowed(self.content.as_slice());
+        for coding in codings.iter().rev() {
+            body = std::borrow::Cow::Owned(decode_content_coding(&body, coding)?);
+        }
+        Ok(body.into_owned())
+    }
+
+    /// Swap in a new identity-encoded body, keeping Content-Length in step
+    pub(crate) fn replace_body(&mut self, content: Vec<u8>) {
+        self.headers.retain(|name, _| {
+            !name.eq_ignore_ascii_case("content-encoding")
+                && !name.eq_ignore_ascii_case("content-length")
+        });
+        self.headers
+            .insert("content-length".to_string(), content.len().to_string());
+        self.content = content;
+        self.lazy_body = None;
+    }
+
+    /// Encode text with the declared encoding, or UTF-8 when none is known
+    pub(crate) fn encode_text(&self, text: &str) -> Vec<u8> {
+        let encoding = self.declared_encoding().unwrap_or(UTF_8);
+        encoding.encode(text).0.into_owned()
+    }
+
+    /// Read a pending lazy body into `content`, releasing the GIL while it downloads
+    pub(crate) fn ensure_body(&mut self) -> PyResult<()> {
+        let pending = match &self.lazy_body {
+            Some(pending) => Arc::clone(pending),
+            None => return Ok(()),
+        };
+
+        let content = Python::with_gil(|py| {
+            py.allow_threads(|| {
+                let mut guard = pending.lock();
+                if guard.content.is_none() {
+                    if guard.streamed {
+                        return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                            "Response body was already consumed by iter_content",
+                        ));
+                    }
+                    // Blocking a runtime thread on its own runtime would panic
+                    if tokio::runtime::Handle::try_current().is_ok() {
+                        return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                            "Lazy response body cannot be read from inside the async runtime",
+                        ));
+                    }
+                    let response = guard.response.take().ok_or_else(|| {
+                        pyo3::exceptions::PyIOError::new_err("Response body is no longer available")
+                    })?;
+                    let content = guard.handle.block_on(read_body(response, guard.max_size))?;
+                    guard.content = Some(content);
+                }
+                Ok(guard.content.clone().unwrap_or_default())
+            })
+        })?;
+
+        self.content = content;
+        self.lazy_body = None;
+        Ok(())
+    }
+
+    /// `save`, reporting to `progress_callback(bytes_downloaded, total_bytes)` after
+    /// every `chunk_size` bytes and once the body is complete
+    pub(crate) fn save_with_progress(
+        &self,
+        py: Python,
+        path: &str,
+        chunk_size: usize,
+        progress_callback: Option<PyObject>,
+    ) -> PyResult<u64> {
+        let mut chunks = self.iter_content(chunk_size)?;
+        let mut progress =
+            DownloadProgress::new(progress_callback, self.announced_length(), chunk_size);
+        let io_error = |e: std::io::Error| {
+            pyo3::exceptions::PyIOError::new_err(format!("Failed to write '{}': {}", path, e))
+        };
+
+        let mut file = std::fs::File::create(path).map_err(io_error)?;
+        let mut written = 0u64;
+        while let Some(chunk) = chunks.next_chunk(py)? {
+            file.write_all(&chunk).map_err(io_error)?;
+            written += chunk.len() as u64;
+            progress.advance(written)?;
+        }
+        file.flush().map_err(io_error)?;
+        progress.finish(written)?;
+        Ok(written)
+    }
+
+    /// Async counterpart of `save_with_progress`, reading a lazy body off the
+    /// connection without blocking the runtime
+    pub(crate) async fn save_with_progress_async(
+        self,
+        path: String,
+        chunk_size: usize,
+        progress_callback: Option<PyObject>,
+    ) -> PyResult<u64> {
+        use std::borrow::Cow;
+        use tokio::io::AsyncWriteExt;
+
+        if chunk_size == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "chunk_size must be greater than 0",
+            ));
+        }
+        let mut progress =
+            DownloadProgress::new(progress_callback, self.announced_length(), chunk_size);
+        let io_error = |e: std::io::Error| {
+            pyo3::exceptions::PyIOError::new_err(format!("Failed to write '{}': {}", path, e))
+        };
+
+        // A lazy body is streamed off the connection unless it was already read
+        let (pending, content) = match &self.lazy_body {
+            Some(pending) => {
+                let mut guard = pending.lock();
+                match guard.content.clone() {
+                    Some(content) => (None, Cow::Owned(content)),
+                    None if guard.streamed => {
+                        return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                            "Response body was already consumed by iter_content",
+                        ))
+                    }
+                    None => {
+                        let response = guard.response.take().ok_or_else(|| {
+                            pyo3::exceptions::PyIOError::new_err(
+                                "Response body is no longer available",
+                            )
+                        })?;
+                        (Some((response, guard.max_size)), Cow::Borrowed(&[][..]))
+                    }
+                }
+            }
+            None => (None, Cow::Borrowed(self.content.as_slice())),
+        };
+
+        let mut file = tokio::fs::File::create(&path).await.map_err(io_error)?;
+        let mut written = 0u64;
+        match pending {
+            Some((mut response, max_size)) => {
+                check_announced_size(&response, max_size)?;
+                while let Some(chunk) = response.chunk().await.map_err(|e| body_read_error(&e))? {
+                    check_body_size(written as usize + chunk.len(), max_size)?;
+                    file.write_all(&chunk).await.map_err(io_error)?;
+                    written += chunk.len() as u64;
+                    progress.advance(written)?;
+                }
+            }
+            None => {
+                for chunk in content.chunks(chunk_size) {
+                    file.write_all(chunk).await.map_err(io_error)?;
+                    written += chunk.len() as u64;
+                    progress.advance(written)?;
+                }
+            }
+        }
+        file.flush().await.map_err(io_error)?;
+        progress.finish(written)?;
+        Ok(written)
+    }
+
+    /// Body size announced by Content-Length
+    fn announced_length(&self) -> Option<u64> {
+        self.headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.parse().ok())
+    }
+
+    /// Body size without forcing a lazy body to be read
+    pub(crate) fn body_size_hint(&self) -> usize {
+        match &self.lazy_body {
+            Some(pending) => match &pending.lock().content {
+                Some(content) => content.len(),
+                None => self
+                    .headers
+                    .get("content-length")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0),
+            },
+            None => self.content.len(),
+        }
+    }
 }
 
 // Protocol stats methods are part of the main Response implementation
@@ -1,16 +1,19 @@
 use crate::client::HttpClient;
 use crate::config::{AuthConfig, RetryConfig, TimeoutConfig};
+use crate::cookies::CookieJar;
 use pyo3::prelude::*;
-use reqwest::cookie::Jar;
 use std::collections::HashMap;
 use std::sync::Mutex;
 use std::sync::{Arc, RwLock};
 
-/// Advanced session management with cookie handling and persistent configuration
+/// Advanced session management with cookie handling and persistent configuration.
+///
+/// Session `headers` and `params` are merged into every request before dispatch.
+/// Per-request values win on a key conflict; header names are compared case-insensitively.
 #[pyclass]
 pub struct Session {
     client: Arc<Mutex<HttpClient>>,
-    cookies: Arc<Jar>,
+    cookies: Arc<CookieJar>,
     persist_cookies: bool,
     default_headers: Arc<RwLock<HashMap<String, String>>>,
     auth_config: Arc<RwLock<Option<AuthConfig>>>,
@@ -31,7 +34,8 @@ impl Session {
         timeout_config = None,
         pool_config = None,
         ssl_config = None,
-        persist_cookies = true
+        persist_cookies = true,
+        params = None
     ))]
     pub fn new(
         base_url: Option<String>,
@@ -42,10 +46,12 @@ impl Session {
         pool_config: Option<crate::config::PoolConfig>,
         ssl_config: Option<crate::config::SSLConfig>,
         persist_cookies: bool,
+        params: Option<HashMap<String, String>>,
     ) -> PyResult<Self> {
-        let client = HttpClient::new(
+        // Session headers are merged per request so later changes apply to the next call
+        let mut client = HttpClient::new(
             base_url.clone(),
-            headers.clone(),
+            None,
             30.0,
             auth_config.clone(),
             retry_config.clone(),
@@ -56,11 +62,28 @@ impl Session {
             None, // compression_config
             None, // protocol_config
             None, // rate_limit_config
+            "eager",
+            true, // allow_redirects
+            crate::config::DEFAULT_MAX_REDIRECTS,
+            None, // dns_cache_ttl
+            crate::dns_cache::DEFAULT_MAX_ENTRIES,
+            None,   // unix_socket
+            None,   // circuit_breaker_config
+            "auto", // ip_version
+            None,   // max_response_size
+            Some(crate::config::DEFAULT_CLEANUP_INTERVAL),
         )?;
 
+        // Responses fill the jar and later requests send back the cookies that match
+        let cookies = Arc::new(CookieJar::new());
+        if persist_cookies {
+            client.set_cookie_jar(Some(Arc::clone(&cookies)));
+        }
+        client.set_default_params(params.unwrap_or_default());
+
         Ok(Session {
             client: Arc::new(Mutex::new(client)),
-            cookies: Arc::new(Jar::default()),
+            cookies,
             persist_cookies,
             default_headers: Arc::new(RwLock::new(headers.unwrap_or_default())),
             auth_config: Arc::new(RwLock::new(auth_config)),
@@ -80,10 +103,11 @@ impl Session {
         params: Option<HashMap<String, String>>,
         headers: Option<HashMap<String, String>>,
     ) -> PyResult<&'py PyAny> {
+        let headers = self.merged_headers(headers)?;
         let mut client = self.client.lock().map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
         })?;
-        let response = client.get(url, params, headers)?;
+        let response = client.get(url, params, headers, None, None, None, None)?;
         Ok(response.into_py(py).into_ref(py))
     }
 
@@ -98,6 +122,7 @@ impl Session {
         files: Option<HashMap<String, String>>,
         headers: Option<HashMap<String, String>>,
     ) -> PyResult<&'py PyAny> {
+        let headers = self.merged_headers(headers)?;
         let mut client = self.client.lock().map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
         })?;
@@ -107,7 +132,19 @@ impl Session {
                 .map(|(k, v)| (k, v.into_bytes()))
                 .collect::<HashMap<String, Vec<u8>>>()
         });
-        let response = client.post(url, json, data, files_converted, headers)?;
+        let response = client.post(
+            url,
+            json,
+            data,
+            files_converted,
+            headers,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+        )?;
         Ok(response.into_py(py).into_ref(py))
     }
 
@@ -122,6 +159,7 @@ impl Session {
         files: Option<HashMap<String, String>>,
         headers: Option<HashMap<String, String>>,
     ) -> PyResult<&'py PyAny> {
+        let headers = self.merged_headers(headers)?;
         let mut client = self.client.lock().map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
         })?;
@@ -131,7 +169,19 @@ impl Session {
                 .map(|(k, v)| (k, v.into_bytes()))
                 .collect::<HashMap<String, Vec<u8>>>()
         });
-        let response = client.put(url, json, data, files_converted, headers)?;
+        let response = client.put(
+            url,
+            json,
+            data,
+            files_converted,
+            headers,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+        )?;
         Ok(response.into_py(py).into_ref(py))
     }
 
@@ -142,10 +192,11 @@ impl Session {
         url: &str,
         headers: Option<HashMap<String, String>>,
     ) -> PyResult<&'py PyAny> {
+        let headers = self.merged_headers(headers)?;
         let mut client = self.client.lock().map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
         })?;
-        let response = client.delete(url, headers)?;
+        let response = client.delete(url, headers, None, None, None, None)?;
         Ok(response.into_py(py).into_ref(py))
     }
 
@@ -160,6 +211,7 @@ impl Session {
         files: Option<HashMap<String, String>>,
         headers: Option<HashMap<String, String>>,
     ) -> PyResult<&'py PyAny> {
+        let headers = self.merged_headers(headers)?;
         let mut client = self.client.lock().map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
         })?;
@@ -169,7 +221,18 @@ impl Session {
                 .map(|(k, v)| (k, v.into_bytes()))
                 .collect::<HashMap<String, Vec<u8>>>()
         });
-        let response = client.patch(url, json, data, files_converted, headers)?;
+        let response = client.patch(
+            url,
+            json,
+            data,
+            files_converted,
+            headers,
+            None,
+            None,
+            None,
+            None,
+            false,
+        )?;
         Ok(response.into_py(py).into_ref(py))
     }
 
@@ -180,10 +243,11 @@ impl Session {
         url: &str,
         headers: Option<HashMap<String, String>>,
     ) -> PyResult<&'py PyAny> {
+        let headers = self.merged_headers(headers)?;
         let mut client = self.client.lock().map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
         })?;
-        let response = client.head(url, headers)?;
+        let response = client.head(url, headers, None, None, None, None)?;
         Ok(response.into_py(py).into_ref(py))
     }
 
@@ -194,10 +258,11 @@ impl Session {
         url: &str,
         headers: Option<HashMap<String, String>>,
     ) -> PyResult<&'py PyAny> {
+        let headers = self.merged_headers(headers)?;
         let mut client = self.client.lock().map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
         })?;
-        let response = client.options(url, headers)?;
+        let response = client.options(url, headers, None, None, None, None)?;
         Ok(response.into_py(py).into_ref(py))
     }
 
@@ -244,6 +309,37 @@ impl Session {
             .unwrap_or_default()
     }
 
+    /// Replace all session headers
+    #[setter]
+    pub fn set_headers(&mut self, headers: HashMap<String, String>) -> PyResult<()> {
+        *self.default_headers.write().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!(
+                "Failed to acquire write lock: {}",
+                e
+            ))
+        })? = headers;
+        Ok(())
+    }
+
+    /// Query parameters sent with every request (per-request params take precedence)
+    #[getter]
+    pub fn params(&self) -> PyResult<HashMap<String, String>> {
+        let client = self.client.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
+        })?;
+        Ok(client.get_default_params())
+    }
+
+    /// Replace all session query parameters
+    #[setter]
+    pub fn set_params(&mut self, params: HashMap<String, String>) -> PyResult<()> {
+        let mut client = self.client.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
+        })?;
+        client.set_default_params(params);
+        Ok(())
+    }
+
     /// Get authentication config as property
     #[getter]
     pub fn auth_config(&self) -> Option<AuthConfig> {
@@ -385,31 +481,45 @@ impl Session {
 
     /// Clear all cookies
     pub fn clear_cookies(&self) {
-        // Cookie clearing would require access to the internal cookie store
-        // This is a simplified implementation
+        self.cookies.clear();
     }
 
-    /// Set cookie (using a dummy URL for cookie store)
-    pub fn set_cookie(&self, name: &str, value: &str) -> PyResult<()> {
-        // Create a simple cookie string for the cookie jar
-        let cookie_str = format!("{}={}", name, value);
+    /// Set a cookie for `domain` (default: the base URL's host) and its subdomains
+    #[pyo3(signature = (name, value, domain = None, path = "/"))]
+    pub fn set_cookie(
+        &self,
+        name: &str,
+        value: &str,
+        domain: Option<String>,
+        path: &str,
+    ) -> PyResult<()> {
         let base_url_guard = self.base_url.read().map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire read lock: {}", e))
         })?;
-        let url = base_url_guard.as_deref().unwrap_or("http://localhost");
-        if let Ok(parsed_url) = url.parse() {
-            self.cookies.add_cookie_str(&cookie_str, &parsed_url);
-        }
+        let domain = cookie_domain(domain, base_url_guard.as_deref());
+        self.cookies.set(name, value, &domain, path);
         Ok(())
     }
 
-    /// Get cookie (simplified implementation)
+    /// Get a stored cookie's value by name
     pub fn get_cookie(&self, name: &str) -> Option<String> {
-        // This is a simplified implementation since reqwest::Jar doesn't provide
-        // easy access to individual cookies. In practice, cookies are handled
-        // automatically by the HTTP client.
-        // For now, return None as cookies are managed internally by reqwest
-        None
+        self.cookies.get(name)
+    }
+
+    /// Session cookies as a name -> value dict
+    #[getter]
+    pub fn cookies(&self) -> HashMap<String, String> {
+        self.cookies.to_dict()
+    }
+
+    /// Save the session's cookies to a JSON file
+    pub fn save_cookies(&self, path: &str) -> PyResult<()> {
+        self.cookies.save(path)
+    }
+
+    /// Load cookies from a file written by `save_cookies`, skipping expired ones
+    pub fn load_cookies(&self, path: &str) -> PyResult<usize> {
+        self.cookies.load(path)
     }
 
     /// Check if cookies are persisted
@@ -464,3 +574,43 @@ impl Session {
         // Cleanup if needed
     }
 }
+
+impl Session {
+    /// Session headers overlaid with the request's own headers
+    fn merged_headers(
+        &self,
+        headers: Option<HashMap<String, String>>,
+    ) -> PyResult<Option<HashMap<String, String>>> {
+        let session_headers = self.default_headers.read().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire read lock: {}", e))
+        })?;
+        Ok(Some(merge_headers(&session_headers, headers)))
+    }
+}
+
+/// Merge session headers with per-request ones.
+/// Request headers win, matched case-insensitively so `user-agent` replaces a session `User-Agent`.
+pub(crate) fn merge_headers(
+    session_headers: &HashMap<String, String>,
+    request_headers: Option<HashMap<String, String>>,
+) -> HashMap<String, String> {
+    let mut merged = session_headers.clone();
+    if let Some(request_headers) = request_headers {
+        for (key, value) in request_headers {
+            merged.retain(|existing, _| !existing.eq_ignore_ascii_case(&key));
+            merged.insert(key, value);
+        }
+    }
+    merged
+}
+
+/// Domain for a manually set cookie, falling back to the base URL's host
+pub(crate) fn cookie_domain(domain: Option<String>, base_url: Option<&str>) -> String {
+    domain
+        .or_else(|| {
+            base_url
+                .and_then(|url| reqwest::Url::parse(url).ok())
+                .and_then(|url| url.host_str().map(|host| host.to_string()))
+        })
+        .unwrap_or_else(|| "localhost".to_string())
+}
@@ -2,24 +2,34 @@ use pyo3::prelude::*;
 use pyo3::PyObject;
 use reqwest::Client;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver};
 use std::sync::{Arc, Mutex};
 use std::thread;
 
-use bytes::Bytes;
-use futures_util::StreamExt;
 use pyo3_asyncio::tokio::future_into_py;
 
+use crate::auth_common::apply_authentication;
+use crate::config::AuthConfig;
+
 /// Server-Sent Events client for real-time event streaming
 #[pyclass]
 pub struct SSEClient {
     url: Option<String>,
     client: Client,
     headers: HashMap<String, String>,
+    #[allow(dead_code)]
     reconnect_timeout: f64,
     max_reconnect_attempts: u32,
+    /// Only yield events with these names (empty yields every event)
+    #[pyo3(get)]
+    event_types: Vec<String>,
+    /// Authentication applied to every (re)connection request
+    #[pyo3(get, set)]
+    auth_config: Option<AuthConfig>,
     connected: Arc<Mutex<bool>>,
-    event_receiver: Arc<Mutex<Option<Receiver<Result<Bytes, String>>>>>,
+    closed: Arc<AtomicBool>,
+    event_receiver: Arc<Mutex<Option<Receiver<Result<SSEEvent, String>>>>>,
     _connection_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
 }
 
@@ -29,12 +39,16 @@ impl SSEClient {
     #[pyo3(signature = (
         reconnect_timeout = 5.0,
         max_reconnect_attempts = 10,
-        headers = None
+        headers = None,
+        event_types = None,
+        auth_config = None
     ))]
     pub fn new(
         reconnect_timeout: f64,
         max_reconnect_attempts: u32,
         headers: Option<HashMap<String, String>>,
+        event_types: Option<Vec<String>>,
+        auth_config: Option<AuthConfig>,
     ) -> PyResult<Self> {
         let client = Client::builder()
             .timeout(std::time::Duration::from_secs(60)) // 60 second request timeout
@@ -56,7 +70,10 @@ impl SSEClient {
             headers: headers.unwrap_or_default(),
             reconnect_timeout,
             max_reconnect_attempts,
+            event_types: event_types.unwrap_or_default(),
+            auth_config,
             connected: Arc::new(Mutex::new(false)),
+            closed: Arc::new(AtomicBool::new(false)),
             event_receiver: Arc::new(Mutex::new(None)),
             _connection_handle: Arc::new(Mutex::new(None)),
         })
@@ -69,14 +86,14 @@ impl SSEClient {
 
         self.url = Some(url.to_string());
 
-        let client = self.client.clone();
-        let url = url.to_string();
-        let headers = self.headers.clone();
+        let options = self.stream_options(url);
         let connected = self.connected.clone();
+        let closed = Arc::new(AtomicBool::new(false));
+        self.closed = closed.clone();
         let event_receiver_arc = self.event_receiver.clone();
 
         // Create a channel for streaming events
-        let (tx, rx) = mpsc::channel::<Result<Bytes, String>>();
+        let (tx, rx) = mpsc::channel::<Result<SSEEvent, String>>();
 
         // Create a channel to signal when connection is established
         let (conn_tx, conn_rx) = mpsc::channel::<Result<(), String>>();
@@ -88,79 +105,18 @@ impl SSEClient {
 
         // Spawn a background thread to handle the connection
         let handle = thread::spawn(move || {
-            // SSE background thread started
-
             // Create a new runtime for this thread
             let rt = match tokio::runtime::Runtime::new() {
-                Ok(rt) => {
-                    // SSE Tokio runtime created
-                    rt
-                }
+                Ok(rt) => rt,
                 Err(e) => {
-                    // Failed to create SSE runtime
                     let _ = conn_tx.send(Err(format!("Failed to create runtime: {}", e)));
                     return;
                 }
             };
 
-            rt.block_on(async move {
-                // Starting SSE HTTP request
-                let mut request = client.get(&url);
-
-                // Add SSE-specific headers
-                request = request.header("Accept", "text/event-stream");
-                request = request.header("Cache-Control", "no-cache");
-
-                // Add custom headers
-                for (key, value) in headers {
-                    request = request.header(&key, &value);
-                }
-
-                // Sending SSE HTTP request
-                match request.send().await {
-                    Ok(mut response) => {
-                        if response.status().is_success() {
-                            // SSE HTTP connection successful
-                            if let Ok(mut connected_guard) = connected.lock() {
-                                *connected_guard = true;
-                            }
-
-                            // Signal that connection is established
-                            let _ = conn_tx.send(Ok(()));
-
-                            // Stream response chunks
-                            while let Ok(Some(chunk)) = response.chunk().await {
-                                eprintln!("SSE: Received chunk of {} bytes", chunk.len());
-                                if tx.send(Ok(chunk)).is_err() {
-                                    // Receiver has been dropped, stop streaming
-                                    // SSE debug statement removed
-                                    break;
-                                }
-                            }
-
-                            // Connection ended
-                            // SSE debug statement removed
-                            if let Ok(mut connected_guard) = connected.lock() {
-                                *connected_guard = false;
-                            }
-                            let _ = tx.send(Err("Connection ended".to_string()));
-                        } else {
-                            eprintln!("SSE: HTTP error: {}", response.status());
-                            if let Ok(mut connected_guard) = connected.lock() {
-                                *connected_guard = false;
-                            }
-                            let _ = conn_tx.send(Err(format!("HTTP error: {}", response.status())));
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("SSE: Connection error: {}", e);
-                        if let Ok(mut connected_guard) = connected.lock() {
-                            *connected_guard = false;
-                        }
-                        let _ = conn_tx.send(Err(format!("Connection error: {}", e)));
-                    }
-                }
-            });
+            rt.block_on(run_stream(options, connected, closed, tx, move |result| {
+                let _ = conn_tx.send(result);
+            }));
         });
 
         // Store the connection handle
@@ -176,10 +132,7 @@ impl SSEClient {
                 // SSE debug statement removed
                 Ok(())
             }
-            Ok(Err(error_msg)) => {
-                eprintln!("SSE: Connection failed: {}", error_msg);
-                Err(pyo3::exceptions::PyConnectionError::new_err(error_msg))
-            }
+            Ok(Err(error_msg)) => Err(pyo3::exceptions::PyConnectionError::new_err(error_msg)),
             Err(mpsc::RecvTimeoutError::Timeout) => {
                 // SSE debug statement removed
                 Err(pyo3::exceptions::PyConnectionError::new_err(
@@ -226,7 +179,8 @@ impl SSEClient {
 
     /// Close the SSE connection
     pub fn close(&mut self) {
-        // Update connection status
+        // Stop the reconnect loop and update connection status
+        self.closed.store(true, Ordering::SeqCst);
         if let Ok(mut connected_guard) = self.connected.lock() {
             *connected_guard = false;
         }
@@ -269,6 +223,19 @@ impl SSEClient {
     }
 }
 
+impl SSEClient {
+    fn stream_options(&self, url: &str) -> StreamOptions {
+        StreamOptions {
+            client: self.client.clone(),
+            url: url.to_string(),
+            headers: self.headers.clone(),
+            max_reconnect_attempts: self.max_reconnect_attempts,
+            event_types: self.event_types.clone(),
+            auth_config: self.auth_config.clone(),
+        }
+    }
+}
+
 /// SSE Event representation
 #[pyclass]
 #[derive(Clone, Debug)]
@@ -356,19 +323,19 @@ impl SSEEvent {
 /// Iterator for SSE events
 #[pyclass]
 pub struct SSEEventIterator {
-    event_receiver: Arc<Mutex<Option<Receiver<Result<Bytes, String>>>>>,
-    buffer: String,
-    current_event: HashMap<String, Vec<String>>,
+    event_receiver: Arc<Mutex<Option<Receiver<Result<SSEEvent, String>>>>>,
+    /// Id of the most recent event that carried one
+    #[pyo3(get)]
+    last_event_id: Option<String>,
 }
 
 impl SSEEventIterator {
     pub fn new(
-        event_receiver: Arc<Mutex<Option<Receiver<Result<Bytes, String>>>>>,
+        event_receiver: Arc<Mutex<Option<Receiver<Result<SSEEvent, String>>>>>,
     ) -> PyResult<Self> {
         Ok(SSEEventIterator {
             event_receiver,
-            buffer: String::new(),
-            current_event: HashMap::new(),
+            last_event_id: None,
         })
     }
 }
@@ -380,119 +347,237 @@ impl SSEEventIterator {
     }
 
     /// Python iterator next method
-    fn __next__(&mut self) -> PyResult<Option<SSEEvent>> {
+    fn __next__(&mut self, py: Python) -> PyResult<Option<SSEEvent>> {
         let receiver_arc = self.event_receiver.clone();
 
-        // First, check if we can process any existing buffered data
-        if !self.buffer.is_empty() {
-            if let Some(event) = self.try_parse_event_from_buffer()? {
-                return Ok(Some(event));
-            }
-        }
-
-        // If no buffered event is ready, try to receive more data
         loop {
-            let chunk_result = {
-                let receiver_guard = receiver_arc.lock().map_err(|e| {
-                    pyo3::exceptions::PyRuntimeError::new_err(format!(
-                        "Failed to acquire lock: {}",
-                        e
-                    ))
-                })?;
-                if let Some(receiver) = receiver_guard.as_ref() {
-                    // Use a longer timeout for better reliability
-                    match receiver.recv_timeout(std::time::Duration::from_secs(5)) {
-                        Ok(result) => Some(result),
-                        Err(mpsc::RecvTimeoutError::Timeout) => {
-                            // Timeout - check if we have any partial data to process
-                            if !self.buffer.is_empty() {
-                                if let Some(event) = self.try_parse_event_from_buffer()? {
-                                    return Ok(Some(event));
-                                }
-                            }
-                            continue; // Keep waiting for more data
-                        }
-                        Err(mpsc::RecvTimeoutError::Disconnected) => {
-                            // Connection ended - process any remaining data
-                            if !self.buffer.is_empty() {
-                                if let Some(event) = self.try_parse_event_from_buffer()? {
-                                    return Ok(Some(event));
-                                }
-                            }
-                            return Ok(None);
-                        }
-                    }
-                } else {
-                    return Ok(None); // No receiver available
-                }
-            };
-
-            match chunk_result {
-                Some(Ok(chunk)) => {
-                    // Convert chunk to string and add to buffer
-                    let chunk_str = String::from_utf8_lossy(&chunk);
-                    self.buffer.push_str(&chunk_str);
+            // Wait without holding the GIL so other Python threads keep running
+            let received = py.allow_threads(|| {
+                let receiver_guard = receiver_arc.lock().map_err(|e| e.to_string())?;
+                Ok::<_, String>(
+                    receiver_guard
+                        .as_ref()
+                        .map(|receiver| receiver.recv_timeout(std::time::Duration::from_secs(5))),
+                )
+            });
 
-                    // Try to parse events from the updated buffer
-                    if let Some(event) = self.try_parse_event_from_buffer()? {
-                        return Ok(Some(event));
+            match received {
+                Ok(Some(Ok(Ok(event)))) => {
+                    if event.id.is_some() {
+                        self.last_event_id = event.id.clone();
                     }
+                    return Ok(Some(event));
                 }
-                Some(Err(e)) => {
+                Ok(Some(Ok(Err(e)))) => {
                     // Only treat this as an error if it's not a normal connection end
                     if e == "Connection ended" {
-                        // Connection ended gracefully - process any remaining data
-                        if !self.buffer.is_empty() {
-                            if let Some(event) = self.try_parse_event_from_buffer()? {
-                                return Ok(Some(event));
-                            }
-                        }
                         return Ok(None);
-                    } else {
-                        return Err(pyo3::exceptions::PyConnectionError::new_err(format!(
-                            "Error reading SSE stream: {}",
-                            e
-                        )));
                     }
+                    return Err(pyo3::exceptions::PyConnectionError::new_err(format!(
+                        "Error reading SSE stream: {}",
+                        e
+                    )));
+                }
+                Ok(Some(Err(mpsc::RecvTimeoutError::Timeout))) => {
+                    // Keep waiting for more data, but let Ctrl-C through
+                    py.check_signals()?;
                 }
-                None => {
-                    // Should not happen in this context
+                Ok(Some(Err(mpsc::RecvTimeoutError::Disconnected))) | Ok(None) => {
                     return Ok(None);
                 }
+                Err(e) => {
+                    return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to acquire lock: {}",
+                        e
+                    )))
+                }
             }
         }
     }
+}
 
-    /// Helper method to try parsing an event from the current buffer
-    fn try_parse_event_from_buffer(&mut self) -> PyResult<Option<SSEEvent>> {
-        // Process lines in buffer
-        loop {
-            if let Some(line_end) = self.buffer.find('\n') {
-                let line = self.buffer[..line_end].trim_end_matches('\r').to_string();
-                self.buffer.drain(..line_end + 1);
-
-                if line.is_empty() {
-                    // Empty line indicates end of event
-                    if !self.current_event.is_empty() {
-                        let event = build_sse_event(&self.current_event);
-                        self.current_event.clear();
-                        return Ok(Some(event));
-                    }
-                } else if let Some((field, value)) = parse_sse_line(&line) {
-                    // Add field to current event
-                    self.current_event
-                        .entry(field)
-                        .or_insert_with(Vec::new)
-                        .push(value);
+/// Reconnection delay the EventSource spec uses until the server sends `retry:`
+const DEFAULT_RETRY_MS: u64 = 3000;
+
+/// Incremental parser turning raw stream chunks into events
+#[derive(Default)]
+struct SseParser {
+    buffer: String,
+    current_event: HashMap<String, Vec<String>>,
+}
+
+impl SseParser {
+    fn feed(&mut self, chunk: &[u8]) {
+        self.buffer.push_str(&String::from_utf8_lossy(chunk));
+    }
+
+    /// Next complete event in the buffer, if any
+    fn next_event(&mut self) -> Option<SSEEvent> {
+        while let Some(line_end) = self.buffer.find('\n') {
+            let line = self.buffer[..line_end].trim_end_matches('\r').to_string();
+            self.buffer.drain(..line_end + 1);
+
+            if line.is_empty() {
+                // Empty line indicates end of event
+                if !self.current_event.is_empty() {
+                    let event = build_sse_event(&self.current_event);
+                    self.current_event.clear();
+                    return Some(event);
                 }
-            } else {
-                // No complete line in buffer
-                break;
+            } else if let Some((field, value)) = parse_sse_line(&line) {
+                self.current_event
+                    .entry(field)
+                    .or_insert_with(Vec::new)
+                    .push(value);
             }
         }
+        None
+    }
+}
 
-        // No complete event found in buffer
-        Ok(None)
+/// Everything the reconnect loop needs to (re)open a stream
+struct StreamOptions {
+    client: Client,
+    url: String,
+    headers: HashMap<String, String>,
+    max_reconnect_attempts: u32,
+    event_types: Vec<String>,
+    auth_config: Option<AuthConfig>,
+}
+
+impl StreamOptions {
+    /// Whether an event passes the `event_types` filter (unnamed events count as "message")
+    fn wants(&self, event: &SSEEvent) -> bool {
+        let event_type = event.event_type.as_deref().unwrap_or("message");
+        self.event_types.is_empty() || self.event_types.iter().any(|t| t == event_type)
+    }
+
+    fn request(&self, last_event_id: Option<&str>) -> reqwest::RequestBuilder {
+        let mut request = self
+            .client
+            .get(&self.url)
+            .header("Accept", "text/event-stream")
+            .header("Cache-Control", "no-cache");
+        for (key, value) in &self.headers {
+            request = request.header(key, value);
+        }
+        if let Some(id) = last_event_id.filter(|id| !id.is_empty()) {
+            request = request.header("Last-Event-ID", id);
+        }
+        apply_authentication(request, self.auth_config.as_ref())
+    }
+}
+
+/// Stream events into `tx`, reconnecting after disconnects as the EventSource spec describes.
+/// `ready` receives the outcome of the first connection attempt.
+async fn run_stream<F>(
+    options: StreamOptions,
+    connected: Arc<Mutex<bool>>,
+    closed: Arc<AtomicBool>,
+    tx: mpsc::Sender<Result<SSEEvent, String>>,
+    ready: F,
+) where
+    F: FnOnce(Result<(), String>) + Send,
+{
+    let set_connected = |value: bool| {
+        if let Ok(mut connected_guard) = connected.lock() {
+            *connected_guard = value;
+        }
+    };
+    let mut ready = Some(ready);
+    let mut last_event_id: Option<String> = None;
+    let mut retry = std::time::Duration::from_millis(DEFAULT_RETRY_MS);
+    let mut attempts = 0;
+
+    loop {
+        let error = match options.request(last_event_id.as_deref()).send().await {
+            Ok(response) if response.status() == reqwest::StatusCode::NO_CONTENT => {
+                // 204 tells the client to stop reconnecting
+                match ready.take() {
+                    Some(ready) => ready(Err("HTTP error: 204 No Content".to_string())),
+                    None => {
+                        let _ = tx.send(Err("Connection ended".to_string()));
+                    }
+                }
+                set_connected(false);
+                return;
+            }
+            Ok(response) if !response.status().is_success() => {
+                let error = format!("HTTP error: {}", response.status());
+                match ready.take() {
+                    Some(ready) => ready(Err(error)),
+                    None => {
+                        let _ = tx.send(Err(error));
+                    }
+                }
+                set_connected(false);
+                return;
+            }
+            Ok(mut response) => {
+                attempts = 0;
+                set_connected(true);
+                if let Some(ready) = ready.take() {
+                    ready(Ok(()));
+                }
+
+                let mut parser = SseParser::default();
+                let error = loop {
+                    match response.chunk().await {
+                        Ok(Some(chunk)) => {
+                            parser.feed(&chunk);
+                            while let Some(event) = parser.next_event() {
+                                if event.id.is_some() {
+                                    last_event_id = event.id.clone();
+                                }
+                                if let Some(retry_ms) = event.retry {
+                                    retry = std::time::Duration::from_millis(retry_ms as u64);
+                                }
+                                if !options.wants(&event) {
+                                    continue;
+                                }
+                                if tx.send(Ok(event)).is_err() {
+                                    // Receiver has been dropped, stop streaming
+                                    set_connected(false);
+                                    return;
+                                }
+                            }
+                        }
+                        Ok(None) => break "Connection ended".to_string(),
+                        Err(e) => break e.to_string(),
+                    }
+                    if closed.load(Ordering::SeqCst) {
+                        return;
+                    }
+                };
+                set_connected(false);
+                error
+            }
+            Err(e) => {
+                let error = format!("Connection error: {}", e);
+                if let Some(ready) = ready.take() {
+                    ready(Err(error));
+                    return;
+                }
+                error
+            }
+        };
+
+        attempts += 1;
+        if closed.load(Ordering::SeqCst) {
+            return;
+        }
+        if attempts > options.max_reconnect_attempts {
+            let _ = tx.send(Err(if options.max_reconnect_attempts == 0 {
+                error
+            } else {
+                format!(
+                    "Gave up after {} reconnect attempts: {}",
+                    options.max_reconnect_attempts, error
+                )
+            }));
+            return;
+        }
+        tokio::time::sleep(retry).await;
     }
 }
 
@@ -534,10 +619,18 @@ pub struct AsyncSSEClient {
     url: Option<String>,
     client: Client,
     headers: HashMap<String, String>,
+    #[allow(dead_code)]
     reconnect_timeout: f64,
     max_reconnect_attempts: u32,
+    /// Only yield events with these names (empty yields every event)
+    #[pyo3(get)]
+    event_types: Vec<String>,
+    /// Authentication applied to every (re)connection request
+    #[pyo3(get, set)]
+    auth_config: Option<AuthConfig>,
     connected: Arc<Mutex<bool>>,
-    event_receiver: Arc<Mutex<Option<Receiver<Result<Bytes, String>>>>>,
+    closed: Arc<AtomicBool>,
+    event_receiver: Arc<Mutex<Option<Receiver<Result<SSEEvent, String>>>>>,
 }
 
 #[pymethods]
@@ -546,12 +639,16 @@ impl AsyncSSEClient {
     #[pyo3(signature = (
         reconnect_timeout = 5.0,
         max_reconnect_attempts = 10,
-        headers = None
+        headers = None,
+        event_types = None,
+        auth_config = None
     ))]
     pub fn new(
         reconnect_timeout: f64,
         max_reconnect_attempts: u32,
         headers: Option<HashMap<String, String>>,
+        event_types: Option<Vec<String>>,
+        auth_config: Option<AuthConfig>,
     ) -> Self {
         let client = Client::new();
 
@@ -561,83 +658,51 @@ impl AsyncSSEClient {
             headers: headers.unwrap_or_default(),
             reconnect_timeout,
             max_reconnect_attempts,
+            event_types: event_types.unwrap_or_default(),
+            auth_config,
             connected: Arc::new(Mutex::new(false)),
+            closed: Arc::new(AtomicBool::new(false)),
             event_receiver: Arc::new(Mutex::new(None)),
         }
     }
 
     /// Connect to SSE endpoint
     pub fn connect<'py>(&mut self, py: Python<'py>, url: &str) -> PyResult<&'py PyAny> {
+        // Close any existing connection
+        self.closed.store(true, Ordering::SeqCst);
+
         self.url = Some(url.to_string());
 
-        let client = self.client.clone();
-        let url_clone = url.to_string();
-        let headers = self.headers.clone();
+        let options = self.stream_options(url);
         let connected = self.connected.clone();
-        let event_receiver_arc = self.event_receiver.clone();
-
-        future_into_py(py, async move {
-            // Create a channel for streaming events
-            let (tx, rx) = mpsc::channel::<Result<Bytes, String>>();
-
-            // Store the receiver
-            *event_receiver_arc.lock().map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
-            })? = Some(rx);
-
-            let mut request = client.get(&url_clone);
-
-            // Add SSE-specific headers
-            request = request.header("Accept", "text/event-stream");
-            request = request.header("Cache-Control", "no-cache");
+        let closed = Arc::new(AtomicBool::new(false));
+        self.closed = closed.clone();
 
-            // Add custom headers
-            for (key, value) in headers {
-                request = request.header(&key, &value);
-            }
-
-            // Make the request and get streaming response
-            let response = request.send().await.map_err(|e| {
-                pyo3::exceptions::PyConnectionError::new_err(format!(
-                    "SSE connection failed: {}",
-                    e
-                ))
-            })?;
+        // Create a channel for streaming events
+        let (tx, rx) = mpsc::channel::<Result<SSEEvent, String>>();
+        *self.event_receiver.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
+        })? = Some(rx);
 
-            if !response.status().is_success() {
-                return Err(pyo3::exceptions::PyConnectionError::new_err(format!(
-                    "SSE connection failed with status: {}",
-                    response.status()
-                )));
-            }
+        let (ready_tx, ready_rx) = tokio::sync::oneshot::channel::<Result<(), String>>();
+        pyo3_asyncio::tokio::get_runtime().spawn(run_stream(
+            options,
+            connected,
+            closed,
+            tx,
+            move |result| {
+                let _ = ready_tx.send(result);
+            },
+        ));
 
-            if let Ok(mut connected_guard) = connected.lock() {
-                *connected_guard = true;
+        future_into_py(py, async move {
+            match ready_rx.await {
+                Ok(Ok(())) => Ok(()),
+                Ok(Err(error_msg)) => Err(pyo3::exceptions::PyConnectionError::new_err(error_msg)),
+                Err(_) => Err(pyo3::exceptions::PyConnectionError::new_err(
+                    "Connection channel disconnected",
+                )),
             }
-
-            // Spawn a task to handle the SSE stream
-            let url_clone = url_clone.clone();
-            tokio::spawn(async move {
-                let mut stream = response.bytes_stream();
-                while let Some(chunk_result) = stream.next().await {
-                    match chunk_result {
-                        Ok(chunk) => {
-                            if tx.send(Ok(chunk)).is_err() {
-                                eprintln!("SSE receiver disconnected for {}", url_clone);
-                                break;
-                            }
-                        }
-                        Err(e) => {
-                            if tx.send(Err(e.to_string())).is_err() {
-                                eprintln!("SSE receiver disconnected for {}", url_clone);
-                            }
-                            break;
-                        }
-                    }
-                }
-            });
-
-            Ok(())
         })
     }
 
@@ -673,6 +738,7 @@ impl AsyncSSEClient {
 
     /// Close connection
     pub fn close<'py>(&mut self, py: Python<'py>) -> PyResult<&'py PyAny> {
+        self.closed.store(true, Ordering::SeqCst);
         let connected = self.connected.clone();
         let event_receiver = self.event_receiver.clone();
 
@@ -715,7 +781,8 @@ impl AsyncSSEClient {
         _exc_value: Option<&PyAny>,
         _traceback: Option<&PyAny>,
     ) -> PyResult<bool> {
-        // Update connection status
+        // Stop the reconnect loop and update connection status
+        self.closed.store(true, Ordering::SeqCst);
         if let Ok(mut connected_guard) = self.connected.lock() {
             *connected_guard = false;
         }
@@ -725,3 +792,16 @@ impl AsyncSSEClient {
         Ok(false)
     }
 }
+
+impl AsyncSSEClient {
+    fn stream_options(&self, url: &str) -> StreamOptions {
+        StreamOptions {
+            client: self.client.clone(),
+            url: url.to_string(),
+            headers: self.headers.clone(),
+            max_reconnect_attempts: self.max_reconnect_attempts,
+            event_types: self.event_types.clone(),
+            auth_config: self.auth_config.clone(),
+        }
+    }
+}
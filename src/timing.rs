@@ -0,0 +1,164 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+tokio::task_local! {
+    /// Connection setup phases of the request being sent on this task
+    static PHASES: Arc<ConnectionPhases>;
+}
+
+/// DNS and connection setup time spent on one request, summed over redirect hops.
+/// Filled in by `ClientResolver` and `ConnectTimingLayer` while the request is sent
+/// inside `ConnectionPhases::scope`.
+#[derive(Default)]
+pub(crate) struct ConnectionPhases {
+    dns: parking_lot::Mutex<Option<Duration>>,
+    setup: parking_lot::Mutex<Option<Duration>>,
+}
+
+impl ConnectionPhases {
+    /// Run `future` with DNS and connect timings attributed to these phases
+    pub(crate) async fn scope<F: Future>(self: &Arc<Self>, future: F) -> F::Output {
+        PHASES.scope(Arc::clone(self), future).await
+    }
+
+    /// The phases of the request being sent on the current task, if any
+    pub(crate) fn current() -> Option<Arc<Self>> {
+        PHASES.try_with(Arc::clone).ok()
+    }
+
+    pub(crate) fn add_dns(&self, elapsed: Duration) {
+        let mut dns = self.dns.lock();
+        *dns = Some(dns.unwrap_or_default() + elapsed);
+    }
+
+    fn add_setup(&self, elapsed: Duration) {
+        let mut setup = self.setup.lock();
+        *setup = Some(setup.unwrap_or_default() + elapsed);
+    }
+
+    /// Breakdown of a request whose exchange began at `exchange_start`, whose
+    /// headers arrived after `ttfb` and whose body has now been read
+    pub(crate) fn finish(&self, exchange_start: Instant, ttfb: Duration) -> Timing {
+        let dns = self.dns.lock().unwrap_or_default();
+        let setup = *self.setup.lock();
+        Timing {
+            dns: dns.as_secs_f64(),
+            // The connector resolves the host itself, so setup includes the lookup
+            connect: setup.map_or(0.0, |setup| setup.saturating_sub(dns).as_secs_f64()),
+            ttfb: ttfb.as_secs_f64(),
+            total: exchange_start.elapsed().as_secs_f64(),
+            reused_connection: setup.is_none(),
+        }
+    }
+}
+
+/// Where a response's time went, in seconds
+#[derive(Clone, Debug, Default)]
+pub struct Timing {
+    pub dns: f64,
+    /// TCP connect plus TLS handshake; reqwest performs both in one connector call
+    pub connect: f64,
+    /// Until the response headers arrived, including DNS and connect
+    pub ttfb: f64,
+    pub total: f64,
+    pub reused_connection: bool,
+}
+
+impl Timing {
+    /// Time spent reading the body after the headers arrived
+    pub fn download(&self) -> f64 {
+        (self.total - self.ttfb).max(0.0)
+    }
+
+    pub(crate) fn to_dict(&self, py: Python) -> PyResult<PyObject> {
+        let dict = PyDict::new(py);
+        dict.set_item("dns", self.dns)?;
+        dict.set_item("connect", self.connect)?;
+        // Not separable from connect: reqwest gives no hook between TCP and TLS
+        dict.set_item("tls", py.None())?;
+        dict.set_item("ttfb", self.ttfb)?;
+        dict.set_item("download", self.download())?;
+        dict.set_item("total", self.total)?;
+        dict.set_item("reused_connection", self.reused_connection)?;
+        Ok(dict.into())
+    }
+}
+
+/// Connector layer that times establishing each new connection
+#[derive(Clone)]
+pub(crate) struct ConnectTimingLayer;
+
+impl<S> tower_layer::Layer<S> for ConnectTimingLayer {
+    type Service = ConnectTiming<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectTiming { inner }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct ConnectTiming<S> {
+    inner: S,
+}
+
+impl<S, R> tower_service::Service<R> for ConnectTiming<S>
+where
+    S: tower_service::Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        // Captured now: hyper may finish the connection on another task
+        let phases = ConnectionPhases::current();
+        let started = Instant::now();
+        let connecting = self.inner.call(request);
+        Box::pin(async move {
+            let connection = connecting.await;
+            if let (Some(phases), Ok(_)) = (&phases, &connection) {
+                phases.add_setup(started.elapsed());
+            }
+            connection
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_phases_are_scoped_to_the_sending_task() {
+        let phases = Arc::new(ConnectionPhases::default());
+        phases
+            .scope(async {
+                let current = ConnectionPhases::current().expect("phases in scope");
+                current.add_dns(Duration::from_millis(20));
+                current.add_setup(Duration::from_millis(50));
+            })
+            .await;
+        assert!(ConnectionPhases::current().is_none());
+
+        let timing = phases.finish(Instant::now(), Duration::from_millis(80));
+        assert!((timing.dns - 0.02).abs() < 1e-9);
+        assert!((timing.connect - 0.03).abs() < 1e-9);
+        assert!((timing.ttfb - 0.08).abs() < 1e-9);
+        assert!(!timing.reused_connection);
+
+        let reused =
+            Arc::new(ConnectionPhases::default()).finish(Instant::now(), Duration::from_millis(5));
+        assert!(reused.reused_connection);
+        assert_eq!(reused.connect, 0.0);
+    }
+}
@@ -2,11 +2,18 @@ use futures_util::{SinkExt, StreamExt};
 use pyo3::prelude::*;
 use pyo3_asyncio::tokio::future_into_py;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::error::{Error as WsError, ProtocolError};
+use tokio_tungstenite::tungstenite::handshake::client::Request;
+use tokio_tungstenite::tungstenite::http::header::SEC_WEBSOCKET_PROTOCOL;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
 use tokio_tungstenite::tungstenite::protocol::CloseFrame;
 use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 
 /// WebSocket message types
 #[pyclass]
@@ -82,6 +89,22 @@ impl WebSocketMessage {
         self.message_type == "close"
     }
 
+    /// Parse a text message as JSON
+    pub fn json(&self, py: Python) -> PyResult<PyObject> {
+        let text = match &self.text_data {
+            Some(text) => text,
+            None => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Expected a text frame, got {}",
+                    self.message_type
+                )))
+            }
+        };
+        let value: serde_json::Value = serde_json::from_str(text)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid JSON: {}", e)))?;
+        crate::client::json_to_python(py, &value)
+    }
+
     fn __repr__(&self) -> String {
         match self.message_type.as_str() {
             "text" => format!(
@@ -156,6 +179,472 @@ impl WebSocketMessage {
     }
 }
 
+/// How a client re-establishes its connection after a disconnect
+struct ReconnectPolicy {
+    auto_reconnect: bool,
+    max_attempts: u32,
+    delay: f64,
+    backoff: f64,
+    on_reconnect: Option<PyObject>,
+}
+
+impl ReconnectPolicy {
+    /// Delay before the given (1-based) reconnection attempt
+    fn delay_for(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        Duration::try_from_secs_f64(self.delay * self.backoff.powi(exponent))
+            .unwrap_or(Duration::MAX)
+    }
+
+    /// Tell Python code the socket came back so it can re-subscribe
+    fn notify(&self, attempt: u32) {
+        if let Some(callback) = &self.on_reconnect {
+            Python::with_gil(|py| {
+                if let Err(e) = callback.call1(py, (attempt,)) {
+                    e.print(py);
+                }
+            });
+        }
+    }
+}
+
+/// Connection status shared between a client and its background task
+#[derive(Default)]
+struct ConnectionState {
+    connected: AtomicBool,
+    reconnect_attempts: AtomicU32,
+    /// Subprotocol the server accepted, requested again on reconnect
+    subprotocol: Mutex<Option<String>>,
+    /// Unix timestamp of the most recent pong frame
+    last_pong_at: Mutex<Option<f64>>,
+}
+
+impl ConnectionState {
+    fn record_pong(&self) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        if let Ok(mut guard) = self.last_pong_at.lock() {
+            *guard = Some(now);
+        }
+    }
+
+    fn last_pong_at(&self) -> Option<f64> {
+        self.last_pong_at.lock().ok().and_then(|guard| *guard)
+    }
+}
+
+/// Keepalive pings sent on an open connection
+#[derive(Clone, Copy)]
+struct Heartbeat {
+    /// Time between pings (None disables the heartbeat)
+    interval: Option<Duration>,
+    /// How long to wait for a pong before treating the connection as dead
+    timeout: Duration,
+}
+
+impl Heartbeat {
+    fn new(ping_interval: Option<f64>, ping_timeout: f64) -> PyResult<Self> {
+        if let Some(interval) = ping_interval {
+            if !interval.is_finite() || interval <= 0.0 {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "ping_interval must be a positive number of seconds",
+                ));
+            }
+        }
+        if !ping_timeout.is_finite() || ping_timeout <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "ping_timeout must be a positive number of seconds",
+            ));
+        }
+        Ok(Heartbeat {
+            interval: ping_interval.map(Duration::from_secs_f64),
+            timeout: Duration::from_secs_f64(ping_timeout),
+        })
+    }
+}
+
+/// Validate the reconnection settings passed to a client constructor
+fn validate_reconnect_options(
+    py: Python,
+    reconnect_delay: f64,
+    reconnect_backoff: f64,
+    on_reconnect: &Option<PyObject>,
+) -> PyResult<()> {
+    if !reconnect_delay.is_finite() || reconnect_delay < 0.0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "reconnect_delay must be a non-negative number of seconds",
+        ));
+    }
+    if !reconnect_backoff.is_finite() || reconnect_backoff < 1.0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "reconnect_backoff must be at least 1.0",
+        ));
+    }
+    if let Some(callback) = on_reconnect {
+        if !callback.as_ref(py).is_callable() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "on_reconnect callback must be callable",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Everything a background task needs to (re)open a client's connection
+struct ConnectionOptions {
+    url: String,
+    headers: HashMap<String, String>,
+    subprotocols: Vec<String>,
+    policy: ReconnectPolicy,
+    heartbeat: Heartbeat,
+}
+
+impl ConnectionOptions {
+    /// Handshake request offering the negotiated subprotocol, or every configured one before that
+    fn request(&self, negotiated: Option<&str>) -> PyResult<Request> {
+        match negotiated {
+            Some(protocol) => build_request(&self.url, &self.headers, &[protocol.to_string()]),
+            None => build_request(&self.url, &self.headers, &self.subprotocols),
+        }
+    }
+
+    /// Error for a server that accepted none of the offered subprotocols
+    fn check_subprotocol(&self, selected: Option<&str>) -> PyResult<()> {
+        if self.subprotocols.is_empty()
+            || selected
+                .is_some_and(|selected| self.subprotocols.iter().any(|offered| offered == selected))
+        {
+            return Ok(());
+        }
+        Err(pyo3::exceptions::PyConnectionError::new_err(format!(
+            "Server did not accept any of the offered subprotocols ({}); it selected {}",
+            self.subprotocols.join(", "),
+            selected.unwrap_or("none")
+        )))
+    }
+}
+
+/// Build the handshake request carrying the client headers and offered subprotocols
+fn build_request(
+    url: &str,
+    headers: &HashMap<String, String>,
+    subprotocols: &[String],
+) -> PyResult<Request> {
+    let invalid = |e: String| {
+        pyo3::exceptions::PyValueError::new_err(format!("Invalid WebSocket request: {}", e))
+    };
+
+    let mut request = url
+        .into_client_request()
+        .map_err(|e| invalid(e.to_string()))?;
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| invalid(e.to_string()))?;
+        let value = HeaderValue::from_str(value).map_err(|e| invalid(e.to_string()))?;
+        request.headers_mut().insert(name, value);
+    }
+    if !subprotocols.is_empty() {
+        let value =
+            HeaderValue::from_str(&subprotocols.join(", ")).map_err(|e| invalid(e.to_string()))?;
+        request.headers_mut().insert(SEC_WEBSOCKET_PROTOCOL, value);
+    }
+    Ok(request)
+}
+
+/// Relay frames between the client channels and one live socket, pinging it on the heartbeat.
+/// Returns true when the client closed the connection, false when it was lost.
+async fn pump_messages(
+    stream: WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>,
+    outgoing: &mut mpsc::UnboundedReceiver<Message>,
+    incoming: &mpsc::UnboundedSender<WebSocketMessage>,
+    heartbeat: Heartbeat,
+    state: &ConnectionState,
+) -> bool {
+    let (mut write, mut read) = stream.split();
+    let mut pings = heartbeat
+        .interval
+        .map(|interval| tokio::time::interval_at(tokio::time::Instant::now() + interval, interval));
+    let mut pong_deadline: Option<tokio::time::Instant> = None;
+
+    loop {
+        tokio::select! {
+            _ = async {
+                match pings.as_mut() {
+                    Some(pings) => {
+                        pings.tick().await;
+                    }
+                    None => std::future::pending::<()>().await,
+                }
+            } => {
+                if write.send(Message::Ping(Vec::new())).await.is_err() {
+                    return false;
+                }
+                if pong_deadline.is_none() {
+                    pong_deadline = Some(tokio::time::Instant::now() + heartbeat.timeout);
+                }
+            },
+            _ = async {
+                match pong_deadline {
+                    Some(deadline) => tokio::time::sleep_until(deadline).await,
+                    None => std::future::pending::<()>().await,
+                }
+            } => {
+                // No pong in time: the connection is dead even if the socket is still open
+                let _ = write.close().await;
+                return false;
+            },
+            message = outgoing.recv() => match message {
+                Some(message) => {
+                    let closing = matches!(message, Message::Close(_));
+                    if write.send(message).await.is_err() || closing {
+                        return closing;
+                    }
+                }
+                None => {
+                    let _ = write.close().await;
+                    return true;
+                }
+            },
+            frame = read.next() => {
+                let message = match frame {
+                    Some(Ok(Message::Text(text))) => WebSocketMessage::new_text(text),
+                    Some(Ok(Message::Binary(data))) => WebSocketMessage::new_binary(data),
+                    Some(Ok(Message::Ping(data))) => WebSocketMessage::new_ping(data),
+                    Some(Ok(Message::Pong(data))) => {
+                        pong_deadline = None;
+                        state.record_pong();
+                        WebSocketMessage::new_pong(data)
+                    }
+                    Some(Ok(Message::Close(_))) => {
+                        let _ = incoming.send(WebSocketMessage::new_close());
+                        return false;
+                    }
+                    Some(Ok(Message::Frame(_))) => continue,
+                    Some(Err(_)) | None => return false,
+                };
+                if incoming.send(message).is_err() {
+                    return true;
+                }
+            }
+        }
+    }
+}
+
+/// Why a handshake attempt failed
+enum HandshakeError {
+    /// Network or server failure that a reconnect may fix
+    Transient(String),
+    /// Misconfiguration or subprotocol rejection that retrying cannot fix
+    Fatal(PyErr),
+}
+
+/// Keep a connection alive until the client closes it or reconnection gives up.
+/// The first handshake result is reported through `ready`; later ones fire `on_reconnect`.
+async fn run_connection(
+    options: ConnectionOptions,
+    state: Arc<ConnectionState>,
+    mut outgoing: mpsc::UnboundedReceiver<Message>,
+    incoming: mpsc::UnboundedSender<WebSocketMessage>,
+    ready: oneshot::Sender<PyResult<()>>,
+) {
+    let mut ready = Some(ready);
+    let policy = &options.policy;
+
+    loop {
+        let negotiated = state
+            .subprotocol
+            .lock()
+            .ok()
+            .and_then(|guard| guard.clone());
+        let result = match options.request(negotiated.as_deref()) {
+            Ok(request) => match tokio_tungstenite::connect_async(request).await {
+                Ok((stream, response)) => {
+                    let selected = response
+                        .headers()
+                        .get(SEC_WEBSOCKET_PROTOCOL)
+                        .and_then(|value| value.to_str().ok())
+                        .map(|value| value.to_string());
+                    options
+                        .check_subprotocol(selected.as_deref())
+                        .map(|_| (stream, selected))
+                        .map_err(HandshakeError::Fatal)
+                }
+                Err(WsError::Protocol(ProtocolError::SecWebSocketSubProtocolError(e))) => Err(
+                    HandshakeError::Fatal(options.check_subprotocol(None).err().unwrap_or_else(
+                        || pyo3::exceptions::PyConnectionError::new_err(e.to_string()),
+                    )),
+                ),
+                Err(e) => Err(HandshakeError::Transient(e.to_string())),
+            },
+            Err(e) => Err(HandshakeError::Fatal(e)),
+        };
+
+        match result {
+            Ok((stream, selected)) => {
+                if let Ok(mut guard) = state.subprotocol.lock() {
+                    *guard = selected;
+                }
+
+                let attempt = state.reconnect_attempts.swap(0, Ordering::SeqCst);
+                state.connected.store(true, Ordering::SeqCst);
+                match ready.take() {
+                    Some(ready) => {
+                        let _ = ready.send(Ok(()));
+                    }
+                    None => policy.notify(attempt),
+                }
+
+                let closed_by_client =
+                    pump_messages(stream, &mut outgoing, &incoming, options.heartbeat, &state)
+                        .await;
+                state.connected.store(false, Ordering::SeqCst);
+                if closed_by_client || !policy.auto_reconnect {
+                    return;
+                }
+            }
+            Err(HandshakeError::Fatal(e)) => {
+                if let Some(ready) = ready.take() {
+                    let _ = ready.send(Err(e));
+                }
+                return;
+            }
+            Err(HandshakeError::Transient(e)) => {
+                let attempts = state.reconnect_attempts.load(Ordering::SeqCst);
+                if !policy.auto_reconnect || attempts >= policy.max_attempts {
+                    if let Some(ready) = ready.take() {
+                        let _ =
+                            ready.send(Err(pyo3::exceptions::PyConnectionError::new_err(format!(
+                                "Failed to connect to WebSocket after {} attempts: {}",
+                                attempts + 1,
+                                e
+                            ))));
+                    }
+                    return;
+                }
+            }
+        }
+
+        let attempt = state.reconnect_attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        if attempt > policy.max_attempts {
+            return;
+        }
+        tokio::time::sleep(policy.delay_for(attempt)).await;
+    }
+}
+
+/// Open the message channels for a new connection and spawn its background task
+fn start_connection(
+    runtime: &tokio::runtime::Handle,
+    options: ConnectionOptions,
+    state: Arc<ConnectionState>,
+    message_receiver: &Arc<Mutex<Option<mpsc::UnboundedReceiver<WebSocketMessage>>>>,
+) -> PyResult<(
+    mpsc::UnboundedSender<Message>,
+    oneshot::Receiver<PyResult<()>>,
+)> {
+    options.request(None)?;
+
+    let (outgoing_tx, outgoing_rx) = mpsc::unbounded_channel();
+    let (incoming_tx, incoming_rx) = mpsc::unbounded_channel();
+    let (ready_tx, ready_rx) = oneshot::channel();
+    {
+        let mut guard = message_receiver.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
+        })?;
+        *guard = Some(incoming_rx);
+    }
+
+    runtime.spawn(run_connection(
+        options,
+        state,
+        outgoing_rx,
+        incoming_tx,
+        ready_tx,
+    ));
+    Ok((outgoing_tx, ready_rx))
+}
+
+/// Wait up to `timeout` for the next incoming message (None on timeout)
+async fn next_message(
+    receiver: &Arc<Mutex<Option<mpsc::UnboundedReceiver<WebSocketMessage>>>>,
+    timeout: Option<Duration>,
+) -> PyResult<Option<WebSocketMessage>> {
+    // Take ownership temporarily to avoid Send issues
+    let rx_option = {
+        let mut guard = receiver.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
+        })?;
+        guard.take()
+    };
+
+    if let Some(mut rx) = rx_option {
+        let received = match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, rx.recv()).await.ok(),
+            None => Some(rx.recv().await),
+        };
+        let result = match received {
+            Some(Some(msg)) => Ok(Some(msg)),
+            Some(None) => Err(pyo3::exceptions::PyConnectionError::new_err(
+                "Connection lost",
+            )),
+            None => Ok(None), // Timeout
+        };
+
+        // Put the receiver back
+        {
+            let mut guard = receiver.lock().map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire lock: {}", e))
+            })?;
+            *guard = Some(rx);
+        }
+        result
+    } else {
+        Err(pyo3::exceptions::PyConnectionError::new_err(
+            "Not connected",
+        ))
+    }
+}
+
+/// Wait for the next data frame and parse it as JSON
+async fn next_json(
+    receiver: Arc<Mutex<Option<mpsc::UnboundedReceiver<WebSocketMessage>>>>,
+    timeout: Option<f64>,
+) -> PyResult<PyObject> {
+    let deadline = timeout
+        .map(|timeout| tokio::time::Instant::now() + Duration::from_secs_f64(timeout.max(0.0)));
+
+    loop {
+        let remaining = deadline
+            .map(|deadline| deadline.saturating_duration_since(tokio::time::Instant::now()));
+        let message = next_message(&receiver, remaining).await?.ok_or_else(|| {
+            pyo3::exceptions::PyTimeoutError::new_err(format!(
+                "No message received within {} seconds",
+                timeout.unwrap_or_default()
+            ))
+        })?;
+        if message.is_ping() || message.is_pong() {
+            continue;
+        }
+        return Python::with_gil(|py| message.json(py));
+    }
+}
+
+/// Serialize a Python object into the text of a JSON frame
+fn json_text(obj: &PyAny) -> PyResult<String> {
+    let value: serde_json::Value = pythonize::depythonize(obj)?;
+    serde_json::to_string(&value).map_err(|e| {
+        pyo3::exceptions::PyValueError::new_err(format!("Failed to serialize JSON: {}", e))
+    })
+}
+
+/// Wait for the first handshake of a freshly started connection
+async fn wait_until_ready(ready: oneshot::Receiver<PyResult<()>>) -> PyResult<()> {
+    ready.await.map_err(|_| {
+        pyo3::exceptions::PyRuntimeError::new_err("WebSocket task ended before connecting")
+    })?
+}
+
 /// WebSocket client for real-time bidirectional communication
 #[pyclass]
 pub struct WebSocketClient {
@@ -165,8 +654,9 @@ pub struct WebSocketClient {
     #[pyo3(get, set)]
     pub headers: HashMap<String, String>,
 
+    /// Subprotocols offered in the `Sec-WebSocket-Protocol` handshake header
     #[pyo3(get)]
-    pub connected: bool,
+    pub subprotocols: Vec<String>,
 
     #[pyo3(get)]
     pub auto_reconnect: bool,
@@ -177,168 +667,86 @@ pub struct WebSocketClient {
     #[pyo3(get)]
     pub reconnect_delay: f64,
 
+    /// Multiplier applied to `reconnect_delay` after each failed attempt
+    #[pyo3(get)]
+    pub reconnect_backoff: f64,
+
+    /// Called with the attempt number whenever the socket is re-established
+    #[pyo3(get)]
+    pub on_reconnect: Option<PyObject>,
+
+    heartbeat: Heartbeat,
+
     message_sender: Option<mpsc::UnboundedSender<Message>>,
     message_receiver: Arc<Mutex<Option<mpsc::UnboundedReceiver<WebSocketMessage>>>>,
     runtime: tokio::runtime::Runtime,
-    reconnect_attempts: u32,
+    state: Arc<ConnectionState>,
 }
 
 #[pymethods]
 impl WebSocketClient {
     #[new]
-    #[pyo3(signature = (auto_reconnect = true, max_reconnect_attempts = 5, reconnect_delay = 1.0))]
+    #[pyo3(signature = (
+        auto_reconnect = true,
+        max_reconnect_attempts = 5,
+        reconnect_delay = 1.0,
+        reconnect_backoff = 2.0,
+        on_reconnect = None,
+        ping_interval = None,
+        ping_timeout = 20.0,
+        subprotocols = None,
+        headers = None
+    ))]
     pub fn new(
+        py: Python,
         auto_reconnect: bool,
         max_reconnect_attempts: u32,
         reconnect_delay: f64,
+        reconnect_backoff: f64,
+        on_reconnect: Option<PyObject>,
+        ping_interval: Option<f64>,
+        ping_timeout: f64,
+        subprotocols: Option<Vec<String>>,
+        headers: Option<HashMap<String, String>>,
     ) -> PyResult<Self> {
+        validate_reconnect_options(py, reconnect_delay, reconnect_backoff, &on_reconnect)?;
+        let heartbeat = Heartbeat::new(ping_interval, ping_timeout)?;
         let runtime = tokio::runtime::Runtime::new().map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to create runtime: {}", e))
         })?;
 
         Ok(WebSocketClient {
             url: None,
-            headers: HashMap::new(),
-            connected: false,
+            headers: headers.unwrap_or_default(),
+            subprotocols: subprotocols.unwrap_or_default(),
             auto_reconnect,
             max_reconnect_attempts,
             reconnect_delay,
+            reconnect_backoff,
+            on_reconnect,
+            heartbeat,
             message_sender: None,
             message_receiver: Arc::new(Mutex::new(None)),
             runtime,
-            reconnect_attempts: 0,
+            state: Arc::new(ConnectionState::default()),
         })
     }
 
     /// Connect to WebSocket server
     pub fn connect<'py>(&mut self, py: Python<'py>, url: &str) -> PyResult<&'py PyAny> {
-        self.url = Some(url.to_string());
-        self.reconnect_attempts = 0;
-
-        let url_clone = url.to_string();
-        let _headers = self.headers.clone();
-        let auto_reconnect = self.auto_reconnect;
-        let max_attempts = self.max_reconnect_attempts;
-        let delay = Duration::from_secs_f64(self.reconnect_delay);
-        let message_receiver_arc = self.message_receiver.clone();
-
-        let (tx, rx) = mpsc::unbounded_channel();
-        self.message_sender = Some(tx);
-
-        let handle = self.runtime.spawn(async move {
-            let mut attempts = 0;
-            let mut rx_option = Some(rx);
-
-            loop {
-                match tokio_tungstenite::connect_async(&url_clone).await {
-                    Ok((ws_stream, _response)) => {
-                        // WebSocket connection established
-                        let (write, read) = ws_stream.split();
-
-                        // Create message receiver and store it
-                        let (msg_tx, msg_rx) = mpsc::unbounded_channel();
-                        {
-                            let mut receiver_guard = message_receiver_arc.lock().map_err(|e| {
-                                pyo3::exceptions::PyRuntimeError::new_err(format!(
-                                    "Failed to acquire lock: {}",
-                                    e
-                                ))
-                            })?;
-                            *receiver_guard = Some(msg_rx);
-                        }
-
-                        // Take the receiver for this connection
-                        if let Some(rx) = rx_option.take() {
-                            // Spawn message sender task
-                            let write_handle = {
-                                let mut write = write;
-                                let mut rx = rx;
-                                tokio::spawn(async move {
-                                    while let Some(message) = rx.recv().await {
-                                        if let Err(e) = write.send(message).await {
-                                            // WebSocket send error - connection will be terminated
-                                            break;
-                                        }
-                                    }
-                                })
-                            };
-
-                            // Handle incoming messages
-                            let mut read = read;
-                            while let Some(msg) = read.next().await {
-                                match msg {
-                                    Ok(Message::Text(text)) => {
-                                        if msg_tx.send(WebSocketMessage::new_text(text)).is_err() {
-                                            break;
-                                        }
-                                    }
-                                    Ok(Message::Binary(data)) => {
-                                        if msg_tx.send(WebSocketMessage::new_binary(data)).is_err()
-                                        {
-                                            break;
-                                        }
-                                    }
-                                    Ok(Message::Close(frame)) => {
-                                        let _ = msg_tx.send(WebSocketMessage::new_close());
-                                        // WebSocket connection closed by remote
-                                        break;
-                                    }
-                                    Ok(Message::Ping(data)) => {
-                                        if msg_tx.send(WebSocketMessage::new_ping(data)).is_err() {
-                                            break;
-                                        }
-                                    }
-                                    Ok(Message::Pong(data)) => {
-                                        if msg_tx.send(WebSocketMessage::new_pong(data)).is_err() {
-                                            break;
-                                        }
-                                    }
-                                    Ok(_) => {} // Frame variants we don't handle
-                                    Err(e) => {
-                                        // WebSocket protocol error - terminating connection
-                                        break;
-                                    }
-                                }
-                            }
-
-                            write_handle.abort(); // Stop the sender task
-                        }
-
-                        // Connection lost - attempt reconnection if enabled
-                        if auto_reconnect && attempts < max_attempts {
-                            attempts += 1;
-                            // Attempting WebSocket reconnection
-                            tokio::time::sleep(delay).await;
-                            continue;
-                        } else {
-                            // WebSocket connection ended
-                            break;
-                        }
-                    }
-                    Err(e) => {
-                        if auto_reconnect && attempts < max_attempts {
-                            attempts += 1;
-                            // WebSocket connection attempt failed
-                            tokio::time::sleep(delay).await;
-                            continue;
-                        } else {
-                            return Err(pyo3::exceptions::PyConnectionError::new_err(format!(
-                                "Failed to connect to WebSocket after {} attempts: {}",
-                                attempts, e
-                            )));
-                        }
-                    }
-                }
-            }
+        let state = Arc::new(ConnectionState::default());
+        let (sender, ready) = start_connection(
+            self.runtime.handle(),
+            self.connection_options(py, url),
+            state.clone(),
+            &self.message_receiver,
+        )?;
 
-            Ok(())
-        });
+        self.url = Some(url.to_string());
+        self.state = state;
+        self.message_sender = Some(sender);
 
-        future_into_py(py, async move {
-            handle.await.map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!("WebSocket task failed: {}", e))
-            })?
-        })
+        future_into_py(py, wait_until_ready(ready))
     }
 
     /// Send a text message
@@ -363,6 +771,12 @@ impl WebSocketClient {
         })
     }
 
+    /// Serialize an object to JSON and send it as a text message
+    pub fn send_json<'py>(&self, py: Python<'py>, obj: &PyAny) -> PyResult<&'py PyAny> {
+        let text = json_text(obj)?;
+        self.send(py, &text)
+    }
+
     /// Send binary data
     pub fn send_bytes<'py>(&self, py: Python<'py>, data: Vec<u8>) -> PyResult<&'py PyAny> {
         let sender = self.message_sender.clone();
@@ -438,50 +852,16 @@ impl WebSocketClient {
         let receiver = self.message_receiver.clone();
 
         future_into_py(py, async move {
-            // Take ownership temporarily to avoid Send issues
-            let rx_option = {
-                let mut guard = receiver.lock().map_err(|e| {
-                    pyo3::exceptions::PyRuntimeError::new_err(format!(
-                        "Failed to acquire lock: {}",
-                        e
-                    ))
-                })?;
-                guard.take()
-            };
-
-            if let Some(mut rx) = rx_option {
-                let result = match tokio::time::timeout(
-                    std::time::Duration::from_secs_f64(timeout),
-                    rx.recv(),
-                )
-                .await
-                {
-                    Ok(Some(msg)) => Ok(Some(msg)),
-                    Ok(None) => Err(pyo3::exceptions::PyConnectionError::new_err(
-                        "Connection lost",
-                    )),
-                    Err(_) => Ok(None), // Timeout
-                };
-
-                // Put the receiver back
-                {
-                    let mut guard = receiver.lock().map_err(|e| {
-                        pyo3::exceptions::PyRuntimeError::new_err(format!(
-                            "Failed to acquire lock: {}",
-                            e
-                        ))
-                    })?;
-                    *guard = Some(rx);
-                }
-                result
-            } else {
-                Err(pyo3::exceptions::PyConnectionError::new_err(
-                    "Not connected",
-                ))
-            }
+            next_message(&receiver, Some(Duration::from_secs_f64(timeout))).await
         })
     }
 
+    /// Receive the next text frame and parse it as JSON, skipping ping/pong frames
+    #[pyo3(signature = (timeout = None))]
+    pub fn recv_json<'py>(&self, py: Python<'py>, timeout: Option<f64>) -> PyResult<&'py PyAny> {
+        future_into_py(py, next_json(self.message_receiver.clone(), timeout))
+    }
+
     /// Receive all available messages
     pub fn receive_all<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
         let receiver = self.message_receiver.clone();
@@ -533,12 +913,53 @@ impl WebSocketClient {
 
     /// Reset reconnection attempts counter
     pub fn reset_reconnect_attempts(&mut self) {
-        self.reconnect_attempts = 0;
+        self.state.reconnect_attempts.store(0, Ordering::SeqCst);
+    }
+
+    /// Reconnection attempts made since the connection was last established
+    #[getter]
+    pub fn reconnect_attempts(&self) -> u32 {
+        self.state.reconnect_attempts.load(Ordering::SeqCst)
+    }
+
+    /// Seconds between heartbeat pings (None when the heartbeat is off)
+    #[getter]
+    pub fn ping_interval(&self) -> Option<f64> {
+        self.heartbeat
+            .interval
+            .map(|interval| interval.as_secs_f64())
+    }
+
+    /// Seconds to wait for a pong before the connection is considered dead
+    #[getter]
+    pub fn ping_timeout(&self) -> f64 {
+        self.heartbeat.timeout.as_secs_f64()
+    }
+
+    /// Unix timestamp of the last pong received, if any
+    #[getter]
+    pub fn last_pong_at(&self) -> Option<f64> {
+        self.state.last_pong_at()
+    }
+
+    /// Subprotocol the server selected during the handshake, if any
+    pub fn selected_protocol(&self) -> Option<String> {
+        self.state
+            .subprotocol
+            .lock()
+            .ok()
+            .and_then(|guard| guard.clone())
+    }
+
+    /// Whether the socket is currently open
+    #[getter]
+    pub fn connected(&self) -> bool {
+        self.state.connected.load(Ordering::SeqCst)
     }
 
     /// Check if connected
     pub fn is_connected(&self) -> bool {
-        self.connected
+        self.state.connected.load(Ordering::SeqCst)
     }
 
     /// Context manager support
@@ -558,6 +979,27 @@ impl WebSocketClient {
     }
 }
 
+impl WebSocketClient {
+    fn connection_options(&self, py: Python, url: &str) -> ConnectionOptions {
+        ConnectionOptions {
+            url: url.to_string(),
+            headers: self.headers.clone(),
+            subprotocols: self.subprotocols.clone(),
+            policy: ReconnectPolicy {
+                auto_reconnect: self.auto_reconnect,
+                max_attempts: self.max_reconnect_attempts,
+                delay: self.reconnect_delay,
+                backoff: self.reconnect_backoff,
+                on_reconnect: self
+                    .on_reconnect
+                    .as_ref()
+                    .map(|callback| callback.clone_ref(py)),
+            },
+            heartbeat: self.heartbeat,
+        }
+    }
+}
+
 /// Async WebSocket client for real-time bidirectional communication over HTTP/1.1
 #[pyclass]
 pub struct AsyncWebSocketClient {
@@ -567,8 +1009,9 @@ pub struct AsyncWebSocketClient {
     #[pyo3(get, set)]
     pub headers: HashMap<String, String>,
 
+    /// Subprotocols offered in the `Sec-WebSocket-Protocol` handshake header
     #[pyo3(get)]
-    pub connected: bool,
+    pub subprotocols: Vec<String>,
 
     #[pyo3(get)]
     pub auto_reconnect: bool,
@@ -579,157 +1022,81 @@ pub struct AsyncWebSocketClient {
     #[pyo3(get)]
     pub reconnect_delay: f64,
 
+    /// Multiplier applied to `reconnect_delay` after each failed attempt
+    #[pyo3(get)]
+    pub reconnect_backoff: f64,
+
+    /// Called with the attempt number whenever the socket is re-established
+    #[pyo3(get)]
+    pub on_reconnect: Option<PyObject>,
+
+    heartbeat: Heartbeat,
+
     message_sender: Option<mpsc::UnboundedSender<Message>>,
     message_receiver: Arc<Mutex<Option<mpsc::UnboundedReceiver<WebSocketMessage>>>>,
-    reconnect_attempts: u32,
+    state: Arc<ConnectionState>,
 }
 
 #[pymethods]
 impl AsyncWebSocketClient {
     #[new]
-    #[pyo3(signature = (auto_reconnect = true, max_reconnect_attempts = 5, reconnect_delay = 1.0))]
-    pub fn new(auto_reconnect: bool, max_reconnect_attempts: u32, reconnect_delay: f64) -> Self {
-        AsyncWebSocketClient {
+    #[pyo3(signature = (
+        auto_reconnect = true,
+        max_reconnect_attempts = 5,
+        reconnect_delay = 1.0,
+        reconnect_backoff = 2.0,
+        on_reconnect = None,
+        ping_interval = None,
+        ping_timeout = 20.0,
+        subprotocols = None,
+        headers = None
+    ))]
+    pub fn new(
+        py: Python,
+        auto_reconnect: bool,
+        max_reconnect_attempts: u32,
+        reconnect_delay: f64,
+        reconnect_backoff: f64,
+        on_reconnect: Option<PyObject>,
+        ping_interval: Option<f64>,
+        ping_timeout: f64,
+        subprotocols: Option<Vec<String>>,
+        headers: Option<HashMap<String, String>>,
+    ) -> PyResult<Self> {
+        validate_reconnect_options(py, reconnect_delay, reconnect_backoff, &on_reconnect)?;
+        let heartbeat = Heartbeat::new(ping_interval, ping_timeout)?;
+
+        Ok(AsyncWebSocketClient {
             url: None,
-            headers: HashMap::new(),
-            connected: false,
+            headers: headers.unwrap_or_default(),
+            subprotocols: subprotocols.unwrap_or_default(),
             auto_reconnect,
             max_reconnect_attempts,
             reconnect_delay,
+            reconnect_backoff,
+            on_reconnect,
+            heartbeat,
             message_sender: None,
             message_receiver: Arc::new(Mutex::new(None)),
-            reconnect_attempts: 0,
-        }
+            state: Arc::new(ConnectionState::default()),
+        })
     }
 
     /// Connect to WebSocket server
     pub fn connect<'py>(&mut self, py: Python<'py>, url: &str) -> PyResult<&'py PyAny> {
-        self.url = Some(url.to_string());
-        self.reconnect_attempts = 0;
-
-        let url_clone = url.to_string();
-        let auto_reconnect = self.auto_reconnect;
-        let max_attempts = self.max_reconnect_attempts;
-        let delay = Duration::from_secs_f64(self.reconnect_delay);
-        let message_receiver_arc = self.message_receiver.clone();
-
-        let (tx, rx) = mpsc::unbounded_channel();
-        self.message_sender = Some(tx);
-
-        let handle = tokio::spawn(async move {
-            let mut attempts = 0;
-            let mut rx_option = Some(rx);
-
-            loop {
-                match tokio_tungstenite::connect_async(&url_clone).await {
-                    Ok((ws_stream, _response)) => {
-                        // WebSocket connection established
-                        let (write, read) = ws_stream.split();
-
-                        // Create message receiver and store it
-                        let (msg_tx, msg_rx) = mpsc::unbounded_channel();
-                        {
-                            let mut receiver_guard = message_receiver_arc.lock().map_err(|e| {
-                                pyo3::exceptions::PyRuntimeError::new_err(format!(
-                                    "Failed to acquire lock: {}",
-                                    e
-                                ))
-                            })?;
-                            *receiver_guard = Some(msg_rx);
-                        }
-
-                        // Take the receiver for this connection
-                        if let Some(rx) = rx_option.take() {
-                            // Spawn message sender task
-                            let write_handle = {
-                                let mut write = write;
-                                let mut rx = rx;
-                                tokio::spawn(async move {
-                                    while let Some(message) = rx.recv().await {
-                                        if let Err(e) = write.send(message).await {
-                                            // WebSocket send error - connection will be terminated
-                                            break;
-                                        }
-                                    }
-                                })
-                            };
-
-                            // Handle incoming messages
-                            let mut read = read;
-                            while let Some(msg) = read.next().await {
-                                match msg {
-                                    Ok(Message::Text(text)) => {
-                                        if msg_tx.send(WebSocketMessage::new_text(text)).is_err() {
-                                            break;
-                                        }
-                                    }
-                                    Ok(Message::Binary(data)) => {
-                                        if msg_tx.send(WebSocketMessage::new_binary(data)).is_err()
-                                        {
-                                            break;
-                                        }
-                                    }
-                                    Ok(Message::Close(frame)) => {
-                                        let _ = msg_tx.send(WebSocketMessage::new_close());
-                                        // WebSocket connection closed by remote
-                                        break;
-                                    }
-                                    Ok(Message::Ping(data)) => {
-                                        if msg_tx.send(WebSocketMessage::new_ping(data)).is_err() {
-                                            break;
-                                        }
-                                    }
-                                    Ok(Message::Pong(data)) => {
-                                        if msg_tx.send(WebSocketMessage::new_pong(data)).is_err() {
-                                            break;
-                                        }
-                                    }
-                                    Ok(_) => {} // Frame variants we don't handle
-                                    Err(e) => {
-                                        // WebSocket protocol error - terminating connection
-                                        break;
-                                    }
-                                }
-                            }
-
-                            write_handle.abort(); // Stop the sender task
-                        }
-
-                        // Connection lost - attempt reconnection if enabled
-                        if auto_reconnect && attempts < max_attempts {
-                            attempts += 1;
-                            // Attempting WebSocket reconnection
-                            tokio::time::sleep(delay).await;
-                            continue;
-                        } else {
-                            // WebSocket connection ended
-                            break;
-                        }
-                    }
-                    Err(e) => {
-                        if auto_reconnect && attempts < max_attempts {
-                            attempts += 1;
-                            // WebSocket connection attempt failed
-                            tokio::time::sleep(delay).await;
-                            continue;
-                        } else {
-                            return Err(pyo3::exceptions::PyConnectionError::new_err(format!(
-                                "Failed to connect to WebSocket after {} attempts: {}",
-                                attempts, e
-                            )));
-                        }
-                    }
-                }
-            }
+        let state = Arc::new(ConnectionState::default());
+        let (sender, ready) = start_connection(
+            pyo3_asyncio::tokio::get_runtime().handle(),
+            self.connection_options(py, url),
+            state.clone(),
+            &self.message_receiver,
+        )?;
 
-            Ok(())
-        });
+        self.url = Some(url.to_string());
+        self.state = state;
+        self.message_sender = Some(sender);
 
-        future_into_py(py, async move {
-            handle.await.map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!("WebSocket task failed: {}", e))
-            })?
-        })
+        future_into_py(py, wait_until_ready(ready))
     }
 
     /// Send a text message
@@ -754,6 +1121,12 @@ impl AsyncWebSocketClient {
         })
     }
 
+    /// Serialize an object to JSON and send it as a text message
+    pub fn send_json<'py>(&self, py: Python<'py>, obj: &PyAny) -> PyResult<&'py PyAny> {
+        let text = json_text(obj)?;
+        self.send(py, &text)
+    }
+
     /// Send binary data
     pub fn send_bytes<'py>(&self, py: Python<'py>, data: Vec<u8>) -> PyResult<&'py PyAny> {
         let sender = self.message_sender.clone();
@@ -801,6 +1174,12 @@ impl AsyncWebSocketClient {
         })
     }
 
+    /// Receive the next text frame and parse it as JSON, skipping ping/pong frames
+    #[pyo3(signature = (timeout = None))]
+    pub fn recv_json<'py>(&self, py: Python<'py>, timeout: Option<f64>) -> PyResult<&'py PyAny> {
+        future_into_py(py, next_json(self.message_receiver.clone(), timeout))
+    }
+
     /// Close the WebSocket connection
     pub fn close<'py>(&mut self, py: Python<'py>) -> PyResult<&'py PyAny> {
         let sender = self.message_sender.clone();
@@ -827,9 +1206,50 @@ impl AsyncWebSocketClient {
         self.headers.remove(key)
     }
 
+    /// Reconnection attempts made since the connection was last established
+    #[getter]
+    pub fn reconnect_attempts(&self) -> u32 {
+        self.state.reconnect_attempts.load(Ordering::SeqCst)
+    }
+
+    /// Seconds between heartbeat pings (None when the heartbeat is off)
+    #[getter]
+    pub fn ping_interval(&self) -> Option<f64> {
+        self.heartbeat
+            .interval
+            .map(|interval| interval.as_secs_f64())
+    }
+
+    /// Seconds to wait for a pong before the connection is considered dead
+    #[getter]
+    pub fn ping_timeout(&self) -> f64 {
+        self.heartbeat.timeout.as_secs_f64()
+    }
+
+    /// Unix timestamp of the last pong received, if any
+    #[getter]
+    pub fn last_pong_at(&self) -> Option<f64> {
+        self.state.last_pong_at()
+    }
+
+    /// Subprotocol the server selected during the handshake, if any
+    pub fn selected_protocol(&self) -> Option<String> {
+        self.state
+            .subprotocol
+            .lock()
+            .ok()
+            .and_then(|guard| guard.clone())
+    }
+
+    /// Whether the socket is currently open
+    #[getter]
+    pub fn connected(&self) -> bool {
+        self.state.connected.load(Ordering::SeqCst)
+    }
+
     /// Check if connected
     pub fn is_connected(&self) -> bool {
-        self.connected
+        self.state.connected.load(Ordering::SeqCst)
     }
 
     /// Context manager support
@@ -848,3 +1268,89 @@ impl AsyncWebSocketClient {
         Ok(false)
     }
 }
+
+impl AsyncWebSocketClient {
+    fn connection_options(&self, py: Python, url: &str) -> ConnectionOptions {
+        ConnectionOptions {
+            url: url.to_string(),
+            headers: self.headers.clone(),
+            subprotocols: self.subprotocols.clone(),
+            policy: ReconnectPolicy {
+                auto_reconnect: self.auto_reconnect,
+                max_attempts: self.max_reconnect_attempts,
+                delay: self.reconnect_delay,
+                backoff: self.reconnect_backoff,
+                on_reconnect: self
+                    .on_reconnect
+                    .as_ref()
+                    .map(|callback| callback.clone_ref(py)),
+            },
+            heartbeat: self.heartbeat,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_delay_grows_exponentially() {
+        let policy = ReconnectPolicy {
+            auto_reconnect: true,
+            max_attempts: 5,
+            delay: 0.5,
+            backoff: 2.0,
+            on_reconnect: None,
+        };
+
+        assert_eq!(policy.delay_for(1), Duration::from_millis(500));
+        assert_eq!(policy.delay_for(2), Duration::from_secs(1));
+        assert_eq!(policy.delay_for(4), Duration::from_secs(4));
+        assert_eq!(policy.delay_for(u32::MAX), Duration::MAX);
+    }
+
+    fn options_offering(subprotocols: &[&str]) -> ConnectionOptions {
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), "Bearer token".to_string());
+        ConnectionOptions {
+            url: "ws://localhost:9001/feed".to_string(),
+            headers,
+            subprotocols: subprotocols.iter().map(|p| p.to_string()).collect(),
+            policy: ReconnectPolicy {
+                auto_reconnect: false,
+                max_attempts: 0,
+                delay: 0.0,
+                backoff: 1.0,
+                on_reconnect: None,
+            },
+            heartbeat: Heartbeat::new(None, 20.0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_handshake_offers_subprotocols_and_headers() {
+        let options = options_offering(&["chat.v2", "chat.v1"]);
+
+        let request = options.request(None).unwrap();
+        assert_eq!(request.headers()["Authorization"], "Bearer token");
+        assert_eq!(
+            request.headers()[SEC_WEBSOCKET_PROTOCOL],
+            "chat.v2, chat.v1"
+        );
+
+        // Reconnects ask for the protocol the server already picked
+        let request = options.request(Some("chat.v1")).unwrap();
+        assert_eq!(request.headers()[SEC_WEBSOCKET_PROTOCOL], "chat.v1");
+    }
+
+    #[test]
+    fn test_unoffered_subprotocol_is_rejected() {
+        let options = options_offering(&["chat.v2"]);
+        assert!(options.check_subprotocol(Some("chat.v2")).is_ok());
+        assert!(options.check_subprotocol(Some("mqtt")).is_err());
+        assert!(options.check_subprotocol(None).is_err());
+
+        assert!(options_offering(&[]).check_subprotocol(None).is_ok());
+    }
+}
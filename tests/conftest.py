@@ -0,0 +1,35 @@
+"""
+Shared fixtures for the UltraFast HTTP Client test suite
+"""
+
+import http.server
+import threading
+
+import pytest
+
+
+@pytest.fixture
+def local_server():
+    """Serve request handlers on a free local port for the duration of a test.
+
+    ``local_server(handler)`` starts an ``HTTPServer`` (or a ``ThreadingHTTPServer``
+    with ``threaded=True``) in a background thread and returns it. ``ssl_context``
+    wraps the listening socket for HTTPS. Request logging is silenced, and every
+    server is shut down when the test ends.
+    """
+    servers = []
+
+    def start(handler, host="127.0.0.1", threaded=False, ssl_context=None):
+        quiet = type(handler.__name__, (handler,), {"log_message": lambda self, *args: None})
+        server_class = http.server.ThreadingHTTPServer if threaded else http.server.HTTPServer
+        server = server_class((host, 0), quiet)
+        if ssl_context is not None:
+            server.socket = ssl_context.wrap_socket(server.socket, server_side=True)
+        threading.Thread(target=server.serve_forever, daemon=True).start()
+        servers.append(server)
+        return server
+
+    yield start
+    for server in servers:
+        server.shutdown()
+        server.server_close()
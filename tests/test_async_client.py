@@ -13,8 +13,12 @@ Tests all features and functionality of the AsyncHttpClient including:
 """
 
 import asyncio
+import http.server
 import os
+import socket
 import tempfile
+import threading
+import time
 from typing import Any, Dict
 
 import pytest
@@ -53,6 +57,15 @@ class TestAsyncHttpClientBasicRequests:
         assert data["args"]["key1"] == "value1"
         assert data["args"]["key2"] == "value2"
 
+    @pytest.mark.asyncio
+    async def test_default_params_merged_with_request_params(self, client, test_url):
+        """Test that async default query params merge with per-request params"""
+        client.set_default_params({"version": "2", "key1": "default"})
+        response = await client.get(f"{test_url}/get", params={"key1": "value1"})
+        data = response.json()
+        assert data["args"]["version"] == "2"
+        assert data["args"]["key1"] == "value1"
+
     @pytest.mark.asyncio
     async def test_get_with_headers(self, client, test_url):
         """Test async GET request with custom headers"""
@@ -151,6 +164,247 @@ class TestAsyncHttpClientBasicRequests:
         response = await client.options(f"{test_url}/get")
         assert response.status_code == 200
 
+    @pytest.mark.asyncio
+    async def test_generic_request(self, client, test_url):
+        """Test async request() with an arbitrary method"""
+        response = await client.request("PATCH", f"{test_url}/patch", data={"a": "1"})
+        assert response.status_code == 200
+        assert response.json()["form"] == {"a": "1"}
+
+        with pytest.raises(ValueError, match="Invalid HTTP method"):
+            await client.request("", f"{test_url}/get")
+
+    @pytest.mark.asyncio
+    async def test_get_many_and_request_many(self, local_server):
+        """Test batch requests run concurrently up to the limit and keep input order"""
+        lock = threading.Lock()
+        in_flight = {"now": 0, "max": 0}
+
+        class Slow(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                with lock:
+                    in_flight["now"] += 1
+                    in_flight["max"] = max(in_flight["max"], in_flight["now"])
+                time.sleep(0.1)
+                with lock:
+                    in_flight["now"] -= 1
+                body = self.path.encode()
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+            do_POST = do_GET
+
+        server = local_server(Slow, threaded=True)
+        client = uf.AsyncHttpClient(retry_config=uf.RetryConfig(max_retries=0))
+        base = f"http://127.0.0.1:{server.server_port}"
+
+        urls = [f"{base}/{i}" for i in range(6)] + ["http://127.0.0.1:1/"]
+        results = await client.get_many(urls, concurrency=2)
+        assert [r.text() for r in results[:6]] == [f"/{i}" for i in range(6)]
+        assert isinstance(results[6], Exception)
+        assert in_flight["max"] == 2
+
+        results = await client.request_many(
+            [{"url": f"{base}/a"}, {"method": "POST", "url": f"{base}/b", "json": {"x": 1}}]
+        )
+        assert [r.text() for r in results] == ["/a", "/b"]
+
+        with pytest.raises(ValueError, match="concurrency"):
+            await client.get_many([f"{base}/"], concurrency=0)
+        with pytest.raises(ValueError, match="url"):
+            await client.request_many([{"method": "GET"}])
+
+    @pytest.mark.asyncio
+    async def test_upload_stream(self, local_server):
+        """Test upload_stream sends sync and async iterators with chunked encoding"""
+        received = []
+
+        class Upload(http.server.BaseHTTPRequestHandler):
+            def do_POST(self):
+                body = b""
+                try:
+                    while True:
+                        size = int(self.rfile.readline().strip(), 16)
+                        if size == 0:
+                            break
+                        body += self.rfile.read(size)
+                        self.rfile.readline()
+                except ValueError:
+                    # The client aborted mid-stream
+                    return
+                received.append((self.headers["Transfer-Encoding"], body))
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        async def produce():
+            for i in range(3):
+                await asyncio.sleep(0.01)
+                yield f"chunk-{i};".encode()
+
+        async def failing():
+            yield b"first"
+            raise ValueError("producer failed")
+
+        server = local_server(Upload, threaded=True)
+        url = f"http://127.0.0.1:{server.server_port}/upload"
+        client = uf.AsyncHttpClient()
+        assert (await client.upload_stream(url, produce())).status_code == 200
+        assert (await client.upload_stream(url, iter([b"a", b"b"]))).status_code == 200
+        assert received == [
+            ("chunked", b"chunk-0;chunk-1;chunk-2;"),
+            ("chunked", b"ab"),
+        ]
+
+        with pytest.raises(ValueError, match="producer failed"):
+            await client.upload_stream(url, failing())
+
+        with pytest.raises(TypeError, match="AsyncHttpClient"):
+            uf.HttpClient().upload_stream(url, produce())
+
+    @pytest.mark.asyncio
+    async def test_timing_breakdown(self, local_server):
+        """Test responses report connection phases and reuse"""
+
+        class Ok(http.server.BaseHTTPRequestHandler):
+            protocol_version = "HTTP/1.1"
+
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "2")
+                self.end_headers()
+                self.wfile.write(b"ok")
+
+        server = local_server(Ok)
+        url = f"http://localhost:{server.server_port}/"
+        client = uf.AsyncHttpClient()
+        first = (await client.get(url)).timing
+        assert first["tls"] is None
+        assert first["reused_connection"] is False
+        assert first["connect"] > 0
+        assert first["ttfb"] <= first["total"]
+
+        second = (await client.get(url)).timing
+        assert second["reused_connection"] is True
+        assert second["connect"] == 0
+
+    @pytest.mark.asyncio
+    async def test_warmup_seeds_connection_pool(self, local_server):
+        """Test async warmup opens pooled connections and reports failures"""
+
+        class Ok(http.server.BaseHTTPRequestHandler):
+            protocol_version = "HTTP/1.1"
+
+            def do_HEAD(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+            do_GET = do_HEAD
+
+        server = local_server(Ok)
+        url = f"http://127.0.0.1:{server.server_port}/"
+        closed = socket.socket()
+        closed.bind(("127.0.0.1", 0))
+        closed_url = f"http://127.0.0.1:{closed.getsockname()[1]}/"
+        closed.close()
+        client = uf.AsyncHttpClient()
+        result = await client.warmup([url, closed_url], timeout=5)
+        assert result == {"succeeded": 1, "failed": 1}
+        assert (await client.get(url)).timing["reused_connection"] is True
+
+    @pytest.mark.asyncio
+    async def test_download_reports_progress(self, tmp_path, local_server):
+        """Test async download streams to a file and reports progress"""
+        payload = os.urandom(200_000)
+
+        class Files(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(payload)))
+                self.end_headers()
+                self.wfile.write(payload)
+
+        server = local_server(Files)
+        calls = []
+        target = tmp_path / "file.bin"
+        written = await uf.AsyncHttpClient().download(
+            f"http://127.0.0.1:{server.server_port}/file",
+            str(target),
+            lambda *args: calls.append(args),
+        )
+        assert written == len(payload)
+        assert target.read_bytes() == payload
+        assert calls[-1] == (200_000, 200_000)
+        assert [done for done, _ in calls] == sorted(done for done, _ in calls)
+
+    @pytest.mark.asyncio
+    async def test_upload_progress_callback(self, local_server):
+        """Test async uploads report progress as the body is sent"""
+
+        class Upload(http.server.BaseHTTPRequestHandler):
+            def do_POST(self):
+                self.rfile.read(int(self.headers["Content-Length"]))
+                self.send_response(204)
+                self.end_headers()
+
+        server = local_server(Upload)
+        calls = []
+        response = await uf.AsyncHttpClient().post(
+            f"http://127.0.0.1:{server.server_port}/upload",
+            json={"blob": "x" * 100_000},
+            progress_callback=lambda *a: calls.append(a),
+        )
+        assert response.status_code == 204
+        sent, total = calls[-1]
+        assert sent == total > 100_000
+        assert len(calls) == 2
+
+    @pytest.mark.asyncio
+    async def test_chunked_upload_is_resent_on_redirect(self, local_server):
+        """Test an async body sent in chunks is sent again in full to a 307 target"""
+        received = []
+
+        class Moved(http.server.BaseHTTPRequestHandler):
+            def do_POST(self):
+                received.append((self.path, self.rfile.read(int(self.headers["Content-Length"]))))
+                self.send_response(307 if self.path == "/old" else 204)
+                self.send_header("Location", "/new")
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Moved)
+        calls = []
+        response = await uf.AsyncHttpClient().post(
+            f"http://127.0.0.1:{server.server_port}/old",
+            json={"blob": "x" * 100_000},
+            expect_continue=True,
+            progress_callback=lambda *a: calls.append(a),
+        )
+        assert response.status_code == 204
+        assert [path for path, _ in received] == ["/old", "/new"]
+        assert received[0][1] == received[1][1]
+        assert [sent for sent, total in calls if sent == total] == [len(received[0][1])] * 2
+
+    @pytest.mark.asyncio
+    async def test_max_response_size(self, local_server):
+        """Test async bodies over max_response_size raise"""
+
+        class Big(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.end_headers()
+                self.wfile.write(b"x" * 100_000)
+
+        server = local_server(Big)
+        url = f"http://127.0.0.1:{server.server_port}/"
+        with pytest.raises(uf.ResponseTooLargeError):
+            await uf.AsyncHttpClient(max_response_size=10_000).get(url)
+        response = await uf.AsyncHttpClient(max_response_size=100_000).get(url)
+        assert response.content_length == 100_000
+
 
 class TestAsyncHttpClientAuthentication:
     """Test async authentication methods"""
@@ -183,6 +437,24 @@ class TestAsyncHttpClientAuthentication:
         assert data["authenticated"] == True
         assert data["token"] == "async-token-123"
 
+    @pytest.mark.asyncio
+    async def test_bearer_provider(self, test_url):
+        """Test async Bearer auth with a coroutine token provider"""
+        calls = []
+
+        async def provider():
+            calls.append(None)
+            return f"provided-{len(calls)}"
+
+        auth_config = uf.AuthConfig.bearer_provider(provider, cache_ttl=60)
+        client = uf.AsyncHttpClient(auth_config=auth_config)
+
+        for _ in range(2):
+            response = await client.get(f"{test_url}/bearer")
+            assert response.status_code == 200
+            assert response.json()["token"] == "provided-1"
+        assert len(calls) == 1
+
     @pytest.mark.asyncio
     async def test_api_key_auth(self, test_url):
         """Test async API Key Authentication via headers"""
@@ -212,6 +484,54 @@ class TestAsyncHttpClientAuthentication:
         assert auth is not None
         assert auth.auth_type == uf.AuthType.OAuth2
 
+    @pytest.mark.asyncio
+    async def test_oauth2_token_lifecycle(self, local_server):
+        """Test awaiting, force-refreshing and observing OAuth2 tokens"""
+
+        class TokenServer(http.server.BaseHTTPRequestHandler):
+            issued = 0
+
+            def do_POST(self):
+                self.rfile.read(int(self.headers.get("Content-Length", 0)))
+                TokenServer.issued += 1
+                body = (
+                    f'{{"access_token": "access-{TokenServer.issued}", "expires_in": 3600}}'
+                ).encode()
+                self.send_response(200)
+                self.send_header("Content-Type", "application/json")
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+            def do_GET(self):
+                body = self.headers.get("Authorization", "").encode()
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        server = local_server(TokenServer, threaded=True)
+        base = f"http://127.0.0.1:{server.server_address[1]}"
+        refreshed = []
+        auth = uf.AuthConfig.oauth2(
+            "client", f"{base}/token", on_token_refresh=refreshed.append
+        )
+        client = uf.AsyncHttpClient(auth_config=auth)
+        assert await client.get_oauth2_token() is None
+
+        response = await client.get(f"{base}/api")
+        assert response.text() == "Bearer access-1"
+        assert (await client.get_oauth2_token()).access_token == "access-1"
+
+        token = await client.refresh_oauth2_token()
+        assert token.access_token == "access-2"
+        response = await client.get(f"{base}/api")
+        assert response.text() == "Bearer access-2"
+        assert [t.access_token for t in refreshed] == ["access-1", "access-2"]
+
+        with pytest.raises(ValueError, match="not configured"):
+            uf.AsyncHttpClient().refresh_oauth2_token()
+
     def test_auth_configuration_methods(self):
         """Test async authentication configuration methods"""
         client = uf.AsyncHttpClient()
@@ -260,6 +580,60 @@ class TestAsyncHttpClientConfiguration:
         new_retry_config = uf.RetryConfig(max_retries=5, initial_delay=0.5)
         client.set_retry_config(new_retry_config)
 
+    @pytest.mark.asyncio
+    async def test_on_retry_callback(self, local_server):
+        """Test on_retry fires before each async retry with attempt, error and delay"""
+        failures = [True, True]
+
+        class FlakyHandler(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                if failures:
+                    # Drop the connection without a response to force a retry
+                    failures.pop()
+                    self.close_connection = True
+                    return
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(FlakyHandler)
+        retry_config = uf.RetryConfig(max_retries=3, initial_delay=0.01, jitter=False)
+        client = uf.AsyncHttpClient(retry_config=retry_config)
+
+        calls = []
+        client.set_on_retry(lambda attempt, reason, delay: calls.append((attempt, reason, delay)))
+
+        response = await client.get(f"http://127.0.0.1:{server.server_port}/")
+        assert response.status_code == 200
+
+        assert [attempt for attempt, _, _ in calls] == [1, 2]
+        assert all(isinstance(reason, str) for _, reason, _ in calls)
+        assert [delay for _, _, delay in calls] == pytest.approx([0.01, 0.02])
+
+    @pytest.mark.asyncio
+    async def test_async_context_manager_closes_client(self, local_server):
+        """Test async with and aclose() close the client and later requests fail"""
+
+        class Hello(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Hello)
+        url = f"http://127.0.0.1:{server.server_port}/"
+        async with uf.AsyncHttpClient() as client:
+            assert (await client.get(url)).status_code == 200
+        assert client.is_closed()
+        with pytest.raises(RuntimeError, match="Client is closed"):
+            await client.get(url)
+
+        other = uf.AsyncHttpClient()
+        await other.aclose()
+        assert other.is_closed()
+        with pytest.raises(RuntimeError, match="Client is closed"):
+            await other.get_many([url])
+
     def test_ssl_configuration(self):
         """Test async SSL configuration"""
         ssl_config = uf.SSLConfig(verify=True)
@@ -335,6 +709,38 @@ class TestAsyncHttpClientConfiguration:
         assert rate_limit is not None
         assert rate_limit.requests_per_second == 10
 
+    @pytest.mark.asyncio
+    async def test_rate_limit_queue(self, local_server):
+        """Test queued requests await a slot and fail once the queue timeout passes"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Reply, threaded=True)
+        url = f"http://127.0.0.1:{server.server_port}/"
+        client = uf.AsyncHttpClient(
+            rate_limit_config=uf.RateLimitConfig(
+                requests_per_second=5, burst_size=1, queue_timeout_seconds=5.0
+            )
+        )
+        start = time.time()
+        responses = await asyncio.gather(*(client.get(url) for _ in range(3)))
+        assert [r.status_code for r in responses] == [200, 200, 200]
+        # Two of the three waited for a token (one every 0.2s)
+        assert time.time() - start >= 0.35
+
+        client = uf.AsyncHttpClient(
+            rate_limit_config=uf.RateLimitConfig(
+                requests_per_second=0.2, burst_size=1, queue_timeout_seconds=0.2
+            )
+        )
+        assert (await client.get(url)).status_code == 200
+        with pytest.raises(Exception, match="queue timeout"):
+            await client.get(url)
+
 
 class TestAsyncHttpClientHeaders:
     """Test async header management"""
@@ -410,7 +816,7 @@ class TestAsyncHttpClientResponse:
         response = await client.get(f"{test_url}/get")
 
         assert response.status_code == 200
-        assert response.ok() == True
+        assert response.ok == True
         assert response.url() is not None
         assert len(response.headers()) > 0
 
@@ -438,6 +844,46 @@ class TestAsyncHttpClientResponse:
         assert len(content) == 1024
 
 
+class TestAsyncHttpClientBodyMode:
+    """Test eager and lazy response body buffering"""
+
+    @pytest.mark.asyncio
+    async def test_lazy_body_not_read_until_accessed(self):
+        """Test that a lazy body is only fetched on first access"""
+        client = uf.AsyncHttpClient(body_mode="lazy")
+        assert client.get_body_mode() == "lazy"
+
+        response = await client.get("https://httpbin.org/bytes/1024")
+        assert response.status_code == 200
+        assert not response.body_loaded
+
+        assert len(response.content) == 1024
+        assert response.body_loaded
+
+    @pytest.mark.asyncio
+    async def test_lazy_body_read_releases_gil(self):
+        """Test other Python threads keep running while a lazy body downloads"""
+        client = uf.AsyncHttpClient(body_mode="lazy", timeout=10.0)
+        response = await client.get("https://httpbin.org/drip?duration=1&numbytes=10&delay=0")
+
+        ticks = []
+        stop = threading.Event()
+
+        def tick():
+            while not stop.is_set():
+                ticks.append(time.time())
+                time.sleep(0.01)
+
+        ticker = threading.Thread(target=tick)
+        ticker.start()
+        try:
+            assert len(response.content) == 10
+        finally:
+            stop.set()
+            ticker.join()
+        assert len(ticks) > 10
+
+
 class TestAsyncHttpClientErrorHandling:
     """Test async error handling and edge cases"""
 
@@ -458,6 +904,53 @@ class TestAsyncHttpClientErrorHandling:
             # This should timeout
             await client.get("https://httpbin.org/delay/5")
 
+    @pytest.mark.asyncio
+    async def test_deadline_already_passed(self):
+        """Test an async request past its deadline fails without being sent"""
+        client = uf.AsyncHttpClient(timeout=30.0)
+
+        start = time.time()
+        with pytest.raises(TimeoutError, match="deadline exceeded"):
+            await client.get("https://httpbin.org/delay/5", deadline=time.time() - 1.0)
+        assert time.time() - start < 0.5
+
+    @pytest.mark.asyncio
+    async def test_redirect_policy(self):
+        """Test async redirect following, opt-out and the redirect limit"""
+        client = uf.AsyncHttpClient(max_redirects=2)
+
+        response = await client.get("https://httpbin.org/redirect/1")
+        assert response.status_code == 200
+        assert response.url == "https://httpbin.org/get"
+        assert [hop.status_code for hop in response.history] == [302]
+
+        response = await client.get("https://httpbin.org/redirect/1", allow_redirects=False)
+        assert response.status_code == 302
+
+        with pytest.raises(uf.TooManyRedirects):
+            await client.get("https://httpbin.org/redirect/4")
+
+    @pytest.mark.asyncio
+    async def test_per_request_timeout(self):
+        """Test a per-call timeout overrides the async client timeout"""
+        client = uf.AsyncHttpClient(timeout=30.0)
+
+        start = time.time()
+        with pytest.raises(TimeoutError):
+            await client.get("https://httpbin.org/delay/5", timeout=1.0)
+        assert time.time() - start < 3.0
+
+    @pytest.mark.asyncio
+    async def test_deadline_shrinks_timeout(self):
+        """Test the remaining deadline budget limits an async request"""
+        client = uf.AsyncHttpClient(timeout=30.0)
+        deadline = time.time() + 1.5
+
+        start = time.time()
+        with pytest.raises(TimeoutError, match="deadline exceeded"):
+            await client.get("https://httpbin.org/delay/5", deadline=deadline)
+        assert time.time() - start < 3.0
+
     @pytest.mark.asyncio
     async def test_network_error(self):
         """Test async network error handling"""
@@ -474,12 +967,26 @@ class TestAsyncHttpClientErrorHandling:
         # Test 404
         response = await client.get("https://httpbin.org/status/404")
         assert response.status_code == 404
-        assert not response.ok()
+        assert not response.ok
 
         # Test 500
         response = await client.get("https://httpbin.org/status/500")
         assert response.status_code == 500
-        assert not response.ok()
+        assert not response.ok
+
+    @pytest.mark.asyncio
+    async def test_raise_for_status(self):
+        """Test async responses raise typed exceptions for 4xx/5xx"""
+        client = uf.AsyncHttpClient()
+
+        response = await client.get("https://httpbin.org/status/404")
+        with pytest.raises(uf.HTTPClientError) as exc_info:
+            response.raise_for_status()
+        assert exc_info.value.status_code == 404
+
+        response = await client.get("https://httpbin.org/status/500")
+        with pytest.raises(uf.HTTPServerError):
+            response.raise_for_status()
 
 
 class TestAsyncHttpClientPerformance:
@@ -569,6 +1076,112 @@ class TestAsyncHttpClientMiddleware:
         response = await client.get("https://httpbin.org/get")
         assert response.status_code == 200
 
+    @pytest.mark.asyncio
+    async def test_interceptor_middleware(self):
+        """Test async interceptors see requests, responses and errors"""
+        client = uf.AsyncHttpClient(timeout=10.0)
+        seen = []
+
+        def on_request(method, url, headers):
+            headers["X-Intercepted"] = "yes"
+
+        def on_error(method, url, error):
+            seen.append((method, url, type(error)))
+
+        await client.add_middleware(
+            uf.InterceptorMiddleware(
+                "async_interceptor",
+                request_interceptor=on_request,
+                response_interceptor=lambda response: seen.append(response.status_code),
+                error_interceptor=on_error,
+            )
+        )
+
+        response = await client.get("https://httpbin.org/headers")
+        assert response.json()["headers"]["X-Intercepted"] == "yes"
+        assert seen == [200]
+
+        with pytest.raises(Exception):
+            await client.get("http://127.0.0.1:1/unreachable")
+        assert seen[1][:2] == ("GET", "http://127.0.0.1:1/unreachable")
+
+        assert await client.remove_middleware("async_interceptor") is True
+        count = len(seen)
+        await client.get("https://httpbin.org/get")
+        assert len(seen) == count
+
+    @pytest.mark.asyncio
+    async def test_transform_middleware(self, local_server):
+        """Test async transforms rewrite eager and lazy response bodies"""
+
+        class Plain(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                body = b"hello"
+                self.send_response(200)
+                self.send_header("Content-Type", "text/plain")
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        server = local_server(Plain)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        for body_mode in ("eager", "lazy"):
+            client = uf.AsyncHttpClient(body_mode=body_mode)
+            await client.add_middleware(
+                uf.TransformMiddleware("upper", lambda body, content_type: body.upper() + b"!")
+            )
+            response = await client.get(url)
+            assert response.text() == "HELLO!"
+            assert response.headers["content-length"] == "6"
+
+    @pytest.mark.asyncio
+    async def test_dedup_middleware_shares_in_flight_requests(self, local_server):
+        """Test concurrent identical GETs share one request, within the TTL"""
+        hits = []
+
+        class Slow(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                hits.append(self.headers.get("Accept"))
+                time.sleep(0.3)
+                body = str(len(hits)).encode()
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        server = local_server(Slow, threaded=True)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        dedup = uf.DedupMiddleware("dedup")
+        assert (dedup.ttl, dedup.methods) == (0.0, ["GET", "HEAD"])
+        client = uf.AsyncHttpClient()
+        await client.add_middleware(dedup)
+
+        responses = await asyncio.gather(*(client.get(url) for _ in range(5)))
+        assert [r.text() for r in responses] == ["1"] * 5
+        assert len(hits) == 1 and dedup.shared == 4
+
+        # Without a TTL a completed response is not reused, and other Accept values
+        # make other requests
+        accepts = await asyncio.gather(
+            client.get(url), client.get(url, headers={"Accept": "text/plain"})
+        )
+        assert sorted(r.text() for r in accepts) == ["2", "3"]
+
+        cached = uf.DedupMiddleware("dedup", ttl=60, max_entries=1)
+        await client.add_middleware(cached)
+        assert (await client.get(url)).text() == "4"
+        assert (await client.get(url)).text() == "4"
+        # The single slot is taken, so another key bypasses deduplication
+        assert (await client.get(f"{url}?page=2")).text() == "5"
+        assert len(cached) == 1
+        cached.clear()
+        assert (await client.get(url)).text() == "6"
+
+        with pytest.raises(ValueError, match="ttl"):
+            uf.DedupMiddleware("dedup", ttl=-1)
+        with pytest.raises(ValueError, match="AsyncHttpClient"):
+            uf.HttpClient().add_middleware(uf.DedupMiddleware("dedup"))
+
 
 class TestAsyncHttpClientConcurrency:
     """Test async concurrency features"""
@@ -591,6 +1204,91 @@ class TestAsyncHttpClientConcurrency:
         for response in responses:
             assert response.status_code == 200
 
+    @pytest.mark.asyncio
+    async def test_cancelled_request_releases_connection(self, local_server):
+        """Test cancelling an in-flight request frees its pool slot without parking it"""
+        release = threading.Event()
+
+        class Slow(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                if self.path == "/slow":
+                    release.wait(5)
+                self.send_response(200)
+                self.send_header("Content-Length", "2")
+                self.end_headers()
+                self.wfile.write(b"ok")
+
+        server = local_server(Slow, threaded=True)
+        base = f"http://127.0.0.1:{server.server_port}"
+        try:
+            client = uf.AsyncHttpClient(retry_config=uf.RetryConfig(max_retries=0))
+            task = asyncio.ensure_future(client.get(f"{base}/slow"))
+            await asyncio.sleep(0.3)
+            assert client.get_pool_stats()["active_connections"] == 1
+
+            task.cancel()
+            with pytest.raises(asyncio.CancelledError):
+                await task
+            await asyncio.sleep(0.1)
+
+            stats = client.get_pool_stats()
+            assert stats["active_connections"] == 0
+            # The aborted connection is closed, not offered for reuse
+            assert stats["idle_connections"] == 0
+
+            response = await client.get(f"{base}/fast")
+            assert response.status_code == 200
+            assert client.get_pool_stats()["idle_connections"] == 1
+        finally:
+            release.set()
+
+    @pytest.mark.asyncio
+    async def test_max_connections_per_host(self, local_server):
+        """Test requests beyond max_connections_per_host wait for a slot"""
+        lock = threading.Lock()
+        in_flight = [0]
+        peak = [0]
+
+        class Slow(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                with lock:
+                    in_flight[0] += 1
+                    peak[0] = max(peak[0], in_flight[0])
+                time.sleep(0.2)
+                with lock:
+                    in_flight[0] -= 1
+                self.send_response(200)
+                self.send_header("Content-Length", "2")
+                self.end_headers()
+                self.wfile.write(b"ok")
+
+        server = local_server(Slow, threaded=True)
+        url = f"http://127.0.0.1:{server.server_port}/"
+        pool_config = uf.PoolConfig(max_connections_per_host=2)
+        client = uf.AsyncHttpClient(
+            pool_config=pool_config, retry_config=uf.RetryConfig(max_retries=0)
+        )
+        tasks = [asyncio.ensure_future(client.get(url)) for _ in range(6)]
+        await asyncio.sleep(0.1)
+        assert client.get_pool_stats()["hosts"]["127.0.0.1"]["in_use"] == 2
+
+        responses = await asyncio.gather(*tasks)
+        assert all(response.status_code == 200 for response in responses)
+        assert peak[0] == 2
+
+        # A slot that does not free up within pool_timeout fails the request
+        client.set_pool_config(
+            uf.PoolConfig(max_connections_per_host=1, pool_timeout=0.05)
+        )
+        slow = asyncio.ensure_future(client.get(url))
+        await asyncio.sleep(0.05)
+        with pytest.raises(uf.TimeoutError):
+            await client.get(url)
+        await slow
+
+        with pytest.raises(ValueError):
+            uf.PoolConfig(max_connections_per_host=0)
+
     @pytest.mark.asyncio
     async def test_async_with_context_manager(self):
         """Test async client with context manager"""
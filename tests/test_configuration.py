@@ -268,6 +268,37 @@ class TestSSLConfig:
         assert ssl_config is not None
 
 
+class TestProxyConfig:
+    """Test ProxyConfig class"""
+
+    def test_no_proxy_exact_host(self):
+        """Test exact no_proxy entries only match that host"""
+        proxy = uf.ProxyConfig("http://proxy:8080", no_proxy=["internal.local"])
+
+        assert proxy.should_bypass("internal.local")
+        assert proxy.should_bypass("INTERNAL.local")
+        assert not proxy.should_bypass("api.internal.local")
+        assert not proxy.should_bypass("example.com")
+
+    def test_no_proxy_suffix_matches_subdomains(self):
+        """Test .suffix entries match the domain and its subdomains"""
+        proxy = uf.ProxyConfig("http://proxy:8080")
+        proxy.set_no_proxy([".example.com"])
+
+        assert proxy.should_bypass("example.com")
+        assert proxy.should_bypass("api.example.com")
+        assert proxy.should_bypass("a.b.example.com")
+        assert not proxy.should_bypass("notexample.com")
+        assert not proxy.should_bypass("example.com.evil.org")
+
+    def test_no_proxy_wildcard(self):
+        """Test * bypasses the proxy for every host"""
+        proxy = uf.ProxyConfig("http://proxy:8080", no_proxy=["*"])
+
+        assert proxy.should_bypass("anything.example.org")
+        assert not uf.ProxyConfig("http://proxy:8080").should_bypass("example.com")
+
+
 class TestCompressionConfig:
     """Test CompressionConfig class"""
 
@@ -301,6 +332,37 @@ class TestCompressionConfig:
 
         assert compression_config is not None
 
+    def test_compression_config_zstd(self):
+        """Test zstd is a supported algorithm and advertised in Accept-Encoding"""
+        compression_config = uf.CompressionConfig()
+        assert compression_config.supports_algorithm("zstd")
+        assert "zstd" in compression_config.get_accept_encoding_header()
+
+        zstd_only = uf.CompressionConfig(
+            enable_request_compression=True,
+            compression_algorithms=["zstd"],
+            compression_level=3,
+        )
+        assert zstd_only.get_accept_encoding_header() == "zstd"
+        body = b'{"message": "hello"}' * 100
+        compressed = bytes(zstd_only.compress_request_body(body, "zstd"))
+        assert compressed[:4] == b"\x28\xb5\x2f\xfd"  # zstd frame magic
+        assert len(compressed) < len(body)
+
+    def test_compression_level_range(self):
+        """Test compression_level is validated against each listed algorithm"""
+        uf.CompressionConfig(compression_algorithms=["brotli"], compression_level=11)
+        with pytest.raises(ValueError):
+            uf.CompressionConfig(compression_algorithms=["gzip"], compression_level=10)
+        with pytest.raises(ValueError):
+            uf.CompressionConfig(compression_algorithms=["brotli"], compression_level=12)
+        with pytest.raises(ValueError):
+            uf.CompressionConfig(compression_level=0)
+
+        # An unset level falls back to each algorithm's default
+        config = uf.CompressionConfig(enable_request_compression=True)
+        assert len(config.compress_request_body(b"x" * 2048, "gzip")) > 0
+
     def test_compression_config_disabled(self):
         """Test disabled compression configuration"""
         compression_config = uf.CompressionConfig.disabled()
@@ -398,6 +460,18 @@ class TestProtocolConfig:
         assert protocol_config.enable_http3 == True
         assert protocol_config.http3_settings is not None
 
+    def test_http3_settings_congestion_control(self):
+        """Test HTTP/3 congestion control other than cubic is rejected"""
+        settings = uf.Http3Settings(congestion_control="cubic", enable_0rtt=True)
+        assert settings.congestion_control == "cubic"
+        assert settings.enable_0rtt == True
+
+        with pytest.raises(ValueError, match="congestion_control 'bbr' is not supported"):
+            uf.Http3Settings(congestion_control="bbr")
+        with pytest.raises(ValueError, match="not supported"):
+            settings.congestion_control = "reno"
+        assert settings.congestion_control == "cubic"
+
     def test_protocol_config_methods(self):
         """Test protocol configuration helper methods"""
         protocol_config = uf.ProtocolConfig(enable_http2=True, enable_http3=False)
@@ -515,6 +589,20 @@ class TestRateLimitConfig:
         )
         assert high_rate_config is not None
 
+    def test_rate_limit_endpoint_rules(self):
+        """Test per-endpoint rate limit rules"""
+        search_limit = uf.RateLimitConfig(requests_per_second=2, burst_size=2)
+        config = uf.RateLimitConfig(
+            requests_per_second=50,
+            endpoint_rules=[("/search*", search_limit)],
+        )
+        assert len(config.endpoint_rules) == 1
+        pattern, rule = config.endpoint_rules[0]
+        assert pattern == "/search*"
+        assert rule.requests_per_second == 2
+
+        assert uf.RateLimitConfig().endpoint_rules == []
+
 
 class TestOAuth2Token:
     """Test OAuth2Token class"""
@@ -13,6 +13,10 @@ Tests performance-related functionality including:
 """
 
 import asyncio
+import csv
+import http.server
+import json
+import socket
 import threading
 import time
 from typing import Any, Dict, List
@@ -66,6 +70,38 @@ class TestPerformanceStatistics:
         # Stats should contain performance metrics
         assert len(stats) >= 0  # Should at least be a valid dict
 
+    def test_error_rate(self, local_server):
+        """Test errors and retryable statuses are counted in the reported error rate"""
+        statuses = [503, 503, 200]
+
+        class Flaky(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(statuses.pop(0))
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        # A port nothing listens on, for a connection error
+        with socket.socket() as sock:
+            sock.bind(("127.0.0.1", 0))
+            closed_port = sock.getsockname()[1]
+
+        server = local_server(Flaky)
+        client = uf.HttpClient(
+            retry_config=uf.RetryConfig(max_retries=0, retry_on_status_codes=[503])
+        )
+        for _ in range(3):
+            client.get(f"http://127.0.0.1:{server.server_port}/")
+        with pytest.raises(Exception):
+            client.get(f"http://127.0.0.1:{closed_port}/")
+
+        stats = client.get_stats()
+        assert stats["request_count"] == 4
+        assert stats["error_count"] == 3
+        assert stats["error_rate_percent"] == pytest.approx(75.0)
+
+        client.reset_stats()
+        assert client.get_stats()["error_count"] == 0
+
     def test_protocol_stats(self, client):
         """Test protocol-specific statistics"""
         stats = client.get_protocol_stats("https://httpbin.org")
@@ -105,6 +141,106 @@ class TestBenchmarking:
         benchmark = uf.Benchmark()
         assert benchmark is not None
 
+    def test_benchmark_percentiles(self):
+        """Test percentile and summary statistics over recorded latencies"""
+        benchmark = uf.Benchmark(max_samples=1000)
+        for i in range(1, 101):
+            benchmark.add_result("get", i / 1000)
+
+        percentiles = benchmark.percentiles([50, 90, 99], name="get")
+        assert percentiles[50] == pytest.approx(0.0505)
+        assert percentiles[90] == pytest.approx(0.0901)
+        assert benchmark.p95("get") == pytest.approx(0.09505)
+        assert benchmark.p99() == pytest.approx(percentiles[99])
+
+        stats = benchmark.calculate_statistics("get")
+        assert stats["min"] == pytest.approx(0.001)
+        assert stats["max"] == pytest.approx(0.1)
+        assert stats["mean"] == pytest.approx(0.0505)
+        assert stats["std_dev"] > 0
+
+        with pytest.raises(ValueError):
+            benchmark.percentiles([101])
+        with pytest.raises(KeyError):
+            benchmark.p95("missing")
+
+    def test_benchmark_samples_are_bounded(self):
+        """Test large runs keep a bounded sample while aggregates stay exact"""
+        benchmark = uf.Benchmark(max_samples=50)
+        for i in range(10_000):
+            benchmark.add_result("bulk", float(i))
+
+        assert len(benchmark.get_results()["bulk"]) == 50
+        stats = benchmark.calculate_statistics()
+        assert stats["count"] == 10_000
+        assert stats["max"] == 9999.0
+
+    def test_benchmark_rps_concurrency(self, local_server):
+        """Test concurrent load generation overlaps requests and counts errors"""
+
+        class Slow(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                time.sleep(0.05)
+                self.send_response(200)
+                self.send_header("Content-Length", "2")
+                self.end_headers()
+                self.wfile.write(b"ok")
+
+        server = local_server(Slow, threaded=True)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        benchmark = uf.Benchmark()
+        serial = benchmark.benchmark_rps(url, 0.5, concurrency=1)
+        parallel = benchmark.benchmark_rps(url, 0.5, concurrency=8)
+
+        assert serial["errors"] == 0 and parallel["errors"] == 0
+        assert parallel["concurrency"] == 8
+        assert parallel["requests_per_second"] > 3 * serial["requests_per_second"]
+        assert parallel["p50"] >= 0.05
+        assert benchmark.p99(url) >= 0.05
+
+        with socket.socket() as probe:
+            probe.bind(("127.0.0.1", 0))
+            closed_port = probe.getsockname()[1]
+        failed = benchmark.benchmark_rps(f"http://127.0.0.1:{closed_port}/", 0.2, concurrency=4)
+        assert failed["total_requests"] == 0
+        assert failed["errors"] > 0
+        assert failed["error_rate"] == 1.0
+
+        with pytest.raises(ValueError):
+            benchmark.benchmark_rps(url, 0.1, concurrency=0)
+
+    def test_benchmark_export(self, tmp_path):
+        """Test JSON and CSV exports carry run configuration and latency percentiles"""
+        benchmark = uf.Benchmark()
+        benchmark.run_test("noop", 20, lambda: None)
+        for value in (0.1, 0.2, 0.3):
+            benchmark.add_result("manual, imported", value)
+
+        report = json.loads(benchmark.to_json())
+        assert report["schema_version"] == 1
+        assert [b["name"] for b in report["benchmarks"]] == ["manual, imported", "noop"]
+        manual, noop = report["benchmarks"]
+        assert noop["iterations"] == 20
+        assert noop["concurrency"] == 1
+        assert noop["errors"] == 0
+        assert set(noop["latency"]) == {
+            "count", "mean", "min", "max", "std_dev", "p50", "p90", "p95", "p99"
+        }
+        assert manual["latency"]["p50"] == pytest.approx(0.2)
+        assert manual["requests_per_second"] == pytest.approx(5.0)
+        assert benchmark.to_json() == benchmark.to_json()
+
+        path = tmp_path / "bench.csv"
+        benchmark.to_csv(str(path))
+        with open(path, newline="") as f:
+            rows = list(csv.DictReader(f))
+        assert [row["name"] for row in rows] == ["manual, imported", "noop"]
+        assert float(rows[0]["p50"]) == pytest.approx(0.2)
+        assert rows[1]["bytes_transferred"] == "0"
+
+        with pytest.raises(IOError):
+            benchmark.to_csv(str(tmp_path / "missing" / "bench.csv"))
+
     def test_benchmark_simple_request(self):
         """Test benchmarking a simple request"""
         benchmark = uf.Benchmark()
@@ -230,6 +366,73 @@ class TestMemoryProfiling:
 class TestConnectionPooling:
     """Test connection pooling performance"""
 
+    def test_pool_stats(self, local_server):
+        """Test get_pool_stats reports acquisitions, idle reuse and per-host counts"""
+
+        class Hello(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "2")
+                self.end_headers()
+                self.wfile.write(b"hi")
+
+        server = local_server(Hello)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        client = uf.HttpClient()
+        assert client.get_pool_stats()["total_acquired"] == 0
+        for _ in range(3):
+            client.get(url)
+
+        async_client = uf.AsyncHttpClient()
+
+        async def fetch():
+            await async_client.get(url)
+            return async_client.get_pool_stats()
+
+        async_stats = asyncio.run(fetch())
+
+        stats = client.get_pool_stats()
+        assert stats["active_connections"] == 0
+        assert stats["total_acquired"] == 3
+        assert stats["connections_created"] == 1
+        assert stats["connections_reused"] == 2
+        assert stats["idle_connections"] == 1
+        assert stats["hosts"]["127.0.0.1"] == {
+            "active_connections": 0,
+            "idle_connections": 1,
+            "total_acquired": 3,
+            "failed_connections": 0,
+        }
+        assert async_stats["total_acquired"] == 1
+        assert async_stats["hosts"]["127.0.0.1"]["idle_connections"] == 1
+
+    def test_dns_cache(self, local_server):
+        """Test requests resolve through the DNS cache and clear_dns_cache resets it"""
+
+        class Hello(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "2")
+                self.end_headers()
+                self.wfile.write(b"hi")
+
+        server = local_server(Hello)
+        url = f"http://localhost:{server.server_address[1]}/"
+        client = uf.HttpClient(dns_cache_ttl=60.0, dns_cache_max_entries=16)
+        assert client.get(url).status_code == 200
+        assert client.get(url).status_code == 200
+        client.clear_dns_cache()
+        assert client.get(url).status_code == 200
+
+        config = client.config_to_dict()
+        assert config["dns_cache_ttl"] == 60.0
+        assert config["dns_cache_max_entries"] == 16
+
+        # Disabled by default, where clearing is a no-op
+        uf.AsyncHttpClient().clear_dns_cache()
+        with pytest.raises(ValueError):
+            uf.HttpClient(dns_cache_ttl=-1.0)
+
     def test_pool_configuration_performance(self):
         """Test different pool configurations for performance"""
         # Small pool
@@ -111,6 +111,58 @@ class TestSessionSync:
         assert data["headers"]["User-Agent"] == "UltraFast-Session-Test"
         assert data["headers"]["X-Session-Test"] == "session-header"
 
+    def test_session_params_merge_with_request_params(self):
+        """Per-request params override session params with the same key"""
+        session = uf.Session(
+            base_url="https://httpbin.org",
+            params={"api_version": "1", "shared": "session"},
+        )
+        assert session.params == {"api_version": "1", "shared": "session"}
+
+        response = session.get("/get", params={"shared": "request", "extra": "x"})
+        assert response.status_code == 200
+        args = response.json()["args"]
+        assert args == {"api_version": "1", "shared": "request", "extra": "x"}
+
+        # Session params apply to methods without their own params too
+        response = session.post("/post", json={})
+        assert response.json()["args"] == {"api_version": "1", "shared": "session"}
+
+    def test_session_params_setter(self):
+        """Assigning Session.params replaces the defaults for later requests"""
+        session = uf.Session(base_url="https://httpbin.org", params={"old": "1"})
+        session.params = {"new": "2"}
+        assert session.params == {"new": "2"}
+
+        response = session.get("/get")
+        assert response.json()["args"] == {"new": "2"}
+
+    def test_session_headers_merge_with_request_headers(self, session):
+        """Per-request headers override session headers, ignoring case"""
+        session.set_header("X-Shared", "session")
+        response = session.get(
+            "/headers", headers={"x-shared": "request", "user-agent": "Override"}
+        )
+        assert response.status_code == 200
+        headers = response.json()["headers"]
+        assert headers["X-Shared"] == "request"
+        assert headers["User-Agent"] == "Override"
+
+        # The override only applies to that request
+        response = session.get("/headers")
+        headers = response.json()["headers"]
+        assert headers["X-Shared"] == "session"
+        assert headers["User-Agent"] == "UltraFast-Session-Test"
+
+    def test_session_headers_setter(self, session):
+        """Assigning Session.headers replaces the session headers"""
+        session.headers = {"X-Replaced": "yes"}
+        assert session.headers == {"X-Replaced": "yes"}
+
+        headers = session.get("/headers").json()["headers"]
+        assert headers["X-Replaced"] == "yes"
+        assert headers.get("User-Agent") != "UltraFast-Session-Test"
+
     def test_post_request_json(self, session):
         """Test POST request with JSON data"""
         payload = {"session_data": "test", "value": 456}
@@ -241,6 +293,44 @@ class TestSessionSync:
         session.clear_data()
         assert session.get_data("user_id") is None
 
+    def test_cookie_persistence(self, session):
+        """Test cookies set by responses are sent on later requests"""
+        session.get("/cookies/set", params={"session_id": "abc123"})
+        assert session.cookies == {"session_id": "abc123"}
+        assert session.get_cookie("session_id") == "abc123"
+
+        response = session.get("/cookies")
+        assert response.json()["cookies"] == {"session_id": "abc123"}
+
+        session.clear_cookies()
+        assert session.cookies == {}
+        assert session.get("/cookies").json()["cookies"] == {}
+
+        # Cookies set by hand go to the base URL's host
+        session.set_cookie("manual", "1")
+        assert session.get("/cookies").json()["cookies"] == {"manual": "1"}
+
+    def test_save_and_load_cookies(self, session):
+        """Test cookies survive a round trip through a file"""
+        session.get("/cookies/set", params={"token": "persisted"})
+
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "cookies.json")
+            session.save_cookies(path)
+
+            restored = uf.Session(base_url="https://httpbin.org")
+            assert restored.load_cookies(path) == 1
+            assert restored.cookies == {"token": "persisted"}
+            assert restored.get("/cookies").json()["cookies"] == {"token": "persisted"}
+
+            with open(path, "w") as f:
+                f.write("{not json")
+            with pytest.raises(ValueError):
+                restored.load_cookies(path)
+
+            with pytest.raises(IOError):
+                restored.load_cookies(os.path.join(tmp, "missing.json"))
+
     def test_context_manager(self, session):
         """Test session as context manager"""
         with session as s:
@@ -329,6 +419,43 @@ class TestAsyncSession:
         assert data["headers"]["User-Agent"] == "UltraFast-Async-Session-Test"
         assert data["headers"]["X-Async-Session-Test"] == "async-session-header"
 
+    @pytest.mark.asyncio
+    async def test_async_session_params_merge_with_request_params(self):
+        """Per-request params override session params with the same key"""
+        session = uf.AsyncSession(
+            base_url="https://httpbin.org",
+            params={"api_version": "1", "shared": "session"},
+        )
+        assert session.params == {"api_version": "1", "shared": "session"}
+
+        response = await session.get(
+            "/get", params={"shared": "request", "extra": "x"}
+        )
+        assert response.status_code == 200
+        args = response.json()["args"]
+        assert args == {"api_version": "1", "shared": "request", "extra": "x"}
+
+        session.params = {"new": "2"}
+        response = await session.get("/get")
+        assert response.json()["args"] == {"new": "2"}
+
+    @pytest.mark.asyncio
+    async def test_async_session_headers_merge_with_request_headers(self, session):
+        """Per-request headers override session headers, ignoring case"""
+        session.set_header("X-Shared", "session")
+        response = await session.get(
+            "/headers", headers={"x-shared": "request", "user-agent": "Override"}
+        )
+        assert response.status_code == 200
+        headers = response.json()["headers"]
+        assert headers["X-Shared"] == "request"
+        assert headers["User-Agent"] == "Override"
+
+        response = await session.get("/headers")
+        headers = response.json()["headers"]
+        assert headers["X-Shared"] == "session"
+        assert headers["User-Agent"] == "UltraFast-Async-Session-Test"
+
     @pytest.mark.asyncio
     async def test_async_post_request_json(self, session):
         """Test async POST request with JSON data"""
@@ -417,6 +544,31 @@ class TestAsyncSession:
         response = await session.options("/get")
         assert response.status_code == 200
 
+    def test_async_save_and_load_cookies(self, session):
+        """Test async session cookies can be saved and reloaded"""
+        session.set_cookie("token", "async-persisted")
+
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "cookies.json")
+            session.save_cookies(path)
+
+            restored = uf.AsyncSession(base_url="https://httpbin.org")
+            assert restored.load_cookies(path) == 1
+            assert restored.get_cookie("token") == "async-persisted"
+
+    @pytest.mark.asyncio
+    async def test_async_cookie_persistence(self, session):
+        """Test async session cookies persist across requests"""
+        await session.get("/cookies/set", params={"session_id": "async123"})
+        assert session.cookies == {"session_id": "async123"}
+
+        response = await session.get("/cookies")
+        assert response.json()["cookies"] == {"session_id": "async123"}
+
+        session.clear_cookies()
+        response = await session.get("/cookies")
+        assert response.json()["cookies"] == {}
+
     def test_async_session_header_management(self, session):
         """Test async session header management"""
         # Set session header
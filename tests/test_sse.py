@@ -12,7 +12,9 @@ Tests all features and functionality of both SSEClient and AsyncSSEClient includ
 """
 
 import asyncio
+import http.server
 import json
+import threading
 import time
 from typing import List, Optional
 
@@ -201,6 +203,86 @@ class TestSSEClientSync:
         except Exception as e:
             pytest.skip(f"SSE listening failed: {e}")
 
+    def test_reconnect_sends_last_event_id(self, local_server):
+        """Test reconnecting after a dropped stream resumes from Last-Event-ID"""
+
+        class StreamHandler(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                last_id = self.headers.get("Last-Event-ID")
+                if last_id is None:
+                    body = "retry: 50\nid: 1\ndata: first\n\n"
+                else:
+                    body = f"id: 2\ndata: resumed after {last_id}\n\n"
+                self.send_response(200)
+                self.send_header("Content-Type", "text/event-stream")
+                self.end_headers()
+                self.wfile.write(body.encode())
+
+        server = local_server(StreamHandler)
+        client = uf.SSEClient(max_reconnect_attempts=2)
+        client.connect(f"http://127.0.0.1:{server.server_port}/events")
+        events = client.listen()
+
+        first = next(events)
+        assert first.data == "first"
+        assert first.retry == 50
+
+        resumed = next(events)
+        assert resumed.data == "resumed after 1"
+        assert events.last_event_id == "2"
+        client.close()
+
+    def test_event_type_filter(self, local_server):
+        """Test only events named in event_types are yielded"""
+
+        class StreamHandler(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Type", "text/event-stream")
+                self.end_headers()
+                self.wfile.write(
+                    b"event: price\ndata: 101\n\n"
+                    b"event: news\ndata: headline\n\n"
+                    b"data: unnamed\n\n"
+                    b"event: price\ndata: 102\n\n"
+                )
+
+        server = local_server(StreamHandler)
+        client = uf.SSEClient(max_reconnect_attempts=0, event_types=["price"])
+        assert client.event_types == ["price"]
+        client.connect(f"http://127.0.0.1:{server.server_port}/events")
+
+        assert [event.data for event in client.listen()] == ["101", "102"]
+
+    def test_authenticated_stream(self, local_server):
+        """Test auth_config and headers are sent when subscribing"""
+
+        class ProtectedStream(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                if self.headers.get("Authorization") != "Bearer secret":
+                    self.send_response(401)
+                    self.send_header("Content-Length", "0")
+                    self.end_headers()
+                    return
+                self.send_response(200)
+                self.send_header("Content-Type", "text/event-stream")
+                self.end_headers()
+                tenant = self.headers.get("X-Tenant")
+                self.wfile.write(f"data: hello {tenant}\n\n".encode())
+
+        server = local_server(ProtectedStream)
+        url = f"http://127.0.0.1:{server.server_port}/events"
+        with pytest.raises(ConnectionError, match="401"):
+            uf.SSEClient(max_reconnect_attempts=0).connect(url)
+
+        client = uf.SSEClient(
+            max_reconnect_attempts=0,
+            headers={"X-Tenant": "acme"},
+            auth_config=uf.AuthConfig.bearer("secret"),
+        )
+        client.connect(url)
+        assert [event.data for event in client.listen()] == ["hello acme"]
+
 
 class TestAsyncSSEClient:
     """Test asynchronous AsyncSSEClient"""
@@ -11,9 +11,25 @@ Tests all features and functionality of the HttpClient including:
 - Performance features
 """
 
+import base64
+import email.parser
+import email.utils
+import gzip
+import hashlib
+import hmac
+import http.server
 import json
 import os
+import shutil
+import socket
+import socketserver
+import ssl
+import struct
+import subprocess
 import tempfile
+import threading
+import time
+import zlib
 from typing import Any, Dict
 
 import pytest
@@ -50,6 +66,28 @@ class TestHttpClientBasicRequests:
         assert data["args"]["key1"] == "value1"
         assert data["args"]["key2"] == "value2"
 
+    def test_default_params_merged_with_request_params(self, client, test_url):
+        """Test that default query params are sent alongside per-request params"""
+        client.set_default_params({"version": "2", "key1": "default"})
+        response = client.get(f"{test_url}/get", params={"key1": "value1"})
+        assert response.status_code == 200
+        data = response.json()
+        assert data["args"]["version"] == "2"
+        # Per-request params win over defaults
+        assert data["args"]["key1"] == "value1"
+
+        client.clear_default_params()
+        assert client.get_default_params() == {}
+
+    def test_default_params_with_api_key_query_auth(self, test_url):
+        """Test that a default param matching the API key is not duplicated"""
+        client = uf.HttpClient(auth_config=uf.AuthConfig.api_key_query("secret", "api_key"))
+        client.set_default_params({"api_key": "other", "version": "2"})
+        response = client.get(f"{test_url}/get")
+        data = response.json()
+        assert data["args"]["api_key"] == "secret"
+        assert data["args"]["version"] == "2"
+
     def test_get_with_headers(self, client, test_url):
         """Test GET request with custom headers"""
         headers = {"X-Custom-Header": "custom-value", "User-Agent": "UltraFast-Test"}
@@ -134,6 +172,507 @@ class TestHttpClientBasicRequests:
         response = client.options(f"{test_url}/get")
         assert response.status_code == 200
 
+    def test_generic_request(self, client, test_url, local_server):
+        """Test request() with standard and custom methods"""
+        response = client.request("put", f"{test_url}/put", json={"key": "value"})
+        assert response.status_code == 200
+        assert response.json()["json"] == {"key": "value"}
+
+        class WebDav(http.server.BaseHTTPRequestHandler):
+            def do_PROPFIND(self):
+                self.send_response(207)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(WebDav)
+        response = client.request("PROPFIND", f"http://127.0.0.1:{server.server_address[1]}/")
+        assert response.status_code == 207
+
+        with pytest.raises(ValueError, match="Invalid HTTP method"):
+            client.request("BAD METHOD", f"{test_url}/get")
+
+    def test_body_content_type_does_not_leak_to_later_requests(self, client, local_server):
+        """Test a POST's Content-Type is not carried over to a following GET"""
+        seen = []
+
+        class Record(http.server.BaseHTTPRequestHandler):
+            def handle_one(self):
+                seen.append((self.command, self.headers.get("Content-Type")))
+                self.rfile.read(int(self.headers.get("Content-Length") or 0))
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+            do_GET = do_POST = handle_one
+
+        server = local_server(Record)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        client.post(url, json={"a": 1})
+        client.post(url, data={"b": "2"})
+        client.get(url)
+
+        assert seen == [
+            ("POST", "application/json"),
+            ("POST", "application/x-www-form-urlencoded"),
+            ("GET", None),
+        ]
+        assert "Content-Type" not in client.get_headers()
+
+    def test_upload_files_streams_from_disk(self, client, local_server):
+        """Test upload_files sends file parts with filename and guessed content type"""
+        received = {}
+
+        class Upload(http.server.BaseHTTPRequestHandler):
+            def do_POST(self):
+                received["content_type"] = self.headers["Content-Type"]
+                received["body"] = self.rfile.read(int(self.headers["Content-Length"]))
+                self.send_response(201)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Upload)
+        payload = os.urandom(256 * 1024)
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = os.path.join(tmpdir, "artifact.png")
+            with open(path, "wb") as f:
+                f.write(payload)
+
+            response = client.upload_files(
+                f"http://127.0.0.1:{server.server_address[1]}/upload",
+                {"artifact": path},
+                data={"version": "1.2.3"},
+            )
+            assert response.status_code == 201
+
+            with pytest.raises(IOError):
+                client.upload_files(
+                    f"http://127.0.0.1:{server.server_address[1]}/upload",
+                    {"artifact": os.path.join(tmpdir, "missing.bin")},
+                )
+
+        assert received["content_type"].startswith("multipart/form-data; boundary=")
+        body = received["body"]
+        assert b'name="artifact"; filename="artifact.png"' in body
+        assert b"Content-Type: image/png" in body
+        assert b'name="version"' in body and b"1.2.3" in body
+        assert payload in body
+
+    def test_upload_files_runs_request_pipeline(self, local_server, tmp_path):
+        """Test upload_files applies default params, middleware and stats like other requests"""
+        seen = []
+
+        class Upload(http.server.BaseHTTPRequestHandler):
+            def do_POST(self):
+                seen.append((self.path, self.headers.get("X-Middleware")))
+                self.rfile.read(int(self.headers["Content-Length"]))
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Upload)
+        path = tmp_path / "report.txt"
+        path.write_bytes(b"report")
+        client = uf.HttpClient()
+        client.set_default_params({"tenant": "a"})
+        client.add_middleware(uf.HeadersMiddleware("upload", {"X-Middleware": "on"}))
+
+        response = client.upload_files(
+            f"http://127.0.0.1:{server.server_address[1]}/upload", {"report": str(path)}
+        )
+        assert response.status_code == 200
+        assert seen == [("/upload?tenant=a", "on")]
+        assert client.get_stats()["request_count"] == 1
+
+    def test_upload_stream_uses_chunked_encoding(self, client, local_server):
+        """Test upload_stream sends a generator's chunks with chunked transfer encoding"""
+        received = {}
+
+        class Upload(http.server.BaseHTTPRequestHandler):
+            def do_POST(self):
+                received["transfer_encoding"] = self.headers["Transfer-Encoding"]
+                received["content_length"] = self.headers["Content-Length"]
+                body = b""
+                try:
+                    while True:
+                        size = int(self.rfile.readline().strip(), 16)
+                        if size == 0:
+                            break
+                        body += self.rfile.read(size)
+                        self.rfile.readline()
+                except ValueError:
+                    # The client aborted mid-stream
+                    return
+                received["body"] = body
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+            do_PUT = do_POST
+
+        def chunks():
+            for i in range(5):
+                yield f"part-{i};".encode()
+            yield b""
+            yield bytearray(b"done")
+
+        def failing():
+            yield b"first"
+            raise ValueError("source went away")
+
+        server = local_server(Upload)
+        url = f"http://127.0.0.1:{server.server_address[1]}/upload"
+        response = client.upload_stream(url, chunks(), method="PUT")
+        assert response.status_code == 200
+        assert received["transfer_encoding"] == "chunked"
+        assert received["content_length"] is None
+        assert received["body"] == b"part-0;part-1;part-2;part-3;part-4;done"
+
+        with pytest.raises(ValueError, match="source went away"):
+            client.upload_stream(url, failing())
+        with pytest.raises(TypeError, match="must yield bytes"):
+            client.upload_stream(url, iter(["text"]))
+        with pytest.raises(TypeError):
+            client.upload_stream(url, 42)
+
+    def test_expect_continue(self, client, local_server):
+        """Test expect_continue sends the header and skips the body when rejected early"""
+        received = {}
+
+        class Upload(http.server.BaseHTTPRequestHandler):
+            protocol_version = "HTTP/1.1"
+
+            def handle_expect_100(self):
+                received["expect"] = self.headers["Expect"]
+                if self.path == "/reject":
+                    self.send_response(413)
+                    self.send_header("Content-Length", "0")
+                    self.end_headers()
+                    self.close_connection = True
+                    return False
+                return super().handle_expect_100()
+
+            def do_PUT(self):
+                received["body"] = self.rfile.read(int(self.headers["Content-Length"]))
+                self.send_response(201)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Upload, threaded=True)
+        base = f"http://127.0.0.1:{server.server_address[1]}"
+        payload = {"blob": "x" * 100_000}
+        start = time.time()
+        response = client.put(f"{base}/reject", json=payload, expect_continue=True)
+        assert response.status_code == 413
+        assert time.time() - start < 0.9
+        assert received == {"expect": "100-continue"}
+
+        response = client.put(f"{base}/accept", json=payload, expect_continue=True)
+        assert response.status_code == 201
+        assert json.loads(received["body"]) == payload
+
+    def test_expect_continue_timeout(self, local_server):
+        """Test an accepted expect_continue body waits out the configured hold"""
+
+        class Accept(http.server.BaseHTTPRequestHandler):
+            protocol_version = "HTTP/1.1"
+
+            def do_PUT(self):
+                self.rfile.read(int(self.headers["Content-Length"]))
+                self.send_response(204)
+                self.end_headers()
+
+        server = local_server(Accept)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        assert uf.TimeoutConfig().expect_continue_timeout is None
+
+        client = uf.HttpClient()
+        client.put(url, data={"a": "b"})  # open the connection
+        start = time.time()
+        assert client.put(url, data={"a": "b"}, expect_continue=True).status_code == 204
+        assert time.time() - start < 0.5
+
+        client = uf.HttpClient(timeout_config=uf.TimeoutConfig(expect_continue_timeout=0.6))
+        start = time.time()
+        assert client.put(url, data={"a": "b"}, expect_continue=True).status_code == 204
+        assert time.time() - start >= 0.6
+
+    def test_timing_breakdown(self, client, local_server):
+        """Test responses report DNS, connect and TTFB phases and connection reuse"""
+
+        class Slow(http.server.BaseHTTPRequestHandler):
+            protocol_version = "HTTP/1.1"
+
+            def do_GET(self):
+                time.sleep(0.05)
+                self.send_response(200)
+                self.send_header("Content-Length", "2")
+                self.end_headers()
+                self.wfile.write(b"ok")
+
+        server = local_server(Slow)
+        url = f"http://localhost:{server.server_address[1]}/"
+        first = client.get(url).timing
+        assert set(first) == {
+            "dns", "connect", "tls", "ttfb", "download", "total", "reused_connection"
+        }
+        assert first["tls"] is None
+        assert first["reused_connection"] is False
+        assert first["connect"] > 0
+        assert 0.05 <= first["ttfb"] <= first["total"]
+        assert first["dns"] + first["connect"] <= first["ttfb"]
+
+        second = client.get(url).timing
+        assert second["reused_connection"] is True
+        assert second["dns"] == 0 and second["connect"] == 0
+
+    def test_warmup_seeds_connection_pool(self, client, local_server):
+        """Test warmup opens pooled connections and reports failures"""
+
+        class Ok(http.server.BaseHTTPRequestHandler):
+            protocol_version = "HTTP/1.1"
+
+            def do_HEAD(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+            do_GET = do_HEAD
+
+        server = local_server(Ok)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        closed = socket.socket()
+        closed.bind(("127.0.0.1", 0))
+        closed_url = f"http://127.0.0.1:{closed.getsockname()[1]}/"
+        closed.close()
+        result = client.warmup([url, closed_url], timeout=5)
+        assert result == {"succeeded": 1, "failed": 1}
+        assert client.get(url).timing["reused_connection"] is True
+
+    def test_download_reports_progress(self, client, tmp_path, local_server):
+        """Test download streams to a file and reports throttled progress"""
+        payload = os.urandom(200_000)
+
+        class Files(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                if self.path == "/missing":
+                    self.send_error(404)
+                    return
+                self.send_response(200)
+                if self.path == "/sized":
+                    self.send_header("Content-Length", str(len(payload)))
+                self.end_headers()
+                self.wfile.write(payload)
+
+        server = local_server(Files)
+        base = f"http://127.0.0.1:{server.server_address[1]}"
+        calls = []
+        target = tmp_path / "sized.bin"
+        written = client.download(
+            f"{base}/sized", str(target), lambda *args: calls.append(args)
+        )
+        assert written == len(payload)
+        assert target.read_bytes() == payload
+        assert calls == [
+            (65536, 200_000),
+            (131072, 200_000),
+            (196608, 200_000),
+            (200_000, 200_000),
+        ]
+
+        calls.clear()
+        target = tmp_path / "unsized.bin"
+        client.download(
+            f"{base}/unsized", str(target), lambda *args: calls.append(args)
+        )
+        assert target.read_bytes() == payload
+        assert calls[-1] == (200_000, None)
+
+        with pytest.raises(uf.HTTPClientError):
+            client.download(f"{base}/missing", str(tmp_path / "missing.bin"))
+        assert not (tmp_path / "missing.bin").exists()
+
+    def test_upload_progress_callback(self, client, local_server):
+        """Test upload progress is reported per chunk for buffered and streamed bodies"""
+        received = []
+
+        class Upload(http.server.BaseHTTPRequestHandler):
+            def do_PUT(self):
+                if "Content-Length" in self.headers:
+                    body = self.rfile.read(int(self.headers["Content-Length"]))
+                else:
+                    body = b""
+                    while size := int(self.rfile.readline().strip(), 16):
+                        body += self.rfile.read(size)
+                        self.rfile.readline()
+                    self.rfile.readline()
+                received.append(body)
+                self.send_response(204)
+                self.end_headers()
+
+        server = local_server(Upload)
+        url = f"http://127.0.0.1:{server.server_address[1]}/upload"
+        payload = os.urandom(150_000)
+        calls = []
+        response = client.put(
+            url,
+            files={"blob": payload},
+            progress_callback=lambda *a: calls.append(a),
+        )
+        assert response.status_code == 204
+        total = len(received[0])
+        assert [sent for sent, _ in calls][-1] == total
+        assert all(size == total for _, size in calls)
+        assert [sent for sent, _ in calls] == sorted(sent for sent, _ in calls)
+        assert len(calls) == -(-total // 65536)
+
+        calls.clear()
+        client.upload_stream(
+            url,
+            iter([b"a" * 10, b"b" * 20]),
+            method="PUT",
+            progress_callback=lambda *a: calls.append(a),
+        )
+        assert received[1] == b"a" * 10 + b"b" * 20
+        assert calls == [(10, None), (30, None)]
+
+        # A callback that raises aborts the upload with its exception
+        class Cancelled(Exception):
+            pass
+
+        def cancel(sent, total):
+            raise Cancelled(sent)
+
+        with pytest.raises(Cancelled):
+            client.put(url, files={"blob": payload}, progress_callback=cancel)
+        with pytest.raises(Cancelled):
+            client.upload_stream(url, iter([b"a" * 10]), method="PUT", progress_callback=cancel)
+
+    def test_chunked_upload_is_resent_on_redirect(self, client, local_server):
+        """Test a body sent in chunks is sent again in full to a 307/308 target"""
+        received = []
+
+        class Moved(http.server.BaseHTTPRequestHandler):
+            def do_PUT(self):
+                received.append((self.path, self.rfile.read(int(self.headers["Content-Length"]))))
+                if self.path in ("/307", "/308"):
+                    self.send_response(int(self.path[1:]))
+                    self.send_header("Location", "/target")
+                else:
+                    self.send_response(204)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Moved)
+        base = f"http://127.0.0.1:{server.server_address[1]}"
+        payload = os.urandom(150_000)
+        for status in (307, 308):
+            received.clear()
+            calls = []
+            response = client.put(
+                f"{base}/{status}",
+                files={"blob": payload},
+                expect_continue=True,
+                progress_callback=lambda *a: calls.append(a),
+            )
+            assert response.status_code == 204
+            assert [path for path, _ in received] == [f"/{status}", "/target"]
+            assert received[0][1] == received[1][1]
+            assert payload in received[1][1]
+            # Each send reports its own progress up to the full body
+            total = len(received[0][1])
+            assert [sent for sent, _ in calls].count(total) == 2
+
+    def test_max_response_size(self, local_server):
+        """Test bodies over max_response_size raise instead of being buffered"""
+
+        class Big(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                if self.path == "/sized":
+                    self.send_header("Content-Length", "100000")
+                self.end_headers()
+                self.wfile.write(b"x" * 100_000)
+
+        server = local_server(Big, threaded=True)
+        base = f"http://127.0.0.1:{server.server_address[1]}"
+        client = uf.HttpClient(max_response_size=10_000)
+        for path in ("/sized", "/unsized"):
+            with pytest.raises(uf.ResponseTooLargeError, match="10000 bytes"):
+                client.get(f"{base}{path}")
+
+        lazy = uf.HttpClient(max_response_size=10_000, body_mode="lazy")
+        response = lazy.get(f"{base}/unsized")
+        with pytest.raises(uf.ResponseTooLargeError):
+            response.text()
+
+        roomy = uf.HttpClient(max_response_size=100_000)
+        assert roomy.get(f"{base}/sized").content_length == 100_000
+
+    def test_decompressed_body(self, local_server):
+        """Test decompressed() decodes bodies the client was told not to decompress"""
+        text = b"compressible " * 200
+        bodies = {
+            "/gzip": ("gzip", gzip.compress(text)),
+            "/stacked": ("deflate, gzip", gzip.compress(zlib.compress(text))),
+            "/unknown": ("compress", text),
+        }
+
+        class Encoded(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                encoding, body = bodies[self.path]
+                self.send_response(200)
+                self.send_header("Content-Encoding", encoding)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        server = local_server(Encoded)
+        base = f"http://127.0.0.1:{server.server_address[1]}"
+        raw = uf.HttpClient(
+            compression_config=uf.CompressionConfig(enable_response_compression=False)
+        )
+        response = raw.get(f"{base}/gzip")
+        assert response.bytes() == bodies["/gzip"][1]
+        assert response.decompressed() == text
+        assert raw.get(f"{base}/stacked").decompressed() == text
+        with pytest.raises(ValueError, match="compress"):
+            raw.get(f"{base}/unknown").decompressed()
+
+        # Auto-decompressed bodies no longer carry Content-Encoding
+        assert uf.HttpClient().get(f"{base}/gzip").decompressed() == text
+
+    def test_in_memory_png_upload_round_trips(self, client, local_server):
+        """Test in-memory binary files are sent byte-for-byte with a guessed content type"""
+        received = {}
+
+        class Upload(http.server.BaseHTTPRequestHandler):
+            def do_POST(self):
+                body = self.rfile.read(int(self.headers["Content-Length"]))
+                message = email.parser.BytesParser().parsebytes(
+                    f"Content-Type: {self.headers['Content-Type']}\r\n\r\n".encode() + body
+                )
+                for part in message.get_payload():
+                    received[part.get_param("name", header="content-disposition")] = part
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Upload)
+        # PNG signature plus bytes that are not valid UTF-8
+        png = b"\x89PNG\r\n\x1a\n" + bytes(range(256)) * 4
+        response = client.post(
+            f"http://127.0.0.1:{server.server_address[1]}/upload",
+            files={"image.png": png},
+            data={"caption": "logo"},
+        )
+        assert response.status_code == 200
+
+        image = received["image.png"]
+        assert image.get_content_type() == "image/png"
+        assert image.get_filename() == "image.png"
+        assert image.get_payload(decode=True) == png
+        assert received["caption"].get_payload() == "logo"
+
 
 class TestHttpClientAuthentication:
     """Test authentication methods"""
@@ -191,6 +730,151 @@ class TestHttpClientAuthentication:
         assert auth is not None
         assert auth.auth_type == uf.AuthType.OAuth2
 
+    def test_oauth2_refresh_token_rotation(self, local_server):
+        """Test expired tokens are renewed with the (rotated) refresh token"""
+        grants = []
+
+        class TokenServer(http.server.BaseHTTPRequestHandler):
+            def do_POST(self):
+                length = int(self.headers.get("Content-Length", 0))
+                form = dict(
+                    pair.split("=", 1) for pair in self.rfile.read(length).decode().split("&")
+                )
+                grants.append((form["grant_type"], form.get("code"), form.get("refresh_token")))
+                body = json.dumps(
+                    {
+                        "access_token": f"access-{len(grants)}",
+                        "expires_in": 0,
+                        "refresh_token": f"refresh-{len(grants)}",
+                    }
+                ).encode()
+                self.send_response(200)
+                self.send_header("Content-Type", "application/json")
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+            def do_GET(self):
+                body = self.headers.get("Authorization", "").encode()
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        server = local_server(TokenServer)
+        base = f"http://127.0.0.1:{server.server_address[1]}"
+        auth = uf.AuthConfig.oauth2(
+            "client",
+            f"{base}/token",
+            grant_type="authorization_code",
+            code="auth-code",
+            redirect_uri="https://app.example/callback",
+        )
+        client = uf.HttpClient(auth_config=auth)
+        assert client.get(f"{base}/api").text() == "Bearer access-1"
+        assert client.get(f"{base}/api").text() == "Bearer access-2"
+        assert client.get(f"{base}/api").text() == "Bearer access-3"
+        assert grants == [
+            ("authorization_code", "auth-code", None),
+            ("refresh_token", None, "refresh-1"),
+            ("refresh_token", None, "refresh-2"),
+        ]
+
+        with pytest.raises(ValueError, match="requires code"):
+            uf.AuthConfig.oauth2("client", "https://auth.example/token", grant_type="authorization_code")
+
+    def test_oauth2_token_lifecycle(self, local_server):
+        """Test reading, force-refreshing and observing OAuth2 tokens"""
+        grants = []
+
+        class TokenServer(http.server.BaseHTTPRequestHandler):
+            def do_POST(self):
+                length = int(self.headers.get("Content-Length", 0))
+                form = dict(
+                    pair.split("=", 1) for pair in self.rfile.read(length).decode().split("&")
+                )
+                grants.append(form["grant_type"])
+                body = json.dumps(
+                    {
+                        "access_token": f"access-{len(grants)}",
+                        "expires_in": 3600,
+                        "refresh_token": f"refresh-{len(grants)}",
+                    }
+                ).encode()
+                self.send_response(200)
+                self.send_header("Content-Type", "application/json")
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+            def do_GET(self):
+                body = self.headers.get("Authorization", "").encode()
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        server = local_server(TokenServer)
+        base = f"http://127.0.0.1:{server.server_address[1]}"
+        refreshed = []
+        auth = uf.AuthConfig.oauth2(
+            "client", f"{base}/token", on_token_refresh=refreshed.append
+        )
+        client = uf.HttpClient(auth_config=auth)
+        assert client.get_oauth2_token() is None
+
+        assert client.get(f"{base}/api").text() == "Bearer access-1"
+        assert client.get_oauth2_token().access_token == "access-1"
+
+        token = client.refresh_oauth2_token()
+        assert token.access_token == "access-2"
+        assert client.get(f"{base}/api").text() == "Bearer access-2"
+        assert grants == ["client_credentials", "refresh_token"]
+        assert [t.access_token for t in refreshed] == ["access-1", "access-2"]
+
+        with pytest.raises(ValueError, match="not configured"):
+            uf.HttpClient().refresh_oauth2_token()
+        with pytest.raises(TypeError, match="callable"):
+            uf.AuthConfig.oauth2("client", "https://auth.example/token", on_token_refresh="no")
+
+    def test_bearer_provider(self, local_server):
+        """Test a token provider is called per request, within its cache TTL"""
+
+        class EchoAuth(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                body = self.headers.get("Authorization", "").encode()
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        server = local_server(EchoAuth)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        calls = []
+
+        def provider():
+            calls.append(None)
+            return f"token-{len(calls)}"
+
+        client = uf.HttpClient(auth_config=uf.AuthConfig.bearer_provider(provider, cache_ttl=0))
+        assert client.get(url).text() == "Bearer token-1"
+        assert client.get(url).text() == "Bearer token-2"
+
+        cached = uf.HttpClient(auth_config=uf.AuthConfig.bearer_provider(provider, cache_ttl=60))
+        assert cached.get(url).text() == "Bearer token-3"
+        assert cached.get(url).text() == "Bearer token-3"
+
+        async def async_provider():
+            return "async-token"
+
+        client = uf.HttpClient(auth_config=uf.AuthConfig.bearer_provider(async_provider))
+        assert client.get(url).text() == "Bearer async-token"
+
+        with pytest.raises(TypeError):
+            uf.AuthConfig.bearer_provider("not callable")
+        with pytest.raises(ValueError):
+            uf.AuthConfig.bearer_provider(lambda: "token", cache_ttl=-1)
+
     def test_auth_configuration_methods(self):
         """Test authentication configuration methods"""
         client = uf.HttpClient()
@@ -210,6 +894,135 @@ class TestHttpClientAuthentication:
         assert client.has_auth() == False
         assert client.get_auth() is None
 
+    def test_digest_auth(self, local_server):
+        """Test Digest auth answers the challenge and reuses it with a growing nonce count"""
+        seen = []
+
+        class DigestHandler(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                header = self.headers.get("Authorization", "")
+                if not header.startswith("Digest "):
+                    self.send_response(401)
+                    self.send_header(
+                        "WWW-Authenticate",
+                        'Digest realm="test", qop="auth", nonce="abc123", algorithm=SHA-256',
+                    )
+                    self.send_header("Content-Length", "0")
+                    self.end_headers()
+                    return
+
+                fields = dict(
+                    part.strip().split("=", 1) for part in header[len("Digest ") :].split(",")
+                )
+                fields = {k: v.strip('"') for k, v in fields.items()}
+                h = lambda data: hashlib.sha256(data.encode()).hexdigest()
+                ha1 = h("user:test:secret")
+                ha2 = h(f"GET:{fields['uri']}")
+                expected = h(
+                    f"{ha1}:abc123:{fields['nc']}:{fields['cnonce']}:auth:{ha2}"
+                )
+                seen.append(fields["nc"])
+                self.send_response(200 if fields["response"] == expected else 403)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(DigestHandler)
+        client = uf.HttpClient(auth_config=uf.AuthConfig.digest("user", "secret"))
+        url = f"http://127.0.0.1:{server.server_address[1]}/protected"
+        assert client.get(url, params={"q": "1"}).status_code == 200
+        assert client.get(url).status_code == 200
+        assert seen == ["00000001", "00000002"]
+
+    def test_ntlm_auth(self, local_server):
+        """Test NTLM completes the negotiate/challenge/authenticate handshake on one connection"""
+        authenticated = []
+
+        def field(message, offset):
+            length, _, start = struct.unpack_from("<HHI", message, offset)
+            return message[start : start + length]
+
+        class NtlmHandler(http.server.BaseHTTPRequestHandler):
+            protocol_version = "HTTP/1.1"
+
+            def reply(self, status, challenge=None):
+                body = b"ok" if status == 200 else b"denied"
+                self.send_response(status)
+                if challenge:
+                    self.send_header("WWW-Authenticate", challenge)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+            def do_GET(self):
+                header = self.headers.get("Authorization", "")
+                if not header.startswith("NTLM "):
+                    return self.reply(401, "NTLM")
+                message = base64.b64decode(header[len("NTLM ") :])
+                assert message[:8] == b"NTLMSSP\0"
+                message_type = struct.unpack_from("<I", message, 8)[0]
+                if message_type == 1:
+                    self.negotiated = True
+                    target_info = b"\0\0\0\0"
+                    challenge = (
+                        b"NTLMSSP\0"
+                        + struct.pack("<IHHII", 2, 0, 0, 48, 0xA2898205)
+                        + bytes(range(8))
+                        + b"\0" * 8
+                        + struct.pack("<HHI", len(target_info), len(target_info), 48)
+                        + target_info
+                    )
+                    return self.reply(401, "NTLM " + base64.b64encode(challenge).decode())
+                if message_type == 3 and getattr(self, "negotiated", False):
+                    authenticated.append(
+                        (
+                            field(message, 28).decode("utf-16-le"),
+                            field(message, 36).decode("utf-16-le"),
+                            len(field(message, 20)) > 24,
+                        )
+                    )
+                    return self.reply(200)
+                self.reply(403)
+
+        server = local_server(NtlmHandler, threaded=True)
+        url = f"http://127.0.0.1:{server.server_address[1]}/protected"
+        client = uf.HttpClient(auth_config=uf.AuthConfig.ntlm("CORP\\alice", "secret"))
+        response = client.get(url)
+        assert response.status_code == 200
+        assert response.text() == "ok"
+        assert authenticated == [("CORP", "alice", True)]
+
+        auth = uf.AuthConfig.ntlm("bob", "secret", "EXAMPLE")
+        assert auth.auth_type == uf.AuthType.Ntlm
+        assert auth.get_credential("domain") == "EXAMPLE"
+
+    def test_aws_sigv4_auth(self, local_server):
+        """Test SigV4 signs the final request including the session token"""
+        captured = {}
+
+        class Capture(http.server.BaseHTTPRequestHandler):
+            def do_POST(self):
+                captured.update({k.lower(): v for k, v in self.headers.items()})
+                self.rfile.read(int(self.headers.get("Content-Length", 0)))
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Capture)
+        auth = uf.AuthConfig.aws_sigv4(
+            "AKIDEXAMPLE", "secret", "us-east-1", "execute-api", session_token="token"
+        )
+        assert auth.auth_type == uf.AuthType.AwsSigV4
+        client = uf.HttpClient(auth_config=auth)
+        url = f"http://127.0.0.1:{server.server_address[1]}/items"
+        assert client.post(url, json={"a": 1}, params={"b": "2"}).status_code == 200
+
+        authorization = captured["authorization"]
+        assert authorization.startswith("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/")
+        assert "/us-east-1/execute-api/aws4_request" in authorization
+        assert "SignedHeaders=host;x-amz-date;x-amz-security-token" in authorization
+        assert captured["x-amz-security-token"] == "token"
+        assert len(captured["x-amz-date"]) == 16
+
 
 class TestHttpClientConfiguration:
     """Test client configuration options"""
@@ -226,6 +1039,53 @@ class TestHttpClientConfiguration:
         client = uf.HttpClient(timeout_config=timeout_config)
         assert client is not None
 
+    def test_cleanup_interval(self):
+        """Test the background cleanup task can be tuned or disabled"""
+        assert uf.HttpClient().config_to_dict()["cleanup_interval"] == 300.0
+
+        disabled = uf.HttpClient(cleanup_interval=None)
+        config = disabled.config_to_dict()
+        assert config["cleanup_interval"] is None
+        restored = uf.HttpClient.from_config_dict(config)
+        assert restored.config_to_dict()["cleanup_interval"] is None
+
+        # A fast sweep runs several times and is aborted on close and drop
+        fast = uf.HttpClient(cleanup_interval=0.05)
+        time.sleep(0.2)
+        fast.close()
+        fast = uf.HttpClient(cleanup_interval=0.05)
+        time.sleep(0.2)
+        del fast
+
+        for invalid in (0, -1.0, float("inf"), float("nan")):
+            with pytest.raises(ValueError, match="cleanup_interval"):
+                uf.HttpClient(cleanup_interval=invalid)
+
+    def test_write_timeout(self):
+        """Test a peer that stops reading the request body trips write_timeout"""
+        listener = socket.socket()
+        listener.bind(("127.0.0.1", 0))
+        listener.listen(1)
+        accepted = []
+        # Accept the connection but never read from it
+        threading.Thread(target=lambda: accepted.append(listener.accept()), daemon=True).start()
+        try:
+            client = uf.HttpClient(
+                timeout=30.0,
+                timeout_config=uf.TimeoutConfig(connect_timeout=5.0, write_timeout=0.5),
+            )
+            start = time.monotonic()
+            with pytest.raises(uf.TimeoutError, match="Write timed out"):
+                client.post(
+                    f"http://127.0.0.1:{listener.getsockname()[1]}/upload",
+                    data={"blob": "x" * (64 * 1024 * 1024)},
+                )
+            assert time.monotonic() - start < 10
+        finally:
+            for conn, _ in accepted:
+                conn.close()
+            listener.close()
+
     def test_retry_configuration(self):
         """Test retry configuration"""
         retry_config = uf.RetryConfig(
@@ -239,6 +1099,178 @@ class TestHttpClientConfiguration:
         new_retry_config = uf.RetryConfig(max_retries=5, initial_delay=0.5)
         client.set_retry_config(new_retry_config)
 
+    def test_on_retry_callback(self, local_server):
+        """Test on_retry fires before each retry with attempt, status and delay"""
+        statuses = [503, 503, 200]
+
+        class FlakyHandler(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(statuses.pop(0))
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+            do_POST = do_GET
+
+        server = local_server(FlakyHandler)
+        retry_config = uf.RetryConfig(
+            max_retries=3,
+            initial_delay=0.01,
+            retry_on_status_codes=[503],
+            jitter=False,
+        )
+        client = uf.HttpClient(retry_config=retry_config)
+
+        calls = []
+        client.set_on_retry(lambda attempt, reason, delay: calls.append((attempt, reason, delay)))
+
+        response = client.get(f"http://127.0.0.1:{server.server_port}/")
+        assert response.status_code == 200
+
+        assert [(attempt, reason) for attempt, reason, _ in calls] == [(1, 503), (2, 503)]
+        assert all(delay > 0 for _, _, delay in calls)
+        assert calls[1][2] > calls[0][2]
+
+        # POST is not idempotent, so it is not retried by default
+        assert "POST" not in retry_config.retry_methods
+        calls.clear()
+        statuses.extend([503])
+        response = client.post(f"http://127.0.0.1:{server.server_port}/")
+        assert response.status_code == 503
+        assert calls == []
+
+        allow_all = uf.RetryConfig.allow_all_methods()
+        allow_all.initial_delay = 0.01
+        allow_all.retry_on_status_codes = [503]
+        client.set_retry_config(allow_all)
+        statuses.extend([503, 200])
+        response = client.post(f"http://127.0.0.1:{server.server_port}/")
+        assert response.status_code == 200
+        assert [(attempt, reason) for attempt, reason, _ in calls] == [(1, 503)]
+
+        with pytest.raises(TypeError):
+            client.set_on_retry("not callable")
+
+    def test_retry_on_error_kinds(self, local_server):
+        """Test retry_on limits which error kinds are retried"""
+        hits = []
+
+        class SlowHandler(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                hits.append(self.path)
+                time.sleep(0.5)
+
+        server = local_server(SlowHandler, threaded=True)
+        url = f"http://127.0.0.1:{server.server_port}/"
+        for retry_on, expected_hits in [(["connect", "tls"], 1), (["timeout"], 3)]:
+            hits.clear()
+            retry_config = uf.RetryConfig(
+                max_retries=2, initial_delay=0.01, jitter=False, retry_on=retry_on
+            )
+            client = uf.HttpClient(timeout=0.1, retry_config=retry_config)
+            with pytest.raises((uf.TimeoutError, ConnectionError)):
+                client.get(url)
+            assert len(hits) == expected_hits
+
+        # Without retry_on, retry_on_connection_errors stands for every kind
+        assert uf.RetryConfig().retry_on == ["timeout", "connect", "tls", "dns"]
+        assert uf.RetryConfig(retry_on_connection_errors=False).retry_on == []
+        assert uf.RetryConfig(retry_on=["Timeout"]).retry_on == ["timeout"]
+        with pytest.raises(ValueError, match="Unknown retry_on"):
+            uf.RetryConfig(retry_on=["http"])
+
+    def test_retry_after_header(self, local_server):
+        """Test Retry-After in delta-seconds and HTTP-date form replaces the backoff"""
+        responses = [
+            (429, "1"),
+            (503, email.utils.formatdate(time.time() + 30, usegmt=True)),
+            (200, None),
+        ]
+
+        class ThrottledHandler(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                status, retry_after = responses.pop(0)
+                self.send_response(status)
+                if retry_after:
+                    self.send_header("Retry-After", retry_after)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(ThrottledHandler)
+        retry_config = uf.RetryConfig(
+            max_retries=3,
+            initial_delay=0.01,
+            max_delay=1.5,
+            retry_on_status_codes=[429, 503],
+            jitter=False,
+        )
+        assert retry_config.respect_retry_after is True
+        client = uf.HttpClient(retry_config=retry_config)
+
+        delays = []
+        client.set_on_retry(lambda attempt, reason, delay: delays.append(delay))
+
+        response = client.get(f"http://127.0.0.1:{server.server_port}/")
+        assert response.status_code == 200
+        # Delta-seconds is used as-is; the HTTP-date is 30s away and capped by max_delay
+        assert delays == [1.0, 1.5]
+
+    def test_circuit_breaker(self, local_server):
+        """Test a failing host's circuit opens, fails fast, then closes after a good trial"""
+        state = {"status": 500, "hits": 0}
+
+        class FailingHandler(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                state["hits"] += 1
+                self.send_response(state["status"])
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        with pytest.raises(ValueError):
+            uf.CircuitBreakerConfig(failure_rate_threshold=1.5)
+
+        server = local_server(FailingHandler)
+        client = uf.HttpClient(
+            circuit_breaker_config=uf.CircuitBreakerConfig(
+                failure_rate_threshold=0.5, minimum_requests=2, cooldown_seconds=0.2
+            )
+        )
+        url = f"http://127.0.0.1:{server.server_port}/"
+        assert client.circuit_state("127.0.0.1") == "closed"
+
+        for _ in range(2):
+            assert client.get(url).status_code == 500
+        assert client.circuit_state("127.0.0.1") == "open"
+
+        with pytest.raises(uf.CircuitOpenError):
+            client.get(url)
+        assert state["hits"] == 2
+
+        time.sleep(0.3)
+        state["status"] = 200
+        assert client.get(url).status_code == 200
+        assert client.circuit_state("127.0.0.1") == "closed"
+
+    def test_context_manager_closes_client(self, local_server):
+        """Test leaving a with block closes the client and later requests fail"""
+
+        class Hello(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Hello)
+        url = f"http://127.0.0.1:{server.server_port}/"
+        with uf.HttpClient() as client:
+            assert client.get(url).status_code == 200
+            assert not client.is_closed()
+        assert client.is_closed()
+
+        with pytest.raises(RuntimeError, match="Client is closed"):
+            client.get(url)
+        # Closing again is harmless
+        client.close()
+
     def test_ssl_configuration(self):
         """Test SSL configuration"""
         ssl_config = uf.SSLConfig(verify=True)
@@ -249,6 +1281,406 @@ class TestHttpClientConfiguration:
         ssl_config_no_verify = uf.SSLConfig(verify=False)
         client.set_ssl_config(ssl_config_no_verify)
 
+    def test_ssl_load_errors(self, tmp_path):
+        """Test unreadable or invalid certificate files raise instead of being ignored"""
+        missing = str(tmp_path / "missing-ca.pem")
+        with pytest.raises(IOError, match="missing-ca.pem"):
+            uf.HttpClient(ssl_config=uf.SSLConfig(ca_bundle=missing))
+
+        garbage = tmp_path / "garbage.pem"
+        garbage.write_text("not a certificate")
+        with pytest.raises(ValueError, match="garbage.pem"):
+            uf.HttpClient(ssl_config=uf.SSLConfig(ca_bundle=str(garbage)))
+        with pytest.raises(ValueError, match="garbage.pem"):
+            uf.HttpClient(
+                ssl_config=uf.SSLConfig(cert_file=str(garbage), key_file=str(garbage))
+            )
+
+        # A failed rebuild keeps the previous configuration
+        client = uf.HttpClient()
+        with pytest.raises(IOError):
+            client.set_ssl_config(uf.SSLConfig(ca_bundle=missing))
+        assert client.config_to_dict()["ssl"]["ca_bundle"] is None
+
+        # ssl_strict=False keeps the old lenient behaviour
+        lenient = uf.SSLConfig(ca_bundle=missing, ssl_strict=False)
+        uf.HttpClient(ssl_config=lenient)
+        uf.AsyncHttpClient(ssl_config=lenient)
+
+    def test_min_tls_version(self):
+        """Test min_tls_version is applied and validated"""
+        client = uf.HttpClient(ssl_config=uf.SSLConfig(min_tls_version="1.2"))
+        response = client.get("https://httpbin.org/get")
+        assert response.status_code == 200
+
+        with pytest.raises(ValueError, match="min_tls_version"):
+            uf.HttpClient(ssl_config=uf.SSLConfig(min_tls_version="2.0"))
+
+        with pytest.raises(ValueError, match="min_tls_version"):
+            client.set_ssl_config(uf.SSLConfig(min_tls_version="tls1.2"))
+        assert client.config_to_dict()["ssl"]["min_tls_version"] == "1.2"
+
+    def test_client_cert_per_host(self, tmp_path, local_server):
+        """Test each host is sent its own client certificate"""
+        if shutil.which("openssl") is None:
+            pytest.skip("openssl CLI not available")
+
+        def openssl(*args):
+            subprocess.run(["openssl", *args], check=True, capture_output=True)
+
+        def issue(name, ca=None):
+            key, cert = tmp_path / f"{name}.key", tmp_path / f"{name}.pem"
+            openssl("genpkey", "-algorithm", "RSA", "-out", str(key))
+            if ca is None:
+                openssl("req", "-x509", "-new", "-key", str(key), "-subj", f"/CN={name}",
+                        "-days", "1", "-out", str(cert))
+            else:
+                csr = tmp_path / f"{name}.csr"
+                openssl("req", "-new", "-key", str(key), "-subj", f"/CN={name}", "-out", str(csr))
+                openssl("x509", "-req", "-in", str(csr), "-CA", str(ca[1]), "-CAkey", str(ca[0]),
+                        "-CAcreateserial", "-days", "1", "-out", str(cert))
+            return key, cert
+
+        server_key, server_cert = issue("server")
+
+        class EchoPeer(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                subject = dict(x[0] for x in self.connection.getpeercert()["subject"])
+                body = subject["commonName"].encode()
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        def serve(host, ca_cert):
+            context = ssl.SSLContext(ssl.PROTOCOL_TLS_SERVER)
+            context.load_cert_chain(str(server_cert), str(server_key))
+            context.verify_mode = ssl.CERT_REQUIRED
+            context.load_verify_locations(str(ca_cert))
+            return local_server(EchoPeer, host=host, ssl_context=context)
+
+        client = uf.HttpClient(ssl_config=uf.SSLConfig(verify=False))
+        servers = []
+        for host in ("127.0.0.1", "localhost"):
+            ca = issue(f"{host}-ca")
+            key, cert = issue(f"{host}-client", ca=ca)
+            client.set_client_cert(host, cert.read_text(), key.read_text())
+            servers.append((host, serve(host, ca[1])))
+
+        assert sorted(client.get_client_cert_hosts()) == ["127.0.0.1", "localhost"]
+        for host, server in servers:
+            response = client.get(f"https://{host}:{server.server_address[1]}/")
+            assert response.text() == f"{host}-client"
+
+        assert client.remove_client_cert("localhost")
+        assert not client.remove_client_cert("localhost")
+        with pytest.raises(ValueError):
+            client.set_client_cert("example.com", "not a cert", "not a key")
+
+    def test_client_cert_host_keeps_timeout(self, tmp_path, local_server):
+        """Test a host with its own client certificate still honours the client timeout"""
+        if shutil.which("openssl") is None:
+            pytest.skip("openssl CLI not available")
+
+        key, cert = tmp_path / "client.key", tmp_path / "client.pem"
+        subprocess.run(["openssl", "genpkey", "-algorithm", "RSA", "-out", str(key)],
+                       check=True, capture_output=True)
+        subprocess.run(["openssl", "req", "-x509", "-new", "-key", str(key), "-subj", "/CN=client",
+                        "-days", "1", "-out", str(cert)], check=True, capture_output=True)
+
+        class Drip(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                # Each byte arrives well within the read timeout; the whole body does not
+                self.send_response(200)
+                self.send_header("Content-Length", "10")
+                self.end_headers()
+                for _ in range(10):
+                    self.wfile.write(b"x")
+                    self.wfile.flush()
+                    time.sleep(0.3)
+
+        client = uf.HttpClient(timeout=1.0)
+        client.set_client_cert("127.0.0.1", cert.read_text(), key.read_text())
+        server = local_server(Drip, threaded=True)
+        start = time.time()
+        # Timeouts while reading the body surface as an IOError
+        with pytest.raises(OSError):
+            client.get(f"http://127.0.0.1:{server.server_address[1]}/")
+        assert time.time() - start < 2.5
+
+    def test_client_cert_from_pem_bytes(self, tmp_path, local_server):
+        """Test a client certificate held in memory is presented to the server"""
+        if shutil.which("openssl") is None:
+            pytest.skip("openssl CLI not available")
+
+        def openssl(*args):
+            subprocess.run(["openssl", *args], check=True, capture_output=True)
+
+        def issue(name, ca=None):
+            key, cert = tmp_path / f"{name}.key", tmp_path / f"{name}.pem"
+            openssl("genpkey", "-algorithm", "RSA", "-out", str(key))
+            if ca is None:
+                openssl("req", "-x509", "-new", "-key", str(key), "-subj", f"/CN={name}",
+                        "-days", "1", "-out", str(cert))
+            else:
+                csr = tmp_path / f"{name}.csr"
+                openssl("req", "-new", "-key", str(key), "-subj", f"/CN={name}", "-out", str(csr))
+                openssl("x509", "-req", "-in", str(csr), "-CA", str(ca[1]), "-CAkey", str(ca[0]),
+                        "-CAcreateserial", "-days", "1", "-out", str(cert))
+            return key, cert
+
+        server_key, server_cert = issue("server")
+        ca = issue("ca")
+        client_key, client_cert = issue("env-client", ca=ca)
+
+        class EchoPeer(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                subject = dict(x[0] for x in self.connection.getpeercert()["subject"])
+                body = subject["commonName"].encode()
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        context = ssl.SSLContext(ssl.PROTOCOL_TLS_SERVER)
+        context.load_cert_chain(str(server_cert), str(server_key))
+        context.verify_mode = ssl.CERT_REQUIRED
+        context.load_verify_locations(str(ca[1]))
+        server = local_server(EchoPeer, ssl_context=context)
+        ssl_config = uf.SSLConfig.from_pem_bytes(
+            client_cert.read_bytes(), client_key.read_bytes(), verify=False
+        )
+        client = uf.HttpClient(ssl_config=ssl_config)
+        response = client.get(f"https://127.0.0.1:{server.server_address[1]}/")
+        assert response.text() == "env-client"
+
+        # The private key never appears in an exported config
+        assert client.config_to_dict()["ssl"]["key_pem"] == "<redacted>"
+
+        with pytest.raises(ValueError):
+            uf.SSLConfig.from_pem_bytes(b"not a cert", b"not a key")
+        with pytest.raises(ValueError):
+            uf.HttpClient(ssl_config=uf.SSLConfig(cert_pem=client_cert.read_text()))
+        with pytest.raises(IOError):
+            uf.HttpClient(
+                ssl_config=uf.SSLConfig(
+                    cert_file=str(tmp_path / "missing.pem"), key_file=str(client_key)
+                )
+            )
+
+    def test_public_key_pinning(self, tmp_path, local_server):
+        """Test connections are refused unless the server key matches a pin"""
+        if shutil.which("openssl") is None:
+            pytest.skip("openssl CLI not available")
+
+        key, cert = tmp_path / "server.key", tmp_path / "server.pem"
+        subprocess.run(
+            ["openssl", "req", "-x509", "-newkey", "ec", "-pkeyopt",
+             "ec_paramgen_curve:prime256v1", "-nodes", "-keyout", str(key), "-out", str(cert),
+             "-subj", "/CN=127.0.0.1", "-days", "1"],
+            check=True, capture_output=True,
+        )
+        pin = uf.SSLConfig.public_key_pin(cert.read_text())
+        assert pin.startswith("sha256/")
+
+        with pytest.raises(ValueError):
+            uf.HttpClient(ssl_config=uf.SSLConfig(pinned_public_keys=["sha256/short"]))
+        try:
+            uf.HttpClient(ssl_config=uf.SSLConfig(pinned_public_keys=[pin]))
+        except NotImplementedError:
+            pytest.skip("built without the rustls-tls feature")
+
+        class Hello(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "2")
+                self.end_headers()
+                self.wfile.write(b"hi")
+
+        context = ssl.SSLContext(ssl.PROTOCOL_TLS_SERVER)
+        context.load_cert_chain(str(cert), str(key))
+        server = local_server(Hello, ssl_context=context)
+        url = f"https://127.0.0.1:{server.server_address[1]}/"
+        wrong_pin = "sha256/" + "A" * 43 + "="
+        pinned = uf.HttpClient(
+            ssl_config=uf.SSLConfig(verify=False, pinned_public_keys=[wrong_pin, pin])
+        )
+        assert pinned.get(url).text() == "hi"
+
+        mismatched = uf.HttpClient(
+            ssl_config=uf.SSLConfig(verify=False, pinned_public_keys=[wrong_pin])
+        )
+        with pytest.raises(uf.TLSError):
+            mismatched.get(url)
+
+    def test_socks5_proxy(self, local_server):
+        """Test requests are tunnelled through a SOCKS5 proxy with remote DNS"""
+        import socket
+        import socketserver
+        import struct
+
+        seen_targets = []
+
+        class Socks5Handler(socketserver.BaseRequestHandler):
+            def handle(self):
+                conn = self.request
+                _, nmethods = conn.recv(2)
+                conn.recv(nmethods)
+                conn.sendall(b"\x05\x00")
+
+                _, _, _, atyp = conn.recv(4)
+                if atyp == 3:
+                    host = conn.recv(conn.recv(1)[0]).decode()
+                else:
+                    host = socket.inet_ntoa(conn.recv(4))
+                (port,) = struct.unpack("!H", conn.recv(2))
+                seen_targets.append(host)
+
+                upstream = socket.create_connection(("127.0.0.1", port))
+                conn.sendall(b"\x05\x00\x00\x01" + socket.inet_aton("0.0.0.0") + b"\x00\x00")
+                conn.settimeout(5)
+                upstream.settimeout(5)
+
+                def pipe(src, dst):
+                    try:
+                        while data := src.recv(65536):
+                            dst.sendall(data)
+                    except OSError:
+                        pass
+                    finally:
+                        dst.close()
+
+                threading.Thread(target=pipe, args=(upstream, conn), daemon=True).start()
+                pipe(conn, upstream)
+
+        class Hello(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "5")
+                self.end_headers()
+                self.wfile.write(b"hello")
+
+        origin = local_server(Hello)
+        proxy = socketserver.ThreadingTCPServer(("127.0.0.1", 0), Socks5Handler)
+        proxy.daemon_threads = True
+        threading.Thread(target=proxy.serve_forever, daemon=True).start()
+        origin_port = origin.server_address[1]
+        proxy_url = f"socks5h://127.0.0.1:{proxy.server_address[1]}"
+        try:
+            client = uf.HttpClient(proxy_config=uf.ProxyConfig.socks5(proxy_url))
+            response = client.get(f"http://localhost:{origin_port}/")
+            assert response.text() == "hello"
+            assert seen_targets == ["localhost"]
+        finally:
+            proxy.shutdown()
+
+        with pytest.raises(ValueError, match="socks5"):
+            uf.ProxyConfig.socks5("http://127.0.0.1:1080")
+
+    def test_no_proxy_bypasses_proxy(self, local_server):
+        """Test hosts listed in no_proxy are requested directly"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                body = self.server.body
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        origin = local_server(Reply)
+        origin.body = b"direct"
+        proxy = local_server(Reply)
+        proxy.body = b"proxied"
+        proxy_url = f"http://127.0.0.1:{proxy.server_address[1]}"
+        origin_url = f"http://127.0.0.1:{origin.server_address[1]}/"
+        client = uf.HttpClient(proxy_config=uf.ProxyConfig(proxy_url))
+        assert client.get(origin_url).text() == "proxied"
+
+        client.set_proxy_config(uf.ProxyConfig(proxy_url, no_proxy=["127.0.0.1"]))
+        assert client.get(origin_url).text() == "direct"
+
+    def test_per_request_proxy(self, local_server):
+        """Test a proxy passed to one request overrides the client's for that call only"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                body = self.server.body
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        servers = {}
+        for name in ("origin", "client_proxy", "request_proxy"):
+            servers[name] = local_server(Reply)
+            servers[name].body = name.encode()
+
+        def proxy(name):
+            return uf.ProxyConfig(f"http://127.0.0.1:{servers[name].server_address[1]}")
+
+        origin_url = f"http://127.0.0.1:{servers['origin'].server_address[1]}/"
+        client = uf.HttpClient(proxy_config=proxy("client_proxy"))
+        assert client.get(origin_url, proxy=proxy("request_proxy")).text() == "request_proxy"
+        assert client.get(origin_url).text() == "client_proxy"
+
+        direct = uf.HttpClient()
+        assert direct.get(origin_url, proxy=proxy("request_proxy")).text() == "request_proxy"
+        assert direct.get(origin_url).text() == "origin"
+
+    @pytest.mark.skipif(not hasattr(socket, "AF_UNIX"), reason="needs Unix sockets")
+    def test_unix_socket(self, tmp_path):
+        """Test requests are sent over the configured Unix domain socket"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                body = f"{self.headers['Host']} {self.path}".encode()
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+            def log_message(self, *args):
+                pass
+
+        class UnixHTTPServer(socketserver.ThreadingMixIn, socketserver.UnixStreamServer):
+            daemon_threads = True
+
+        path = str(tmp_path / "daemon.sock")
+        server = UnixHTTPServer(path, Reply)
+        threading.Thread(target=server.serve_forever, daemon=True).start()
+        try:
+            client = uf.HttpClient(unix_socket=path)
+            response = client.get("http://docker/v1.43/info")
+            assert response.status_code == 200
+            assert response.text() == "docker /v1.43/info"
+            assert client.config_to_dict()["unix_socket"] == path
+        finally:
+            server.shutdown()
+            server.server_close()
+
+    def test_ip_version(self, local_server):
+        """Test ip_version restricts which resolved addresses are connected to"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Reply)
+        url = f"http://localhost:{server.server_address[1]}/"
+        client = uf.HttpClient(ip_version="v4")
+        assert client.get(url).status_code == 200
+        assert client.config_to_dict()["ip_version"] == "v4"
+
+        # Nothing listens on the IPv6 loopback
+        with pytest.raises(Exception):
+            uf.HttpClient(ip_version="v6", timeout=5.0).get(url)
+
+        with pytest.raises(ValueError):
+            uf.HttpClient(ip_version="ipv4")
+
     def test_compression_configuration(self):
         """Test compression configuration"""
         compression_config = uf.CompressionConfig(
@@ -265,6 +1697,41 @@ class TestHttpClientConfiguration:
         all_algorithms_config = uf.CompressionConfig.all_algorithms()
         client.set_compression_config(all_algorithms_config)
 
+    def test_request_encoding_negotiation(self, local_server):
+        """Test the OPTIONS preflight picks an encoding the server accepts"""
+        seen = []
+
+        class EncodingHandler(http.server.BaseHTTPRequestHandler):
+            def do_OPTIONS(self):
+                seen.append(("OPTIONS", None))
+                self.send_response(204)
+                self.send_header("Accept-Encoding", "gzip")
+                self.end_headers()
+
+            def do_POST(self):
+                self.rfile.read(int(self.headers["Content-Length"]))
+                seen.append(("POST", self.headers.get("Content-Encoding")))
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(EncodingHandler)
+        compression_config = uf.CompressionConfig(
+            enable_request_compression=True,
+            compression_algorithms=["brotli", "gzip"],
+            compression_level=6,
+            min_compression_size=16,
+            negotiate_request_encoding=True,
+        )
+        client = uf.HttpClient(compression_config=compression_config)
+        url = f"http://127.0.0.1:{server.server_port}/upload"
+
+        client.post(url, json={"payload": "x" * 4096})
+        client.post(url, json={"payload": "y" * 4096})
+
+        # One preflight per host, then gzip rather than the preferred brotli
+        assert seen == [("OPTIONS", None), ("POST", "gzip"), ("POST", "gzip")]
+
     def test_protocol_configuration(self):
         """Test protocol configuration"""
         protocol_config = uf.ProtocolConfig(
@@ -288,6 +1755,99 @@ class TestHttpClientConfiguration:
         client.set_protocol_config(http3_config)
         assert client.is_http3_enabled() == True
 
+    def test_http2_window_settings(self):
+        """Test HTTP/2 flow-control settings build a working client"""
+        settings = uf.Http2Settings(
+            initial_window_size=1048576,
+            initial_connection_window_size=10485760,
+            max_frame_size=65536,
+            adaptive_window=False,
+        )
+        protocol_config = uf.ProtocolConfig(http2_settings=settings)
+        client = uf.HttpClient(protocol_config=protocol_config)
+        response = client.get("https://httpbin.org/get")
+        assert response.status_code == 200
+
+        client.set_protocol_config(uf.ProtocolConfig(http2_settings=uf.Http2Settings.high_performance()))
+        response = client.get("https://httpbin.org/get")
+        assert response.status_code == 200
+
+    def test_preferred_version_is_enforced(self, local_server):
+        """Test HTTP/1.1 is forced and strict HTTP/2 refuses to downgrade"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            protocol_version = "HTTP/1.1"
+
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Reply)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        http1 = uf.HttpClient(
+            protocol_config=uf.ProtocolConfig(preferred_version=uf.HttpVersion.Http1)
+        )
+        assert http1.get(url).protocol == "HTTP/1.1"
+
+        strict_http2 = uf.HttpClient(
+            protocol_config=uf.ProtocolConfig(
+                preferred_version=uf.HttpVersion.Http2,
+                fallback_strategy=getattr(uf.ProtocolFallback, "None"),
+            )
+        )
+        with pytest.raises(Exception):
+            strict_http2.get(url)
+
+        with pytest.raises(ValueError):
+            uf.ProtocolConfig(
+                preferred_version=uf.HttpVersion.Http1,
+                enable_http2_prior_knowledge=True,
+            ).validate()
+
+    def test_redirect_policy(self):
+        """Test redirects can be disabled per client and per request"""
+        client = uf.HttpClient(max_redirects=3)
+
+        response = client.get("https://httpbin.org/redirect/2")
+        assert response.status_code == 200
+        assert response.url == "https://httpbin.org/get"
+
+        response = client.get("https://httpbin.org/redirect/2", allow_redirects=False)
+        assert response.status_code == 302
+        assert response.get_header("location") is not None
+
+        with pytest.raises(uf.TooManyRedirects):
+            client.get("https://httpbin.org/redirect/5")
+
+        client.set_redirect_policy(allow_redirects=False)
+        assert client.get("https://httpbin.org/redirect/1").status_code == 302
+        assert client.get("https://httpbin.org/redirect/1", allow_redirects=True).status_code == 200
+
+    def test_redirect_history(self):
+        """Test followed redirects are recorded in Response.history"""
+        client = uf.HttpClient()
+        response = client.get("https://httpbin.org/redirect/2")
+
+        assert response.url == "https://httpbin.org/get"
+        assert [hop.status_code for hop in response.history] == [302, 302]
+        assert response.history[0].url == "https://httpbin.org/redirect/2"
+        assert response.history[0].get_header("location") is not None
+        assert response.history[0].history == []
+
+        direct = client.get("https://httpbin.org/get")
+        assert direct.history == []
+
+    def test_redirect_303_switches_to_get(self):
+        """Test a 303 after POST is followed with a GET and no body"""
+        client = uf.HttpClient()
+        response = client.post(
+            "https://httpbin.org/redirect-to?url=https%3A%2F%2Fhttpbin.org%2Fget&status_code=303",
+            data={"field": "value"},
+        )
+        assert response.status_code == 200
+        assert response.json()["url"] == "https://httpbin.org/get"
+
     def test_pool_configuration(self):
         """Test connection pool configuration"""
         pool_config = uf.PoolConfig(
@@ -297,6 +1857,46 @@ class TestHttpClientConfiguration:
         client = uf.HttpClient(pool_config=pool_config)
         assert client is not None
 
+    def test_config_dict_round_trip(self):
+        """Test a client rebuilt from an exported config behaves identically"""
+        client = uf.HttpClient(
+            base_url="https://httpbin.org",
+            headers={"X-Profile": "primary"},
+            timeout=12.0,
+            retry_config=uf.RetryConfig(max_retries=2),
+            pool_config=uf.PoolConfig(max_idle_connections=20),
+            body_mode="lazy",
+        )
+        client.set_default_params({"source": "profile"})
+
+        config = client.config_to_dict()
+        assert json.loads(json.dumps(config)) == config
+        assert config["timeout"] == 12.0
+        assert config["pool"]["max_idle_connections"] == 20
+
+        rebuilt = uf.HttpClient.from_config_dict(config)
+        assert rebuilt.config_to_dict() == config
+        assert rebuilt.get_body_mode() == "lazy"
+        assert rebuilt.get_default_params() == {"source": "profile"}
+
+        response = rebuilt.get("/get")
+        assert response.status_code == 200
+        data = response.json()
+        assert data["headers"]["X-Profile"] == "primary"
+        assert data["args"]["source"] == "profile"
+
+    def test_config_dict_redacts_secrets(self):
+        """Test auth credentials are redacted unless explicitly included"""
+        client = uf.HttpClient(auth_config=uf.AuthConfig.bearer("secret-token"))
+
+        redacted = client.config_to_dict()
+        assert "secret-token" not in json.dumps(redacted)
+        assert uf.HttpClient.from_config_dict(redacted).has_auth() == False
+
+        full = client.config_to_dict(include_secrets=True)
+        assert full["auth"]["credentials"]["token"] == "secret-token"
+        assert uf.HttpClient.from_config_dict(full).has_auth() == True
+
     def test_rate_limit_configuration(self):
         """Test rate limiting configuration"""
         rate_limit_config = uf.RateLimitConfig(
@@ -314,6 +1914,129 @@ class TestHttpClientConfiguration:
         assert rate_limit is not None
         assert rate_limit.requests_per_second == 10
 
+    def test_rate_limit_queue(self):
+        """Test requests over the limit wait in the queue instead of failing"""
+        rate_limit_config = uf.RateLimitConfig(
+            requests_per_second=2,
+            burst_size=1,
+            queue_requests=True,
+            queue_timeout_seconds=5.0,
+        )
+        client = uf.HttpClient(rate_limit_config=rate_limit_config)
+        assert client.peek_rate_limit_queue() == []
+
+        start = time.time()
+        assert client.get("https://httpbin.org/get").status_code == 200
+        assert client.get("https://httpbin.org/get").status_code == 200
+        # The second request waited for a token (one every 0.5s)
+        assert time.time() - start >= 0.4
+        assert client.peek_rate_limit_queue() == []
+
+    def test_rate_limit_queue_timeout(self):
+        """Test a queued request fails once the queue timeout passes"""
+        rate_limit_config = uf.RateLimitConfig(
+            requests_per_second=0.2,
+            burst_size=1,
+            queue_requests=True,
+            queue_timeout_seconds=0.2,
+        )
+        client = uf.HttpClient(rate_limit_config=rate_limit_config)
+
+        client.get("https://httpbin.org/get")
+        with pytest.raises(uf.TimeoutError, match="queue timeout"):
+            client.get("https://httpbin.org/get")
+
+    def test_rate_limit_exceeded_error(self, local_server):
+        """Test a request over the limit without queueing raises RateLimitError"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Reply)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        rate_limit_config = uf.RateLimitConfig(
+            requests_per_second=0.2, burst_size=1, queue_requests=False
+        )
+        client = uf.HttpClient(rate_limit_config=rate_limit_config)
+
+        assert issubclass(uf.RateLimitError, uf.UltrafastError)
+        assert issubclass(uf.RateLimitError, RuntimeError)
+        assert client.get(url).status_code == 200
+        with pytest.raises(uf.RateLimitError, match="Rate limit exceeded"):
+            client.get(url)
+
+    def test_rate_limit_queue_releases_gil(self, local_server):
+        """Test other Python threads keep running while a request waits in the queue"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Reply)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        rate_limit_config = uf.RateLimitConfig(
+            requests_per_second=2,
+            burst_size=1,
+            queue_requests=True,
+            queue_timeout_seconds=5.0,
+        )
+        client = uf.HttpClient(rate_limit_config=rate_limit_config)
+        assert client.get(url).status_code == 200
+
+        waiter = threading.Thread(target=client.get, args=(url,))
+        waiter.start()
+        ticks = 0
+        while waiter.is_alive():
+            ticks += 1
+            time.sleep(0.01)
+        # The queued request waited about 0.5s without holding the GIL
+        assert ticks >= 10
+
+    def test_adaptive_rate_limit(self, local_server):
+        """Test 429 responses lower the effective rate and successes restore it"""
+        throttle = {"on": True}
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(429 if throttle["on"] else 200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Reply)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        rate_limit_config = uf.RateLimitConfig(
+            requests_per_second=20,
+            burst_size=20,
+            queue_requests=True,
+            adaptive=True,
+            recovery_step=0.25,
+        )
+        client = uf.HttpClient(rate_limit_config=rate_limit_config)
+        assert client.get_effective_rate("127.0.0.1") == 20
+
+        assert client.get(url).status_code == 429
+        assert client.get(url).status_code == 429
+        assert client.get_effective_rate("127.0.0.1") == 5
+
+        # Backed off requests are paced at the reduced rate (one per 0.2s)
+        throttle["on"] = False
+        start = time.time()
+        assert client.get(url).status_code == 200
+        assert client.get_effective_rate("127.0.0.1") == 10
+        assert client.get(url).status_code == 200
+        assert time.time() - start >= 0.2
+        assert client.get_effective_rate("127.0.0.1") == 15
+
+        client.get(url)
+        assert client.get_effective_rate("127.0.0.1") == 20
+
+        assert uf.HttpClient().get_effective_rate("127.0.0.1") is None
+
 
 class TestHttpClientHeaders:
     """Test header management"""
@@ -382,7 +2105,7 @@ class TestHttpClientResponse:
         response = client.get(f"{test_url}/get")
 
         assert response.status_code == 200
-        assert response.ok() == True
+        assert response.ok == True
         assert response.url() is not None
         assert len(response.headers()) > 0
 
@@ -399,6 +2122,218 @@ class TestHttpClientResponse:
         json_data = response.json()
         assert isinstance(json_data, dict)
 
+    def test_text_encoding(self, client, local_server):
+        """Test text honours the charset, then a BOM, then the apparent encoding"""
+        bodies = {
+            "/latin1": ("text/plain; charset=ISO-8859-1", "café".encode("latin-1")),
+            "/bom": ("text/plain", "\ufeffnaïve".encode("utf-16-le")),
+            "/utf8": ("text/plain", "naïve ✓".encode("utf-8")),
+            "/unlabelled": ("text/plain", "déjà".encode("cp1252")),
+            "/broken": ("text/plain; charset=utf-8", b"ok \xff"),
+        }
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                content_type, body = bodies[self.path]
+                self.send_response(200)
+                self.send_header("Content-Type", content_type)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        server = local_server(Reply)
+        base = f"http://127.0.0.1:{server.server_address[1]}"
+        responses = {path: client.get(f"{base}{path}") for path in bodies}
+
+        latin1 = responses["/latin1"]
+        assert latin1.encoding == "windows-1252"
+        assert latin1.text() == "café"
+
+        bom = responses["/bom"]
+        assert bom.encoding is None
+        assert bom.apparent_encoding == "UTF-16LE"
+        assert bom.text() == "naïve"
+
+        assert responses["/utf8"].apparent_encoding == "UTF-8"
+        assert responses["/utf8"].text() == "naïve ✓"
+
+        unlabelled = responses["/unlabelled"]
+        assert unlabelled.apparent_encoding == "windows-1252"
+        assert unlabelled.text() == "déjà"
+        unlabelled.encoding = "utf-8"
+        assert unlabelled.encoding == "UTF-8"
+        assert unlabelled.text() == "d\ufffdj\ufffd"
+        unlabelled.encoding = None
+        assert unlabelled.text() == "déjà"
+        with pytest.raises(LookupError):
+            unlabelled.encoding = "no-such-charset"
+
+        broken = responses["/broken"]
+        assert broken.text() == "ok \ufffd"
+        with pytest.raises(UnicodeDecodeError):
+            broken.text(errors="strict")
+        with pytest.raises(ValueError):
+            broken.text(errors="ignore")
+
+    def test_status_helpers(self, client, local_server):
+        """Test ok, reason and redirect helpers"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                status = int(self.path.strip("/"))
+                self.send_response(status)
+                if status in (301, 302, 308):
+                    self.send_header("Location", "/200")
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Reply)
+        base = f"http://127.0.0.1:{server.server_address[1]}"
+        ok = client.get(f"{base}/200")
+        missing = client.get(f"{base}/404")
+        failed = client.get(f"{base}/500")
+        moved = client.get(f"{base}/301", allow_redirects=False)
+        found = client.get(f"{base}/302", allow_redirects=False)
+
+        assert ok.ok and ok.reason == "OK"
+        assert not missing.ok and missing.reason == "Not Found"
+        assert not failed.ok
+        assert not ok.is_redirect
+        assert moved.is_redirect and moved.is_permanent_redirect
+        assert found.is_redirect and not found.is_permanent_redirect
+
+        assert repr(ok) == f"<Response [200 OK] {base}/200>"
+        assert str(missing) == f"<Response [404 Not Found] {base}/404>"
+        assert ok and not missing and not failed
+
+    def test_links(self, client, local_server):
+        """Test links parses the Link header keyed by rel"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                if self.path == "/paged":
+                    self.send_header(
+                        "Link",
+                        '<https://api.example.com/items?page=2>; rel="next", '
+                        '<https://api.example.com/items?page=9>; rel=last; '
+                        'title="Last, \\"final\\" page", '
+                        "<https://api.example.com/schema>",
+                    )
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Reply)
+        base = f"http://127.0.0.1:{server.server_address[1]}"
+        paged = client.get(f"{base}/paged")
+        plain = client.get(f"{base}/plain")
+
+        links = paged.links
+        assert links["next"] == {
+            "url": "https://api.example.com/items?page=2",
+            "rel": "next",
+        }
+        assert links["last"] == {
+            "url": "https://api.example.com/items?page=9",
+            "rel": "last",
+            "title": 'Last, "final" page',
+        }
+        assert links["https://api.example.com/schema"] == {
+            "url": "https://api.example.com/schema"
+        }
+        assert plain.links == {}
+
+    def test_json_path(self, client, local_server):
+        """Test json_path extracts a single value by JSON pointer"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                body = json.dumps(
+                    {"data": {"items": [{"id": 7}, {"id": 8}], "a/b": {"m~n": True}}}
+                ).encode()
+                self.send_response(200)
+                self.send_header("Content-Type", "application/json")
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        server = local_server(Reply)
+        response = client.get(f"http://127.0.0.1:{server.server_address[1]}/")
+
+        assert response.json_path("/data/items/1/id") == 8
+        assert response.json_path("/data/items/0") == {"id": 7}
+        assert response.json_path("/data/a~1b/m~0n") is True
+        assert response.json_path("") == response.json()
+        assert response.json_path("/data/missing") is None
+        assert response.json_path("/data/items/5", default=0) == 0
+        with pytest.raises(KeyError):
+            response.json_path("/data/missing", strict=True)
+        with pytest.raises(ValueError):
+            response.json_path("data/items")
+
+    def test_validate_json_schema_conforming(self, client, test_url):
+        """Test schema validation returns the parsed body on success"""
+        response = client.get(f"{test_url}/json")
+        schema = {
+            "type": "object",
+            "required": ["slideshow"],
+            "properties": {"slideshow": {"type": "object"}},
+        }
+        data = response.validate_json_schema(schema, schema_id="slideshow")
+        assert "slideshow" in data
+
+        # The cached schema can be reused by id alone
+        assert "slideshow" in response.validate_json_schema(schema_id="slideshow")
+
+    def test_validate_json_schema_non_conforming(self, client, test_url):
+        """Test schema validation raises with the failing path"""
+        response = client.get(f"{test_url}/json")
+        schema = {
+            "type": "object",
+            "properties": {"slideshow": {"type": "array"}},
+        }
+        with pytest.raises(uf.SchemaValidationError) as exc_info:
+            response.validate_json_schema(schema)
+        assert "/slideshow" in str(exc_info.value)
+        assert isinstance(exc_info.value, uf.UltrafastError)
+        assert isinstance(exc_info.value, ValueError)
+
+    def test_response_cookies(self, client, test_url):
+        """Test every Set-Cookie header is parsed into cookies"""
+        response = client.get(
+            f"{test_url}/response-headers",
+            params={"Set-Cookie": "theme=dark; Path=/; HttpOnly"},
+        )
+        assert response.cookies == {"theme": "dark"}
+
+        cookie = response.get_cookies()[0]
+        assert isinstance(cookie, uf.Cookie)
+        assert cookie.path == "/"
+        assert cookie.http_only
+        assert not cookie.secure
+        assert not cookie.is_expired()
+
+    def test_response_multiple_cookies(self, local_server):
+        """Test duplicate Set-Cookie headers are not collapsed"""
+
+        class SetCookies(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Set-Cookie", "a=1; Domain=.example.com; Secure")
+                self.send_header("Set-Cookie", "b=2; Expires=Wed, 21 Oct 2015 07:28:00 GMT")
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(SetCookies)
+        response = uf.HttpClient().get(f"http://127.0.0.1:{server.server_address[1]}/")
+
+        assert response.cookies == {"a": "1", "b": "2"}
+        a, b = response.get_cookies()
+        assert a.domain == "example.com"
+        assert a.secure
+        assert b.expires == 1445412480.0
+        assert b.is_expired()
+
     def test_response_bytes(self, client, test_url):
         """Test response binary content"""
         response = client.get(f"{test_url}/bytes/1024")
@@ -407,6 +2342,98 @@ class TestHttpClientResponse:
         assert len(content) == 1024
 
 
+class TestHttpClientBodyMode:
+    """Test eager and lazy response body buffering"""
+
+    @pytest.fixture
+    def test_url(self):
+        return "https://httpbin.org"
+
+    def test_default_body_mode_is_eager(self, test_url):
+        """Test that bodies are read eagerly by default"""
+        client = uf.HttpClient()
+        assert client.get_body_mode() == "eager"
+
+        response = client.get(f"{test_url}/bytes/1024")
+        assert response.body_loaded
+
+    def test_invalid_body_mode(self):
+        """Test that an unknown body mode is rejected"""
+        with pytest.raises(ValueError):
+            uf.HttpClient(body_mode="streaming")
+
+    def test_lazy_body_not_read_until_accessed(self, test_url):
+        """Test that a lazy body is only fetched on first access"""
+        client = uf.HttpClient(body_mode="lazy")
+        response = client.get(f"{test_url}/bytes/1024")
+
+        assert response.status_code == 200
+        assert not response.body_loaded
+
+        content = response.content
+        assert response.body_loaded
+        assert len(content) == 1024
+        # Later accesses reuse the buffered body
+        assert len(response.bytes()) == 1024
+
+    def test_lazy_body_released_on_drop(self, test_url):
+        """Test that unread lazy bodies release their connections"""
+        pool_config = uf.PoolConfig(
+            max_idle_connections=1,
+            max_idle_per_host=1,
+            idle_timeout=30.0,
+            pool_timeout=5.0,
+        )
+        client = uf.HttpClient(body_mode="lazy", pool_config=pool_config, timeout=10.0)
+
+        for _ in range(10):
+            response = client.get(f"{test_url}/bytes/512")
+            assert response.status_code == 200
+            del response
+
+        response = client.get(f"{test_url}/get")
+        assert response.json()["url"] == f"{test_url}/get"
+
+    def test_iter_content_streams_lazy_body(self, test_url):
+        """Test iter_content pulls chunks from the connection in lazy mode"""
+        client = uf.HttpClient(body_mode="lazy", timeout=10.0)
+        response = client.get(f"{test_url}/stream-bytes/20000?chunk_size=1000")
+        assert response.status_code == 200
+
+        chunks = list(response.iter_content(4096))
+        assert sum(len(chunk) for chunk in chunks) == 20000
+        assert all(len(chunk) == 4096 for chunk in chunks[:-1])
+        assert not response.body_loaded
+        with pytest.raises(RuntimeError, match="iter_content"):
+            response.text()
+
+    def test_iter_content_on_eager_body(self, test_url):
+        """Test iter_content chunks an already-read body"""
+        client = uf.HttpClient()
+        response = client.get(f"{test_url}/bytes/1000")
+        chunks = list(response.iter_content(chunk_size=300))
+        assert [len(chunk) for chunk in chunks] == [300, 300, 300, 100]
+        assert b"".join(chunks) == response.content
+
+        with pytest.raises(ValueError):
+            response.iter_content(0)
+
+    def test_save_streams_body_to_file(self, test_url):
+        """Test save writes the body to disk and reports the byte count"""
+        client = uf.HttpClient(body_mode="lazy", timeout=10.0)
+        response = client.get(f"{test_url}/stream-bytes/20000?chunk_size=1000&seed=1")
+
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = os.path.join(tmpdir, "download.bin")
+            assert response.save(path, chunk_size=4096) == 20000
+            assert os.path.getsize(path) == 20000
+
+            with pytest.raises(IOError):
+                uf.HttpClient().get(f"{test_url}/bytes/10").save(
+                    os.path.join(tmpdir, "missing", "file.bin")
+                )
+
+
 class TestHttpClientErrorHandling:
     """Test error handling and edge cases"""
 
@@ -425,6 +2452,84 @@ class TestHttpClientErrorHandling:
             # This should timeout
             client.get("https://httpbin.org/delay/5")
 
+    def test_typed_exception_hierarchy(self, local_server):
+        """Test transport failures raise distinct UltrafastError subclasses"""
+
+        class Slow(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                time.sleep(2)
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        assert issubclass(uf.TimeoutError, uf.UltrafastError)
+        assert issubclass(uf.TimeoutError, TimeoutError)
+        assert issubclass(uf.ConnectionError, ConnectionError)
+        assert issubclass(uf.TLSError, uf.ConnectionError)
+        assert issubclass(uf.DNSError, uf.ConnectionError)
+        assert issubclass(uf.HTTPStatusError, uf.UltrafastError)
+        assert uf.TooManyRedirectsError is uf.TooManyRedirects
+
+        client = uf.HttpClient(timeout=0.5)
+        server = local_server(Slow, threaded=True)
+        port = server.server_address[1]
+        with pytest.raises(uf.TimeoutError):
+            client.get(f"http://127.0.0.1:{port}/")
+        # Plain HTTP on the other end makes the TLS handshake fail
+        with pytest.raises(uf.TLSError):
+            client.get(f"https://127.0.0.1:{port}/")
+
+        server.shutdown()
+        server.server_close()
+        with pytest.raises(uf.ConnectionError) as exc_info:
+            client.get(f"http://127.0.0.1:{port}/")
+        assert not isinstance(exc_info.value, uf.TLSError)
+        assert not isinstance(exc_info.value, uf.DNSError)
+        assert isinstance(exc_info.value, uf.UltrafastError)
+
+        # RFC 2606 reserves .invalid, so it never resolves
+        with pytest.raises(uf.DNSError):
+            client.get("http://ultrafast-client.invalid/")
+
+    def test_deadline_already_passed(self):
+        """Test a request past its deadline fails without being sent"""
+        client = uf.HttpClient(timeout=30.0)
+
+        start = time.time()
+        with pytest.raises(uf.TimeoutError, match="deadline exceeded"):
+            client.get("https://httpbin.org/delay/5", deadline=time.time() - 1.0)
+        assert time.time() - start < 0.5
+
+    def test_deadline_shrinks_timeout(self):
+        """Test the remaining deadline budget limits the request"""
+        client = uf.HttpClient(timeout=30.0)
+        deadline = time.time() + 1.5
+
+        start = time.time()
+        with pytest.raises(TimeoutError, match="deadline exceeded"):
+            client.get("https://httpbin.org/delay/5", deadline=deadline)
+        assert time.time() - start < 3.0
+
+        # The shared deadline is spent, so the next call fails immediately
+        with pytest.raises(TimeoutError, match="deadline exceeded"):
+            client.get("https://httpbin.org/get", deadline=deadline)
+
+    def test_per_request_timeout(self):
+        """Test a per-call timeout overrides the client timeout"""
+        client = uf.HttpClient(timeout=30.0)
+
+        start = time.time()
+        with pytest.raises(TimeoutError):
+            client.get("https://httpbin.org/delay/5", timeout=1.0)
+        assert time.time() - start < 3.0
+
+        short_client = uf.HttpClient(timeout=1.0)
+        response = short_client.get("https://httpbin.org/delay/2", timeout=10.0)
+        assert response.status_code == 200
+
+        with pytest.raises(ValueError, match="timeout"):
+            client.get("https://httpbin.org/get", timeout=0)
+
     def test_network_error(self):
         """Test network error handling"""
         client = uf.HttpClient(timeout=5.0)
@@ -439,12 +2544,30 @@ class TestHttpClientErrorHandling:
         # Test 404
         response = client.get("https://httpbin.org/status/404")
         assert response.status_code == 404
-        assert not response.ok()
+        assert not response.ok
 
         # Test 500
         response = client.get("https://httpbin.org/status/500")
         assert response.status_code == 500
-        assert not response.ok()
+        assert not response.ok
+
+    def test_raise_for_status(self):
+        """Test raise_for_status raises typed exceptions for 4xx/5xx"""
+        client = uf.HttpClient()
+
+        client.get("https://httpbin.org/status/200").raise_for_status()
+
+        with pytest.raises(uf.HTTPClientError) as exc_info:
+            client.get("https://httpbin.org/status/404").raise_for_status()
+        assert exc_info.value.status_code == 404
+        assert exc_info.value.url == "https://httpbin.org/status/404"
+        assert exc_info.value.reason == "Not Found"
+        assert "404" in str(exc_info.value)
+
+        with pytest.raises(uf.HTTPServerError) as exc_info:
+            client.get("https://httpbin.org/status/503").raise_for_status()
+        assert exc_info.value.status_code == 503
+        assert isinstance(exc_info.value, uf.HTTPStatusError)
 
 
 class TestHttpClientPerformance:
@@ -498,6 +2621,63 @@ class TestHttpClientMiddleware:
         response = client.get("https://httpbin.org/get")
         assert response.status_code == 200
 
+    def test_logging_middleware_redacts_secrets(self, capfd, local_server):
+        """Test logged requests never show credential headers or query values"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Reply)
+        client = uf.HttpClient()
+        client.add_middleware(uf.LoggingMiddleware("logger", log_headers=True))
+        client.get(
+            f"http://127.0.0.1:{server.server_address[1]}/items?page=2",
+            params={"api_key": "query-secret"},
+            headers={
+                "Authorization": "Bearer header-secret",
+                "Cookie": "session=cookie-secret",
+                "X-Trace": "visible",
+            },
+        )
+
+        output = capfd.readouterr().out
+        assert "page=2" in output and "X-Trace: visible" in output
+        assert "Authorization: ***" in output
+        for secret in ("query-secret", "header-secret", "cookie-secret"):
+            assert secret not in output
+
+    def test_logging_middleware_json_format(self, capfd, local_server):
+        """Test JSON logging emits one object per request and response"""
+
+        class Reply(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(404)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+        server = local_server(Reply)
+        url = f"http://127.0.0.1:{server.server_address[1]}/missing"
+        client = uf.HttpClient()
+        client.add_middleware(uf.LoggingMiddleware("logger", format="json"))
+        client.get(url)
+
+        entries = [
+            json.loads(line)
+            for line in capfd.readouterr().out.splitlines()
+            if line.startswith("{")
+        ]
+        request, response = entries[-2:]
+        assert request["event"] == "request" and request["method"] == "GET"
+        assert request["url"] == url
+        assert response["event"] == "response" and response["status"] == 404
+        assert response["duration_ms"] >= 0 and response["timestamp"] > 0
+
+        with pytest.raises(ValueError):
+            uf.LoggingMiddleware("logger", format="xml")
+
     def test_headers_middleware(self):
         """Test headers middleware"""
         client = uf.HttpClient()
@@ -530,6 +2710,189 @@ class TestHttpClientMiddleware:
         response = client.get("https://httpbin.org/get")
         assert response.status_code == 200
 
+    def test_metrics_middleware(self):
+        """Test metrics middleware can be configured and reports in-flight requests"""
+        metrics = uf.MetricsMiddleware("metrics", stale_after=60.0)
+        assert metrics.in_flight_count() == 0
+        uf.HttpClient().add_middleware(metrics)
+
+        with pytest.raises(ValueError, match="stale_after"):
+            uf.MetricsMiddleware("metrics", stale_after=0)
+
+    def test_add_and_remove_interceptor_middleware(self):
+        """Test interceptors run on the sync client until removed by name"""
+        client = uf.HttpClient()
+        seen = []
+
+        def on_request(method, url, headers):
+            seen.append((method, url))
+            return {**headers, "X-Intercepted": "yes"}
+
+        client.add_middleware(
+            uf.InterceptorMiddleware(
+                "interceptor",
+                request_interceptor=on_request,
+                response_interceptor=lambda response: seen.append(response.status_code),
+            )
+        )
+
+        response = client.get("https://httpbin.org/headers")
+        assert response.json()["headers"]["X-Intercepted"] == "yes"
+        assert seen == [("GET", "https://httpbin.org/headers"), 200]
+
+        assert client.remove_middleware("interceptor") is True
+        assert client.remove_middleware("interceptor") is False
+        response = client.get("https://httpbin.org/headers")
+        assert "X-Intercepted" not in response.json()["headers"]
+        assert len(seen) == 2
+
+        with pytest.raises(ValueError, match="Unsupported middleware type"):
+            client.add_middleware(object())
+
+    def test_middleware_priority_orders_request_hooks(self, local_server):
+        """Test request hooks run lowest priority first regardless of middleware type"""
+
+        class EchoDefault(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                body = self.headers.get("X-Default", "").encode()
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        server = local_server(EchoDefault)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        seen = []
+
+        def record(label):
+            return lambda method, url, headers: seen.append((label, "X-Default" in headers))
+
+        client = uf.HttpClient()
+        defaults = uf.HeadersMiddleware("defaults", {"X-Default": "1"}, priority=150)
+        assert uf.InterceptorMiddleware("default").priority == 200
+        client.add_middleware(uf.InterceptorMiddleware("late", request_interceptor=record("late")))
+        client.add_middleware(defaults)
+        client.add_middleware(
+            uf.InterceptorMiddleware("early", request_interceptor=record("early"), priority=100)
+        )
+        assert client.get(url).text() == "1"
+        assert seen == [("early", False), ("late", True)]
+
+        seen.clear()
+        defaults.priority = 250
+        client.add_middleware(defaults)
+        assert client.get(url).text() == "1"
+        assert seen == [("early", False), ("late", False)]
+
+    def test_transform_middleware_rewrites_body(self, local_server):
+        """Test a transform replaces the decoded body and fixes the framing headers"""
+        payload = gzip.compress(json.dumps({"id": 1, "secret": "hunter2"}).encode())
+
+        class GzipJSON(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Type", "application/json; charset=utf-8")
+                self.send_header("Content-Encoding", "gzip")
+                self.send_header("Content-Length", str(len(payload)))
+                self.end_headers()
+                self.wfile.write(payload)
+
+        server = local_server(GzipJSON)
+        url = f"http://127.0.0.1:{server.server_address[1]}/"
+        seen = []
+
+        def strip_secret(body, content_type):
+            seen.append(content_type)
+            data = json.loads(body)
+            data.pop("secret")
+            return json.dumps(data)
+
+        client = uf.HttpClient(
+            compression_config=uf.CompressionConfig(enable_response_compression=False)
+        )
+        client.add_middleware(uf.TransformMiddleware("strip", strip_secret))
+        response = client.get(url)
+        assert response.json() == {"id": 1}
+        assert seen == ["application/json; charset=utf-8"]
+        assert "content-encoding" not in response.headers
+        assert int(response.headers["content-length"]) == len(response.content)
+
+        client.add_middleware(uf.TransformMiddleware("strip", lambda body, content_type: None))
+        assert client.get(url).json() == {"id": 1, "secret": "hunter2"}
+
+        client.add_middleware(uf.TransformMiddleware("strip", lambda body, content_type: 1))
+        with pytest.raises(TypeError, match="bytes, str or None"):
+            client.get(url)
+
+        with pytest.raises(TypeError, match="callable"):
+            uf.TransformMiddleware("strip", "not callable")
+
+    def test_cache_middleware_revalidates_with_etag(self, local_server):
+        """Test cached responses are revalidated and a 304 returns the stored body"""
+        conditional = []
+
+        class ETagHandler(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                conditional.append(self.headers.get("If-None-Match"))
+                if self.headers.get("If-None-Match") == '"v1"':
+                    self.send_response(304)
+                    self.send_header("ETag", '"v1"')
+                    self.end_headers()
+                    return
+                body = b"cached payload"
+                self.send_response(200)
+                self.send_header("ETag", '"v1"')
+                self.send_header("Cache-Control", "no-cache")
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+        server = local_server(ETagHandler)
+        cache = uf.CacheMiddleware("cache", max_entries=4)
+        client = uf.HttpClient()
+        client.add_middleware(cache)
+        url = f"http://127.0.0.1:{server.server_address[1]}/resource"
+
+        first = client.get(url)
+        second = client.get(url)
+        assert conditional == [None, '"v1"']
+        assert second.status_code == 200
+        assert second.content == first.content == b"cached payload"
+        assert (cache.hits, cache.misses, len(cache)) == (1, 1, 1)
+
+        with pytest.raises(ValueError):
+            uf.CacheMiddleware("cache", max_entries=0)
+
+    def test_hmac_signing_middleware(self, local_server):
+        """Test the signature header covers method, path, timestamp and body"""
+        received = []
+
+        class SignedHandler(http.server.BaseHTTPRequestHandler):
+            def do_POST(self):
+                body = self.rfile.read(int(self.headers["Content-Length"]))
+                received.append((self.path, dict(self.headers), body))
+                self.send_response(204)
+                self.end_headers()
+
+        server = local_server(SignedHandler)
+        client = uf.HttpClient()
+        client.add_middleware(uf.HmacSigningMiddleware("signer", "s3cret"))
+        client.post(
+            f"http://127.0.0.1:{server.server_address[1]}/hook?id=7",
+            json={"event": "ping"},
+        )
+
+        path, headers, body = received[0]
+        assert path == "/hook?id=7"
+        message = f"POST\n{path}\n{headers['X-Timestamp']}\n".encode() + body
+        expected = hmac.new(b"s3cret", message, hashlib.sha256).hexdigest()
+        assert headers["X-Signature"] == expected
+
+        with pytest.raises(ValueError):
+            uf.HmacSigningMiddleware("signer", "s3cret", algorithm="md5")
+        with pytest.raises(ValueError):
+            uf.HmacSigningMiddleware("signer", "s3cret", template="{host}")
+
 
 class TestHttpClientContextManager:
     """Test context manager functionality"""
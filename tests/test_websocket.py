@@ -387,6 +387,52 @@ class TestWebSocketConfiguration:
         assert sync_client.reconnect_delay == 1.0
         assert async_client.reconnect_delay == 1.0
 
+    def test_reconnect_backoff_configuration(self):
+        """Test reconnect backoff and callback options"""
+        events = []
+        client = uf.WebSocketClient(reconnect_backoff=1.5, on_reconnect=events.append)
+        assert client.reconnect_backoff == 1.5
+        assert client.on_reconnect is not None
+        assert client.reconnect_attempts == 0
+        assert uf.AsyncWebSocketClient().reconnect_backoff == 2.0
+
+        with pytest.raises(ValueError):
+            uf.WebSocketClient(reconnect_backoff=0.5)
+        with pytest.raises(ValueError):
+            uf.AsyncWebSocketClient(reconnect_delay=-1.0)
+        with pytest.raises(TypeError):
+            uf.AsyncWebSocketClient(on_reconnect="not callable")
+
+    def test_heartbeat_configuration(self):
+        """Test ping interval and timeout options"""
+        client = uf.WebSocketClient(ping_interval=15.0, ping_timeout=5.0)
+        assert client.ping_interval == 15.0
+        assert client.ping_timeout == 5.0
+        assert client.last_pong_at is None
+
+        async_client = uf.AsyncWebSocketClient()
+        assert async_client.ping_interval is None
+        assert async_client.ping_timeout == 20.0
+
+        with pytest.raises(ValueError):
+            uf.WebSocketClient(ping_interval=0)
+        with pytest.raises(ValueError):
+            uf.AsyncWebSocketClient(ping_interval=10.0, ping_timeout=-1.0)
+
+    def test_subprotocol_and_header_configuration(self):
+        """Test handshake subprotocols and headers passed to the constructor"""
+        client = uf.WebSocketClient(
+            subprotocols=["graphql-ws", "chat"],
+            headers={"Authorization": "Bearer token123"},
+        )
+        assert client.subprotocols == ["graphql-ws", "chat"]
+        assert client.headers["Authorization"] == "Bearer token123"
+        assert client.selected_protocol() is None
+
+        async_client = uf.AsyncWebSocketClient(subprotocols=["chat"])
+        assert async_client.subprotocols == ["chat"]
+        assert async_client.headers == {}
+
 
 class TestWebSocketErrorHandling:
     """Test WebSocket error handling"""
@@ -416,6 +462,28 @@ class TestWebSocketErrorHandling:
             # Expected for invalid URL
             assert "invalid" in str(e).lower() or "error" in str(e).lower()
 
+    def test_json_message_parsing(self):
+        """Test parsing JSON from text frames"""
+        msg = uf.WebSocketMessage.new_text('{"id": 7, "tags": ["a", "b"]}')
+        assert msg.json() == {"id": 7, "tags": ["a", "b"]}
+
+        with pytest.raises(ValueError):
+            uf.WebSocketMessage.new_text("not json").json()
+        with pytest.raises(ValueError):
+            uf.WebSocketMessage.new_binary([1, 2, 3]).json()
+
+    def test_json_methods_require_connection(self):
+        """Test send_json/recv_json before connecting"""
+        client = uf.AsyncWebSocketClient()
+
+        async def exercise():
+            with pytest.raises(ConnectionError):
+                await client.send_json({"op": "subscribe"})
+            with pytest.raises(ConnectionError):
+                await client.recv_json(timeout=0.1)
+
+        asyncio.run(exercise())
+
     def test_message_errors(self):
         """Test message-related error handling"""
         # Test accessing text data from binary message